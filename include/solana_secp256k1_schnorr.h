@@ -0,0 +1,162 @@
+#ifndef SOLANA_SECP256K1_SCHNORR_H
+#define SOLANA_SECP256K1_SCHNORR_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+// Returned by every function on success. Failures return the discriminant of the
+// `Secp256k1SchnorrError` that caused them.
+#define SSCH_OK 0
+
+// Returned if a required pointer argument is null
+#define SSCH_ERR_NULL_POINTER -1
+
+// Discriminants of `Secp256k1SchnorrError`, returned on failure
+#define SSCH_ERR_INVALID_SECRET_KEY 1
+
+#define SSCH_ERR_INVALID_PUBLIC_KEY 2
+
+#define SSCH_ERR_INVALID_RECOVERY_ID 3
+
+#define SSCH_ERR_INVALID_SIGNATURE 4
+
+#define SSCH_ERR_INVALID_NONCE 5
+
+#define SSCH_ERR_ARITHMETIC_OVERFLOW 6
+
+#define SSCH_ERR_INVALID_CHALLENGE 7
+
+#define SSCH_ERR_POINT_AT_INFINITY 8
+
+#define SSCH_ERR_INVALID_MESSAGE 9
+
+#define SSCH_ERR_DOMAIN_MISMATCH 10
+
+#define SSCH_ERR_EXPIRED 11
+
+#define SSCH_ERR_NOT_YET_VALID 12
+
+#define SSCH_ERR_NONCE_MISMATCH 13
+
+#define SSCH_ERR_LIMIT_EXCEEDED 14
+
+#define SSCH_ERR_UNAUTHORIZED 15
+
+#define SSCH_ERR_INVALID_PROOF 16
+
+#define SSCH_ERR_INVALID_STATE 17
+
+#define SSCH_ERR_INVALID_SIGNATURE_R 18
+
+#define SSCH_ERR_INVALID_SIGNATURE_S 19
+
+#define SSCH_ERR_DUPLICATE_SIGNER 20
+
+// Discriminants of `Scheme`, selecting the challenge scheme to sign or verify under
+#define SSCH_SCHEME_BIP340 0
+
+#define SSCH_SCHEME_SHA256 1
+
+#define SSCH_SCHEME_KECCAK256 2
+
+#define SSCH_PRIVKEY_LENGTH 32
+
+#define SSCH_PUBKEY_LENGTH 33
+
+#define SSCH_SIGNATURE_LENGTH 64
+
+#define SSCH_BUNDLE_LENGTH 97
+
+#define SSCH_ADDRESS_LENGTH 20
+
+// ### SSCH Pubkey Create
+//
+// Writes the 33-byte compressed public key of a 32-byte private key to `out_pubkey`.
+//
+// # Safety
+//
+// `privkey` must be valid for reads of 32 bytes and `out_pubkey` for writes of 33 bytes.
+int32_t ssch_pubkey_create(const uint8_t *privkey, uint8_t *out_pubkey);
+
+// ### SSCH Keygen
+//
+// Generates a private key and its compressed public key from 32 bytes of secret, uniformly
+// random `seed`, writing them to `out_privkey` and `out_pubkey`. The same seed always yields
+// the same key.
+//
+// # Safety
+//
+// `seed` must be valid for reads of 32 bytes, `out_privkey` for writes of 32 bytes and
+// `out_pubkey` for writes of 33 bytes.
+int32_t ssch_keygen(const uint8_t *seed, uint8_t *out_privkey, uint8_t *out_pubkey);
+
+// ### SSCH Sign
+//
+// Signs a message under `scheme`, a `Scheme` discriminant, writing the 64-byte signature to
+// `out_signature`.
+//
+// # Safety
+//
+// `message` must be valid for reads of `message_len` bytes, or null if it is zero, `privkey`
+// must be valid for reads of 32 bytes and `out_signature` for writes of 64 bytes.
+int32_t ssch_sign(uint8_t scheme,
+                  const uint8_t *message,
+                  size_t message_len,
+                  const uint8_t *privkey,
+                  uint8_t *out_signature);
+
+// ### SSCH Verify
+//
+// Verifies a 64-byte signature over a message against a 33-byte compressed public key under
+// `scheme`, a `Scheme` discriminant.
+//
+// # Safety
+//
+// `message` must be valid for reads of `message_len` bytes, or null if it is zero, `signature`
+// must be valid for reads of 64 bytes and `pubkey` for reads of 33 bytes.
+int32_t ssch_verify(uint8_t scheme,
+                    const uint8_t *message,
+                    size_t message_len,
+                    const uint8_t *signature,
+                    const uint8_t *pubkey);
+
+// ### SSCH Bundle Encode
+//
+// Encodes a 64-byte signature and the signer's 33-byte compressed public key into a 97-byte
+// signature bundle, as accepted by `verify_by_eth_address`.
+//
+// # Safety
+//
+// `signature` must be valid for reads of 64 bytes, `pubkey` for reads of 33 bytes and
+// `out_bundle` for writes of 97 bytes.
+int32_t ssch_bundle_encode(const uint8_t *signature, const uint8_t *pubkey, uint8_t *out_bundle);
+
+// ### SSCH Bundle Decode
+//
+// Decodes a 97-byte signature bundle into its 64-byte signature and 33-byte compressed public
+// key.
+//
+// # Safety
+//
+// `bundle` must be valid for reads of 97 bytes, `out_signature` for writes of 64 bytes and
+// `out_pubkey` for writes of 33 bytes.
+int32_t ssch_bundle_decode(const uint8_t *bundle, uint8_t *out_signature, uint8_t *out_pubkey);
+
+// ### SSCH Verify By Eth Address
+//
+// Verifies a 97-byte signature bundle over a message under `scheme`, a `Scheme` discriminant,
+// against a signer known only by their 20-byte Ethereum address.
+//
+// # Safety
+//
+// `message` must be valid for reads of `message_len` bytes, or null if it is zero, `bundle`
+// must be valid for reads of 97 bytes and `address` for reads of 20 bytes.
+int32_t ssch_verify_by_eth_address(uint8_t scheme,
+                                   const uint8_t *message,
+                                   size_t message_len,
+                                   const uint8_t *bundle,
+                                   const uint8_t *address);
+
+#endif  /* SOLANA_SECP256K1_SCHNORR_H */
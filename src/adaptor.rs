@@ -0,0 +1,208 @@
+use solana_secp256k1::{CompressedPoint, Curve, Secp256k1Point, UncompressedPoint};
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify, errors::Secp256k1SchnorrError, recover_nonce,
+    Secp256k1SchnorrSignature,
+};
+
+pub const SECP256K1_SCHNORR_PRESIGNATURE_LENGTH: usize = 65;
+
+/// Domain separator for the auxiliary randomness of pre-signatures, ensuring that pre-signatures
+/// under different adaptor points never share a nonce.
+#[cfg(feature = "sign")]
+const ADAPTOR_AUX_DOMAIN: &[u8] = b"solana-secp256k1-schnorr/adaptor/aux";
+
+/// Adds two points, failing with `PointAtInfinity` if they are inverses of one another.
+fn add_points(
+    a: UncompressedPoint,
+    b: UncompressedPoint,
+) -> Result<UncompressedPoint, Secp256k1SchnorrError> {
+    if a.x() == b.x() && a.y() != b.y() {
+        return Err(Secp256k1SchnorrError::PointAtInfinity);
+    }
+    Ok(a + b)
+}
+
+/// Returns true if the adaptor secret is subtracted from, rather than added to, the pre-signature.
+///
+/// The final nonce point is 𝑅 = 𝑘⋅𝐺 + 𝑇. Schemes committing to an even 𝑅 sign with its even
+/// counterpart -𝑅 when 𝑅 is odd, which negates both the nonce and the adaptor secret.
+fn is_negated<C: Secp256k1SchnorrVerify>(r: &CompressedPoint) -> bool {
+    C::EVEN_R && r.is_odd()
+}
+
+/// # Secp256k1SchnorrPresignature
+/// An adaptor pre-signature `𝑅∥𝑠'`, where 𝑅 is the compressed final nonce point. A pre-signature
+/// is not a valid signature, but becomes one once adapted with the discrete logarithm 𝑡 of its
+/// adaptor point 𝑇, and the published signature in turn reveals 𝑡 to the holder of the
+/// pre-signature. This enables atomic swaps and scriptless scripts whose final signatures verify
+/// on-chain with the existing `verify`.
+///
+/// There are 4 main functions that it performs:
+///
+/// 1. Presign - Pre-signs a message with a private key under an adaptor point.
+/// 2. Verify Adaptor - Verifies a pre-signature against a message, public key and adaptor point.
+/// 3. Adapt - Completes a pre-signature into a signature with the adaptor secret.
+/// 4. Extract Secret - Recovers the adaptor secret from a pre-signature and its signature.
+pub struct Secp256k1SchnorrPresignature(pub [u8; SECP256K1_SCHNORR_PRESIGNATURE_LENGTH]);
+
+impl Secp256k1SchnorrPresignature {
+    /// The compressed final nonce point 𝑅
+    pub fn r(&self) -> CompressedPoint {
+        let mut r = [0u8; 33];
+        r.clone_from_slice(&self.0[..33]);
+        CompressedPoint(r)
+    }
+
+    pub fn s(&self) -> [u8; 32] {
+        let mut s = [0u8; 32];
+        s.clone_from_slice(&self.0[33..]);
+        s
+    }
+
+    /// ### Verify Adaptor
+    ///
+    /// Verifies that the pre-signature commits to `message`, `pubkey` and `adaptor_point`, such
+    /// that adapting it with the discrete logarithm of `adaptor_point` yields a valid signature.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::{adaptor::Secp256k1SchnorrPresignature, BIP340Challenge};
+    ///
+    /// presignature.verify_adaptor::<BIP340Challenge, _, _>(message, &pubkey, &adaptor_point)
+    ///     .expect("Invalid pre-signature");
+    /// ```
+    pub fn verify_adaptor<C: Secp256k1SchnorrVerify, T: Secp256k1Point, A: Secp256k1Point>(
+        &self,
+        message: &[u8],
+        pubkey: &T,
+        adaptor_point: &A,
+    ) -> Result<(), Secp256k1SchnorrError> {
+        if C::XONLY && pubkey.is_odd() {
+            return self.verify_adaptor::<C, CompressedPoint, A>(
+                message,
+                &crate::xonly_point(pubkey),
+                adaptor_point,
+            );
+        }
+        if pubkey.x().ge(&Curve::P) {
+            return Err(Secp256k1SchnorrError::InvalidPublicKey);
+        }
+        let r = self.r();
+        let r_point =
+            UncompressedPoint::try_from(r).map_err(|_| Secp256k1SchnorrError::InvalidSignature)?;
+        let adaptor_point = adaptor_point.decompress();
+
+        let e = C::challenge_scalar(&r.x(), pubkey, message);
+        if crate::is_zero_mod_n(&e) {
+            return Err(Secp256k1SchnorrError::InvalidChallenge);
+        }
+
+        // s'*G - e*P must equal R - T, or T - R when the adaptor secret is negated
+        let k_g = UncompressedPoint(recover_nonce(&self.s(), &e, pubkey)?);
+        let (expected, sum) = match is_negated::<C>(&r) {
+            false => (r_point, add_points(k_g, adaptor_point)?),
+            true => (adaptor_point, add_points(k_g, r_point)?),
+        };
+        if expected.ne(&sum) {
+            return Err(Secp256k1SchnorrError::InvalidSignature);
+        }
+        Ok(())
+    }
+
+    /// ### Adapt
+    ///
+    /// Completes the pre-signature into a signature with the adaptor `secret`. The pre-signature
+    /// must have been verified against the adaptor point of `secret` for the result to be valid.
+    pub fn adapt<C: Secp256k1SchnorrVerify>(&self, secret: &[u8; 32]) -> Secp256k1SchnorrSignature {
+        let r = self.r();
+        let s = match is_negated::<C>(&r) {
+            false => Curve::add_mod_n(&self.s(), secret),
+            true => Curve::add_mod_n(&self.s(), &Curve::negate_n(secret)),
+        };
+        let mut signature = [0u8; 64];
+        signature[..32].clone_from_slice(&r.x());
+        signature[32..].clone_from_slice(&s);
+        Secp256k1SchnorrSignature(signature)
+    }
+
+    /// ### Extract Secret
+    ///
+    /// Recovers the adaptor secret from a `signature` produced by adapting this pre-signature.
+    /// Fails with `InvalidSignature` if the signature does not share the pre-signature's nonce.
+    pub fn extract_secret<C: Secp256k1SchnorrVerify>(
+        &self,
+        signature: &Secp256k1SchnorrSignature,
+    ) -> Result<[u8; 32], Secp256k1SchnorrError> {
+        let r = self.r();
+        if signature.r().ne(&r.x()) {
+            return Err(Secp256k1SchnorrError::InvalidSignature);
+        }
+        let secret = match is_negated::<C>(&r) {
+            false => Curve::add_mod_n(&signature.s(), &Curve::negate_n(&self.s())),
+            true => Curve::add_mod_n(&self.s(), &Curve::negate_n(&signature.s())),
+        };
+        Ok(secret)
+    }
+}
+
+#[cfg(feature = "sign")]
+impl Secp256k1SchnorrPresignature {
+    /// ### Presign
+    ///
+    /// Pre-signs `message` with `privkey` under `adaptor_point`, using the nonce generation of the
+    /// challenge scheme with auxiliary randomness bound to the adaptor point.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::{adaptor::Secp256k1SchnorrPresignature, BIP340Challenge};
+    ///
+    /// let presignature =
+    ///     Secp256k1SchnorrPresignature::presign::<BIP340Challenge, _>(message, &privkey, &adaptor_point)
+    ///         .expect("Invalid pre-signature");
+    /// ```
+    pub fn presign<C: crate::challenges::Secp256k1SchnorrSign, A: Secp256k1Point>(
+        message: &[u8],
+        privkey: &[u8; 32],
+        adaptor_point: &A,
+    ) -> Result<Self, Secp256k1SchnorrError> {
+        let adaptor_point = adaptor_point.decompress();
+        let mut pubkey =
+            Curve::mul_g(privkey).map_err(|_| Secp256k1SchnorrError::InvalidPublicKey)?;
+
+        // X-only schemes sign with the secret key of the even-Y point sharing P's X-coordinate
+        let mut d = *privkey;
+        if C::XONLY && pubkey.is_odd() {
+            Curve::negate_n_assign(&mut d);
+            pubkey.invert();
+        }
+        let privkey = &d;
+
+        let aux = C::aux_randomness(
+            privkey,
+            &solana_nostd_sha256::hashv(&[ADAPTOR_AUX_DOMAIN, &adaptor_point.compress().0]),
+        );
+        let (mut k, k_g) = C::nonce::<UncompressedPoint>(&pubkey, message, &aux)?;
+        if crate::is_zero_mod_n(&k) {
+            return Err(Secp256k1SchnorrError::InvalidNonce);
+        }
+
+        // R = k*G + T is the nonce point of the adapted signature
+        let r = add_points(k_g, adaptor_point)
+            .map_err(|_| Secp256k1SchnorrError::InvalidNonce)?
+            .compress();
+        if is_negated::<C>(&r) {
+            Curve::negate_n_assign(&mut k);
+        }
+
+        let e = C::challenge_scalar(&r.x(), &pubkey, message);
+        if crate::is_zero_mod_n(&e) {
+            return Err(Secp256k1SchnorrError::InvalidChallenge);
+        }
+
+        let mut presignature = [0u8; SECP256K1_SCHNORR_PRESIGNATURE_LENGTH];
+        presignature[..33].clone_from_slice(&r.0);
+        presignature[33..].clone_from_slice(&Curve::add_mod_n(&k, &Curve::mul_mod_n(&e, privkey)));
+        Ok(Self(presignature))
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(feature = "sign")]
+use solana_secp256k1::Secp256k1Point;
+use solana_secp256k1::{CompressedPoint, UncompressedPoint};
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify, errors::Secp256k1SchnorrError, keys,
+    Secp256k1SchnorrSignature, SECP256K1_SCHNORR_SIGNATURE_LENGTH,
+};
+
+#[cfg(feature = "sign")]
+use crate::challenges::Secp256k1SchnorrSign;
+
+pub const SECP256K1_SCHNORR_SIGNATURE_BUNDLE_LENGTH: usize =
+    SECP256K1_SCHNORR_SIGNATURE_LENGTH + 33;
+
+/// # Secp256k1SchnorrSignatureBundle
+/// A Schnorr signature followed by the signer's 33-byte compressed public key, for verifiers that
+/// only know the signer by their 20-byte Ethereum address.
+///
+/// The public key is carried alongside the signature rather than recovered from it, as a Schnorr
+/// signature does not commit to its signer in a recoverable way. Binding it to the expected
+/// address makes it just as trustworthy as a stored public key.
+pub struct Secp256k1SchnorrSignatureBundle(pub [u8; SECP256K1_SCHNORR_SIGNATURE_BUNDLE_LENGTH]);
+
+impl Secp256k1SchnorrSignatureBundle {
+    pub fn signature(&self) -> Secp256k1SchnorrSignature {
+        let mut signature = [0u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH];
+        signature.clone_from_slice(&self.0[..SECP256K1_SCHNORR_SIGNATURE_LENGTH]);
+        Secp256k1SchnorrSignature(signature)
+    }
+
+    pub fn pubkey(&self) -> CompressedPoint {
+        let mut pubkey = [0u8; 33];
+        pubkey.clone_from_slice(&self.0[SECP256K1_SCHNORR_SIGNATURE_LENGTH..]);
+        CompressedPoint(pubkey)
+    }
+
+    /// ### Verify By Ethereum Address
+    /// Verifies the bundled signature against a signer known only by their 20-byte Ethereum
+    /// address. The bundled public key is decompressed and hashed into its address, and, if it
+    /// matches `expected_address`, the signature is verified against it as usual with a single
+    /// `secp256k1_recover` call.
+    ///
+    /// Fails with `InvalidPublicKey` if the bundled public key is not on the curve, `Unauthorized`
+    /// if it does not belong to `expected_address`, and as `verify` otherwise.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::{address::Secp256k1SchnorrSignatureBundle, Keccak256Challenge},
+    ///
+    /// bundle.verify_by_eth_address::<Keccak256Challenge>(&message, &signer_address)
+    ///     .expect("Invalid signature");
+    /// ```
+    #[inline]
+    pub fn verify_by_eth_address<C: Secp256k1SchnorrVerify>(
+        &self,
+        message: &[u8],
+        expected_address: &[u8; 20],
+    ) -> Result<(), Secp256k1SchnorrError> {
+        let pubkey = keys::parse_sec1(&self.0[SECP256K1_SCHNORR_SIGNATURE_LENGTH..])?;
+        if keys::eth_address(&pubkey).ne(expected_address) {
+            return Err(Secp256k1SchnorrError::Unauthorized);
+        }
+        self.signature()
+            .verify::<C, UncompressedPoint>(message, &pubkey)
+    }
+}
+
+#[cfg(feature = "sign")]
+impl Secp256k1SchnorrSignatureBundle {
+    /// ### Sign
+    /// Signs a message as `Secp256k1SchnorrSignature::sign`, bundling the signature with the
+    /// signer's compressed public key.
+    #[inline]
+    pub fn sign<C: Secp256k1SchnorrSign>(
+        message: &[u8],
+        privkey: &[u8; 32],
+    ) -> Result<Secp256k1SchnorrSignatureBundle, Secp256k1SchnorrError> {
+        let signature = Secp256k1SchnorrSignature::sign::<C>(message, privkey)?;
+        let pubkey = solana_secp256k1::Curve::mul_g(privkey)
+            .map_err(|_| Secp256k1SchnorrError::InvalidPublicKey)?;
+        let mut bundle = [0u8; SECP256K1_SCHNORR_SIGNATURE_BUNDLE_LENGTH];
+        bundle[..SECP256K1_SCHNORR_SIGNATURE_LENGTH].clone_from_slice(&signature.0);
+        bundle[SECP256K1_SCHNORR_SIGNATURE_LENGTH..].clone_from_slice(&pubkey.compress().0);
+        Ok(Secp256k1SchnorrSignatureBundle(bundle))
+    }
+}
+
+/// ### Verify By Ethereum Address
+/// Verifies a 97-byte signature bundle against a signer known only by their 20-byte Ethereum
+/// address, as `Secp256k1SchnorrSignatureBundle::verify_by_eth_address`.
+#[inline]
+pub fn verify_by_eth_address<C: Secp256k1SchnorrVerify>(
+    message: &[u8],
+    sig_bundle: &[u8; SECP256K1_SCHNORR_SIGNATURE_BUNDLE_LENGTH],
+    expected_address: &[u8; 20],
+) -> Result<(), Secp256k1SchnorrError> {
+    Secp256k1SchnorrSignatureBundle(*sig_bundle)
+        .verify_by_eth_address::<C>(message, expected_address)
+}
@@ -0,0 +1,85 @@
+use anchor_lang::{
+    error::{AnchorError, Error},
+    prelude::{borsh, AccountInfo, AnchorDeserialize, AnchorSerialize},
+    Space,
+};
+use solana_secp256k1::Secp256k1Point;
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify, errors::Secp256k1SchnorrError, Secp256k1SchnorrSignature,
+    SECP256K1_SCHNORR_SIGNATURE_LENGTH,
+};
+
+/// Serializes signatures as a fixed 64-byte array with no length prefix, so that they may be
+/// passed as instruction arguments and stored in accounts.
+impl AnchorSerialize for Secp256k1SchnorrSignature {
+    fn serialize<W: borsh::io::Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
+        writer.write_all(&self.0)
+    }
+}
+
+impl AnchorDeserialize for Secp256k1SchnorrSignature {
+    fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        let mut signature = [0u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH];
+        reader.read_exact(&mut signature)?;
+        Ok(Self(signature))
+    }
+}
+
+impl Space for Secp256k1SchnorrSignature {
+    const INIT_SPACE: usize = SECP256K1_SCHNORR_SIGNATURE_LENGTH;
+}
+
+/// Maps errors to an `AnchorError` named after the variant, keeping the custom error code of
+/// `ProgramError::Custom` so clients decode failures identically with or without Anchor.
+impl From<Secp256k1SchnorrError> for Error {
+    fn from(error: Secp256k1SchnorrError) -> Self {
+        let error_name = format!("{:?}", error);
+        AnchorError {
+            error_msg: format!("Secp256k1 Schnorr error: {}", error_name),
+            error_name,
+            error_code_number: error as u32,
+            error_origin: None,
+            compared_values: None,
+        }
+        .into()
+    }
+}
+
+/// ### Verify
+///
+/// Verifies a signature as `Secp256k1SchnorrSignature::verify`, returning an Anchor `Result` so
+/// that handlers may propagate failures with `?`.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::anchor::verify;
+///
+/// pub fn claim(ctx: Context<Claim>, signature: Secp256k1SchnorrSignature) -> Result<()> {
+///     let pubkey = CompressedPoint(ctx.accounts.vault.signer);
+///     verify::<BIP340Challenge, CompressedPoint>(&signature, &ctx.accounts.claimant.key().to_bytes(), &pubkey)?;
+///     Ok(())
+/// }
+/// ```
+pub fn verify<C: Secp256k1SchnorrVerify, T: Secp256k1Point>(
+    signature: &Secp256k1SchnorrSignature,
+    message: &[u8],
+    pubkey: &T,
+) -> anchor_lang::Result<()> {
+    Ok(signature.verify::<C, T>(message, pubkey)?)
+}
+
+/// ### Verify Account Message
+///
+/// Verifies a signature over the `len` bytes at `offset` in the data of an account, borrowing
+/// them in place, as `Secp256k1SchnorrSignature::verify_account_data`.
+pub fn verify_account_message<C: Secp256k1SchnorrVerify, T: Secp256k1Point>(
+    signature: &Secp256k1SchnorrSignature,
+    account: &AccountInfo,
+    offset: usize,
+    len: usize,
+    pubkey: &T,
+) -> anchor_lang::Result<()> {
+    let data = account.try_borrow_data()?;
+    Ok(signature.verify_account_data::<C, T>(&data, offset, len, pubkey)?)
+}
@@ -0,0 +1,81 @@
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+use solana_secp256k1::{CompressedPoint, Curve, SEC1_OCTET_COMPRESSED_EVEN};
+
+use crate::{keys::XOnlyPublicKey, scalar::Scalar, Secp256k1SchnorrSignature};
+
+/// ### Arbitrary Bounded
+///
+/// Draws 32 bytes biased towards the edges of the range `[0, bound)`, returning zero, one,
+/// `bound - 1`, `bound` itself or `2²⁵⁶ - 1` for a few selector values, and uniformly random bytes
+/// otherwise, consuming at most 33 bytes of input. Fuzzers thereby reach both the largest valid
+/// values and the smallest invalid ones, which random bytes almost never hit for 𝑃 and 𝑁.
+pub fn arbitrary_bounded(u: &mut Unstructured, bound: &[u8; 32]) -> Result<[u8; 32]> {
+    let mut bytes = match u8::arbitrary(u)? {
+        0 | 1 => [0u8; 32],
+        2 | 3 => *bound,
+        4 => [0xff; 32],
+        _ => return u.arbitrary(),
+    };
+    match u8::arbitrary(u)? & 1 {
+        // Neither 𝑃 nor 𝑁 ends in a zero byte, so no borrow is needed
+        1 if bytes.eq(bound) => bytes[31] -= 1,
+        1 if bytes.eq(&[0u8; 32]) => bytes[31] = 1,
+        _ => (),
+    }
+    Ok(bytes)
+}
+
+/// ### Arbitrary Compressed Point
+///
+/// Draws a compressed point for fields of foreign point types, as in
+/// `#[arbitrary(with = arbitrary_compressed_point)]`. The prefix is almost always a valid parity
+/// byte, and the 𝑋-coordinate is drawn as by `arbitrary_bounded` over 𝑃, so about half of all
+/// random points lie on the curve.
+pub fn arbitrary_compressed_point(u: &mut Unstructured) -> Result<CompressedPoint> {
+    let mut point = [0u8; 33];
+    point[0] = match u8::arbitrary(u)? {
+        0 => u.arbitrary()?,
+        selector => SEC1_OCTET_COMPRESSED_EVEN + (selector & 1),
+    };
+    point[1..].clone_from_slice(&arbitrary_bounded(u, &Curve::P)?);
+    Ok(CompressedPoint(point))
+}
+
+/// Draws 𝑟 and 𝑠 independently as by `arbitrary_bounded` over 𝑃 and 𝑁, so signatures exercise
+/// every range check of verification as well as the full verification path.
+impl<'a> Arbitrary<'a> for Secp256k1SchnorrSignature {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self::from_parts(
+            arbitrary_bounded(u, &Curve::P)?,
+            arbitrary_bounded(u, &Curve::N)?,
+        ))
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (2, Some(2 * 33))
+    }
+}
+
+/// Draws the 𝑋-coordinate as by `arbitrary_bounded` over 𝑃, keeping keys that are not on the
+/// curve, as the type itself does.
+impl<'a> Arbitrary<'a> for XOnlyPublicKey {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self(arbitrary_bounded(u, &Curve::P)?))
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (1, Some(33))
+    }
+}
+
+/// Draws a reduced scalar biased towards zero, one and 𝑁 - 1, reducing random values modulo 𝑁 so
+/// that the invariant of the type always holds.
+impl<'a> Arbitrary<'a> for Scalar {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Scalar::reduce(&arbitrary_bounded(u, &Curve::N)?))
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (1, Some(33))
+    }
+}
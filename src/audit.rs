@@ -0,0 +1,162 @@
+use core::cell::RefCell;
+use core::fmt::Write;
+
+#[cfg(any(feature = "bip340", feature = "sha256", feature = "keccak256"))]
+use solana_secp256k1::{Curve, Secp256k1Point, UncompressedPoint};
+
+#[cfg(any(feature = "bip340", feature = "sha256", feature = "keccak256"))]
+use crate::{challenges::Secp256k1SchnorrSign, Secp256k1SchnorrSignature};
+
+/// ### TranscriptEntry
+///
+/// A single labelled value recorded during a signing or verification operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptEntry {
+    pub label: &'static str,
+    pub value: Vec<u8>,
+}
+
+/// ### Transcript
+///
+/// An ordered record of the inputs, intermediate hashes and scalar values computed by the crate
+/// over the course of a single known-answer test. Transcripts are fully deterministic, allowing
+/// auditors to diff them release-to-release and against an independent reference implementation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transcript {
+    pub name: String,
+    pub entries: Vec<TranscriptEntry>,
+}
+
+thread_local! {
+    static RECORDER: RefCell<Option<Vec<TranscriptEntry>>> = const { RefCell::new(None) };
+}
+
+/// Records a value into the active transcript, if any. Called from the signing and verification
+/// paths so that transcripts reflect the values the crate actually computes.
+#[inline(always)]
+pub(crate) fn record(label: &'static str, value: &[u8]) {
+    RECORDER.with(|recorder| {
+        if let Some(entries) = recorder.borrow_mut().as_mut() {
+            entries.push(TranscriptEntry {
+                label,
+                value: value.to_vec(),
+            });
+        }
+    });
+}
+
+/// Runs `f` without recording into the active transcript, for internal checks such as the
+/// self-verification performed after signing that are not part of the audited operation.
+#[cfg(feature = "verify-after-sign")]
+pub(crate) fn suspend<R, F: FnOnce() -> R>(f: F) -> R {
+    let previous = RECORDER.with(|recorder| recorder.borrow_mut().take());
+    let result = f();
+    RECORDER.with(|recorder| *recorder.borrow_mut() = previous);
+    result
+}
+
+/// ### Capture
+///
+/// Runs `f` while recording every value reported by the crate into a new named transcript.
+pub fn capture<R, F: FnOnce() -> R>(name: &str, f: F) -> (Transcript, R) {
+    let previous = RECORDER.with(|recorder| recorder.borrow_mut().replace(Vec::new()));
+    let result = f();
+    let entries = RECORDER
+        .with(|recorder| core::mem::replace(&mut *recorder.borrow_mut(), previous))
+        .unwrap_or_default();
+    (
+        Transcript {
+            name: name.to_string(),
+            entries,
+        },
+        result,
+    )
+}
+
+impl Transcript {
+    /// ### To JSON
+    ///
+    /// Serializes the transcript as a JSON object with hex-encoded values.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        write!(json, "{{\"name\":\"{}\",\"entries\":[", self.name).unwrap();
+        for (i, entry) in self.entries.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            write!(json, "{{\"label\":\"{}\",\"value\":\"", entry.label).unwrap();
+            for byte in &entry.value {
+                write!(json, "{:02x}", byte).unwrap();
+            }
+            json.push_str("\"}");
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+/// Fixed private keys used by the known-answer suite.
+#[cfg(any(feature = "bip340", feature = "sha256", feature = "keccak256"))]
+const KAT_PRIVKEYS: [[u8; 32]; 2] = [
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x01,
+    ],
+    [
+        0xb7, 0xe1, 0x51, 0x62, 0x8a, 0xed, 0x2a, 0x6a, 0xbf, 0x71, 0x58, 0x80, 0x9c, 0xf4, 0xf3,
+        0xc7, 0x62, 0xe7, 0x16, 0x0f, 0x38, 0xb4, 0xda, 0x56, 0xa7, 0x84, 0xd9, 0x04, 0x51, 0x90,
+        0xcf, 0xef,
+    ],
+];
+
+/// Fixed messages used by the known-answer suite.
+#[cfg(any(feature = "bip340", feature = "sha256", feature = "keccak256"))]
+const KAT_MESSAGES: [&[u8]; 3] = [b"", b"test", &[0xffu8; 32]];
+
+/// Signs and verifies every fixed message under every fixed key with challenge scheme `C`,
+/// appending one transcript per signature to `transcripts`.
+#[cfg(any(feature = "bip340", feature = "sha256", feature = "keccak256"))]
+fn kat<C: Secp256k1SchnorrSign>(scheme: &str, mut transcripts: Vec<Transcript>) -> Vec<Transcript> {
+    for (i, privkey) in KAT_PRIVKEYS.iter().enumerate() {
+        for (j, message) in KAT_MESSAGES.iter().enumerate() {
+            let (transcript, _) = capture(&format!("{}/{}/{}", scheme, i, j), || {
+                record("privkey", privkey);
+                record("message", message);
+                let signature = Secp256k1SchnorrSignature::sign::<C>(message, privkey).ok()?;
+                let pubkey = Curve::mul_g(privkey).ok()?;
+                let valid = signature
+                    .verify::<C, UncompressedPoint>(message, &pubkey)
+                    .is_ok();
+                record("valid", &[valid as u8]);
+                Some(pubkey.x())
+            });
+            transcripts.push(transcript);
+        }
+    }
+    transcripts
+}
+
+/// ### KAT Suite
+///
+/// Runs the fixed known-answer suite over every enabled challenge scheme, returning the recorded
+/// transcripts in a stable order.
+pub fn kat_suite() -> Vec<Transcript> {
+    let transcripts = Vec::new();
+    #[cfg(feature = "bip340")]
+    let transcripts = kat::<crate::challenges::bip340::BIP340Challenge>("bip340", transcripts);
+    #[cfg(feature = "sha256")]
+    let transcripts = kat::<crate::challenges::sha256::Sha256Challenge>("sha256", transcripts);
+    #[cfg(feature = "keccak256")]
+    let transcripts =
+        kat::<crate::challenges::keccak256::Keccak256Challenge>("keccak256", transcripts);
+    transcripts
+}
+
+/// ### KAT Suite JSON
+///
+/// Runs the known-answer suite and serializes it as a JSON array of transcripts.
+pub fn kat_suite_json() -> String {
+    let transcripts: Vec<String> = kat_suite().iter().map(Transcript::to_json).collect();
+    format!("[{}]", transcripts.join(","))
+}
@@ -0,0 +1,186 @@
+#[cfg(any(
+    feature = "solana-program-recover",
+    not(all(feature = "host", not(target_os = "solana")))
+))]
+use solana_secp256k1::Curve;
+use solana_secp256k1::UncompressedPoint;
+
+use crate::errors::Secp256k1SchnorrError;
+
+/// ### Secp256k1RecoverBackend
+///
+/// An implementation of the `secp256k1_recover` syscall, through which every verification in this
+/// crate performs its elliptic curve multiplication.
+///
+/// Given a 32-byte `hash`, the parity of 𝑅 and a 64-byte `signature` 𝑟∥𝑠, it must return the
+/// 64-byte uncompressed point 𝑟⁻¹(𝑠⋅𝑅 - ℎ⋅𝐺), failing if the operands are invalid or the result
+/// is the point at infinity.
+pub trait Secp256k1RecoverBackend {
+    fn recover(
+        hash: &[u8; 32],
+        is_odd: bool,
+        signature: &[u8; 64],
+    ) -> Result<[u8; 64], Secp256k1SchnorrError>;
+}
+
+/// ### NostdRecover
+///
+/// The default backend, binding the syscall through `solana-nostd-secp256k1-recover`.
+pub struct NostdRecover;
+
+impl Secp256k1RecoverBackend for NostdRecover {
+    #[inline(always)]
+    fn recover(
+        hash: &[u8; 32],
+        is_odd: bool,
+        signature: &[u8; 64],
+    ) -> Result<[u8; 64], Secp256k1SchnorrError> {
+        solana_nostd_secp256k1_recover::secp256k1_recover(hash, is_odd, signature)
+            .map_err(|_| Secp256k1SchnorrError::InvalidSignature)
+    }
+}
+
+/// ### SolanaProgramRecover
+///
+/// Binds the syscall through Anza's `solana-secp256k1-recover`, the crate re-exported by
+/// `solana_program::secp256k1_recover`, for programs whose dependency policy only admits
+/// Anza-published crates.
+///
+/// Its host implementation rejects ECDSA signatures with a high 𝑠, so operands with 𝑠 > 𝑁/2 are
+/// first replaced by the equivalent `(-𝑠, !is_odd)`, which recovers the same point as
+/// 𝑟⁻¹(-𝑠⋅-𝑅 - ℎ⋅𝐺) = 𝑟⁻¹(𝑠⋅𝑅 - ℎ⋅𝐺).
+#[cfg(feature = "solana-program-recover")]
+pub struct SolanaProgramRecover;
+
+#[cfg(feature = "solana-program-recover")]
+impl Secp256k1RecoverBackend for SolanaProgramRecover {
+    #[inline(always)]
+    fn recover(
+        hash: &[u8; 32],
+        is_odd: bool,
+        signature: &[u8; 64],
+    ) -> Result<[u8; 64], Secp256k1SchnorrError> {
+        let mut signature = *signature;
+        let mut is_odd = is_odd;
+        if signature[32..].gt(&Curve::N_DIV_2[..]) {
+            let mut s = [0u8; 32];
+            s.clone_from_slice(&signature[32..]);
+            signature[32..].clone_from_slice(&Curve::negate_n(&s));
+            is_odd = !is_odd;
+        }
+        solana_secp256k1_recover::secp256k1_recover(hash, is_odd as u8, &signature)
+            .map(|pubkey| pubkey.to_bytes())
+            .map_err(|_| Secp256k1SchnorrError::InvalidSignature)
+    }
+}
+
+/// ### HostRecover
+///
+/// Computes 𝑟⁻¹(𝑠⋅𝑅 - ℎ⋅𝐺) natively with `k256` arithmetic for non-SBF targets, skipping the
+/// ECDSA recovery emulation behind the syscall's host implementation. Indexers and test suites
+/// verifying large numbers of signatures off-chain get native-speed verification under every
+/// challenge scheme, as well as native `ecmul` and everything built on it. Signing computes its
+/// public keys and nonce points natively too, through `mul_g`.
+#[cfg(all(feature = "host", not(target_os = "solana")))]
+pub struct HostRecover;
+
+#[cfg(all(feature = "host", not(target_os = "solana")))]
+impl Secp256k1RecoverBackend for HostRecover {
+    fn recover(
+        hash: &[u8; 32],
+        is_odd: bool,
+        signature: &[u8; 64],
+    ) -> Result<[u8; 64], Secp256k1SchnorrError> {
+        use k256::{
+            elliptic_curve::{
+                ops::{LinearCombination, Reduce},
+                point::DecompressPoint,
+                sec1::ToEncodedPoint,
+                subtle::Choice,
+                PrimeField,
+            },
+            AffinePoint, FieldBytes, ProjectivePoint, Scalar, U256,
+        };
+
+        let scalar = |bytes: &[u8]| {
+            Option::<Scalar>::from(Scalar::from_repr(*FieldBytes::from_slice(bytes)))
+                .filter(|k| !bool::from(k.is_zero()))
+                .ok_or(Secp256k1SchnorrError::InvalidSignature)
+        };
+        let r = scalar(&signature[..32])?;
+        let s = scalar(&signature[32..])?;
+        let big_r: AffinePoint = Option::from(AffinePoint::decompress(
+            FieldBytes::from_slice(&signature[..32]),
+            Choice::from(is_odd as u8),
+        ))
+        .ok_or(Secp256k1SchnorrError::InvalidSignature)?;
+        let h = <Scalar as Reduce<U256>>::reduce_bytes(FieldBytes::from_slice(hash));
+
+        // Q = r⁻¹⋅s⋅R - r⁻¹⋅h⋅G
+        let r_inv = r.invert().unwrap();
+        let q = ProjectivePoint::lincomb(
+            &ProjectivePoint::from(big_r),
+            &(s * r_inv),
+            &ProjectivePoint::GENERATOR,
+            &-(h * r_inv),
+        );
+        if q == ProjectivePoint::IDENTITY {
+            return Err(Secp256k1SchnorrError::InvalidSignature);
+        }
+        let mut out = [0u8; 64];
+        out.clone_from_slice(&q.to_affine().to_encoded_point(false).as_bytes()[1..]);
+        Ok(out)
+    }
+}
+
+/// ### DefaultRecover
+///
+/// The backend used by `verify`, selected at compile time by the "solana-program-recover"
+/// feature flag, or by the "host" feature flag on non-SBF targets, which takes precedence.
+#[cfg(all(
+    not(feature = "solana-program-recover"),
+    not(all(feature = "host", not(target_os = "solana")))
+))]
+pub type DefaultRecover = NostdRecover;
+
+#[cfg(all(
+    feature = "solana-program-recover",
+    not(all(feature = "host", not(target_os = "solana")))
+))]
+pub type DefaultRecover = SolanaProgramRecover;
+
+#[cfg(all(feature = "host", not(target_os = "solana")))]
+pub type DefaultRecover = HostRecover;
+
+/// ### Mul G
+///
+/// Computes 𝑘⋅𝐺, reducing 𝑘 modulo 𝑁 and failing if it is zero, as `Curve::mul_g` does. Signing
+/// derives public keys and nonce points through it, so that under the "host" feature flag they
+/// are computed natively with `k256` along with verification, rather than through the recover
+/// emulation.
+#[cfg(not(all(feature = "host", not(target_os = "solana"))))]
+#[inline(always)]
+pub(crate) fn mul_g(k: &[u8; 32]) -> Result<UncompressedPoint, Secp256k1SchnorrError> {
+    Curve::mul_g(k).map_err(|_| Secp256k1SchnorrError::InvalidSecretKey)
+}
+
+#[cfg(all(feature = "host", not(target_os = "solana")))]
+pub(crate) fn mul_g(k: &[u8; 32]) -> Result<UncompressedPoint, Secp256k1SchnorrError> {
+    use k256::{
+        elliptic_curve::{ops::Reduce, sec1::ToEncodedPoint},
+        FieldBytes, ProjectivePoint, Scalar, U256,
+    };
+
+    let k = <Scalar as Reduce<U256>>::reduce_bytes(FieldBytes::from_slice(k));
+    if bool::from(k.is_zero()) {
+        return Err(Secp256k1SchnorrError::InvalidSecretKey);
+    }
+    let mut out = [0u8; 64];
+    out.clone_from_slice(
+        &(ProjectivePoint::GENERATOR * k)
+            .to_affine()
+            .to_encoded_point(false)
+            .as_bytes()[1..],
+    );
+    Ok(UncompressedPoint(out))
+}
@@ -0,0 +1,236 @@
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256, Sha512};
+use solana_secp256k1::{CompressedPoint, Secp256k1Point};
+
+use crate::{ecmul, errors::Secp256k1SchnorrError, scalar::Scalar};
+
+/// Offset of hardened child indices, written `i'` or `iH` in derivation paths
+pub const BIP32_HARDENED: u32 = 0x8000_0000;
+
+/// HMAC key of master key generation
+#[cfg(feature = "sign")]
+const BIP32_SEED_KEY: &[u8] = b"Bitcoin seed";
+
+/// Computes HMAC-SHA512 of the concatenation of `data` under a `key` of at most 128 bytes.
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    let mut ipad = [0x36u8; 128];
+    let mut opad = [0x5cu8; 128];
+    for (i, byte) in key.iter().enumerate() {
+        ipad[i] ^= byte;
+        opad[i] ^= byte;
+    }
+    let mut inner = Sha512::new();
+    inner.update(ipad);
+    for part in data {
+        inner.update(part);
+    }
+    let mut outer = Sha512::new();
+    outer.update(opad);
+    outer.update(inner.finalize());
+    outer.finalize().into()
+}
+
+/// Splits the output of HMAC-SHA512 into the scalar 𝐼ʟ, failing unless it is below 𝑁, and the
+/// child chain code 𝐼ʀ.
+fn split(i: [u8; 64]) -> Result<(Scalar, [u8; 32]), Secp256k1SchnorrError> {
+    let mut il = [0u8; 32];
+    let mut chain_code = [0u8; 32];
+    il.clone_from_slice(&i[..32]);
+    chain_code.clone_from_slice(&i[32..]);
+    let il = Scalar::new(il).map_err(|_| Secp256k1SchnorrError::InvalidSecretKey)?;
+    Ok((il, chain_code))
+}
+
+/// Returns the first 4 bytes of `RIPEMD160(SHA256(𝐾))`, identifying a compressed public key.
+fn fingerprint(pubkey: &CompressedPoint) -> [u8; 4] {
+    let hash = Ripemd160::digest(Sha256::digest(pubkey.0));
+    let mut fingerprint = [0u8; 4];
+    fingerprint.clone_from_slice(&hash[..4]);
+    fingerprint
+}
+
+/// # ExtendedPublicKey
+/// A BIP32 extended public key, the compressed public key 𝐾 and chain code from which the
+/// public keys of non-hardened children are derived without any secrets. Programs holding a
+/// service's extended public key can thus derive the public key of each of its users, and verify
+/// their signatures, without storing each key.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ExtendedPublicKey {
+    pub pubkey: CompressedPoint,
+    pub chain_code: [u8; 32],
+    pub depth: u8,
+    pub parent_fingerprint: [u8; 4],
+    pub child_number: u32,
+}
+
+impl ExtendedPublicKey {
+    /// The fingerprint of this key, the parent fingerprint of its children
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint(&self.pubkey)
+    }
+
+    /// ### Derive Child
+    ///
+    /// Derives the non-hardened child 𝐾ᵢ = 𝐼ʟ⋅𝐺 + 𝐾 at `index`, where `𝐼ʟ∥𝐼ʀ =
+    /// HMAC-SHA512(chain code, 𝐾∥index)`, with a single call to `secp256k1_recover`.
+    ///
+    /// Fails with `InvalidSecretKey` for hardened indices, which require the private key, and
+    /// with `LimitExceeded` beyond a depth of 255. BIP32 skips to the next index if 𝐼ʟ ≥ 𝑁 or
+    /// 𝐾ᵢ is the point at infinity, which happens with negligible probability, failing here with
+    /// `InvalidSecretKey` or `PointAtInfinity` respectively.
+    pub fn derive_child(&self, index: u32) -> Result<Self, Secp256k1SchnorrError> {
+        if index >= BIP32_HARDENED {
+            return Err(Secp256k1SchnorrError::InvalidSecretKey);
+        }
+        let depth = self
+            .depth
+            .checked_add(1)
+            .ok_or(Secp256k1SchnorrError::LimitExceeded)?;
+        let (il, chain_code) = split(hmac_sha512(
+            &self.chain_code,
+            &[&self.pubkey.0, &index.to_be_bytes()],
+        ))?;
+        let pubkey = self.pubkey.decompress();
+        let child = ecmul::mul_add_g(&pubkey, &Scalar::ONE.to_bytes(), &il.to_bytes())?;
+        Ok(Self {
+            pubkey: child.compress(),
+            chain_code,
+            depth,
+            parent_fingerprint: self.fingerprint(),
+            child_number: index,
+        })
+    }
+
+    /// ### Derive Path
+    ///
+    /// Derives the descendant at a path of non-hardened indices, failing as `derive_child`.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::bip32::ExtendedPublicKey;
+    ///
+    /// // m/0/42 relative to the service's account key
+    /// let user = xpub.derive_path(&[0, 42])?;
+    /// signature.verify::<BIP340Challenge, CompressedPoint>(message, &user.pubkey)?;
+    /// ```
+    pub fn derive_path(&self, path: &[u32]) -> Result<Self, Secp256k1SchnorrError> {
+        path.iter()
+            .try_fold(*self, |key, index| key.derive_child(*index))
+    }
+}
+
+/// # ExtendedPrivateKey
+/// A BIP32 extended private key, the private key 𝑘 and chain code from which the keys of both
+/// hardened and non-hardened children are derived, so that signing services can derive per-user
+/// keys from a single seed.
+#[cfg(feature = "sign")]
+#[derive(Clone)]
+pub struct ExtendedPrivateKey {
+    privkey: [u8; 32],
+    pub chain_code: [u8; 32],
+    pub depth: u8,
+    pub parent_fingerprint: [u8; 4],
+    pub child_number: u32,
+}
+
+#[cfg(all(feature = "sign", feature = "hardening"))]
+impl Drop for ExtendedPrivateKey {
+    fn drop(&mut self) {
+        crate::hardening::zeroize(&mut self.privkey);
+        crate::hardening::zeroize(&mut self.chain_code);
+    }
+}
+
+#[cfg(feature = "sign")]
+impl ExtendedPrivateKey {
+    /// ### New Master
+    ///
+    /// Generates the master key `𝐼ʟ∥𝐼ʀ = HMAC-SHA512("Bitcoin seed", seed)` from a seed of 16 to
+    /// 64 bytes, failing with `InvalidSecretKey` for seeds of any other length or in the
+    /// negligibly rare case that 𝐼ʟ is not a valid private key.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::bip32::{ExtendedPrivateKey, BIP32_HARDENED};
+    ///
+    /// let master = ExtendedPrivateKey::new_master(&seed)?;
+    /// let user = master.derive_path(&[44 + BIP32_HARDENED, BIP32_HARDENED, BIP32_HARDENED, 0, 42])?;
+    /// let signature = Secp256k1SchnorrSignature::sign::<BIP340Challenge>(message, user.privkey())?;
+    /// ```
+    pub fn new_master(seed: &[u8]) -> Result<Self, Secp256k1SchnorrError> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(Secp256k1SchnorrError::InvalidSecretKey);
+        }
+        let (il, chain_code) = split(hmac_sha512(BIP32_SEED_KEY, &[seed]))?;
+        let privkey = Scalar::new_nonzero(il.to_bytes())?;
+        Ok(Self {
+            privkey: privkey.to_bytes(),
+            chain_code,
+            depth: 0,
+            parent_fingerprint: [0u8; 4],
+            child_number: 0,
+        })
+    }
+
+    pub fn privkey(&self) -> &[u8; 32] {
+        &self.privkey
+    }
+
+    /// ### Public Key
+    ///
+    /// Returns the extended public key sharing this key's chain code and position in the tree.
+    pub fn public_key(&self) -> Result<ExtendedPublicKey, Secp256k1SchnorrError> {
+        let pubkey = ecmul::mul_g(&self.privkey)
+            .map_err(|_| Secp256k1SchnorrError::InvalidSecretKey)?
+            .compress();
+        Ok(ExtendedPublicKey {
+            pubkey,
+            chain_code: self.chain_code,
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_number: self.child_number,
+        })
+    }
+
+    /// ### Derive Child
+    ///
+    /// Derives the child 𝑘ᵢ = 𝐼ʟ + 𝑘 at `index`, where `𝐼ʟ∥𝐼ʀ = HMAC-SHA512(chain code, data)`
+    /// over `0x00∥𝑘∥index` for hardened indices and `𝐾∥index` otherwise, failing as
+    /// `ExtendedPublicKey::derive_child`.
+    pub fn derive_child(&self, index: u32) -> Result<Self, Secp256k1SchnorrError> {
+        let depth = self
+            .depth
+            .checked_add(1)
+            .ok_or(Secp256k1SchnorrError::LimitExceeded)?;
+        let pubkey = ecmul::mul_g(&self.privkey)
+            .map_err(|_| Secp256k1SchnorrError::InvalidSecretKey)?
+            .compress();
+        let i = match index >= BIP32_HARDENED {
+            true => hmac_sha512(
+                &self.chain_code,
+                &[&[0x00], &self.privkey, &index.to_be_bytes()],
+            ),
+            false => hmac_sha512(&self.chain_code, &[&pubkey.0, &index.to_be_bytes()]),
+        };
+        let (il, chain_code) = split(i)?;
+        let privkey = il.add(&Scalar::reduce(&self.privkey));
+        if privkey.is_zero() {
+            return Err(Secp256k1SchnorrError::InvalidSecretKey);
+        }
+        Ok(Self {
+            privkey: privkey.to_bytes(),
+            chain_code,
+            depth,
+            parent_fingerprint: fingerprint(&pubkey),
+            child_number: index,
+        })
+    }
+
+    /// ### Derive Path
+    ///
+    /// Derives the descendant at a path of indices, failing as `derive_child`.
+    pub fn derive_path(&self, path: &[u32]) -> Result<Self, Secp256k1SchnorrError> {
+        path.iter()
+            .try_fold(self.clone(), |key, index| key.derive_child(*index))
+    }
+}
@@ -0,0 +1,156 @@
+use solana_nostd_sha256::hashv;
+
+use crate::{
+    challenges::bip340::BIP340Challenge, errors::Secp256k1SchnorrError, Secp256k1SchnorrSignature,
+};
+
+// sha256(BIP0322-signed-message) tagged hash
+const BIP0322_SIGNED_MESSAGE: [u8; 32] = [
+    0x74, 0x65, 0x84, 0xa1, 0x87, 0x2f, 0xa1, 0x00, 0x41, 0x55, 0x4e, 0xff, 0xa0, 0x38, 0xd6, 0x12,
+    0x49, 0x42, 0xdd, 0x79, 0xb4, 0xe5, 0x8a, 0x4c, 0xda, 0x18, 0x4e, 0x13, 0xdb, 0xe6, 0x2c, 0x49,
+];
+
+// sha256(TapSighash) tagged hash
+const TAP_SIGHASH: [u8; 32] = [
+    0xf4, 0x0a, 0x48, 0xdf, 0x4b, 0x2a, 0x70, 0xc8, 0xb4, 0x92, 0x4b, 0xf2, 0x65, 0x46, 0x61, 0xed,
+    0x3d, 0x95, 0xfd, 0x66, 0xa3, 0x13, 0xeb, 0x87, 0x23, 0x75, 0x97, 0xc6, 0x28, 0xe4, 0xa0, 0x31,
+];
+
+/// The sighash type committing to the whole transaction without an explicit byte
+pub const SIGHASH_DEFAULT: u8 = 0x00;
+/// The sighash type committing to the whole transaction
+pub const SIGHASH_ALL: u8 = 0x01;
+
+/// The scriptPubKey of the `to_sign` output, `OP_RETURN`
+const OP_RETURN_SCRIPT: [u8; 1] = [0x6a];
+
+/// Returns the P2TR scriptPubKey `OP_1 OP_PUSHBYTES_32 𝑄` of an 𝑋-only output key.
+fn p2tr_script(output_key: &[u8; 32]) -> [u8; 34] {
+    let mut script = [0u8; 34];
+    script[..2].clone_from_slice(&[0x51, 0x20]);
+    script[2..].clone_from_slice(output_key);
+    script
+}
+
+/// ### Message Hash
+///
+/// Computes the BIP322 tagged hash of `message`, which the virtual `to_spend` transaction
+/// commits to.
+pub fn message_hash(message: &[u8]) -> [u8; 32] {
+    hashv(&[
+        BIP0322_SIGNED_MESSAGE.as_ref(),
+        BIP0322_SIGNED_MESSAGE.as_ref(),
+        message,
+    ])
+}
+
+/// ### To Spend Txid
+///
+/// Computes the txid of the virtual `to_spend` transaction of a message, whose only output pays
+/// the P2TR `output_key` and is spent by the `to_sign` transaction.
+pub fn to_spend_txid(message: &[u8], output_key: &[u8; 32]) -> [u8; 32] {
+    let message_hash = message_hash(message);
+    let script = p2tr_script(output_key);
+    let txid = hashv(&[
+        // nVersion, and a single input spending the null outpoint
+        &[0u8; 4],
+        &[0x01],
+        &[0u8; 32],
+        &[0xff; 4],
+        // scriptSig OP_0 OP_PUSHBYTES_32 message_hash, and nSequence
+        &[0x22, 0x00, 0x20],
+        &message_hash,
+        &[0u8; 4],
+        // A single output of 0 to the message challenge, and nLockTime
+        &[0x01],
+        &[0u8; 8],
+        &[0x22],
+        &script,
+        &[0u8; 4],
+    ]);
+    hashv(&[&txid])
+}
+
+/// ### Sighash
+///
+/// Computes the BIP341 key-path signature hash of the virtual `to_sign` transaction of a message,
+/// which the Schnorr signature of a BIP322 "simple" signature signs.
+///
+/// Fails with `InvalidSignature` unless `hash_type` is `SIGHASH_DEFAULT` or `SIGHASH_ALL`, the only
+/// types a BIP322 signature may use.
+pub fn sighash(
+    message: &[u8],
+    output_key: &[u8; 32],
+    hash_type: u8,
+) -> Result<[u8; 32], Secp256k1SchnorrError> {
+    if hash_type != SIGHASH_DEFAULT && hash_type != SIGHASH_ALL {
+        return Err(Secp256k1SchnorrError::InvalidSignature);
+    }
+    let txid = to_spend_txid(message, output_key);
+    let script = p2tr_script(output_key);
+
+    let sha_prevouts = hashv(&[&txid, &[0u8; 4]]);
+    let sha_amounts = hashv(&[&[0u8; 8]]);
+    let sha_scriptpubkeys = hashv(&[&[0x22], &script]);
+    let sha_sequences = hashv(&[&[0u8; 4]]);
+    let sha_outputs = hashv(&[&[0u8; 8], &[0x01], &OP_RETURN_SCRIPT]);
+
+    Ok(hashv(&[
+        TAP_SIGHASH.as_ref(),
+        TAP_SIGHASH.as_ref(),
+        // Epoch, hash type, nVersion and nLockTime
+        &[0x00, hash_type],
+        &[0u8; 4],
+        &[0u8; 4],
+        &sha_prevouts,
+        &sha_amounts,
+        &sha_scriptpubkeys,
+        &sha_sequences,
+        &sha_outputs,
+        // Key-path spend without an annex of input 0
+        &[0x00],
+        &[0u8; 4],
+    ]))
+}
+
+/// ### Parse Simple
+///
+/// Parses a BIP322 "simple" signature, the serialized witness stack of the `to_sign` input, into
+/// its Schnorr signature and sighash type. The witness must hold a single 64-byte signature, or a
+/// 65-byte signature ending in an explicit `SIGHASH_ALL`.
+///
+/// Fails with `InvalidSignature` for any other witness, including those of non-Taproot outputs.
+pub fn parse_simple(
+    witness: &[u8],
+) -> Result<(Secp256k1SchnorrSignature, u8), Secp256k1SchnorrError> {
+    let (hash_type, signature) = match witness {
+        [0x01, 0x40, signature @ ..] if signature.len() == 64 => (SIGHASH_DEFAULT, signature),
+        [0x01, 0x41, signature @ .., SIGHASH_ALL] if signature.len() == 64 => {
+            (SIGHASH_ALL, signature)
+        }
+        _ => return Err(Secp256k1SchnorrError::InvalidSignature),
+    };
+    Ok((Secp256k1SchnorrSignature::try_from(signature)?, hash_type))
+}
+
+/// ### Verify Simple
+///
+/// Verifies a BIP322 "simple" signature of `message` by the Bitcoin wallet owning the P2TR output
+/// key `output_key`, the 32-byte witness program of its `bc1p` address, such as for wallet logins
+/// or proofs of reserves.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::bip322::verify_simple;
+///
+/// verify_simple(b"Hello World", &witness, &output_key).expect("Invalid signature");
+/// ```
+pub fn verify_simple(
+    message: &[u8],
+    witness: &[u8],
+    output_key: &[u8; 32],
+) -> Result<(), Secp256k1SchnorrError> {
+    let (signature, hash_type) = parse_simple(witness)?;
+    let sighash = sighash(message, output_key, hash_type)?;
+    signature.verify_xonly::<BIP340Challenge>(&sighash, output_key)
+}
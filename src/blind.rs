@@ -0,0 +1,235 @@
+//! Blind Schnorr signatures, in which a signer signs a message it never sees and cannot link the
+//! resulting signature to the session that produced it.
+//!
+//! Blind Schnorr signatures are only secure when the signer runs its sessions strictly one after
+//! another. A user who holds several sessions open at once can combine the signer's responses
+//! into one more signature than there were sessions, by the ROS attack of Benhamouda, Lepoint,
+//! Loss, Orrù and Raykova ("On the (in)security of ROS", Eurocrypt 2021), which is practical from
+//! a few hundred concurrent sessions. Signers should use `BlindSigner`, which refuses to open a
+//! session while another is pending, rather than `commit` and `blind_sign` directly.
+
+use solana_nostd_sha256::hashv;
+use solana_secp256k1::{Curve, Secp256k1Point, UncompressedPoint};
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify,
+    ecmul,
+    errors::Secp256k1SchnorrError,
+    rng::{self, Secp256k1SchnorrRng},
+    scalar::Scalar,
+    xonly_point, Secp256k1SchnorrSignature,
+};
+
+/// Domain separator for hedging the signer's blind nonces with its private key
+const BLIND_NONCE_DOMAIN: &[u8] = b"solana-secp256k1-schnorr/blind/nonce";
+
+/// # BlindNonce
+/// A signer's secret nonce 𝑘 for a single blind signing session, as generated by `commit`. It is
+/// consumed by `blind_sign`, as reusing it leaks the private key.
+pub struct BlindNonce {
+    k: [u8; 32],
+}
+
+#[cfg(feature = "hardening")]
+impl Drop for BlindNonce {
+    fn drop(&mut self) {
+        crate::hardening::zeroize(&mut self.k);
+    }
+}
+
+/// # BlindingFactors
+/// A user's secret blinding factors 𝛼 and 𝛽 for a single session, along with the 𝑟 of the
+/// unblinded signature, as returned by `blind`. They must be kept until `unblind`, and never
+/// revealed to the signer, as they link the unblinded signature to the session.
+pub struct BlindingFactors {
+    alpha: [u8; 32],
+    r: [u8; 32],
+}
+
+#[cfg(feature = "hardening")]
+impl Drop for BlindingFactors {
+    fn drop(&mut self) {
+        crate::hardening::zeroize(&mut self.alpha);
+    }
+}
+
+/// ### Commit
+///
+/// Generates the signer's nonce for a single session, hedging the randomness drawn from `rng`
+/// with the private key, along with its public commitment 𝑅 = 𝑘⋅𝐺, which is sent to the user.
+///
+/// Callers must not commit to a new session until the previous one has been signed or abandoned,
+/// as concurrent sessions are forgeable. `BlindSigner` enforces this.
+pub fn commit<R: Secp256k1SchnorrRng>(
+    rng: &mut R,
+    privkey: &[u8; 32],
+) -> Result<(BlindNonce, UncompressedPoint), Secp256k1SchnorrError> {
+    let k = Scalar::reduce(&hashv(&[
+        BLIND_NONCE_DOMAIN,
+        rng::aux(rng).as_ref(),
+        privkey.as_ref(),
+    ]));
+    if k.is_zero() {
+        return Err(Secp256k1SchnorrError::InvalidNonce);
+    }
+    let nonce = BlindNonce { k: k.to_bytes() };
+    let commitment = ecmul::mul_g(&nonce.k)?;
+    Ok((nonce, commitment))
+}
+
+/// ### Blind
+///
+/// Blinds the signer's `commitment` for a message the signer never sees, returning the user's
+/// blinding factors and the blinded challenge 𝑒 = 𝑒' + 𝛽 to send to the signer, where 𝑒' is the
+/// challenge of the final signature over the nonce 𝑅' = 𝑅 + 𝛼⋅𝐺 + 𝛽⋅𝑃.
+///
+/// Schemes committing to an even 𝑅 redraw 𝛼 and 𝛽 until 𝑅' is even, and 𝑋-only schemes blind
+/// against the even public key as the signer signs for it.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::{blind, BIP340Challenge};
+///
+/// let (factors, challenge) = blind::blind::<BIP340Challenge, _>(&mut rng, &commitment, &pubkey, message)?;
+/// // The signer responds with blind::blind_sign(nonce, &privkey, &challenge)
+/// let signature = blind::unblind(&factors, &blind_signature);
+/// ```
+pub fn blind<C: Secp256k1SchnorrVerify, R: Secp256k1SchnorrRng>(
+    rng: &mut R,
+    commitment: &UncompressedPoint,
+    pubkey: &UncompressedPoint,
+    message: &[u8],
+) -> Result<(BlindingFactors, [u8; 32]), Secp256k1SchnorrError> {
+    let pubkey = match C::XONLY && pubkey.is_odd() {
+        true => xonly_point(pubkey).decompress(),
+        false => *pubkey,
+    };
+    loop {
+        let alpha = rng::scalar(rng);
+        let beta = rng::scalar(rng);
+        // R' = R + α⋅G + β⋅P
+        let r = match ecmul::mul_add_g(&pubkey, &beta, &alpha) {
+            Ok(point) => ecmul::accumulate(Some(*commitment), point),
+            Err(Secp256k1SchnorrError::PointAtInfinity) => continue,
+            Err(error) => return Err(error),
+        };
+        let r = match r {
+            Some(r) if !(C::EVEN_R && r.is_odd()) => r.x(),
+            _ => continue,
+        };
+        let e = C::challenge_scalar(&r, &pubkey, message);
+        if e.eq(&[0u8; 32]) {
+            continue;
+        }
+        let challenge = Curve::add_mod_n(&e, &beta);
+        return Ok((BlindingFactors { alpha, r }, challenge));
+    }
+}
+
+/// ### Blind Sign
+///
+/// Signs a blinded `challenge` with the signer's nonce, returning 𝑠 = 𝑘 + 𝑒⋅𝑑. Under 𝑋-only
+/// schemes, the private key of an odd public key is negated, as when signing.
+///
+/// The signer learns nothing about the message or the final signature, and so must authorize the
+/// session by other means, for instance by charging for it.
+pub fn blind_sign<C: Secp256k1SchnorrVerify>(
+    nonce: BlindNonce,
+    privkey: &[u8; 32],
+    challenge: &[u8; 32],
+) -> Result<[u8; 32], Secp256k1SchnorrError> {
+    let pubkey = ecmul::mul_g(privkey).map_err(|_| Secp256k1SchnorrError::InvalidSecretKey)?;
+    let e = Scalar::new(*challenge).map_err(|_| Secp256k1SchnorrError::InvalidChallenge)?;
+    #[allow(unused_mut)]
+    let mut d = match C::XONLY && pubkey.is_odd() {
+        true => Curve::negate_n(privkey),
+        false => *privkey,
+    };
+    let s = Curve::add_mod_n(&nonce.k, &Curve::mul_mod_n(&e.to_bytes(), &d));
+    #[cfg(feature = "hardening")]
+    crate::hardening::zeroize(&mut d);
+    Ok(s)
+}
+
+/// # BlindSigner
+/// The signer's side of blind signing, holding its private key and at most one open session, so
+/// that sessions run strictly one after another as required against the ROS attack.
+pub struct BlindSigner {
+    privkey: [u8; 32],
+    pending: Option<BlindNonce>,
+}
+
+#[cfg(feature = "hardening")]
+impl Drop for BlindSigner {
+    fn drop(&mut self) {
+        crate::hardening::zeroize(&mut self.privkey);
+    }
+}
+
+impl BlindSigner {
+    /// ### New
+    ///
+    /// Creates a signer for `privkey` with no open session.
+    pub fn new(privkey: [u8; 32]) -> Self {
+        Self {
+            privkey,
+            pending: None,
+        }
+    }
+
+    /// Returns true if a session has been committed to but not yet signed or aborted.
+    pub fn is_open(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// ### Commit
+    ///
+    /// Opens a session as in `commit`, returning the commitment 𝑅 to send to the user. Fails with
+    /// `InvalidState` while a previous session is still open.
+    pub fn commit<R: Secp256k1SchnorrRng>(
+        &mut self,
+        rng: &mut R,
+    ) -> Result<UncompressedPoint, Secp256k1SchnorrError> {
+        if self.pending.is_some() {
+            return Err(Secp256k1SchnorrError::InvalidState);
+        }
+        let (nonce, commitment) = commit(rng, &self.privkey)?;
+        self.pending = Some(nonce);
+        Ok(commitment)
+    }
+
+    /// ### Sign
+    ///
+    /// Closes the open session by signing the user's blinded `challenge` as in `blind_sign`. Fails
+    /// with `InvalidState` if no session is open. The session's nonce is consumed even if signing
+    /// fails, so a rejected challenge requires a new session.
+    pub fn sign<C: Secp256k1SchnorrVerify>(
+        &mut self,
+        challenge: &[u8; 32],
+    ) -> Result<[u8; 32], Secp256k1SchnorrError> {
+        let nonce = self
+            .pending
+            .take()
+            .ok_or(Secp256k1SchnorrError::InvalidState)?;
+        blind_sign::<C>(nonce, &self.privkey, challenge)
+    }
+
+    /// ### Abort
+    ///
+    /// Abandons the open session, if any, discarding its nonce so that a new session may begin.
+    pub fn abort(&mut self) {
+        self.pending = None;
+    }
+}
+
+/// ### Unblind
+///
+/// Unblinds the signer's response into the signature `𝑟'∥𝑠'`, where 𝑠' = 𝑠 + 𝛼, which verifies
+/// with `verify` but cannot be linked to the session that produced it. The signature should be
+/// verified before use, as a dishonest signer may have responded with garbage.
+pub fn unblind(factors: &BlindingFactors, blind_signature: &[u8; 32]) -> Secp256k1SchnorrSignature {
+    Secp256k1SchnorrSignature::from_parts(
+        factors.r,
+        Curve::add_mod_n(blind_signature, &factors.alpha),
+    )
+}
@@ -0,0 +1,63 @@
+use crate::*;
+
+use solana_nostd_sha256::hashv;
+
+// Algorithm tag libsecp256k1 appends to the RFC6979 seed of Bitcoin Cash Schnorr nonces
+const BCH_ALGO16: &[u8] = b"Schnorr+SHA256  ";
+
+/// ### BchSchnorrChallenge
+///
+/// The Bitcoin Cash Schnorr scheme, computing `𝑒 = SHA256(𝑟∥𝑃∥𝑀)` over the 33-byte compressed
+/// public key and committing to an 𝑅 whose 𝑌-coordinate is a quadratic residue modulo 𝑃, so that
+/// Bitcoin Cash transaction and message signatures can be checked on Solana. The square check
+/// is a field exponentiation, making verification considerably more expensive than BIP340's.
+///
+/// Signing with zero auxiliary randomness derives nonces with RFC6979 exactly as libsecp256k1
+/// does for 32-byte messages, while any other `aux` is mixed into the private key before
+/// seeding it.
+pub struct BchSchnorrChallenge;
+
+impl Secp256k1SchnorrVerify for BchSchnorrChallenge {
+    const SQUARE_R: bool = true;
+
+    fn challenge<T: Secp256k1Point>(r: &[u8; 32], pubkey: &T, message: &[u8]) -> [u8; 32] {
+        hashv(&[r, &pubkey.compress().0, message])
+    }
+
+    fn challenge_parts<T: Secp256k1Point>(r: &[u8; 32], pubkey: &T, parts: &[&[u8]]) -> [u8; 32] {
+        challenges::with_parts(&[r, &pubkey.compress().0], parts, hashv)
+    }
+
+    fn challenge_compute_units(message_len: usize) -> u64 {
+        compute::hash_compute_units(&[32, 33, message_len])
+    }
+}
+
+impl Secp256k1SchnorrSign for BchSchnorrChallenge {
+    fn aux_randomness(privkey: &[u8; 32], aux: &[u8; 32]) -> [u8; 32] {
+        if aux.eq(&[0u8; 32]) {
+            return *privkey;
+        }
+        let mut t = solana_nostd_sha256::hash(aux);
+        for (a, b) in t.iter_mut().zip(privkey.iter()) {
+            *a ^= b
+        }
+        t
+    }
+
+    fn nonce<T: Secp256k1Point>(
+        _pubkey: &T,
+        message: &[u8],
+        aux: &[u8; 32],
+    ) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
+        let mut k = crate::rfc6979::rfc6979(aux, &[message, BCH_ALGO16]);
+        let mut r = crate::backend::mul_g(&k).map_err(|_| Secp256k1SchnorrError::InvalidNonce)?;
+
+        if !is_square_mod_p(&r.y()) {
+            Curve::negate_n_assign(&mut k);
+            r.invert();
+        }
+
+        Ok((k, r))
+    }
+}
@@ -24,6 +24,9 @@ const BIP0340_NONCE: [u8; 32] = [
 pub struct BIP340Challenge;
 
 impl Secp256k1SchnorrVerify for BIP340Challenge {
+    const EVEN_R: bool = true;
+    const XONLY: bool = true;
+
     fn challenge<T: Secp256k1Point>(r: &[u8; 32], pubkey: &T, message: &[u8]) -> [u8; 32] {
         hashv(&[
             BIP0340_CHALLENGE.as_ref(),
@@ -33,6 +36,22 @@ impl Secp256k1SchnorrVerify for BIP340Challenge {
             message,
         ])
     }
+
+    fn challenge_compute_units(message_len: usize) -> u64 {
+        compute::hash_compute_units(&[32, 32, 32, 32, message_len])
+    }
+
+    fn challenge_parts<T: Secp256k1Point>(r: &[u8; 32], pubkey: &T, parts: &[&[u8]]) -> [u8; 32] {
+        if let [message] = parts {
+            return Self::challenge(r, pubkey, message);
+        }
+        let x = pubkey.x();
+        challenges::with_parts(
+            &[&BIP0340_CHALLENGE, &BIP0340_CHALLENGE, r, &x],
+            parts,
+            hashv,
+        )
+    }
 }
 
 impl Secp256k1SchnorrSign for BIP340Challenge {
@@ -49,15 +68,18 @@ impl Secp256k1SchnorrSign for BIP340Challenge {
         message: &[u8],
         aux: &[u8; 32],
     ) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
-        let mut k = hashv(&[
-            BIP0340_NONCE.as_ref(),
-            BIP0340_NONCE.as_ref(),
-            aux,
-            pubkey.x().as_ref(),
-            message,
-        ]);
-        let mut r =
-            UncompressedPoint::try_from(k).map_err(|_| Secp256k1SchnorrError::InvalidNonce)?;
+        Self::nonce_parts(pubkey, &[message], aux)
+    }
+
+    fn nonce_parts<T: Secp256k1Point>(
+        pubkey: &T,
+        parts: &[&[u8]],
+        aux: &[u8; 32],
+    ) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
+        let x = pubkey.x();
+        let mut k =
+            challenges::with_parts(&[&BIP0340_NONCE, &BIP0340_NONCE, aux, &x], parts, hashv);
+        let mut r = crate::backend::mul_g(&k).map_err(|_| Secp256k1SchnorrError::InvalidNonce)?;
 
         if r.is_odd() {
             Curve::negate_n_assign(&mut k);
@@ -0,0 +1,94 @@
+use crate::*;
+
+use challenges::bip340::BIP340Challenge;
+use solana_nostd_sha256::{hash, hashv};
+
+// Magic prefixed by its own length, 24, as the varint 0x18
+const BSM_MAGIC: &[u8] = b"\x18Bitcoin Signed Message:\n";
+
+/// Encodes `len` as a Bitcoin CompactSize varint, returning the bytes written to `buf`.
+fn compact_size(len: usize, buf: &mut [u8; 9]) -> &[u8] {
+    let len = len as u64;
+    match len {
+        0..=0xfc => {
+            buf[0] = len as u8;
+            &buf[..1]
+        }
+        0xfd..=0xffff => {
+            buf[0] = 0xfd;
+            buf[1..3].clone_from_slice(&(len as u16).to_le_bytes());
+            &buf[..3]
+        }
+        0x10000..=0xffff_ffff => {
+            buf[0] = 0xfe;
+            buf[1..5].clone_from_slice(&(len as u32).to_le_bytes());
+            &buf[..5]
+        }
+        _ => {
+            buf[0] = 0xff;
+            buf[1..].clone_from_slice(&len.to_le_bytes());
+            buf
+        }
+    }
+}
+
+/// ### BSM Digest
+///
+/// Computes the Bitcoin Signed Message digest
+/// `SHA256(SHA256("\x18Bitcoin Signed Message:\n"∥varint(len(𝑀))∥𝑀))` of the concatenation of
+/// message `parts`.
+pub fn bsm_digest(parts: &[&[u8]]) -> [u8; 32] {
+    let mut buf = [0u8; 9];
+    let len = compact_size(parts.iter().map(|part| part.len()).sum(), &mut buf);
+    hash(&challenges::with_parts(&[BSM_MAGIC, len], parts, hashv))
+}
+
+/// ### BsmChallenge
+///
+/// Computes the BIP340 challenge over the Bitcoin Signed Message digest of 𝑀, as Bitcoin wallets
+/// that "sign message" with Schnorr signatures do, so that their output verifies on Solana. A
+/// signature under this scheme is exactly a BIP340 signature of `bsm_digest(𝑀)`.
+pub struct BsmChallenge;
+
+impl Secp256k1SchnorrVerify for BsmChallenge {
+    const EVEN_R: bool = true;
+    const XONLY: bool = true;
+
+    fn challenge<T: Secp256k1Point>(r: &[u8; 32], pubkey: &T, message: &[u8]) -> [u8; 32] {
+        BIP340Challenge::challenge(r, pubkey, &bsm_digest(&[message]))
+    }
+
+    fn challenge_parts<T: Secp256k1Point>(r: &[u8; 32], pubkey: &T, parts: &[&[u8]]) -> [u8; 32] {
+        BIP340Challenge::challenge(r, pubkey, &bsm_digest(parts))
+    }
+
+    fn challenge_compute_units(message_len: usize) -> u64 {
+        let mut buf = [0u8; 9];
+        let len = compact_size(message_len, &mut buf).len();
+        compute::hash_compute_units(&[BSM_MAGIC.len(), len, message_len])
+            + compute::hash_compute_units(&[32])
+            + BIP340Challenge::challenge_compute_units(32)
+    }
+}
+
+impl Secp256k1SchnorrSign for BsmChallenge {
+    fn aux_randomness(privkey: &[u8; 32], aux: &[u8; 32]) -> [u8; 32] {
+        BIP340Challenge::aux_randomness(privkey, aux)
+    }
+
+    fn nonce<T: Secp256k1Point>(
+        pubkey: &T,
+        message: &[u8],
+        aux: &[u8; 32],
+    ) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
+        BIP340Challenge::nonce(pubkey, &bsm_digest(&[message]), aux)
+    }
+
+    fn nonce_parts<T: Secp256k1Point>(
+        pubkey: &T,
+        parts: &[&[u8]],
+        aux: &[u8; 32],
+    ) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
+        BIP340Challenge::nonce(pubkey, &bsm_digest(parts), aux)
+    }
+}
@@ -0,0 +1,127 @@
+use core::marker::PhantomData;
+
+use ::digest::{consts::U32, Digest, OutputSizeUser};
+
+use crate::*;
+
+// Domain separator mixed into nonce derivation so that signing the same message with the same key
+// under another challenge scheme can never produce a related nonce
+const DIGEST_NONCE_DOMAIN: &[u8] = b"solana-secp256k1-schnorr/digest/nonce";
+
+/// Interprets a digest as a 32-byte big-endian scalar, left-padding shorter outputs and truncating
+/// longer ones to their leftmost 32 bytes.
+fn finalize_scalar<D: Digest>(hasher: D) -> [u8; 32] {
+    let output = hasher.finalize();
+    let mut scalar = [0u8; 32];
+    if output.len() < 32 {
+        scalar[32 - output.len()..].clone_from_slice(&output);
+    } else {
+        scalar.clone_from_slice(&output[..32]);
+    }
+    scalar
+}
+
+/// ### DigestChallenge
+///
+/// Adapts any RustCrypto `Digest`, such as SHA3-256 or RIPEMD-160, into a challenge scheme
+/// computing `𝑒 = 𝐻(𝑟∥𝑃ₓ∥𝑀)`, without writing a new challenge module.
+///
+/// Digests of any length may be used for verification. Signing requires a 32-byte digest, as
+/// shorter nonces would be biased and leak the private key.
+pub struct DigestChallenge<D>(PhantomData<D>);
+
+impl<D: Digest> Secp256k1SchnorrVerify for DigestChallenge<D> {
+    fn challenge<T: Secp256k1Point>(r: &[u8; 32], pubkey: &T, message: &[u8]) -> [u8; 32] {
+        finalize_scalar(
+            D::new()
+                .chain_update(r)
+                .chain_update(pubkey.x())
+                .chain_update(message),
+        )
+    }
+}
+
+impl<D: Digest + OutputSizeUser<OutputSize = U32>> Secp256k1SchnorrSign for DigestChallenge<D> {
+    fn aux_randomness(privkey: &[u8; 32], aux: &[u8; 32]) -> [u8; 32] {
+        let mut t = finalize_scalar(D::new().chain_update(aux));
+        for (a, b) in t.iter_mut().zip(privkey.iter()) {
+            *a ^= b
+        }
+        t
+    }
+
+    fn nonce<T: Secp256k1Point>(
+        pubkey: &T,
+        message: &[u8],
+        aux: &[u8; 32],
+    ) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
+        let k = finalize_scalar(
+            D::new()
+                .chain_update(DIGEST_NONCE_DOMAIN)
+                .chain_update(aux)
+                .chain_update(pubkey.x())
+                .chain_update(message),
+        );
+        let r = crate::backend::mul_g(&k).map_err(|_| Secp256k1SchnorrError::InvalidNonce)?;
+        Ok((k, r))
+    }
+}
+
+/// ### DigestTag
+///
+/// A domain separation tag for `TaggedDigestChallenge`.
+pub trait DigestTag {
+    const TAG: &'static [u8];
+}
+
+/// Returns a hasher primed with the BIP340-style tagged prefix `𝐻(tag)∥𝐻(tag)`.
+fn tagged<D: Digest, T: DigestTag>() -> D {
+    let tag = D::digest(T::TAG);
+    D::new().chain_update(&tag).chain_update(&tag)
+}
+
+/// ### TaggedDigestChallenge
+///
+/// A `DigestChallenge` domain separated by a BIP340-style tagged prefix, computing
+/// `𝑒 = 𝐻(𝐻(tag)∥𝐻(tag)∥𝑟∥𝑃ₓ∥𝑀)`, so that challenges computed for one protocol can never collide
+/// with those of another using the same digest.
+pub struct TaggedDigestChallenge<D, T>(PhantomData<(D, T)>);
+
+impl<D: Digest, T: DigestTag> Secp256k1SchnorrVerify for TaggedDigestChallenge<D, T> {
+    fn challenge<P: Secp256k1Point>(r: &[u8; 32], pubkey: &P, message: &[u8]) -> [u8; 32] {
+        finalize_scalar(
+            tagged::<D, T>()
+                .chain_update(r)
+                .chain_update(pubkey.x())
+                .chain_update(message),
+        )
+    }
+}
+
+impl<D: Digest + OutputSizeUser<OutputSize = U32>, T: DigestTag> Secp256k1SchnorrSign
+    for TaggedDigestChallenge<D, T>
+{
+    fn aux_randomness(privkey: &[u8; 32], aux: &[u8; 32]) -> [u8; 32] {
+        let mut t = finalize_scalar(tagged::<D, T>().chain_update(aux));
+        for (a, b) in t.iter_mut().zip(privkey.iter()) {
+            *a ^= b
+        }
+        t
+    }
+
+    fn nonce<P: Secp256k1Point>(
+        pubkey: &P,
+        message: &[u8],
+        aux: &[u8; 32],
+    ) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
+        let k = finalize_scalar(
+            tagged::<D, T>()
+                .chain_update(DIGEST_NONCE_DOMAIN)
+                .chain_update(aux)
+                .chain_update(pubkey.x())
+                .chain_update(message),
+        );
+        let r = crate::backend::mul_g(&k).map_err(|_| Secp256k1SchnorrError::InvalidNonce)?;
+        Ok((k, r))
+    }
+}
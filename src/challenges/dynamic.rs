@@ -0,0 +1,125 @@
+use solana_secp256k1::{CompressedPoint, Curve, Secp256k1Point};
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify, check_nonce, errors::Secp256k1SchnorrError, is_zero_mod_n,
+    recover_nonce, scalar::Scalar, xonly_point, Secp256k1SchnorrSignature,
+};
+
+/// ### DynChallenge
+///
+/// An object-safe counterpart to `Secp256k1SchnorrVerify`, allowing host applications to select
+/// verification schemes at runtime, for instance from configuration, rather than monomorphizing
+/// every possibility at compile time.
+///
+/// Every `Secp256k1SchnorrVerify` implementation is a `DynChallenge`, and `FnChallenge` adapts a
+/// pair of plain values for schemes defined at runtime.
+pub trait DynChallenge {
+    /// Whether the scheme commits to an even 𝑅, as with `Secp256k1SchnorrVerify::EVEN_R`
+    fn even_r(&self) -> bool;
+
+    /// Whether the scheme identifies public keys by their 𝑋-coordinate alone, as with
+    /// `Secp256k1SchnorrVerify::XONLY`
+    fn xonly(&self) -> bool {
+        false
+    }
+
+    /// Whether the scheme commits to an 𝑅 with a square 𝑌-coordinate, as with
+    /// `Secp256k1SchnorrVerify::SQUARE_R`
+    fn square_r(&self) -> bool {
+        false
+    }
+
+    /// Computes the challenge reduced modulo 𝑁, as with `Secp256k1SchnorrVerify::challenge_scalar`
+    fn dyn_challenge(&self, r: &[u8; 32], pubkey: &CompressedPoint, message: &[u8]) -> [u8; 32];
+}
+
+impl<C: Secp256k1SchnorrVerify> DynChallenge for C {
+    fn even_r(&self) -> bool {
+        C::EVEN_R
+    }
+
+    fn xonly(&self) -> bool {
+        C::XONLY
+    }
+
+    fn square_r(&self) -> bool {
+        C::SQUARE_R
+    }
+
+    fn dyn_challenge(&self, r: &[u8; 32], pubkey: &CompressedPoint, message: &[u8]) -> [u8; 32] {
+        C::challenge_scalar(r, pubkey, message)
+    }
+}
+
+/// ### FnChallenge
+///
+/// A challenge scheme defined by a function pointer.
+#[derive(Debug, Clone, Copy)]
+pub struct FnChallenge {
+    pub even_r: bool,
+    pub challenge: fn(&[u8; 32], &CompressedPoint, &[u8]) -> [u8; 32],
+}
+
+impl DynChallenge for FnChallenge {
+    fn even_r(&self) -> bool {
+        self.even_r
+    }
+
+    fn dyn_challenge(&self, r: &[u8; 32], pubkey: &CompressedPoint, message: &[u8]) -> [u8; 32] {
+        Scalar::reduce(&(self.challenge)(r, pubkey, message)).to_bytes()
+    }
+}
+
+/// ### Scheme By Name
+///
+/// Looks up one of the crate's enabled challenge schemes by name: `bip340`, `keccak256` or
+/// `sha256`.
+pub fn scheme_by_name(name: &str) -> Option<&'static dyn DynChallenge> {
+    match name {
+        #[cfg(feature = "bip340")]
+        "bip340" => Some(&crate::challenges::bip340::BIP340Challenge),
+        #[cfg(feature = "keccak256")]
+        "keccak256" => Some(&crate::challenges::keccak256::Keccak256Challenge),
+        #[cfg(feature = "sha256")]
+        "sha256" => Some(&crate::challenges::sha256::Sha256Challenge),
+        _ => None,
+    }
+}
+
+impl Secp256k1SchnorrSignature {
+    /// ### Verify Dyn
+    /// Verifies a signature as with `verify`, under a challenge scheme chosen at runtime.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::challenges::dynamic::scheme_by_name;
+    ///
+    /// let scheme = scheme_by_name(&config.scheme).expect("Unknown scheme");
+    /// schnorr_signature.verify_dyn(scheme, &message, &pubkey)
+    ///     .expect("Invalid signature");
+    /// ```
+    pub fn verify_dyn<T: Secp256k1Point>(
+        &self,
+        scheme: &dyn DynChallenge,
+        message: &[u8],
+        pubkey: &T,
+    ) -> Result<(), Secp256k1SchnorrError> {
+        if scheme.xonly() && pubkey.is_odd() {
+            return self.verify_dyn::<CompressedPoint>(scheme, message, &xonly_point(pubkey));
+        }
+        self.check_range()?;
+        // Px must be a valid field element
+        if pubkey.x().ge(&Curve::P) {
+            return Err(Secp256k1SchnorrError::InvalidPublicKey);
+        }
+        let e = scheme.dyn_challenge(&self.r(), &pubkey.compress(), message);
+        if is_zero_mod_n(&e) {
+            return Err(Secp256k1SchnorrError::InvalidChallenge);
+        }
+
+        // R = s*G - e*P
+        let r = recover_nonce(&self.s(), &e, pubkey)?;
+
+        check_nonce(&self.r(), scheme.even_r(), scheme.square_r(), &r)
+    }
+}
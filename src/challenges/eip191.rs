@@ -0,0 +1,85 @@
+use crate::*;
+
+use solana_nostd_keccak::{hash, hashv};
+
+// Prefix of the EIP-191 personal_sign digest, followed by the decimal message length
+const EIP191_PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n";
+
+// Domain separator mixed into nonce derivation, as for `Keccak256Challenge`
+const EIP191_NONCE_DOMAIN: &[u8] = b"solana-secp256k1-schnorr/eip191/nonce";
+
+/// Writes `len` in decimal ASCII to the end of `buf`, returning the digits written.
+fn decimal(mut len: usize, buf: &mut [u8; 20]) -> &[u8] {
+    let mut i = buf.len();
+    loop {
+        i -= 1;
+        buf[i] = b'0' + (len % 10) as u8;
+        len /= 10;
+        if len == 0 {
+            return &buf[i..];
+        }
+    }
+}
+
+/// ### EIP-191 Digest
+///
+/// Computes the `personal_sign` digest
+/// `keccak256("\x19Ethereum Signed Message:\n"∥len(𝑀)∥𝑀)` of the concatenation of message
+/// `parts`, with the length in decimal ASCII.
+pub fn eip191_digest(parts: &[&[u8]]) -> [u8; 32] {
+    let mut buf = [0u8; 20];
+    let len = decimal(parts.iter().map(|part| part.len()).sum(), &mut buf);
+    challenges::with_parts(&[EIP191_PREFIX, len], parts, hashv)
+}
+
+/// ### Eip191Challenge
+///
+/// Computes the challenge `𝑒 = keccak256(𝑟∥𝑃ₓ∥eip191_digest(𝑀))`, so that messages signed by
+/// EVM wallet-style Schnorr signers, which prefix every message as `personal_sign` does, verify
+/// identically on Solana.
+pub struct Eip191Challenge;
+
+impl Secp256k1SchnorrVerify for Eip191Challenge {
+    fn challenge<T: Secp256k1Point>(r: &[u8; 32], pubkey: &T, message: &[u8]) -> [u8; 32] {
+        Self::challenge_parts(r, pubkey, &[message])
+    }
+
+    fn challenge_parts<T: Secp256k1Point>(r: &[u8; 32], pubkey: &T, parts: &[&[u8]]) -> [u8; 32] {
+        hashv(&[r, &pubkey.x(), &eip191_digest(parts)])
+    }
+
+    fn challenge_compute_units(message_len: usize) -> u64 {
+        let mut buf = [0u8; 20];
+        let len = decimal(message_len, &mut buf).len();
+        compute::hash_compute_units(&[EIP191_PREFIX.len(), len, message_len])
+            + compute::hash_compute_units(&[32, 32, 32])
+    }
+}
+
+impl Secp256k1SchnorrSign for Eip191Challenge {
+    fn aux_randomness(secret_key: &[u8; 32], aux: &[u8; 32]) -> [u8; 32] {
+        let mut t = hash(aux);
+        for (a, b) in t.iter_mut().zip(secret_key.iter()) {
+            *a ^= b
+        }
+        t
+    }
+
+    fn nonce<T: Secp256k1Point>(
+        pubkey: &T,
+        message: &[u8],
+        aux: &[u8; 32],
+    ) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
+        Self::nonce_parts(pubkey, &[message], aux)
+    }
+
+    fn nonce_parts<T: Secp256k1Point>(
+        pubkey: &T,
+        parts: &[&[u8]],
+        aux: &[u8; 32],
+    ) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
+        let k = challenges::with_parts(&[EIP191_NONCE_DOMAIN, aux, &pubkey.x()], parts, hashv);
+        let r = crate::backend::mul_g(&k).map_err(|_| Secp256k1SchnorrError::InvalidNonce)?;
+        Ok((k, r))
+    }
+}
@@ -0,0 +1,87 @@
+use crate::*;
+
+use solana_nostd_keccak::{hash, hashv};
+
+// Prefix of the EIP-712 signing digest
+const EIP712_PREFIX: &[u8] = b"\x19\x01";
+
+// Domain separator mixed into nonce derivation, as for `Keccak256Challenge`
+const EIP712_NONCE_DOMAIN: &[u8] = b"solana-secp256k1-schnorr/eip712/nonce";
+
+/// ### EIP-712 Digest
+///
+/// Computes the EIP-712 signing digest `keccak256(0x19∥0x01∥domainSeparator∥hashStruct(message))`
+/// from a precomputed domain separator and struct hash.
+pub fn eip712_digest(domain_separator: &[u8; 32], struct_hash: &[u8; 32]) -> [u8; 32] {
+    hashv(&[EIP712_PREFIX, domain_separator, struct_hash])
+}
+
+/// ### EIP-712 Message
+///
+/// Returns the message signed under `Eip712Challenge` for typed data, the concatenation
+/// `domainSeparator∥hashStruct(message)`.
+pub fn eip712_message(domain_separator: &[u8; 32], struct_hash: &[u8; 32]) -> [u8; 64] {
+    let mut message = [0u8; 64];
+    message[..32].clone_from_slice(domain_separator);
+    message[32..].clone_from_slice(struct_hash);
+    message
+}
+
+/// ### Eip712Challenge
+///
+/// Computes the challenge `𝑒 = keccak256(𝑟∥𝑃ₓ∥keccak256(0x19∥0x01∥𝑀))` over typed data, where the
+/// message 𝑀 is `domainSeparator∥hashStruct(message)` as returned by `eip712_message`, so that
+/// the signed digest is exactly the one Ethereum tooling derives from the typed data.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::challenges::eip712::{eip712_message, Eip712Challenge};
+///
+/// let message = eip712_message(&domain_separator, &struct_hash);
+/// signature.verify::<Eip712Challenge, CompressedPoint>(&message, &pubkey)?;
+/// ```
+pub struct Eip712Challenge;
+
+impl Secp256k1SchnorrVerify for Eip712Challenge {
+    fn challenge<T: Secp256k1Point>(r: &[u8; 32], pubkey: &T, message: &[u8]) -> [u8; 32] {
+        Self::challenge_parts(r, pubkey, &[message])
+    }
+
+    fn challenge_parts<T: Secp256k1Point>(r: &[u8; 32], pubkey: &T, parts: &[&[u8]]) -> [u8; 32] {
+        let digest = challenges::with_parts(&[EIP712_PREFIX], parts, hashv);
+        hashv(&[r, &pubkey.x(), &digest])
+    }
+
+    fn challenge_compute_units(message_len: usize) -> u64 {
+        compute::hash_compute_units(&[EIP712_PREFIX.len(), message_len])
+            + compute::hash_compute_units(&[32, 32, 32])
+    }
+}
+
+impl Secp256k1SchnorrSign for Eip712Challenge {
+    fn aux_randomness(secret_key: &[u8; 32], aux: &[u8; 32]) -> [u8; 32] {
+        let mut t = hash(aux);
+        for (a, b) in t.iter_mut().zip(secret_key.iter()) {
+            *a ^= b
+        }
+        t
+    }
+
+    fn nonce<T: Secp256k1Point>(
+        pubkey: &T,
+        message: &[u8],
+        aux: &[u8; 32],
+    ) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
+        Self::nonce_parts(pubkey, &[message], aux)
+    }
+
+    fn nonce_parts<T: Secp256k1Point>(
+        pubkey: &T,
+        parts: &[&[u8]],
+        aux: &[u8; 32],
+    ) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
+        let k = challenges::with_parts(&[EIP712_NONCE_DOMAIN, aux, &pubkey.x()], parts, hashv);
+        let r = crate::backend::mul_g(&k).map_err(|_| Secp256k1SchnorrError::InvalidNonce)?;
+        Ok((k, r))
+    }
+}
@@ -2,12 +2,23 @@ use crate::*;
 
 use solana_nostd_keccak::hashv;
 
+// Domain separator mixed into nonce derivation so that signing the same message with the same key
+// under another challenge scheme can never produce a related nonce
+const KECCAK256_NONCE_DOMAIN: &[u8] = b"solana-secp256k1-schnorr/keccak256/nonce";
+
 pub struct Keccak256Challenge;
 
 impl Secp256k1SchnorrVerify for Keccak256Challenge {
     fn challenge<T: Secp256k1Point>(r: &[u8; 32], pubkey: &T, message: &[u8]) -> [u8; 32] {
         hashv(&[r, &pubkey.x(), message])
     }
+
+    fn challenge_parts<T: Secp256k1Point>(r: &[u8; 32], pubkey: &T, parts: &[&[u8]]) -> [u8; 32] {
+        if let [message] = parts {
+            return Self::challenge(r, pubkey, message);
+        }
+        challenges::with_parts(&[r, &pubkey.x()], parts, hashv)
+    }
 }
 
 impl Secp256k1SchnorrSign for Keccak256Challenge {
@@ -24,8 +35,16 @@ impl Secp256k1SchnorrSign for Keccak256Challenge {
         message: &[u8],
         aux: &[u8; 32],
     ) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
-        let k = hashv(&[aux, pubkey.x().as_ref(), message]);
-        let r = Curve::mul_g(&k).map_err(|_| Secp256k1SchnorrError::InvalidNonce)?;
+        Self::nonce_parts(pubkey, &[message], aux)
+    }
+
+    fn nonce_parts<T: Secp256k1Point>(
+        pubkey: &T,
+        parts: &[&[u8]],
+        aux: &[u8; 32],
+    ) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
+        let k = challenges::with_parts(&[KECCAK256_NONCE_DOMAIN, aux, &pubkey.x()], parts, hashv);
+        let r = crate::backend::mul_g(&k).map_err(|_| Secp256k1SchnorrError::InvalidNonce)?;
         Ok((k, r))
     }
 }
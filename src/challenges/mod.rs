@@ -1,6 +1,39 @@
 use solana_secp256k1::{Secp256k1Point, UncompressedPoint};
 
-use crate::errors::Secp256k1SchnorrError;
+use crate::{compute, errors::Secp256k1SchnorrError, scalar::Scalar};
+
+/// Number of slices, including a scheme's own prefix, hashed on the stack by `with_parts`
+#[cfg(any(
+    feature = "bip340",
+    feature = "keccak256",
+    feature = "sha256",
+    feature = "tagged"
+))]
+const INLINE_PARTS: usize = 16;
+
+/// Calls `hash` with `prefix` followed by message `parts` as a single list of slices, for use
+/// with `hashv`. The list is built on the stack unless it exceeds `INLINE_PARTS` slices, so
+/// hashing never allocates for typical messages.
+#[cfg(any(
+    feature = "bip340",
+    feature = "keccak256",
+    feature = "sha256",
+    feature = "tagged"
+))]
+pub(crate) fn with_parts<R>(
+    prefix: &[&[u8]],
+    parts: &[&[u8]],
+    hash: impl FnOnce(&[&[u8]]) -> R,
+) -> R {
+    let len = prefix.len() + parts.len();
+    if len > INLINE_PARTS {
+        return hash(&[prefix, parts].concat());
+    }
+    let mut data: [&[u8]; INLINE_PARTS] = [&[]; INLINE_PARTS];
+    data[..prefix.len()].copy_from_slice(prefix);
+    data[prefix.len()..len].copy_from_slice(parts);
+    hash(&data[..len])
+}
 
 /// ### Secp256k1SchnorrChallenge
 ///
@@ -14,7 +47,74 @@ use crate::errors::Secp256k1SchnorrError;
 ///
 /// Different Schnorr-based signing protocols may wish to generate challenges in a different way depending upon their specific use case, as such, we implement a trait to enable flexibilty to support as many use cases as possible.
 pub trait Secp256k1SchnorrVerify: Sized {
+    /// ### Even R
+    ///
+    /// Schemes such as BIP340 commit to an 𝑅 with an even 𝑌-coordinate, making its parity part of
+    /// the signature semantics. When set, verification rejects signatures whose recovered 𝑅 is odd,
+    /// rather than comparing 𝑋-coordinates alone.
+    const EVEN_R: bool = false;
+
+    /// ### X-Only
+    ///
+    /// Schemes such as BIP340 identify public keys by their 𝑋-coordinate alone. When set, signing
+    /// negates the secret key of any public key with an odd 𝑌-coordinate, and verification treats
+    /// every public key as the point with an even 𝑌-coordinate sharing its 𝑋-coordinate, so that
+    /// signatures interoperate with reference implementations of the scheme.
+    const XONLY: bool = false;
+
+    /// ### Square R
+    ///
+    /// Schemes such as Bitcoin Cash's commit to an 𝑅 whose 𝑌-coordinate is a quadratic residue
+    /// modulo 𝑃 rather than an even one. When set, verification rejects signatures whose recovered
+    /// 𝑅 has a non-square 𝑌-coordinate, at the cost of a field exponentiation.
+    const SQUARE_R: bool = false;
+
+    /// ### Challenge Compute Units
+    ///
+    /// Estimates the compute units consumed on-chain by `challenge` over a `message_len`-byte
+    /// message. The default charges a single hash syscall over `𝑟∥𝑃ₓ∥𝑀`, as the SHA256 and
+    /// Keccak256 schemes compute. Schemes hashing in program code rather than with a syscall,
+    /// such as `DigestChallenge`, cost considerably more than estimated and should be measured.
+    fn challenge_compute_units(message_len: usize) -> u64 {
+        compute::hash_compute_units(&[32, 32, message_len])
+    }
+
+    /// ### Challenge
+    ///
+    /// Returns the raw 32-byte hash 𝐻(𝑟∥𝑃∥𝑀), which may exceed the curve order 𝑁.
     fn challenge<T: Secp256k1Point>(r: &[u8; 32], pubkey: &T, message: &[u8]) -> [u8; 32];
+
+    /// ### Challenge Scalar
+    ///
+    /// Returns the challenge reduced modulo 𝑁, which is the scalar 𝑒 signing and verification
+    /// actually use. Hash outputs of 𝑁 or above are reduced as they are in libsecp256k1, rather
+    /// than being used as unreduced scalars.
+    fn challenge_scalar<T: Secp256k1Point>(r: &[u8; 32], pubkey: &T, message: &[u8]) -> [u8; 32] {
+        Scalar::reduce(&Self::challenge(r, pubkey, message)).to_bytes()
+    }
+
+    /// ### Challenge Parts
+    ///
+    /// Returns the raw challenge over the concatenation of message `parts`, which must equal
+    /// `challenge` over the concatenated message. Schemes hashing with `hashv` override this to
+    /// hash the parts in place without allocating, while the default concatenates any more than one part.
+    fn challenge_parts<T: Secp256k1Point>(r: &[u8; 32], pubkey: &T, parts: &[&[u8]]) -> [u8; 32] {
+        match parts {
+            [message] => Self::challenge(r, pubkey, message),
+            parts => Self::challenge(r, pubkey, &parts.concat()),
+        }
+    }
+
+    /// ### Challenge Scalar Parts
+    ///
+    /// Returns the challenge over message `parts` reduced modulo 𝑁, as `challenge_scalar`.
+    fn challenge_scalar_parts<T: Secp256k1Point>(
+        r: &[u8; 32],
+        pubkey: &T,
+        parts: &[&[u8]],
+    ) -> [u8; 32] {
+        Scalar::reduce(&Self::challenge_parts(r, pubkey, parts)).to_bytes()
+    }
 }
 
 /// Scep256k1SchnorrNonce
@@ -40,13 +140,48 @@ pub trait Secp256k1SchnorrSign: Sized + Secp256k1SchnorrVerify {
         message: &[u8],
         aux: &[u8; 32],
     ) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError>;
+
+    /// ### Nonce Parts
+    ///
+    /// Derives the nonce for message `parts`, which must equal `nonce` over the concatenated
+    /// message, with the default concatenating any more than one part as `challenge_parts`.
+    fn nonce_parts<T: Secp256k1Point>(
+        pubkey: &T,
+        parts: &[&[u8]],
+        aux: &[u8; 32],
+    ) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
+        match parts {
+            [message] => Self::nonce(pubkey, message, aux),
+            parts => Self::nonce(pubkey, &parts.concat(), aux),
+        }
+    }
 }
 
+#[cfg(feature = "bch")]
+pub mod bch;
+
 #[cfg(feature = "bip340")]
 pub mod bip340;
 
+#[cfg(feature = "bsm")]
+pub mod bsm;
+
+#[cfg(feature = "digest")]
+pub mod digest;
+
+pub mod dynamic;
+
 #[cfg(feature = "sha256")]
 pub mod sha256;
 
 #[cfg(feature = "keccak256")]
 pub mod keccak256;
+
+#[cfg(feature = "eip191")]
+pub mod eip191;
+
+#[cfg(feature = "eip712")]
+pub mod eip712;
+
+#[cfg(feature = "tagged")]
+pub mod tagged;
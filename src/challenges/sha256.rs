@@ -1,15 +1,24 @@
 use crate::*;
 
 use solana_nostd_sha256::hashv;
+
+// Domain separator mixed into nonce derivation so that signing the same message with the same key
+// under another challenge scheme can never produce a related nonce
+const SHA256_NONCE_DOMAIN: &[u8] = b"solana-secp256k1-schnorr/sha256/nonce";
+
 pub struct Sha256Challenge;
 
 impl Secp256k1SchnorrVerify for Sha256Challenge {
     fn challenge<T: Secp256k1Point>(r: &[u8; 32], pubkey: &T, message: &[u8]) -> [u8; 32] {
-        let mut m = r.to_vec();
-        m.extend_from_slice(&pubkey.x());
-        m.extend_from_slice(message);
         hashv(&[r.as_ref(), &pubkey.x(), message])
     }
+
+    fn challenge_parts<T: Secp256k1Point>(r: &[u8; 32], pubkey: &T, parts: &[&[u8]]) -> [u8; 32] {
+        if let [message] = parts {
+            return Self::challenge(r, pubkey, message);
+        }
+        challenges::with_parts(&[r, &pubkey.x()], parts, hashv)
+    }
 }
 
 impl Secp256k1SchnorrSign for Sha256Challenge {
@@ -26,8 +35,16 @@ impl Secp256k1SchnorrSign for Sha256Challenge {
         message: &[u8],
         aux: &[u8; 32],
     ) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
-        let k = hashv(&[aux, pubkey.x().as_ref(), message]);
-        let r = Curve::mul_g(&k).map_err(|_| Secp256k1SchnorrError::InvalidNonce)?;
+        Self::nonce_parts(pubkey, &[message], aux)
+    }
+
+    fn nonce_parts<T: Secp256k1Point>(
+        pubkey: &T,
+        parts: &[&[u8]],
+        aux: &[u8; 32],
+    ) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
+        let k = challenges::with_parts(&[SHA256_NONCE_DOMAIN, aux, &pubkey.x()], parts, hashv);
+        let r = crate::backend::mul_g(&k).map_err(|_| Secp256k1SchnorrError::InvalidNonce)?;
         Ok((k, r))
     }
 }
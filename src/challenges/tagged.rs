@@ -0,0 +1,183 @@
+use core::marker::PhantomData;
+
+use solana_nostd_sha256::hashv;
+use solana_secp256k1::{Curve, Secp256k1Point, UncompressedPoint};
+
+use crate::{
+    challenges::{self, Secp256k1SchnorrSign, Secp256k1SchnorrVerify},
+    compute,
+    errors::Secp256k1SchnorrError,
+};
+
+/// ### ChallengeTag
+///
+/// The domain of a `TaggedChallenge`, such as `"MyProtocol"`, from which the tags
+/// `MyProtocol/challenge`, `MyProtocol/aux` and `MyProtocol/nonce` are derived as BIP340 derives
+/// its own from `BIP0340`.
+///
+/// The tag hashes are computed on every call by default. Implementors may override them with
+/// precomputed constants, as `bip340.rs` does, to save a hash per signature on-chain.
+pub trait ChallengeTag {
+    const TAG: &'static str;
+
+    /// sha256(TAG/challenge)
+    fn challenge_tag() -> [u8; 32] {
+        hashv(&[Self::TAG.as_bytes(), b"/challenge"])
+    }
+
+    /// sha256(TAG/aux)
+    fn aux_tag() -> [u8; 32] {
+        hashv(&[Self::TAG.as_bytes(), b"/aux"])
+    }
+
+    /// sha256(TAG/nonce)
+    fn nonce_tag() -> [u8; 32] {
+        hashv(&[Self::TAG.as_bytes(), b"/nonce"])
+    }
+}
+
+/// ### TaggedChallenge
+///
+/// A BIP340-style challenge scheme domain separated by the tags of `T`, computing
+/// `𝑒 = 𝐻(𝐻(T/challenge)∥𝐻(T/challenge)∥𝑟∥𝑃ₓ∥𝑀)` with 𝑋-only public keys and even 𝑅, and deriving
+/// nonces and auxiliary randomness from its own tags. With the tag `"BIP0340"` it is exactly
+/// `BIP340Challenge`.
+///
+/// Most protocols should declare their scheme with `define_tagged_challenge!` instead.
+pub struct TaggedChallenge<T>(PhantomData<T>);
+
+impl<T: ChallengeTag> Secp256k1SchnorrVerify for TaggedChallenge<T> {
+    const EVEN_R: bool = true;
+    const XONLY: bool = true;
+
+    fn challenge<P: Secp256k1Point>(r: &[u8; 32], pubkey: &P, message: &[u8]) -> [u8; 32] {
+        Self::challenge_parts(r, pubkey, &[message])
+    }
+
+    fn challenge_parts<P: Secp256k1Point>(r: &[u8; 32], pubkey: &P, parts: &[&[u8]]) -> [u8; 32] {
+        let tag = T::challenge_tag();
+        let x = pubkey.x();
+        challenges::with_parts(&[&tag, &tag, r, &x], parts, hashv)
+    }
+
+    /// Charges for hashing the challenge tag on every call, as `ChallengeTag` does by default
+    fn challenge_compute_units(message_len: usize) -> u64 {
+        compute::hash_compute_units(&[T::TAG.len(), b"/challenge".len()])
+            + compute::hash_compute_units(&[32, 32, 32, 32, message_len])
+    }
+}
+
+impl<T: ChallengeTag> Secp256k1SchnorrSign for TaggedChallenge<T> {
+    fn aux_randomness(privkey: &[u8; 32], aux: &[u8; 32]) -> [u8; 32] {
+        let tag = T::aux_tag();
+        let mut t = hashv(&[tag.as_ref(), tag.as_ref(), aux]);
+        for (a, b) in t.iter_mut().zip(privkey.iter()) {
+            *a ^= b
+        }
+        t
+    }
+
+    fn nonce<P: Secp256k1Point>(
+        pubkey: &P,
+        message: &[u8],
+        aux: &[u8; 32],
+    ) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
+        Self::nonce_parts(pubkey, &[message], aux)
+    }
+
+    fn nonce_parts<P: Secp256k1Point>(
+        pubkey: &P,
+        parts: &[&[u8]],
+        aux: &[u8; 32],
+    ) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
+        let tag = T::nonce_tag();
+        let x = pubkey.x();
+        let mut k = challenges::with_parts(&[&tag, &tag, aux, &x], parts, hashv);
+        let mut r = crate::backend::mul_g(&k).map_err(|_| Secp256k1SchnorrError::InvalidNonce)?;
+
+        if r.is_odd() {
+            Curve::negate_n_assign(&mut k);
+            r.invert();
+        }
+
+        Ok((k, r))
+    }
+}
+
+/// ### Define Tagged Challenge
+///
+/// Declares a challenge scheme as a unit struct, domain separated by the tags derived from a
+/// protocol name as `TaggedChallenge`.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::define_tagged_challenge;
+///
+/// define_tagged_challenge!(pub MyProtocolChallenge, "MyProtocol");
+///
+/// let signature = Secp256k1SchnorrSignature::sign::<MyProtocolChallenge>(message, &privkey)?;
+/// ```
+#[macro_export]
+macro_rules! define_tagged_challenge {
+    ($(#[$meta:meta])* $vis:vis $name:ident, $tag:literal) => {
+        $(#[$meta])*
+        $vis struct $name;
+
+        impl $crate::challenges::tagged::ChallengeTag for $name {
+            const TAG: &'static str = $tag;
+        }
+
+        impl $crate::challenges::Secp256k1SchnorrVerify for $name {
+            const EVEN_R: bool = true;
+            const XONLY: bool = true;
+
+            fn challenge<P: $crate::prelude::Secp256k1Point>(
+                r: &[u8; 32],
+                pubkey: &P,
+                message: &[u8],
+            ) -> [u8; 32] {
+                <$crate::challenges::tagged::TaggedChallenge<Self> as $crate::challenges::Secp256k1SchnorrVerify>::challenge(r, pubkey, message)
+            }
+
+            fn challenge_parts<P: $crate::prelude::Secp256k1Point>(
+                r: &[u8; 32],
+                pubkey: &P,
+                parts: &[&[u8]],
+            ) -> [u8; 32] {
+                <$crate::challenges::tagged::TaggedChallenge<Self> as $crate::challenges::Secp256k1SchnorrVerify>::challenge_parts(r, pubkey, parts)
+            }
+
+            fn challenge_compute_units(message_len: usize) -> u64 {
+                <$crate::challenges::tagged::TaggedChallenge<Self> as $crate::challenges::Secp256k1SchnorrVerify>::challenge_compute_units(message_len)
+            }
+        }
+
+        impl $crate::challenges::Secp256k1SchnorrSign for $name {
+            fn aux_randomness(privkey: &[u8; 32], aux: &[u8; 32]) -> [u8; 32] {
+                <$crate::challenges::tagged::TaggedChallenge<Self> as $crate::challenges::Secp256k1SchnorrSign>::aux_randomness(privkey, aux)
+            }
+
+            fn nonce<P: $crate::prelude::Secp256k1Point>(
+                pubkey: &P,
+                message: &[u8],
+                aux: &[u8; 32],
+            ) -> Result<
+                ([u8; 32], $crate::prelude::UncompressedPoint),
+                $crate::errors::Secp256k1SchnorrError,
+            > {
+                <$crate::challenges::tagged::TaggedChallenge<Self> as $crate::challenges::Secp256k1SchnorrSign>::nonce(pubkey, message, aux)
+            }
+
+            fn nonce_parts<P: $crate::prelude::Secp256k1Point>(
+                pubkey: &P,
+                parts: &[&[u8]],
+                aux: &[u8; 32],
+            ) -> Result<
+                ([u8; 32], $crate::prelude::UncompressedPoint),
+                $crate::errors::Secp256k1SchnorrError,
+            > {
+                <$crate::challenges::tagged::TaggedChallenge<Self> as $crate::challenges::Secp256k1SchnorrSign>::nonce_parts(pubkey, parts, aux)
+            }
+        }
+    };
+}
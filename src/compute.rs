@@ -0,0 +1,56 @@
+/// Compute units charged by the `sol_secp256k1_recover` syscall
+pub const SECP256K1_RECOVER_COMPUTE_UNITS: u64 = 25_000;
+
+/// Compute units charged by the `sol_sha256` and `sol_keccak256` syscalls on every call
+pub const HASH_BASE_COMPUTE_UNITS: u64 = 85;
+
+/// Minimum compute units charged by the hash syscalls per hashed slice, which otherwise charge
+/// one compute unit per two bytes
+pub const HASH_SLICE_MIN_COMPUTE_UNITS: u64 = 10;
+
+/// Allowance for the program-side work of a single verification: range checks, reducing the
+/// challenge, the scalar multiplications and negations preparing the recover operands, and
+/// comparing the recovered nonce. Scalar arithmetic runs on heap-allocated big integers, making
+/// it the bulk of the cost besides the recover syscall itself.
+pub const VERIFY_ARITHMETIC_COMPUTE_UNITS: u64 = 12_000;
+
+/// Allowance for a single multiplication modulo 𝑃, of which verifying a square 𝑅 performs 505
+pub const FIELD_MUL_COMPUTE_UNITS: u64 = 1_000;
+
+/// Field multiplications performed by Euler's criterion over (𝑃-1)/2, one squaring per bit and
+/// one multiplication per set bit
+const SQUARE_CHECK_FIELD_MULS: u64 = 256 + 249;
+
+/// ### Hash Compute Units
+///
+/// Returns the compute units charged by the `sol_sha256` and `sol_keccak256` syscalls for
+/// hashing slices of the given lengths, as the runtime's cost schedule defines them.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::compute::hash_compute_units;
+///
+/// // sha256(r∥Px∥M) over a 100-byte message
+/// let units = hash_compute_units(&[32, 32, 100]);
+/// ```
+pub const fn hash_compute_units(lens: &[usize]) -> u64 {
+    let mut units = HASH_BASE_COMPUTE_UNITS;
+    let mut i = 0;
+    while i < lens.len() {
+        let bytes = (lens[i] / 2) as u64;
+        units += match bytes > HASH_SLICE_MIN_COMPUTE_UNITS {
+            true => bytes,
+            false => HASH_SLICE_MIN_COMPUTE_UNITS,
+        };
+        i += 1;
+    }
+    units
+}
+
+/// ### Square Check Compute Units
+///
+/// Returns the allowance for checking that a recovered 𝑅 has a square 𝑌-coordinate, as schemes
+/// setting `SQUARE_R` do on every verification.
+pub const fn square_check_compute_units() -> u64 {
+    SQUARE_CHECK_FIELD_MULS * FIELD_MUL_COMPUTE_UNITS
+}
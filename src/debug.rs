@@ -0,0 +1,20 @@
+/// Prefix of every log line, so that failures stand out among the logs of the calling program
+#[cfg(target_os = "solana")]
+const DEBUG_LOG_PREFIX: &str = "secp256k1-schnorr: ";
+
+/// Logs the stage at which a verification failed with `sol_log`. Off-chain this does nothing, so
+/// host tests and clients are unaffected by the feature flag.
+#[inline(always)]
+pub(crate) fn log(stage: &str) {
+    #[cfg(target_os = "solana")]
+    {
+        let mut line = [0u8; 96];
+        let len = (DEBUG_LOG_PREFIX.len() + stage.len()).min(line.len());
+        line[..DEBUG_LOG_PREFIX.len()].clone_from_slice(DEBUG_LOG_PREFIX.as_bytes());
+        line[DEBUG_LOG_PREFIX.len()..len]
+            .clone_from_slice(&stage.as_bytes()[..len - DEBUG_LOG_PREFIX.len()]);
+        unsafe { solana_define_syscall::definitions::sol_log_(line.as_ptr(), len as u64) }
+    }
+    #[cfg(not(target_os = "solana"))]
+    let _ = stage;
+}
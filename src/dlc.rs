@@ -0,0 +1,171 @@
+use solana_secp256k1::{CompressedPoint, Secp256k1Point, UncompressedPoint};
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify, ecmul, errors::Secp256k1SchnorrError, xonly_point,
+    Secp256k1SchnorrSignature,
+};
+
+#[cfg(feature = "sign")]
+use solana_secp256k1::Curve;
+
+#[cfg(feature = "sign")]
+use crate::{challenges::Secp256k1SchnorrSign, scalar::Scalar};
+
+/// Domain separator for deriving an oracle's nonce for an event
+#[cfg(feature = "sign")]
+const DLC_NONCE_DOMAIN: &[u8] = b"solana-secp256k1-schnorr/dlc/nonce";
+
+/// # OracleNonce
+/// An oracle's secret nonce 𝑘 for a single event, as generated by `announce`. It is consumed by
+/// `attest`, as attesting to two outcomes with the same nonce leaks the oracle's private key.
+#[cfg(feature = "sign")]
+pub struct OracleNonce {
+    k: [u8; 32],
+}
+
+#[cfg(all(feature = "sign", feature = "hardening"))]
+impl Drop for OracleNonce {
+    fn drop(&mut self) {
+        crate::hardening::zeroize(&mut self.k);
+    }
+}
+
+/// ### Announce
+///
+/// Derives the oracle's nonce for `event_id`, hedged with 32 bytes of fresh auxiliary randomness,
+/// returning it along with the nonce point 𝑅 = 𝑘⋅𝐺 that the oracle announces ahead of the event.
+/// Schemes committing to an even 𝑅 are announced the even point, negating 𝑘 as needed.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::{dlc, BIP340Challenge};
+///
+/// let (nonce, nonce_point) = dlc::announce::<BIP340Challenge>(&privkey, b"btcusd-2026-10-14", &aux)?;
+/// // Publish nonce_point, and later attest with dlc::attest(nonce, &privkey, outcome)
+/// ```
+#[cfg(feature = "sign")]
+pub fn announce<C: Secp256k1SchnorrSign>(
+    privkey: &[u8; 32],
+    event_id: &[u8],
+    aux: &[u8; 32],
+) -> Result<(OracleNonce, CompressedPoint), Secp256k1SchnorrError> {
+    let k = Scalar::reduce(&solana_nostd_sha256::hashv(&[
+        DLC_NONCE_DOMAIN,
+        aux,
+        privkey,
+        event_id,
+    ]));
+    if k.is_zero() {
+        return Err(Secp256k1SchnorrError::InvalidNonce);
+    }
+    let mut nonce = OracleNonce { k: k.to_bytes() };
+    let mut r = ecmul::mul_g(&nonce.k)?;
+    if (C::EVEN_R && r.is_odd()) || (C::SQUARE_R && !crate::is_square_mod_p(&r.y())) {
+        Curve::negate_n_assign(&mut nonce.k);
+        r.invert();
+    }
+    Ok((nonce, r.compress()))
+}
+
+/// ### Attest
+///
+/// Attests to the `outcome` of an event with the nonce previously announced for it, producing
+/// the signature `𝑟∥𝑠` whose 𝑠 is the discrete logarithm of the outcome's anticipation point.
+///
+/// Outcomes are signed as given, so DLC clients hashing outcomes, such as with a tagged hash
+/// under the DLC specification, must pass the digest.
+#[cfg(feature = "sign")]
+pub fn attest<C: Secp256k1SchnorrSign>(
+    nonce: OracleNonce,
+    privkey: &[u8; 32],
+    outcome: &[u8],
+) -> Result<Secp256k1SchnorrSignature, Secp256k1SchnorrError> {
+    let mut pubkey = Curve::mul_g(privkey).map_err(|_| Secp256k1SchnorrError::InvalidSecretKey)?;
+
+    // X-only schemes sign with the secret key of the even-Y point sharing P's X-coordinate
+    #[allow(unused_mut)]
+    let mut d = *privkey;
+    if C::XONLY && pubkey.is_odd() {
+        Curve::negate_n_assign(&mut d);
+        pubkey.invert();
+    }
+
+    let r = ecmul::mul_g(&nonce.k)?.x();
+    let e = C::challenge_scalar(&r, &pubkey, outcome);
+    if crate::is_zero_mod_n(&e) {
+        return Err(Secp256k1SchnorrError::InvalidChallenge);
+    }
+    let s = Curve::add_mod_n(&nonce.k, &Curve::mul_mod_n(&e, &d));
+    #[cfg(feature = "hardening")]
+    crate::hardening::zeroize(&mut d);
+    Ok(Secp256k1SchnorrSignature::from_parts(r, s))
+}
+
+/// ### Anticipation Point
+///
+/// Computes the anticipation point 𝑆 = 𝑅 + 𝑒⋅𝑃 of an `outcome`, with 𝑒 the challenge of the
+/// oracle's attestation to it. 𝑆 = 𝑠⋅𝐺 is known before the event, so DLC clients lock each
+/// contract execution transaction to it, and the attestation's 𝑠 later unlocks exactly one.
+///
+/// Fails with `InvalidSignatureR` if `nonce_point` is not a valid announced nonce, and with
+/// `PointAtInfinity` if 𝑆 cancels out, which happens with negligible probability.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::{dlc, BIP340Challenge};
+///
+/// let point = dlc::anticipation_point::<BIP340Challenge, _>(&oracle_pubkey, &nonce_point, b"heads")?;
+/// ```
+pub fn anticipation_point<C: Secp256k1SchnorrVerify, T: Secp256k1Point>(
+    pubkey: &T,
+    nonce_point: &CompressedPoint,
+    outcome: &[u8],
+) -> Result<UncompressedPoint, Secp256k1SchnorrError> {
+    if C::XONLY && pubkey.is_odd() {
+        return anticipation_point::<C, CompressedPoint>(
+            &xonly_point(pubkey),
+            nonce_point,
+            outcome,
+        );
+    }
+    let r = UncompressedPoint::try_from(*nonce_point)
+        .map_err(|_| Secp256k1SchnorrError::InvalidSignatureR)?;
+    if C::EVEN_R && r.is_odd() {
+        return Err(Secp256k1SchnorrError::InvalidSignatureR);
+    }
+    let p = pubkey.decompress();
+    let e = C::challenge_scalar(&r.x(), &p, outcome);
+    let e_p = ecmul::mul(&p, &e)?;
+    match ecmul::accumulate(Some(r), e_p) {
+        Some(point) => Ok(point),
+        None => Err(Secp256k1SchnorrError::PointAtInfinity),
+    }
+}
+
+/// ### Verify Attestation
+///
+/// Verifies that `signature` attests to `outcome` under the oracle's `pubkey` with the announced
+/// `nonce_point`, as settlement programs must before paying out, since a valid signature under a
+/// different nonce would not match the contract's anticipation points.
+///
+/// Fails with `NonceMismatch` if the signature does not use the announced nonce, and otherwise
+/// as `verify`.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::{dlc, BIP340Challenge};
+///
+/// dlc::verify_attestation::<BIP340Challenge, _>(&attestation, &oracle_pubkey, &nonce_point, b"heads")
+///     .expect("Invalid attestation");
+/// ```
+pub fn verify_attestation<C: Secp256k1SchnorrVerify, T: Secp256k1Point>(
+    signature: &Secp256k1SchnorrSignature,
+    pubkey: &T,
+    nonce_point: &CompressedPoint,
+    outcome: &[u8],
+) -> Result<(), Secp256k1SchnorrError> {
+    if !crate::bytes_eq(signature.r_ref(), &nonce_point.x()) {
+        return Err(Secp256k1SchnorrError::NonceMismatch);
+    }
+    signature.verify::<C, T>(outcome, pubkey)
+}
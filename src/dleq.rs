@@ -0,0 +1,157 @@
+#[cfg(feature = "sign")]
+use solana_nostd_sha256::hashv;
+use solana_secp256k1::{Secp256k1Point, UncompressedPoint};
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify, ecmul, errors::Secp256k1SchnorrError, scalar::Scalar,
+};
+
+pub const DLEQ_PROOF_LENGTH: usize = 64;
+
+/// Domain separator for the nonces of proofs, hedging `aux` with the secret
+#[cfg(feature = "sign")]
+const DLEQ_NONCE_DOMAIN: &[u8] = b"solana-secp256k1-schnorr/dleq/nonce";
+
+/// Computes the Fiat-Shamir challenge over every point of the statement and both commitments,
+/// through the challenge scheme `C` with the first commitment as its 𝑟 and 𝐴 as its public key,
+/// so that proofs inherit the scheme's domain separation.
+fn challenge<C: Secp256k1SchnorrVerify>(
+    a: &UncompressedPoint,
+    h: &UncompressedPoint,
+    b: &UncompressedPoint,
+    a1: &UncompressedPoint,
+    a2: &UncompressedPoint,
+    context: &[u8],
+) -> [u8; 32] {
+    C::challenge_scalar_parts(
+        &a1.x(),
+        a,
+        &[
+            &a.compress().0,
+            &h.compress().0,
+            &b.compress().0,
+            &a1.compress().0,
+            &a2.compress().0,
+            context,
+        ],
+    )
+}
+
+/// # DleqProof
+/// A Chaum-Pedersen proof `𝑒∥𝑧` that two points 𝐴 = 𝑥⋅𝐺 and 𝐵 = 𝑥⋅𝐻 share the same discrete
+/// logarithm 𝑥, without revealing it. This is the building block with which an oracle proves that
+/// its attestation point matches its published nonce, or an adaptor signer proves that a
+/// pre-signature encrypts a known secret.
+///
+/// The challenge is computed with a `Secp256k1SchnorrVerify` scheme, which prover and verifier
+/// must agree upon along with the `context` the proof is bound to.
+pub struct DleqProof(pub [u8; DLEQ_PROOF_LENGTH]);
+
+impl DleqProof {
+    pub fn e(&self) -> [u8; 32] {
+        let mut e = [0u8; 32];
+        e.clone_from_slice(&self.0[..32]);
+        e
+    }
+
+    pub fn z(&self) -> [u8; 32] {
+        let mut z = [0u8; 32];
+        z.clone_from_slice(&self.0[32..]);
+        z
+    }
+
+    /// ### Prove
+    ///
+    /// Proves that 𝐴 = 𝑥⋅𝐺 and 𝐵 = 𝑥⋅𝐻 for the `secret` 𝑥, returning the proof along with 𝐴 and
+    /// 𝐵. The nonce is derived from the secret, the statement and 32 bytes of fresh auxiliary
+    /// randomness, as with `sign_with_aux`.
+    ///
+    /// Fails with `InvalidSecretKey` unless `secret` is a nonzero scalar.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::{dleq::DleqProof, BIP340Challenge};
+    ///
+    /// let (proof, a, b) = DleqProof::prove::<BIP340Challenge>(&secret, &h, b"oracle", &aux)?;
+    /// ```
+    #[cfg(feature = "sign")]
+    pub fn prove<C: Secp256k1SchnorrVerify>(
+        secret: &[u8; 32],
+        h: &UncompressedPoint,
+        context: &[u8],
+        aux: &[u8; 32],
+    ) -> Result<(Self, UncompressedPoint, UncompressedPoint), Secp256k1SchnorrError> {
+        let x = Scalar::new_nonzero(*secret)?;
+        let a = ecmul::mul_g(secret)?;
+        let b = ecmul::mul(h, secret)?;
+
+        #[allow(unused_mut)]
+        let mut k = Scalar::reduce(&hashv(&[
+            DLEQ_NONCE_DOMAIN,
+            aux,
+            secret,
+            &h.compress().0,
+            context,
+        ]))
+        .to_bytes();
+        if k.eq(&[0u8; 32]) {
+            return Err(Secp256k1SchnorrError::InvalidNonce);
+        }
+        let a1 = ecmul::mul_g(&k)?;
+        let a2 = ecmul::mul(h, &k)?;
+
+        let e = challenge::<C>(&a, h, &b, &a1, &a2, context);
+        // z = k + e*x
+        let z = Scalar::reduce(&k)
+            .add(&Scalar::reduce(&e).mul(&x))
+            .to_bytes();
+        #[cfg(feature = "hardening")]
+        crate::hardening::zeroize(&mut k);
+
+        let mut proof = [0u8; DLEQ_PROOF_LENGTH];
+        proof[..32].clone_from_slice(&e);
+        proof[32..].clone_from_slice(&z);
+        Ok((Self(proof), a, b))
+    }
+
+    /// ### Verify
+    ///
+    /// Verifies that log_𝐺(𝐴) = log_𝐻(𝐵), recomputing the commitments 𝐴₁ = 𝑧⋅𝐺 - 𝑒⋅𝐴 and
+    /// 𝐴₂ = 𝑧⋅𝐻 - 𝑒⋅𝐵 with three calls to `secp256k1_recover` and a point addition.
+    ///
+    /// Fails with `InvalidProof` if 𝑒 or 𝑧 is not a valid scalar, or if the proof does not hold.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::{dleq::DleqProof, BIP340Challenge};
+    ///
+    /// proof.verify::<BIP340Challenge>(&a, &h, &b, b"oracle").expect("Invalid proof");
+    /// ```
+    pub fn verify<C: Secp256k1SchnorrVerify>(
+        &self,
+        a: &UncompressedPoint,
+        h: &UncompressedPoint,
+        b: &UncompressedPoint,
+        context: &[u8],
+    ) -> Result<(), Secp256k1SchnorrError> {
+        let e = Scalar::new(self.e()).map_err(|_| Secp256k1SchnorrError::InvalidProof)?;
+        let z = Scalar::new(self.z()).map_err(|_| Secp256k1SchnorrError::InvalidProof)?;
+        let minus_e = e.negate().to_bytes();
+
+        // A₁ = z*G - e*A
+        let a1 = ecmul::mul_add_g(a, &minus_e, &z.to_bytes())
+            .map_err(|_| Secp256k1SchnorrError::InvalidProof)?;
+        // A₂ = z*H - e*B
+        let z_h = ecmul::mul(h, &z.to_bytes()).map_err(|_| Secp256k1SchnorrError::InvalidProof)?;
+        let e_b = ecmul::mul(b, &minus_e).map_err(|_| Secp256k1SchnorrError::InvalidProof)?;
+        if z_h.x() == e_b.x() && z_h.y() != e_b.y() {
+            return Err(Secp256k1SchnorrError::InvalidProof);
+        }
+        let a2 = z_h + e_b;
+
+        if challenge::<C>(a, h, b, &a1, &a2, context).ne(&e.to_bytes()) {
+            return Err(Secp256k1SchnorrError::InvalidProof);
+        }
+        Ok(())
+    }
+}
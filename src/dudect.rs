@@ -0,0 +1,139 @@
+use std::time::Instant;
+
+use crate::{challenges::Secp256k1SchnorrSign, Secp256k1SchnorrSignature};
+
+/// ### T Threshold
+///
+/// The Welch t-statistic above which dudect considers a timing difference between the two input
+/// classes statistically significant, indicating a likely leak.
+pub const T_THRESHOLD: f64 = 4.5;
+
+/// Percentiles at which measurements are cropped before testing. Cropping discards the long upper
+/// tail caused by preemption and cache misses, which otherwise dominates the variance and masks
+/// smaller, secret-dependent differences.
+const PERCENTILES: [f64; 5] = [0.5, 0.75, 0.9, 0.95, 1.0];
+
+/// ### Welch
+///
+/// Online accumulator for Welch's t-test between two classes of measurements.
+#[derive(Debug, Default, Clone, Copy)]
+struct Welch {
+    n: [f64; 2],
+    mean: [f64; 2],
+    m2: [f64; 2],
+}
+
+impl Welch {
+    fn push(&mut self, class: usize, x: f64) {
+        self.n[class] += 1.0;
+        let delta = x - self.mean[class];
+        self.mean[class] += delta / self.n[class];
+        self.m2[class] += delta * (x - self.mean[class]);
+    }
+
+    fn t(&self) -> f64 {
+        if self.n[0] < 2.0 || self.n[1] < 2.0 {
+            return 0.0;
+        }
+        let var0 = self.m2[0] / (self.n[0] - 1.0);
+        let var1 = self.m2[1] / (self.n[1] - 1.0);
+        let denominator = (var0 / self.n[0] + var1 / self.n[1]).sqrt();
+        if denominator == 0.0 {
+            return 0.0;
+        }
+        (self.mean[0] - self.mean[1]) / denominator
+    }
+}
+
+/// ### Timing Report
+///
+/// The result of a fixed-vs-random timing test. `max_t` is the largest absolute t-statistic
+/// across all cropping percentiles.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimingReport {
+    pub samples: usize,
+    pub max_t: f64,
+}
+
+impl TimingReport {
+    /// Returns true if no statistically significant timing difference was detected.
+    pub fn passed(&self) -> bool {
+        self.max_t.abs() < T_THRESHOLD
+    }
+}
+
+/// Deterministic xorshift64* generator used to pick classes and random secrets. Statistical
+/// timing tests need cheap, reproducible inputs rather than cryptographic randomness.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn fill(&mut self, bytes: &mut [u8; 32]) {
+        for chunk in bytes.chunks_mut(8) {
+            chunk.clone_from_slice(&self.next_u64().to_le_bytes());
+        }
+        // Keep the scalar comfortably below the curve order
+        bytes[0] &= 0x7f;
+    }
+}
+
+/// ### Test Signing
+///
+/// Runs a dudect-style fixed-vs-random test over the host signing path for challenge scheme `C`.
+///
+/// Class 0 always signs with `fixed_privkey`, while class 1 signs with a fresh pseudorandom key.
+/// Both classes sign the same message, and classes are interleaved randomly to decorrelate them
+/// from drift in the measurement environment. With no samples there is nothing to compare, and
+/// the report's `max_t` is zero.
+pub fn test_signing<C: Secp256k1SchnorrSign>(
+    fixed_privkey: &[u8; 32],
+    message: &[u8],
+    samples: usize,
+    seed: u64,
+) -> TimingReport {
+    let mut rng = XorShift(seed | 1);
+    let mut measurements = Vec::with_capacity(samples);
+
+    for _ in 0..samples {
+        let class = (rng.next_u64() & 1) as usize;
+        let mut privkey = *fixed_privkey;
+        if class == 1 {
+            rng.fill(&mut privkey);
+        }
+        let start = Instant::now();
+        let signature = Secp256k1SchnorrSignature::sign::<C>(message, &privkey);
+        let elapsed = start.elapsed().as_nanos() as f64;
+        core::hint::black_box(signature.ok());
+        measurements.push((class, elapsed));
+    }
+
+    if measurements.is_empty() {
+        return TimingReport {
+            samples,
+            max_t: 0.0,
+        };
+    }
+
+    let mut sorted: Vec<f64> = measurements.iter().map(|(_, t)| *t).collect();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+
+    let max_t = PERCENTILES
+        .iter()
+        .map(|p| {
+            let cutoff = sorted[((sorted.len() - 1) as f64 * p) as usize];
+            let mut welch = Welch::default();
+            for (class, t) in measurements.iter().filter(|(_, t)| *t <= cutoff) {
+                welch.push(*class, *t);
+            }
+            welch.t()
+        })
+        .fold(0.0f64, |max, t| if t.abs() > max.abs() { t } else { max });
+
+    TimingReport { samples, max_t }
+}
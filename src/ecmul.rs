@@ -0,0 +1,143 @@
+use solana_secp256k1::{Curve, Secp256k1Point, UncompressedPoint};
+
+use crate::{
+    backend::{DefaultRecover, Secp256k1RecoverBackend},
+    errors::Secp256k1SchnorrError,
+};
+
+/// ### EcmulOperands
+///
+/// The operands of a single `secp256k1_recover` call computing 𝑎⋅𝑃 + 𝑏⋅𝐺, as returned by
+/// `mul_add_g_operands`.
+///
+/// Recover returns 𝑄 = 𝑟⁻¹(𝑠⋅𝑅 - 𝑧⋅𝐺), where 𝑅 is the point with 𝑋-coordinate 𝑟 and the given
+/// parity. Choosing 𝑅 = 𝑃, 𝑟 = 𝑃ₓ, 𝑠 = 𝑎⋅𝑃ₓ and 𝑧 = -𝑏⋅𝑃ₓ yields 𝑄 = 𝑎⋅𝑃 + 𝑏⋅𝐺.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EcmulOperands {
+    pub hash: [u8; 32],
+    pub is_odd: bool,
+    pub signature: [u8; 64],
+}
+
+/// Fails unless `k` is a nonzero scalar modulo 𝑁.
+#[inline(always)]
+fn check_scalar(k: &[u8; 32]) -> Result<(), Secp256k1SchnorrError> {
+    if k.ge(&Curve::N) {
+        return Err(Secp256k1SchnorrError::ArithmeticOverflow);
+    }
+    if k.eq(&[0u8; 32]) {
+        return Err(Secp256k1SchnorrError::PointAtInfinity);
+    }
+    Ok(())
+}
+
+/// ### Mul Add G Operands
+///
+/// Computes the operands for which `secp256k1_recover` returns 𝑎⋅𝑃 + 𝑏⋅𝐺, allowing callers to
+/// route the call through the recover implementation of their choice.
+///
+/// Fails with `ArithmeticOverflow` if either scalar is not reduced modulo 𝑁, `PointAtInfinity` if
+/// 𝑎 is zero, and `InvalidPublicKey` if 𝑃ₓ is not a valid 𝑟, as is the case for fewer than 2⁻¹²⁷
+/// of all points.
+pub fn mul_add_g_operands<T: Secp256k1Point>(
+    point: &T,
+    a: &[u8; 32],
+    b: &[u8; 32],
+) -> Result<EcmulOperands, Secp256k1SchnorrError> {
+    check_scalar(a)?;
+    if b.ge(&Curve::N) {
+        return Err(Secp256k1SchnorrError::ArithmeticOverflow);
+    }
+    let px = point.x();
+    if px.ge(&Curve::N) {
+        return Err(Secp256k1SchnorrError::InvalidPublicKey);
+    }
+
+    let mut signature = [0u8; 64];
+    signature[..32].clone_from_slice(&px);
+    signature[32..].clone_from_slice(&Curve::mul_mod_n(a, &px));
+    Ok(EcmulOperands {
+        hash: Curve::negate_n(&Curve::mul_mod_n(b, &px)),
+        is_odd: point.is_odd(),
+        signature,
+    })
+}
+
+/// ### Mul Add G
+///
+/// Computes 𝑎⋅𝑃 + 𝑏⋅𝐺 with a single call to `secp256k1_recover`, at a fraction of the cost of
+/// performing either multiplication in program code. This is the same trick with which
+/// signatures are verified, exposed for other protocols such as ECDH or Pedersen commitments.
+///
+/// Fails as `mul_add_g_operands`, or with `PointAtInfinity` if the result is the point at
+/// infinity. A zero 𝑎 is accepted here, falling back to `mul_g`.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::ecmul::mul_add_g;
+///
+/// // C = v*H + r*G
+/// let commitment = mul_add_g(&h, &value, &blinding)?;
+/// ```
+pub fn mul_add_g<T: Secp256k1Point>(
+    point: &T,
+    a: &[u8; 32],
+    b: &[u8; 32],
+) -> Result<UncompressedPoint, Secp256k1SchnorrError> {
+    if a.eq(&[0u8; 32]) {
+        return mul_g(b);
+    }
+    let operands = mul_add_g_operands(point, a, b)?;
+
+    // The host recover implementation panics for the point at infinity, so it is identified here
+    // up front, whereas on-chain the syscall itself rejects it, as does the "host" backend.
+    #[cfg(not(any(target_os = "solana", feature = "host")))]
+    if b.ne(&[0u8; 32]) {
+        if let (Ok(a_p), Ok(b_g)) = (Curve::ecmul(point, a), Curve::mul_g(&Curve::negate_n(b))) {
+            if a_p.eq(&b_g) {
+                return Err(Secp256k1SchnorrError::PointAtInfinity);
+            }
+        }
+    }
+
+    DefaultRecover::recover(&operands.hash, operands.is_odd, &operands.signature)
+        .map(UncompressedPoint)
+        .map_err(|_| Secp256k1SchnorrError::PointAtInfinity)
+}
+
+/// ### Mul
+///
+/// Computes 𝑎⋅𝑃 with a single call to `secp256k1_recover`, failing as `mul_add_g`.
+pub fn mul<T: Secp256k1Point>(
+    point: &T,
+    a: &[u8; 32],
+) -> Result<UncompressedPoint, Secp256k1SchnorrError> {
+    check_scalar(a)?;
+    mul_add_g(point, a, &[0u8; 32])
+}
+
+/// ### Mul G
+///
+/// Computes 𝑏⋅𝐺 with a single call to `secp256k1_recover`, failing as `mul_add_g`.
+pub fn mul_g(b: &[u8; 32]) -> Result<UncompressedPoint, Secp256k1SchnorrError> {
+    check_scalar(b)?;
+    mul_add_g(&Curve::G, b, &[0u8; 32])
+}
+
+/// Adds a point to an accumulator, where `None` is the point at infinity.
+#[cfg(any(
+    feature = "blind",
+    feature = "dlc",
+    feature = "frost",
+    feature = "musig2"
+))]
+pub(crate) fn accumulate(
+    acc: Option<UncompressedPoint>,
+    point: UncompressedPoint,
+) -> Option<UncompressedPoint> {
+    match acc {
+        None => Some(point),
+        Some(acc) if acc.x() == point.x() && acc.y() != point.y() => None,
+        Some(acc) => Some(acc + point),
+    }
+}
@@ -0,0 +1,185 @@
+use solana_nostd_sha256::hashv;
+use solana_secp256k1::{CompressedPoint, Secp256k1Point, UncompressedPoint};
+
+use crate::{ecmul, errors::Secp256k1SchnorrError, scalar::Scalar};
+
+pub const ECVRF_PROOF_LENGTH: usize = 81;
+
+/// Suite string of ECVRF-SECP256K1-SHA256-TAI, as used by existing secp256k1 VRF implementations
+const ECVRF_SUITE: u8 = 0xfe;
+
+/// Length in bytes of the challenge 𝑐
+const ECVRF_C_LENGTH: usize = 16;
+
+/// Returns the compressed encoding of a point.
+fn point_to_string(point: &UncompressedPoint) -> [u8; 33] {
+    point.compress().0
+}
+
+/// ### Encode To Curve
+///
+/// Hashes the VRF input `alpha` under a public key to a curve point 𝐻 with the try-and-increment
+/// method of RFC 9381, hashing `suite∥0x01∥𝑌∥alpha∥ctr∥0x00` until the digest is the
+/// 𝑋-coordinate of a point with an even 𝑌-coordinate. Each attempt decompresses a candidate, so
+/// verification on-chain costs about two attempts on average.
+///
+/// Fails with `InvalidMessage` if none of the 256 attempts yields a point, which happens with
+/// negligible probability.
+pub fn encode_to_curve<T: Secp256k1Point>(
+    pubkey: &T,
+    alpha: &[u8],
+) -> Result<UncompressedPoint, Secp256k1SchnorrError> {
+    let salt = pubkey.compress();
+    for ctr in 0..=u8::MAX {
+        let hash = hashv(&[&[ECVRF_SUITE, 0x01], &salt.0, alpha, &[ctr], &[0x00]]);
+        let mut candidate = [0x02u8; 33];
+        candidate[1..].clone_from_slice(&hash);
+        if let Ok(point) = UncompressedPoint::try_from(CompressedPoint(candidate)) {
+            return Ok(point);
+        }
+    }
+    Err(Secp256k1SchnorrError::InvalidMessage)
+}
+
+/// Computes the challenge 𝑐, the first 16 bytes of `suite∥0x02∥𝑌∥𝐻∥Γ∥𝑈∥𝑉∥0x00`, as a scalar.
+fn challenge(points: [&UncompressedPoint; 5]) -> [u8; 32] {
+    let [y, h, gamma, u, v] = points.map(point_to_string);
+    let hash = hashv(&[&[ECVRF_SUITE, 0x02], &y, &h, &gamma, &u, &v, &[0x00]]);
+    let mut c = [0u8; 32];
+    c[32 - ECVRF_C_LENGTH..].clone_from_slice(&hash[..ECVRF_C_LENGTH]);
+    c
+}
+
+/// # EcvrfProof
+/// An ECVRF-SECP256K1-SHA256-TAI proof `Γ∥𝑐∥𝑠` of RFC 9381 over secp256k1, where Γ = 𝑥⋅𝐻 is the
+/// compressed VRF point of the input under the prover's key. The VRF output, a pseudorandom
+/// value that only the holder of the private key can compute yet anyone can verify, is a hash
+/// of Γ. This gives lottery and raffle programs randomness that neither the prover nor the
+/// program can bias once the input is fixed.
+///
+/// There are 3 main functions that it performs:
+///
+/// 1. Prove - Proves the VRF output of an input with a private key.
+/// 2. Verify - Verifies a proof against a public key and input, returning the output.
+/// 3. Proof To Hash - Returns the output of a proof without verifying it.
+pub struct EcvrfProof(pub [u8; ECVRF_PROOF_LENGTH]);
+
+impl EcvrfProof {
+    /// The compressed VRF point Γ
+    pub fn gamma(&self) -> CompressedPoint {
+        let mut gamma = [0u8; 33];
+        gamma.clone_from_slice(&self.0[..33]);
+        CompressedPoint(gamma)
+    }
+
+    /// The 16-byte challenge 𝑐, left-padded to a 32-byte scalar
+    pub fn c(&self) -> [u8; 32] {
+        let mut c = [0u8; 32];
+        c[32 - ECVRF_C_LENGTH..].clone_from_slice(&self.0[33..33 + ECVRF_C_LENGTH]);
+        c
+    }
+
+    pub fn s(&self) -> [u8; 32] {
+        let mut s = [0u8; 32];
+        s.clone_from_slice(&self.0[33 + ECVRF_C_LENGTH..]);
+        s
+    }
+
+    /// ### Proof To Hash
+    ///
+    /// Returns the VRF output `SHA256(suite∥0x03∥Γ∥0x00)` of the proof without verifying it, for
+    /// callers that have already verified it. Fails with `InvalidProof` if Γ is not on the curve.
+    pub fn proof_to_hash(&self) -> Result<[u8; 32], Secp256k1SchnorrError> {
+        let gamma = UncompressedPoint::try_from(self.gamma())
+            .map_err(|_| Secp256k1SchnorrError::InvalidProof)?;
+        Ok(hashv(&[
+            &[ECVRF_SUITE, 0x03],
+            &point_to_string(&gamma),
+            &[0x00],
+        ]))
+    }
+
+    /// ### Verify
+    ///
+    /// Verifies the proof for the input `alpha` under `pubkey`, returning the VRF output. The
+    /// commitments 𝑈 = 𝑠⋅𝐺 - 𝑐⋅𝑌 and 𝑉 = 𝑠⋅𝐻 - 𝑐⋅Γ are recomputed with three calls to
+    /// `secp256k1_recover` and a point addition, on top of hashing the input to the curve.
+    ///
+    /// Fails with `InvalidProof` if Γ is not on the curve, 𝑠 is not a valid scalar, or the proof
+    /// does not hold, and as `encode_to_curve`.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::ecvrf::EcvrfProof;
+    ///
+    /// let output = EcvrfProof(proof).verify(&pubkey, b"raffle-42").expect("Invalid proof");
+    /// let winner = u64::from_le_bytes(output[..8].try_into().unwrap()) % entrants;
+    /// ```
+    pub fn verify<T: Secp256k1Point>(
+        &self,
+        pubkey: &T,
+        alpha: &[u8],
+    ) -> Result<[u8; 32], Secp256k1SchnorrError> {
+        let y = pubkey.decompress();
+        let gamma = UncompressedPoint::try_from(self.gamma())
+            .map_err(|_| Secp256k1SchnorrError::InvalidProof)?;
+        let s = Scalar::new(self.s()).map_err(|_| Secp256k1SchnorrError::InvalidProof)?;
+        let minus_c = Scalar::reduce(&self.c()).negate().to_bytes();
+        let h = encode_to_curve(&y, alpha)?;
+
+        // U = s*G - c*Y
+        let u = ecmul::mul_add_g(&y, &minus_c, &s.to_bytes())
+            .map_err(|_| Secp256k1SchnorrError::InvalidProof)?;
+        // V = s*H - c*Γ
+        let s_h = ecmul::mul(&h, &s.to_bytes()).map_err(|_| Secp256k1SchnorrError::InvalidProof)?;
+        let c_gamma =
+            ecmul::mul(&gamma, &minus_c).map_err(|_| Secp256k1SchnorrError::InvalidProof)?;
+        if s_h.x() == c_gamma.x() && s_h.y() != c_gamma.y() {
+            return Err(Secp256k1SchnorrError::InvalidProof);
+        }
+        let v = s_h + c_gamma;
+
+        if challenge([&y, &h, &gamma, &u, &v]).ne(&self.c()) {
+            return Err(Secp256k1SchnorrError::InvalidProof);
+        }
+        self.proof_to_hash()
+    }
+
+    /// ### Prove
+    ///
+    /// Proves the VRF output of the input `alpha` under `privkey`, deriving the nonce from the
+    /// private key and 𝐻 with RFC6979 as RFC 9381 specifies, so proofs are deterministic.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::ecvrf::EcvrfProof;
+    ///
+    /// let proof = EcvrfProof::prove(&privkey, b"raffle-42")?;
+    /// let output = proof.proof_to_hash()?;
+    /// ```
+    #[cfg(feature = "sign")]
+    pub fn prove(privkey: &[u8; 32], alpha: &[u8]) -> Result<Self, Secp256k1SchnorrError> {
+        let x = Scalar::new_nonzero(*privkey)?;
+        let y = ecmul::mul_g(privkey)?;
+        let h = encode_to_curve(&y, alpha)?;
+        let gamma = ecmul::mul(&h, privkey)?;
+
+        let h1 = Scalar::reduce(&hashv(&[&point_to_string(&h)]));
+        #[allow(unused_mut)]
+        let mut k = crate::rfc6979::rfc6979(privkey, &[&h1.to_bytes()]);
+        let u = ecmul::mul_g(&k)?;
+        let v = ecmul::mul(&h, &k)?;
+
+        let c = challenge([&y, &h, &gamma, &u, &v]);
+        // s = k + c*x
+        let s = Scalar::reduce(&k).add(&Scalar::reduce(&c).mul(&x));
+        #[cfg(feature = "hardening")]
+        crate::hardening::zeroize(&mut k);
+
+        let mut proof = [0u8; ECVRF_PROOF_LENGTH];
+        proof[..33].clone_from_slice(&point_to_string(&gamma));
+        proof[33..33 + ECVRF_C_LENGTH].clone_from_slice(&c[32 - ECVRF_C_LENGTH..]);
+        proof[33 + ECVRF_C_LENGTH..].clone_from_slice(&s.to_bytes());
+        Ok(Self(proof))
+    }
+}
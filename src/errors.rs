@@ -6,4 +6,18 @@ pub enum Secp256k1SchnorrError {
     InvalidSignature = 4,
     InvalidNonce = 5,
     ArithmeticOverflow = 6,
+    InvalidChallenge = 7,
+    PointAtInfinity = 8,
+    InvalidMessage = 9,
+    DomainMismatch = 10,
+    Expired = 11,
+    NotYetValid = 12,
+    NonceMismatch = 13,
+    LimitExceeded = 14,
+    Unauthorized = 15,
+    InvalidProof = 16,
+    InvalidState = 17,
+    InvalidSignatureR = 18,
+    InvalidSignatureS = 19,
+    DuplicateSigner = 20,
 }
@@ -0,0 +1,134 @@
+use solana_nostd_keccak::hashv;
+use solana_secp256k1::{Curve, Secp256k1Point, UncompressedPoint};
+
+use crate::{errors::Secp256k1SchnorrError, keys, recover_nonce};
+
+pub const EVM_SCHNORR_SIGNATURE_LENGTH: usize = 64;
+
+// Domain separator mixed into nonce derivation, as for `Keccak256Challenge`
+#[cfg(feature = "sign")]
+const EVM_NONCE_DOMAIN: &[u8] = b"solana-secp256k1-schnorr/evm/nonce";
+
+/// ### EVM Challenge
+///
+/// Computes the challenge `𝑒 = keccak256(address(𝑅)∥parity∥𝑃ₓ∥𝑀)` of the EVM Schnorr scheme,
+/// where `address(𝑅)` is the Ethereum address of the nonce point and `parity` is 27 or 28 as the
+/// `v` that `ecrecover` expects for the public key.
+pub fn evm_challenge<T: Secp256k1Point>(
+    nonce_address: &[u8; 20],
+    pubkey: &T,
+    message: &[u8],
+) -> [u8; 32] {
+    hashv(&[
+        nonce_address,
+        &[27 + pubkey.is_odd() as u8],
+        &pubkey.x(),
+        message,
+    ])
+}
+
+/// # EvmSchnorrSignature
+/// A Schnorr signature `𝑒∥𝑠` of the scheme EVM contracts verify with a single `ecrecover`, which
+/// commits to the address of the nonce point rather than the point itself. The verifier recovers
+/// the address of 𝑅 = 𝑠⋅𝐺 - 𝑒⋅𝑃 and checks that it hashes back to 𝑒, so one signature validates
+/// both in an EVM contract and in a Solana program.
+///
+/// Since `ecrecover` takes 𝑃ₓ as the 𝑟 of an ECDSA signature, public keys must have 𝑃ₓ < 𝑁.
+pub struct EvmSchnorrSignature(pub [u8; EVM_SCHNORR_SIGNATURE_LENGTH]);
+
+impl EvmSchnorrSignature {
+    pub fn e(&self) -> [u8; 32] {
+        let mut e = [0u8; 32];
+        e.clone_from_slice(&self.0[..32]);
+        e
+    }
+
+    pub fn s(&self) -> [u8; 32] {
+        let mut s = [0u8; 32];
+        s.clone_from_slice(&self.0[32..]);
+        s
+    }
+
+    /// ### Verify
+    /// Verifies the signature as the EVM contract does, recovering the nonce point with a single
+    /// `secp256k1_recover` call.
+    ///
+    /// Fails with `InvalidSignatureS` if 𝑠 ≥ 𝑁, `InvalidPublicKey` if 𝑃ₓ ≥ 𝑁 and
+    /// `InvalidSignature` if the recovered nonce does not hash back to 𝑒.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::evm::EvmSchnorrSignature;
+    ///
+    /// EvmSchnorrSignature(signature).verify(&message, &pubkey)
+    ///     .expect("Invalid signature");
+    /// ```
+    pub fn verify<T: Secp256k1Point>(
+        &self,
+        message: &[u8],
+        pubkey: &T,
+    ) -> Result<(), Secp256k1SchnorrError> {
+        let (e, s) = (self.e(), self.s());
+        if s.ge(&Curve::N) {
+            return Err(Secp256k1SchnorrError::InvalidSignatureS);
+        }
+        if pubkey.x().ge(&Curve::N) {
+            return Err(Secp256k1SchnorrError::InvalidPublicKey);
+        }
+
+        // R = s*G - e*P
+        let r = UncompressedPoint(recover_nonce(&s, &e, pubkey)?);
+
+        if evm_challenge(&keys::eth_address(&r), pubkey, message).ne(&e) {
+            return Err(Secp256k1SchnorrError::InvalidSignature);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sign")]
+impl EvmSchnorrSignature {
+    /// ### Sign
+    /// Signs a message under the EVM Schnorr scheme, mixing 32 bytes of auxiliary randomness
+    /// into nonce derivation as `Secp256k1SchnorrSignature::sign_with_aux`.
+    ///
+    /// Fails with `InvalidPublicKey` if 𝑃ₓ ≥ 𝑁, as no EVM contract could verify the signature.
+    pub fn sign(
+        message: &[u8],
+        privkey: &[u8; 32],
+        aux: &[u8; 32],
+    ) -> Result<Self, Secp256k1SchnorrError> {
+        let pubkey = Curve::mul_g(privkey).map_err(|_| Secp256k1SchnorrError::InvalidSecretKey)?;
+        if pubkey.x().ge(&Curve::N) {
+            return Err(Secp256k1SchnorrError::InvalidPublicKey);
+        }
+
+        let mut t = solana_nostd_keccak::hash(aux);
+        for (a, b) in t.iter_mut().zip(privkey.iter()) {
+            *a ^= b
+        }
+        #[allow(unused_mut)]
+        let mut k = hashv(&[EVM_NONCE_DOMAIN, &t, &pubkey.x(), message]);
+        if crate::is_zero_mod_n(&k) {
+            return Err(Secp256k1SchnorrError::InvalidNonce);
+        }
+        let r = Curve::mul_g(&k).map_err(|_| Secp256k1SchnorrError::InvalidNonce)?;
+
+        let e = evm_challenge(&keys::eth_address(&r), &pubkey, message);
+        if crate::is_zero_mod_n(&e) {
+            return Err(Secp256k1SchnorrError::InvalidChallenge);
+        }
+
+        let mut signature = [0u8; EVM_SCHNORR_SIGNATURE_LENGTH];
+        signature[..32].clone_from_slice(&e);
+        signature[32..].clone_from_slice(&Curve::add_mod_n(&k, &Curve::mul_mod_n(&e, privkey)));
+
+        #[cfg(feature = "hardening")]
+        {
+            crate::hardening::zeroize(&mut t);
+            crate::hardening::zeroize(&mut k);
+        }
+
+        Ok(Self(signature))
+    }
+}
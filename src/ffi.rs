@@ -0,0 +1,329 @@
+//! C bindings, declared in `include/solana_secp256k1_schnorr.h`. The crate is built as an `rlib`
+//! by default; build the shared library for C consumers with:
+//!
+//! ```sh
+//! cargo rustc --release --crate-type cdylib --features ffi
+//! ```
+
+use core::slice;
+
+use solana_secp256k1::{CompressedPoint, Secp256k1Point};
+
+use crate::{
+    address::SECP256K1_SCHNORR_SIGNATURE_BUNDLE_LENGTH, errors::Secp256k1SchnorrError, keys,
+    program::Scheme, rng, Secp256k1SchnorrSignature, SECP256K1_SCHNORR_SIGNATURE_LENGTH,
+};
+
+/// Returned by every function on success. Failures return the discriminant of the
+/// `Secp256k1SchnorrError` that caused them.
+pub const SSCH_OK: i32 = 0;
+
+/// Returned if a required pointer argument is null
+pub const SSCH_ERR_NULL_POINTER: i32 = -1;
+
+/// Discriminants of `Secp256k1SchnorrError`, returned on failure
+pub const SSCH_ERR_INVALID_SECRET_KEY: i32 = 1;
+pub const SSCH_ERR_INVALID_PUBLIC_KEY: i32 = 2;
+pub const SSCH_ERR_INVALID_RECOVERY_ID: i32 = 3;
+pub const SSCH_ERR_INVALID_SIGNATURE: i32 = 4;
+pub const SSCH_ERR_INVALID_NONCE: i32 = 5;
+pub const SSCH_ERR_ARITHMETIC_OVERFLOW: i32 = 6;
+pub const SSCH_ERR_INVALID_CHALLENGE: i32 = 7;
+pub const SSCH_ERR_POINT_AT_INFINITY: i32 = 8;
+pub const SSCH_ERR_INVALID_MESSAGE: i32 = 9;
+pub const SSCH_ERR_DOMAIN_MISMATCH: i32 = 10;
+pub const SSCH_ERR_EXPIRED: i32 = 11;
+pub const SSCH_ERR_NOT_YET_VALID: i32 = 12;
+pub const SSCH_ERR_NONCE_MISMATCH: i32 = 13;
+pub const SSCH_ERR_LIMIT_EXCEEDED: i32 = 14;
+pub const SSCH_ERR_UNAUTHORIZED: i32 = 15;
+pub const SSCH_ERR_INVALID_PROOF: i32 = 16;
+pub const SSCH_ERR_INVALID_STATE: i32 = 17;
+pub const SSCH_ERR_INVALID_SIGNATURE_R: i32 = 18;
+pub const SSCH_ERR_INVALID_SIGNATURE_S: i32 = 19;
+pub const SSCH_ERR_DUPLICATE_SIGNER: i32 = 20;
+
+/// Discriminants of `Scheme`, selecting the challenge scheme to sign or verify under
+pub const SSCH_SCHEME_BIP340: u8 = 0;
+pub const SSCH_SCHEME_SHA256: u8 = 1;
+pub const SSCH_SCHEME_KECCAK256: u8 = 2;
+
+pub const SSCH_PRIVKEY_LENGTH: usize = 32;
+pub const SSCH_PUBKEY_LENGTH: usize = 33;
+pub const SSCH_SIGNATURE_LENGTH: usize = 64;
+pub const SSCH_BUNDLE_LENGTH: usize = 97;
+pub const SSCH_ADDRESS_LENGTH: usize = 20;
+
+// The header is generated from the literals above, which must not drift from the crate
+const _: () = assert!(
+    SSCH_SCHEME_BIP340 == Scheme::Bip340 as u8
+        && SSCH_SCHEME_SHA256 == Scheme::Sha256 as u8
+        && SSCH_SCHEME_KECCAK256 == Scheme::Keccak256 as u8
+        && SSCH_SIGNATURE_LENGTH == SECP256K1_SCHNORR_SIGNATURE_LENGTH
+        && SSCH_BUNDLE_LENGTH == SECP256K1_SCHNORR_SIGNATURE_BUNDLE_LENGTH
+        && SSCH_ERR_INVALID_SECRET_KEY == Secp256k1SchnorrError::InvalidSecretKey as i32
+        && SSCH_ERR_INVALID_PUBLIC_KEY == Secp256k1SchnorrError::InvalidPublicKey as i32
+        && SSCH_ERR_INVALID_RECOVERY_ID == Secp256k1SchnorrError::InvalidRecoveryId as i32
+        && SSCH_ERR_INVALID_SIGNATURE == Secp256k1SchnorrError::InvalidSignature as i32
+        && SSCH_ERR_INVALID_NONCE == Secp256k1SchnorrError::InvalidNonce as i32
+        && SSCH_ERR_ARITHMETIC_OVERFLOW == Secp256k1SchnorrError::ArithmeticOverflow as i32
+        && SSCH_ERR_INVALID_CHALLENGE == Secp256k1SchnorrError::InvalidChallenge as i32
+        && SSCH_ERR_POINT_AT_INFINITY == Secp256k1SchnorrError::PointAtInfinity as i32
+        && SSCH_ERR_INVALID_MESSAGE == Secp256k1SchnorrError::InvalidMessage as i32
+        && SSCH_ERR_DOMAIN_MISMATCH == Secp256k1SchnorrError::DomainMismatch as i32
+        && SSCH_ERR_EXPIRED == Secp256k1SchnorrError::Expired as i32
+        && SSCH_ERR_NOT_YET_VALID == Secp256k1SchnorrError::NotYetValid as i32
+        && SSCH_ERR_NONCE_MISMATCH == Secp256k1SchnorrError::NonceMismatch as i32
+        && SSCH_ERR_LIMIT_EXCEEDED == Secp256k1SchnorrError::LimitExceeded as i32
+        && SSCH_ERR_UNAUTHORIZED == Secp256k1SchnorrError::Unauthorized as i32
+        && SSCH_ERR_INVALID_PROOF == Secp256k1SchnorrError::InvalidProof as i32
+        && SSCH_ERR_INVALID_STATE == Secp256k1SchnorrError::InvalidState as i32
+        && SSCH_ERR_INVALID_SIGNATURE_R == Secp256k1SchnorrError::InvalidSignatureR as i32
+        && SSCH_ERR_INVALID_SIGNATURE_S == Secp256k1SchnorrError::InvalidSignatureS as i32
+        && SSCH_ERR_DUPLICATE_SIGNER == Secp256k1SchnorrError::DuplicateSigner as i32
+);
+
+fn code(result: Result<(), Secp256k1SchnorrError>) -> i32 {
+    match result {
+        Ok(()) => SSCH_OK,
+        Err(error) => error as i32,
+    }
+}
+
+/// Copies `N` bytes from a caller-provided pointer.
+unsafe fn read<const N: usize>(ptr: *const u8) -> Option<[u8; N]> {
+    if ptr.is_null() {
+        return None;
+    }
+    let mut out = [0u8; N];
+    out.clone_from_slice(slice::from_raw_parts(ptr, N));
+    Some(out)
+}
+
+/// Copies `bytes` to a caller-provided pointer.
+unsafe fn write(ptr: *mut u8, bytes: &[u8]) {
+    slice::from_raw_parts_mut(ptr, bytes.len()).clone_from_slice(bytes);
+}
+
+/// Borrows a caller-provided message, which may be null if empty.
+unsafe fn message<'a>(ptr: *const u8, len: usize) -> Option<&'a [u8]> {
+    match (ptr.is_null(), len) {
+        (true, 0) => Some(&[]),
+        (true, _) => None,
+        (false, _) => Some(slice::from_raw_parts(ptr, len)),
+    }
+}
+
+/// ### SSCH Pubkey Create
+///
+/// Writes the 33-byte compressed public key of a 32-byte private key to `out_pubkey`.
+///
+/// # Safety
+///
+/// `privkey` must be valid for reads of 32 bytes and `out_pubkey` for writes of 33 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ssch_pubkey_create(privkey: *const u8, out_pubkey: *mut u8) -> i32 {
+    let Some(privkey) = read::<32>(privkey) else {
+        return SSCH_ERR_NULL_POINTER;
+    };
+    if out_pubkey.is_null() {
+        return SSCH_ERR_NULL_POINTER;
+    }
+    code(
+        solana_secp256k1::Curve::mul_g(&privkey)
+            .map_err(|_| Secp256k1SchnorrError::InvalidSecretKey)
+            .map(|pubkey| write(out_pubkey, &pubkey.compress().0)),
+    )
+}
+
+/// ### SSCH Keygen
+///
+/// Generates a private key and its compressed public key from 32 bytes of secret, uniformly
+/// random `seed`, writing them to `out_privkey` and `out_pubkey`. The same seed always yields
+/// the same key.
+///
+/// # Safety
+///
+/// `seed` must be valid for reads of 32 bytes, `out_privkey` for writes of 32 bytes and
+/// `out_pubkey` for writes of 33 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ssch_keygen(
+    seed: *const u8,
+    out_privkey: *mut u8,
+    out_pubkey: *mut u8,
+) -> i32 {
+    let Some(seed) = read::<32>(seed) else {
+        return SSCH_ERR_NULL_POINTER;
+    };
+    if out_privkey.is_null() || out_pubkey.is_null() {
+        return SSCH_ERR_NULL_POINTER;
+    }
+    code(
+        rng::keygen(&mut rng::SeededRng::new(seed)).map(|(privkey, pubkey)| {
+            write(out_privkey, &privkey);
+            write(out_pubkey, &pubkey.compress().0);
+        }),
+    )
+}
+
+/// ### SSCH Sign
+///
+/// Signs a message under `scheme`, a `Scheme` discriminant, writing the 64-byte signature to
+/// `out_signature`.
+///
+/// # Safety
+///
+/// `message` must be valid for reads of `message_len` bytes, or null if it is zero, `privkey`
+/// must be valid for reads of 32 bytes and `out_signature` for writes of 64 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ssch_sign(
+    scheme: u8,
+    message: *const u8,
+    message_len: usize,
+    privkey: *const u8,
+    out_signature: *mut u8,
+) -> i32 {
+    let (Some(message), Some(privkey)) = (self::message(message, message_len), read::<32>(privkey))
+    else {
+        return SSCH_ERR_NULL_POINTER;
+    };
+    if out_signature.is_null() {
+        return SSCH_ERR_NULL_POINTER;
+    }
+    code(
+        Scheme::try_from(scheme)
+            .and_then(|scheme| scheme.sign(message, &privkey))
+            .map(|signature| write(out_signature, &signature.0)),
+    )
+}
+
+/// ### SSCH Verify
+///
+/// Verifies a 64-byte signature over a message against a 33-byte compressed public key under
+/// `scheme`, a `Scheme` discriminant.
+///
+/// # Safety
+///
+/// `message` must be valid for reads of `message_len` bytes, or null if it is zero, `signature`
+/// must be valid for reads of 64 bytes and `pubkey` for reads of 33 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ssch_verify(
+    scheme: u8,
+    message: *const u8,
+    message_len: usize,
+    signature: *const u8,
+    pubkey: *const u8,
+) -> i32 {
+    let (Some(message), Some(signature), Some(pubkey)) = (
+        self::message(message, message_len),
+        read::<SECP256K1_SCHNORR_SIGNATURE_LENGTH>(signature),
+        read::<33>(pubkey),
+    ) else {
+        return SSCH_ERR_NULL_POINTER;
+    };
+    code(
+        Scheme::try_from(scheme)
+            .and_then(|scheme| keys::parse_sec1_compressed(&pubkey).map(|pubkey| (scheme, pubkey)))
+            .and_then(|(scheme, pubkey)| {
+                scheme.verify(&Secp256k1SchnorrSignature(signature), message, &pubkey)
+            }),
+    )
+}
+
+/// ### SSCH Bundle Encode
+///
+/// Encodes a 64-byte signature and the signer's 33-byte compressed public key into a 97-byte
+/// signature bundle, as accepted by `verify_by_eth_address`.
+///
+/// # Safety
+///
+/// `signature` must be valid for reads of 64 bytes, `pubkey` for reads of 33 bytes and
+/// `out_bundle` for writes of 97 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ssch_bundle_encode(
+    signature: *const u8,
+    pubkey: *const u8,
+    out_bundle: *mut u8,
+) -> i32 {
+    let (Some(signature), Some(pubkey)) = (
+        read::<SECP256K1_SCHNORR_SIGNATURE_LENGTH>(signature),
+        read::<33>(pubkey),
+    ) else {
+        return SSCH_ERR_NULL_POINTER;
+    };
+    if out_bundle.is_null() {
+        return SSCH_ERR_NULL_POINTER;
+    }
+    code(keys::parse_sec1_compressed(&pubkey).map(|_| {
+        write(out_bundle, &signature);
+        write(out_bundle.add(SECP256K1_SCHNORR_SIGNATURE_LENGTH), &pubkey);
+    }))
+}
+
+/// ### SSCH Bundle Decode
+///
+/// Decodes a 97-byte signature bundle into its 64-byte signature and 33-byte compressed public
+/// key.
+///
+/// # Safety
+///
+/// `bundle` must be valid for reads of 97 bytes, `out_signature` for writes of 64 bytes and
+/// `out_pubkey` for writes of 33 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ssch_bundle_decode(
+    bundle: *const u8,
+    out_signature: *mut u8,
+    out_pubkey: *mut u8,
+) -> i32 {
+    let Some(bundle) = read::<SECP256K1_SCHNORR_SIGNATURE_BUNDLE_LENGTH>(bundle) else {
+        return SSCH_ERR_NULL_POINTER;
+    };
+    if out_signature.is_null() || out_pubkey.is_null() {
+        return SSCH_ERR_NULL_POINTER;
+    }
+    let (signature, pubkey) = bundle.split_at(SECP256K1_SCHNORR_SIGNATURE_LENGTH);
+    code(keys::parse_sec1_compressed(pubkey).map(|_| {
+        write(out_signature, signature);
+        write(out_pubkey, pubkey);
+    }))
+}
+
+/// ### SSCH Verify By Eth Address
+///
+/// Verifies a 97-byte signature bundle over a message under `scheme`, a `Scheme` discriminant,
+/// against a signer known only by their 20-byte Ethereum address.
+///
+/// # Safety
+///
+/// `message` must be valid for reads of `message_len` bytes, or null if it is zero, `bundle`
+/// must be valid for reads of 97 bytes and `address` for reads of 20 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ssch_verify_by_eth_address(
+    scheme: u8,
+    message: *const u8,
+    message_len: usize,
+    bundle: *const u8,
+    address: *const u8,
+) -> i32 {
+    let (Some(message), Some(bundle), Some(address)) = (
+        self::message(message, message_len),
+        read::<SECP256K1_SCHNORR_SIGNATURE_BUNDLE_LENGTH>(bundle),
+        read::<20>(address),
+    ) else {
+        return SSCH_ERR_NULL_POINTER;
+    };
+    let (signature, pubkey) = bundle.split_at(SECP256K1_SCHNORR_SIGNATURE_LENGTH);
+    let mut signature_bytes = [0u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH];
+    signature_bytes.clone_from_slice(signature);
+    code(Scheme::try_from(scheme).and_then(|scheme| {
+        let pubkey = keys::parse_sec1(pubkey)?;
+        if keys::eth_address(&pubkey).ne(&address) {
+            return Err(Secp256k1SchnorrError::Unauthorized);
+        }
+        scheme.verify(
+            &Secp256k1SchnorrSignature(signature_bytes),
+            message,
+            &CompressedPoint::from(pubkey),
+        )
+    }))
+}
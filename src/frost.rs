@@ -0,0 +1,809 @@
+use solana_nostd_sha256::hashv;
+use solana_secp256k1::{CompressedPoint, Secp256k1Point, UncompressedPoint};
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify,
+    ecmul::{self, accumulate},
+    errors::Secp256k1SchnorrError,
+    keys::parse_sec1,
+    rng::{self, Secp256k1SchnorrRng},
+    scalar::Scalar,
+    xonly_point, Secp256k1SchnorrSignature,
+};
+
+/// Domain separator for the hiding and binding nonces of each signer
+const FROST_NONCE_DOMAIN: &[u8] = b"solana-secp256k1-schnorr/frost/nonce";
+
+/// Domain separator for the binding factor of each signer
+const FROST_BINDING_DOMAIN: &[u8] = b"solana-secp256k1-schnorr/frost/binding";
+
+/// Returns a participant index as a scalar
+pub(crate) fn index_scalar(index: u32) -> Scalar {
+    let mut bytes = [0u8; 32];
+    bytes[28..].clone_from_slice(&index.to_be_bytes());
+    Scalar::reduce(&bytes)
+}
+
+/// Evaluates the polynomial with `coefficients`, constant term first, at `x`.
+pub(crate) fn evaluate(coefficients: &[Scalar], x: &Scalar) -> Scalar {
+    coefficients
+        .iter()
+        .rev()
+        .fold(Scalar::ZERO, |acc, coefficient| acc.mul(x).add(coefficient))
+}
+
+/// Computes 𝑘⋅𝑃, where `None` is the point at infinity.
+fn mul_point(
+    point: &UncompressedPoint,
+    k: &Scalar,
+) -> Result<Option<UncompressedPoint>, Secp256k1SchnorrError> {
+    match k.is_zero() {
+        true => Ok(None),
+        false => ecmul::mul(point, &k.to_bytes()).map(Some),
+    }
+}
+
+/// ### Lagrange Coefficient
+///
+/// Computes the Lagrange coefficient λᵢ at zero of the participant `index` within the signing set
+/// `indices`, by which its secret share is weighted when the shares are combined.
+///
+/// Fails with `DuplicateSigner` if `indices` repeats an index, and `InvalidState` if `index` is
+/// not one of `indices`.
+pub fn lagrange_coefficient(
+    index: u32,
+    indices: &[u32],
+) -> Result<[u8; 32], Secp256k1SchnorrError> {
+    if indices
+        .iter()
+        .enumerate()
+        .any(|(i, j)| indices[..i].contains(j))
+    {
+        return Err(Secp256k1SchnorrError::DuplicateSigner);
+    }
+    if !indices.contains(&index) {
+        return Err(Secp256k1SchnorrError::InvalidState);
+    }
+    let x_i = index_scalar(index);
+    let (numerator, denominator) = indices.iter().filter(|j| j.ne(&&index)).fold(
+        (Scalar::ONE, Scalar::ONE),
+        |(numerator, denominator), j| {
+            let x_j = index_scalar(*j);
+            (numerator.mul(&x_j), denominator.mul(&x_j.sub(&x_i)))
+        },
+    );
+    Ok(numerator.mul(&denominator.invert()?).to_bytes())
+}
+
+/// # KeyShare
+/// A participant's secret share 𝑠ᵢ = 𝑓(𝑖) of the group secret key 𝑓(0), along with the public
+/// parameters every signer needs. Any `threshold` of the shares can sign for the group key.
+#[derive(Clone)]
+pub struct KeyShare {
+    pub index: u32,
+    pub threshold: u32,
+    pub secret: [u8; 32],
+    pub group_pubkey: UncompressedPoint,
+}
+
+impl KeyShare {
+    /// The participant's public verifying share 𝑌ᵢ = 𝑠ᵢ⋅𝐺
+    pub fn verifying_share(&self) -> Result<UncompressedPoint, Secp256k1SchnorrError> {
+        ecmul::mul_g(&self.secret)
+    }
+}
+
+#[cfg(feature = "hardening")]
+impl Drop for KeyShare {
+    fn drop(&mut self) {
+        crate::hardening::zeroize(&mut self.secret);
+    }
+}
+
+/// # PublicKeyPackage
+/// The public outcome of key generation: the group public key and the verifying share of every
+/// participant, against which an aggregator checks each signature share.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKeyPackage {
+    pub threshold: u32,
+    pub group_pubkey: UncompressedPoint,
+    pub verifying_shares: Vec<(u32, UncompressedPoint)>,
+}
+
+impl PublicKeyPackage {
+    /// The 32-byte 𝑋-only group key, against which BIP340 signatures are verified
+    pub fn xonly(&self) -> [u8; 32] {
+        self.group_pubkey.x()
+    }
+
+    pub fn verifying_share(&self, index: u32) -> Option<&UncompressedPoint> {
+        self.verifying_shares
+            .iter()
+            .find(|(i, _)| i.eq(&index))
+            .map(|(_, share)| share)
+    }
+}
+
+/// ### Trusted Dealer Keygen
+///
+/// Splits a freshly generated group secret key into `participants` shares, any `threshold` of
+/// which can sign, by evaluating a random polynomial of degree `threshold - 1` at each index from
+/// 1 to `participants`.
+///
+/// WARNING: The dealer learns the group secret key and must be trusted to erase it. Use the
+/// distributed key generation instead if no single party may ever hold it.
+///
+/// Fails with `LimitExceeded` unless 1 ≤ `threshold` ≤ `participants`.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::frost::trusted_dealer_keygen;
+///
+/// // 2-of-3
+/// let (shares, package) = trusted_dealer_keygen(&mut rng, 2, 3)?;
+/// ```
+pub fn trusted_dealer_keygen<R: Secp256k1SchnorrRng>(
+    rng: &mut R,
+    threshold: u32,
+    participants: u32,
+) -> Result<(Vec<KeyShare>, PublicKeyPackage), Secp256k1SchnorrError> {
+    if threshold == 0 || threshold > participants {
+        return Err(Secp256k1SchnorrError::LimitExceeded);
+    }
+    #[allow(unused_mut)]
+    let mut coefficients: Vec<Scalar> = (0..threshold)
+        .map(|_| Scalar::reduce(&rng::scalar(&mut *rng)))
+        .collect();
+    let group_pubkey = ecmul::mul_g(&coefficients[0].to_bytes())?;
+
+    let mut shares = Vec::with_capacity(participants as usize);
+    let mut verifying_shares = Vec::with_capacity(participants as usize);
+    for index in 1..=participants {
+        let share = KeyShare {
+            index,
+            threshold,
+            secret: evaluate(&coefficients, &index_scalar(index)).to_bytes(),
+            group_pubkey,
+        };
+        verifying_shares.push((index, share.verifying_share()?));
+        shares.push(share);
+    }
+
+    #[cfg(feature = "hardening")]
+    coefficients
+        .iter_mut()
+        .for_each(|coefficient| *coefficient = Scalar::ZERO);
+
+    Ok((
+        shares,
+        PublicKeyPackage {
+            threshold,
+            group_pubkey,
+            verifying_shares,
+        },
+    ))
+}
+
+/// # SigningNonces
+/// A signer's secret hiding and binding nonces for a single signing session, as generated by
+/// `commit`. They are consumed by `sign`, as reusing them leaks the signer's share.
+pub struct SigningNonces {
+    hiding: [u8; 32],
+    binding: [u8; 32],
+}
+
+#[cfg(feature = "hardening")]
+impl Drop for SigningNonces {
+    fn drop(&mut self) {
+        crate::hardening::zeroize(&mut self.hiding);
+        crate::hardening::zeroize(&mut self.binding);
+    }
+}
+
+/// # SigningCommitments
+/// A signer's public commitments 𝐷ᵢ and 𝐸ᵢ to its hiding and binding nonces, which are sent to
+/// every other signer ahead of signing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SigningCommitments {
+    pub index: u32,
+    pub hiding: CompressedPoint,
+    pub binding: CompressedPoint,
+}
+
+/// # SignatureShare
+/// A signer's share 𝑧ᵢ of the final signature, as produced by `sign`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignatureShare {
+    pub index: u32,
+    pub z: [u8; 32],
+}
+
+/// ### Commit
+///
+/// Generates the signing nonces of `share` for a single session, hedging the randomness drawn
+/// from `rng` with the secret share, along with their public commitments.
+pub fn commit<R: Secp256k1SchnorrRng>(
+    rng: &mut R,
+    share: &KeyShare,
+) -> Result<(SigningNonces, SigningCommitments), Secp256k1SchnorrError> {
+    let mut nonce = || {
+        let k = Scalar::reduce(&hashv(&[
+            FROST_NONCE_DOMAIN,
+            rng::aux(&mut *rng).as_ref(),
+            share.secret.as_ref(),
+        ]));
+        match k.is_zero() {
+            true => Err(Secp256k1SchnorrError::InvalidNonce),
+            false => Ok(k.to_bytes()),
+        }
+    };
+    let nonces = SigningNonces {
+        hiding: nonce()?,
+        binding: nonce()?,
+    };
+    let commitments = SigningCommitments {
+        index: share.index,
+        hiding: ecmul::mul_g(&nonces.hiding)?.compress(),
+        binding: ecmul::mul_g(&nonces.binding)?.compress(),
+    };
+    Ok((nonces, commitments))
+}
+
+/// The public state of a signing session, shared by signers and the aggregator.
+struct Session {
+    r: UncompressedPoint,
+    negate_nonces: bool,
+    negate_shares: bool,
+    challenge: Scalar,
+    binding_factors: Vec<Scalar>,
+    indices: Vec<u32>,
+}
+
+impl Session {
+    /// Derives the session of the signers of `commitments`, which must be sorted by index.
+    ///
+    /// The group nonce is 𝑅 = Σ 𝐷ᵢ + ρᵢ⋅𝐸ᵢ, where each binding factor ρᵢ commits to the message
+    /// and every commitment. Schemes committing to an even 𝑅 or an even group key negate the
+    /// nonces or shares respectively when they are odd, which every signer can determine alone.
+    fn new<C: Secp256k1SchnorrVerify>(
+        message: &[u8],
+        group_pubkey: &UncompressedPoint,
+        commitments: &[SigningCommitments],
+    ) -> Result<Self, Secp256k1SchnorrError> {
+        let indices: Vec<u32> = commitments.iter().map(|c| c.index).collect();
+        if indices.is_empty() || indices[0] == 0 || indices.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(Secp256k1SchnorrError::InvalidState);
+        }
+
+        let encoded: Vec<u8> = commitments
+            .iter()
+            .flat_map(|c| {
+                c.index
+                    .to_be_bytes()
+                    .into_iter()
+                    .chain(c.hiding.0)
+                    .chain(c.binding.0)
+            })
+            .collect();
+        let message_hash = hashv(&[message]);
+        let commitments_hash = hashv(&[&encoded]);
+        let group_key = group_pubkey.compress();
+        let binding_factors: Vec<Scalar> = commitments
+            .iter()
+            .map(|c| {
+                Scalar::reduce(&hashv(&[
+                    FROST_BINDING_DOMAIN,
+                    group_key.0.as_ref(),
+                    message_hash.as_ref(),
+                    commitments_hash.as_ref(),
+                    c.index.to_be_bytes().as_ref(),
+                ]))
+            })
+            .collect();
+
+        let mut r = None;
+        for (c, rho) in commitments.iter().zip(binding_factors.iter()) {
+            let hiding =
+                parse_sec1(&c.hiding.0).map_err(|_| Secp256k1SchnorrError::InvalidNonce)?;
+            let binding =
+                parse_sec1(&c.binding.0).map_err(|_| Secp256k1SchnorrError::InvalidNonce)?;
+            r = accumulate(r, hiding);
+            if let Some(point) = mul_point(&binding, rho)? {
+                r = accumulate(r, point);
+            }
+        }
+        let r = r.ok_or(Secp256k1SchnorrError::PointAtInfinity)?;
+
+        let challenge = match C::XONLY {
+            true => C::challenge_scalar(&r.x(), &xonly_point(group_pubkey), message),
+            false => C::challenge_scalar(&r.x(), &group_pubkey.compress(), message),
+        };
+        let challenge = Scalar::reduce(&challenge);
+        if challenge.is_zero() {
+            return Err(Secp256k1SchnorrError::InvalidChallenge);
+        }
+
+        Ok(Self {
+            r,
+            negate_nonces: C::EVEN_R && r.is_odd(),
+            negate_shares: C::XONLY && group_pubkey.is_odd(),
+            challenge,
+            binding_factors,
+            indices,
+        })
+    }
+
+    /// Returns the position of `index` in the session, and its weighted challenge 𝑐⋅λᵢ.
+    fn signer(&self, index: u32) -> Result<(usize, Scalar), Secp256k1SchnorrError> {
+        let position = self
+            .indices
+            .iter()
+            .position(|i| i.eq(&index))
+            .ok_or(Secp256k1SchnorrError::InvalidState)?;
+        let lambda = Scalar::reduce(&lagrange_coefficient(index, &self.indices)?);
+        Ok((position, self.challenge.mul(&lambda)))
+    }
+}
+
+/// ### Sign
+///
+/// Produces the signature share 𝑧ᵢ = 𝑑ᵢ + ρᵢ⋅𝑒ᵢ + λᵢ⋅𝑠ᵢ⋅𝑐 of `share` over `message`, given the
+/// commitments of every signer in the session sorted by index, including its own.
+///
+/// Fails with `InvalidState` if the commitments are unsorted, omit the signer or number fewer
+/// than the threshold, and with `NonceMismatch` if the signer's commitments are not those of
+/// `nonces`.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::{frost, BIP340Challenge};
+///
+/// let (nonces, commitments) = frost::commit(&mut rng, &share)?;
+/// // ... exchange commitments with the other signers ...
+/// let signature_share = frost::sign::<BIP340Challenge>(&message, &share, nonces, &all_commitments)?;
+/// ```
+pub fn sign<C: Secp256k1SchnorrVerify>(
+    message: &[u8],
+    share: &KeyShare,
+    nonces: SigningNonces,
+    commitments: &[SigningCommitments],
+) -> Result<SignatureShare, Secp256k1SchnorrError> {
+    if (commitments.len() as u64) < share.threshold as u64 {
+        return Err(Secp256k1SchnorrError::InvalidState);
+    }
+    let session = Session::new::<C>(message, &share.group_pubkey, commitments)?;
+    let (position, weighted_challenge) = session.signer(share.index)?;
+
+    let own = &commitments[position];
+    if own.hiding.ne(&ecmul::mul_g(&nonces.hiding)?.compress())
+        || own.binding.ne(&ecmul::mul_g(&nonces.binding)?.compress())
+    {
+        return Err(Secp256k1SchnorrError::NonceMismatch);
+    }
+
+    let mut nonce = Scalar::reduce(&nonces.hiding)
+        .add(&Scalar::reduce(&nonces.binding).mul(&session.binding_factors[position]));
+    if session.negate_nonces {
+        nonce = nonce.negate();
+    }
+    let mut secret = Scalar::reduce(&share.secret);
+    if session.negate_shares {
+        secret = secret.negate();
+    }
+
+    Ok(SignatureShare {
+        index: share.index,
+        z: nonce.add(&secret.mul(&weighted_challenge)).to_bytes(),
+    })
+}
+
+/// ### Verify Share
+///
+/// Verifies a signature share against the signer's verifying share in `package`, checking that
+/// 𝑧ᵢ⋅𝐺 = 𝑅ᵢ + 𝑐⋅λᵢ⋅𝑌ᵢ, so that an aggregator can identify a misbehaving signer.
+///
+/// Fails with `Unauthorized` if the signer is not in `package`, and `InvalidSignature` if the
+/// share is invalid.
+pub fn verify_share<C: Secp256k1SchnorrVerify>(
+    message: &[u8],
+    package: &PublicKeyPackage,
+    commitments: &[SigningCommitments],
+    share: &SignatureShare,
+) -> Result<(), Secp256k1SchnorrError> {
+    let session = Session::new::<C>(message, &package.group_pubkey, commitments)?;
+    verify_session_share(&session, package, commitments, share)
+}
+
+fn verify_session_share(
+    session: &Session,
+    package: &PublicKeyPackage,
+    commitments: &[SigningCommitments],
+    share: &SignatureShare,
+) -> Result<(), Secp256k1SchnorrError> {
+    let mut verifying_share = *package
+        .verifying_share(share.index)
+        .ok_or(Secp256k1SchnorrError::Unauthorized)?;
+    let (position, weighted_challenge) = session.signer(share.index)?;
+    let c = &commitments[position];
+
+    // Rᵢ = Dᵢ + ρᵢ⋅Eᵢ
+    let hiding = parse_sec1(&c.hiding.0).map_err(|_| Secp256k1SchnorrError::InvalidNonce)?;
+    let binding = parse_sec1(&c.binding.0).map_err(|_| Secp256k1SchnorrError::InvalidNonce)?;
+    let mut r = match mul_point(&binding, &session.binding_factors[position])? {
+        Some(point) => accumulate(Some(hiding), point),
+        None => Some(hiding),
+    };
+    if session.negate_nonces {
+        r.iter_mut().for_each(|r| r.invert());
+    }
+    if session.negate_shares {
+        verifying_share.invert();
+    }
+
+    let expected = match mul_point(&verifying_share, &weighted_challenge)? {
+        Some(point) => r.and_then(|r| accumulate(Some(r), point)).or(Some(point)),
+        None => r,
+    };
+    let z = Scalar::new(share.z).map_err(|_| Secp256k1SchnorrError::InvalidSignature)?;
+    let actual = match z.is_zero() {
+        true => None,
+        false => Some(ecmul::mul_g(&z.to_bytes())?),
+    };
+    if actual.ne(&expected) {
+        return Err(Secp256k1SchnorrError::InvalidSignature);
+    }
+    Ok(())
+}
+
+/// ### Aggregate
+///
+/// Verifies every signature share and combines them into the signature `𝑅∥Σ𝑧ᵢ`, an ordinary
+/// Schnorr signature under the group key that verifies with the existing `verify`.
+///
+/// Fails with `InvalidState` unless there is exactly one share per commitment and at least
+/// `threshold` of them, and as `verify_share` for the first invalid share.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::{frost, BIP340Challenge};
+///
+/// let signature = frost::aggregate::<BIP340Challenge>(&message, &package, &commitments, &shares)?;
+/// signature.verify_xonly::<BIP340Challenge>(&message, &package.xonly())?;
+/// ```
+pub fn aggregate<C: Secp256k1SchnorrVerify>(
+    message: &[u8],
+    package: &PublicKeyPackage,
+    commitments: &[SigningCommitments],
+    shares: &[SignatureShare],
+) -> Result<Secp256k1SchnorrSignature, Secp256k1SchnorrError> {
+    if (commitments.len() as u64) < package.threshold as u64
+        || shares.len() != commitments.len()
+        || shares
+            .iter()
+            .zip(commitments)
+            .any(|(s, c)| s.index != c.index)
+    {
+        return Err(Secp256k1SchnorrError::InvalidState);
+    }
+    let session = Session::new::<C>(message, &package.group_pubkey, commitments)?;
+
+    let mut z = Scalar::ZERO;
+    for share in shares {
+        verify_session_share(&session, package, commitments, share)?;
+        z = z.add(&Scalar::reduce(&share.z));
+    }
+
+    let mut signature = [0u8; 64];
+    signature[..32].clone_from_slice(&session.r.x());
+    signature[32..].clone_from_slice(&z.to_bytes());
+    Ok(Secp256k1SchnorrSignature(signature))
+}
+
+/// Domain separator for the proof of knowledge of each participant's secret in the DKG
+const FROST_DKG_DOMAIN: &[u8] = b"solana-secp256k1-schnorr/frost/dkg";
+
+/// Computes Σ 𝐶ₖ⋅𝑥ᵏ, the commitment to a polynomial evaluated at 𝑥.
+fn evaluate_commitments(
+    commitments: &[UncompressedPoint],
+    x: &Scalar,
+) -> Result<Option<UncompressedPoint>, Secp256k1SchnorrError> {
+    let mut acc = None;
+    let mut power = Scalar::ONE;
+    for commitment in commitments {
+        if let Some(point) = mul_point(commitment, &power)? {
+            acc = accumulate(acc, point);
+        }
+        power = power.mul(x);
+    }
+    Ok(acc)
+}
+
+/// Computes the challenge of a participant's proof of knowledge of its secret 𝑎₀.
+fn dkg_challenge(index: u32, secret_commitment: &CompressedPoint, r: &CompressedPoint) -> Scalar {
+    Scalar::reduce(&hashv(&[
+        FROST_DKG_DOMAIN,
+        index.to_be_bytes().as_ref(),
+        secret_commitment.0.as_ref(),
+        r.0.as_ref(),
+    ]))
+}
+
+/// # Round1Secret
+/// A participant's secret polynomial in the distributed key generation, which must be kept until
+/// `dkg_finalize` and erased afterwards.
+pub struct Round1Secret {
+    pub index: u32,
+    pub threshold: u32,
+    pub participants: u32,
+    coefficients: Vec<Scalar>,
+}
+
+#[cfg(feature = "hardening")]
+impl Drop for Round1Secret {
+    fn drop(&mut self) {
+        self.coefficients
+            .iter_mut()
+            .for_each(|coefficient| *coefficient = Scalar::ZERO);
+    }
+}
+
+/// # Round1Package
+/// A participant's broadcast in the first round of the distributed key generation: commitments
+/// 𝐶ₖ = 𝑎ₖ⋅𝐺 to each coefficient of its secret polynomial, and a Schnorr proof `𝑅∥μ` of knowledge
+/// of 𝑎₀, which prevents rogue-key attacks on the group key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Round1Package {
+    pub index: u32,
+    pub commitments: Vec<CompressedPoint>,
+    pub proof_r: CompressedPoint,
+    pub proof_mu: [u8; 32],
+}
+
+impl Round1Package {
+    /// Verifies the proof of knowledge and decompresses the commitments of a package.
+    fn verify(&self, threshold: u32) -> Result<Vec<UncompressedPoint>, Secp256k1SchnorrError> {
+        if self.commitments.len() as u64 != threshold as u64 {
+            return Err(Secp256k1SchnorrError::InvalidState);
+        }
+        let commitments = self
+            .commitments
+            .iter()
+            .map(|commitment| parse_sec1(&commitment.0))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| Secp256k1SchnorrError::InvalidProof)?;
+        let r = parse_sec1(&self.proof_r.0).map_err(|_| Secp256k1SchnorrError::InvalidProof)?;
+
+        // μ⋅𝐺 = 𝑅 + 𝑐⋅𝐶₀
+        let c = dkg_challenge(self.index, &self.commitments[0], &self.proof_r);
+        let expected = match mul_point(&commitments[0], &c)? {
+            Some(point) => accumulate(Some(r), point),
+            None => Some(r),
+        };
+        let mu = Scalar::new(self.proof_mu).map_err(|_| Secp256k1SchnorrError::InvalidProof)?;
+        let actual = match mu.is_zero() {
+            true => None,
+            false => Some(ecmul::mul_g(&mu.to_bytes())?),
+        };
+        if actual.ne(&expected) {
+            return Err(Secp256k1SchnorrError::InvalidProof);
+        }
+        Ok(commitments)
+    }
+}
+
+/// # Round2Package
+/// The secret share 𝑓ᵢ(𝑗) of its polynomial that participant 𝑖 sends to participant 𝑗 in the
+/// second round of the distributed key generation, over a confidential and authenticated channel.
+#[derive(Clone)]
+pub struct Round2Package {
+    pub sender: u32,
+    pub receiver: u32,
+    pub share: [u8; 32],
+}
+
+#[cfg(feature = "hardening")]
+impl Drop for Round2Package {
+    fn drop(&mut self) {
+        crate::hardening::zeroize(&mut self.share);
+    }
+}
+
+/// Checks that `packages` contains exactly one package from every participant other than
+/// `secret`, as identified by `index`.
+fn check_participants<P>(
+    secret: &Round1Secret,
+    packages: &[P],
+    index: impl Fn(&P) -> u32,
+) -> Result<(), Secp256k1SchnorrError> {
+    let mut indices: Vec<u32> = packages.iter().map(index).collect();
+    indices.sort_unstable();
+    let expected = (1..=secret.participants).filter(|i| i.ne(&secret.index));
+    if indices.len() as u64 != secret.participants as u64 - 1 || !indices.into_iter().eq(expected) {
+        return Err(Secp256k1SchnorrError::InvalidState);
+    }
+    Ok(())
+}
+
+/// ### DKG Round 1
+///
+/// Begins the two-round FROST distributed key generation for the participant `index` of
+/// `participants`, any `threshold` of whom can sign, returning its secret polynomial and the
+/// package to broadcast to every other participant.
+///
+/// Unlike `trusted_dealer_keygen`, no party ever learns the group secret key.
+///
+/// Fails with `LimitExceeded` unless 1 ≤ `threshold` ≤ `participants`, and `InvalidState` unless
+/// 1 ≤ `index` ≤ `participants`.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::frost;
+///
+/// let (secret, package) = frost::dkg_round1(&mut rng, 1, 2, 3)?;
+/// // ... broadcast package, then collect the others ...
+/// let shares = frost::dkg_round2(&secret, &round1_packages)?;
+/// // ... send each share to its receiver, then collect the others ...
+/// let (share, package) = frost::dkg_finalize(&secret, &round1_packages, &round2_packages)?;
+/// ```
+pub fn dkg_round1<R: Secp256k1SchnorrRng>(
+    rng: &mut R,
+    index: u32,
+    threshold: u32,
+    participants: u32,
+) -> Result<(Round1Secret, Round1Package), Secp256k1SchnorrError> {
+    if threshold == 0 || threshold > participants {
+        return Err(Secp256k1SchnorrError::LimitExceeded);
+    }
+    if index == 0 || index > participants {
+        return Err(Secp256k1SchnorrError::InvalidState);
+    }
+
+    let secret = Round1Secret {
+        index,
+        threshold,
+        participants,
+        coefficients: (0..threshold)
+            .map(|_| Scalar::reduce(&rng::scalar(&mut *rng)))
+            .collect(),
+    };
+    let commitments = secret
+        .coefficients
+        .iter()
+        .map(|coefficient| Ok(ecmul::mul_g(&coefficient.to_bytes())?.compress()))
+        .collect::<Result<Vec<_>, Secp256k1SchnorrError>>()?;
+
+    // μ = 𝑘 + 𝑐⋅𝑎₀
+    let k = Scalar::reduce(&rng::scalar(&mut *rng));
+    let proof_r = ecmul::mul_g(&k.to_bytes())?.compress();
+    let c = dkg_challenge(index, &commitments[0], &proof_r);
+    let proof_mu = k.add(&c.mul(&secret.coefficients[0])).to_bytes();
+
+    Ok((
+        secret,
+        Round1Package {
+            index,
+            commitments,
+            proof_r,
+            proof_mu,
+        },
+    ))
+}
+
+/// ### DKG Round 2
+///
+/// Verifies the round 1 package of every other participant and computes the secret share of
+/// `secret` to send to each of them.
+///
+/// Fails with `InvalidState` unless there is exactly one package from every other participant,
+/// each committing to `threshold` coefficients, and `InvalidProof` if any proof of knowledge is
+/// invalid.
+pub fn dkg_round2(
+    secret: &Round1Secret,
+    packages: &[Round1Package],
+) -> Result<Vec<Round2Package>, Secp256k1SchnorrError> {
+    check_participants(secret, packages, |package| package.index)?;
+    for package in packages {
+        package.verify(secret.threshold)?;
+    }
+    Ok(packages
+        .iter()
+        .map(|package| Round2Package {
+            sender: secret.index,
+            receiver: package.index,
+            share: evaluate(&secret.coefficients, &index_scalar(package.index)).to_bytes(),
+        })
+        .collect())
+}
+
+/// ### DKG Finalize
+///
+/// Verifies the secret share received from every other participant against its round 1
+/// commitments and combines them into this participant's key share, along with the group's
+/// public key package.
+///
+/// Fails as `dkg_round2`, with `InvalidState` if a share is not addressed to this participant,
+/// and with `InvalidProof` if a share does not match its sender's commitments.
+pub fn dkg_finalize(
+    secret: &Round1Secret,
+    round1_packages: &[Round1Package],
+    round2_packages: &[Round2Package],
+) -> Result<(KeyShare, PublicKeyPackage), Secp256k1SchnorrError> {
+    check_participants(secret, round1_packages, |package| package.index)?;
+    check_participants(secret, round2_packages, |package| package.sender)?;
+    if round2_packages
+        .iter()
+        .any(|package| package.receiver != secret.index)
+    {
+        return Err(Secp256k1SchnorrError::InvalidState);
+    }
+
+    // The commitments of every participant, including this one, ordered by index
+    let mut commitments = Vec::with_capacity(secret.participants as usize);
+    for index in 1..=secret.participants {
+        commitments.push(match index == secret.index {
+            true => secret
+                .coefficients
+                .iter()
+                .map(|coefficient| ecmul::mul_g(&coefficient.to_bytes()))
+                .collect::<Result<Vec<_>, _>>()?,
+            false => round1_packages
+                .iter()
+                .find(|package| package.index == index)
+                .ok_or(Secp256k1SchnorrError::InvalidState)?
+                .verify(secret.threshold)?,
+        });
+    }
+
+    // 𝑠ᵢ = Σ 𝑓ⱼ(𝑖), each of which must match 𝑓ⱼ(𝑖)⋅𝐺 = Σ 𝐶ⱼₖ⋅𝑖ᵏ
+    let x = index_scalar(secret.index);
+    let mut share = evaluate(&secret.coefficients, &x);
+    for package in round2_packages {
+        let share_j =
+            Scalar::new(package.share).map_err(|_| Secp256k1SchnorrError::InvalidProof)?;
+        let expected = evaluate_commitments(&commitments[package.sender as usize - 1], &x)?;
+        let actual = match share_j.is_zero() {
+            true => None,
+            false => Some(ecmul::mul_g(&share_j.to_bytes())?),
+        };
+        if actual.ne(&expected) {
+            return Err(Secp256k1SchnorrError::InvalidProof);
+        }
+        share = share.add(&share_j);
+    }
+
+    // 𝑌 = Σ 𝐶ⱼ₀, and 𝑌ₘ = Σ 𝑓ⱼ(𝑚)⋅𝐺 for every participant 𝑚
+    let group_pubkey = commitments
+        .iter()
+        .fold(None, |acc, commitments| accumulate(acc, commitments[0]))
+        .ok_or(Secp256k1SchnorrError::PointAtInfinity)?;
+    let mut verifying_shares = Vec::with_capacity(secret.participants as usize);
+    for index in 1..=secret.participants {
+        let x = index_scalar(index);
+        let mut verifying_share = None;
+        for commitments in &commitments {
+            if let Some(point) = evaluate_commitments(commitments, &x)? {
+                verifying_share = accumulate(verifying_share, point);
+            }
+        }
+        verifying_shares.push((
+            index,
+            verifying_share.ok_or(Secp256k1SchnorrError::PointAtInfinity)?,
+        ));
+    }
+
+    let key_share = KeyShare {
+        index: secret.index,
+        threshold: secret.threshold,
+        secret: share.to_bytes(),
+        group_pubkey,
+    };
+
+    Ok((
+        key_share,
+        PublicKeyPackage {
+            threshold: secret.threshold,
+            group_pubkey,
+            verifying_shares,
+        },
+    ))
+}
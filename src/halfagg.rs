@@ -0,0 +1,169 @@
+use solana_nostd_sha256::hashv;
+use solana_secp256k1::{Secp256k1Point, UncompressedPoint};
+
+use crate::{
+    challenges::{bip340::BIP340Challenge, Secp256k1SchnorrVerify},
+    ecmul,
+    errors::Secp256k1SchnorrError,
+    keys::lift_x,
+    scalar::Scalar,
+    Secp256k1SchnorrSignature,
+};
+
+// sha256(HalfAgg/randomizer) tagged hash
+const HALFAGG_RANDOMIZER: [u8; 32] = [
+    0xfa, 0x9c, 0x06, 0x60, 0x9f, 0xd9, 0xa4, 0xa6, 0x00, 0xa4, 0x49, 0x46, 0xb4, 0x18, 0x16, 0x2e,
+    0xe6, 0x2a, 0x5a, 0xec, 0x41, 0xf3, 0x24, 0x04, 0x20, 0xf4, 0xaf, 0x8b, 0xe7, 0x0d, 0x0c, 0xc4,
+];
+
+/// Maximum number of signatures in a half-aggregate, as set by the draft
+pub const HALFAGG_MAX_SIGNATURES: usize = u16::MAX as usize;
+
+/// Appends `r∥pk∥m` of the next entry to `transcript` and returns its randomizer
+/// 𝑧ᵢ = hash_HalfAgg/randomizer(𝑟₀∥𝑝𝑘₀∥𝑚₀∥…∥𝑟ᵢ∥𝑝𝑘ᵢ∥𝑚ᵢ) mod 𝑁, with 𝑧₀ = 1.
+fn randomizer(
+    transcript: &mut Vec<u8>,
+    r: &[u8; 32],
+    pubkey: &[u8; 32],
+    message: &[u8; 32],
+) -> Scalar {
+    let first = transcript.is_empty();
+    transcript.extend_from_slice(r);
+    transcript.extend_from_slice(pubkey);
+    transcript.extend_from_slice(message);
+    match first {
+        true => Scalar::ONE,
+        false => Scalar::reduce(&hashv(&[
+            &HALFAGG_RANDOMIZER,
+            &HALFAGG_RANDOMIZER,
+            transcript,
+        ])),
+    }
+}
+
+/// Adds two points, failing with `PointAtInfinity` if they are inverses of one another.
+fn add_points(
+    a: UncompressedPoint,
+    b: UncompressedPoint,
+) -> Result<UncompressedPoint, Secp256k1SchnorrError> {
+    if a.x() == b.x() && a.y() != b.y() {
+        return Err(Secp256k1SchnorrError::PointAtInfinity);
+    }
+    Ok(a + b)
+}
+
+/// ### Aggregate
+///
+/// Half-aggregates BIP340 signatures of 32-byte messages under 𝑋-only public keys into
+/// `𝑟₀∥…∥𝑟ᵤ₋₁∥𝑠`, where 𝑠 = Σ𝑧ᵢ⋅𝑠ᵢ, per the cross-input signature aggregation draft. The
+/// aggregate is `32⋅(𝑢+1)` bytes rather than `64⋅𝑢`. Aggregation needs no secrets, so anyone
+/// holding the signatures, such as a relayer, may perform it.
+///
+/// The signatures themselves are not verified, failing only with `InvalidSignatureS` if an 𝑠
+/// is not a valid scalar, and with `LimitExceeded` for more than `HALFAGG_MAX_SIGNATURES`.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::halfagg::aggregate;
+///
+/// let aggregate = aggregate(&[(pubkey_a, message_a, signature_a), (pubkey_b, message_b, signature_b)])?;
+/// ```
+pub fn aggregate(
+    entries: &[([u8; 32], [u8; 32], Secp256k1SchnorrSignature)],
+) -> Result<Vec<u8>, Secp256k1SchnorrError> {
+    if entries.len() > HALFAGG_MAX_SIGNATURES {
+        return Err(Secp256k1SchnorrError::LimitExceeded);
+    }
+    let mut out = Vec::with_capacity(32 * (entries.len() + 1));
+    let mut transcript = Vec::with_capacity(96 * entries.len());
+    let mut s = Scalar::ZERO;
+    for (pubkey, message, signature) in entries {
+        let s_i =
+            Scalar::new(signature.s()).map_err(|_| Secp256k1SchnorrError::InvalidSignatureS)?;
+        let z = randomizer(&mut transcript, signature.r_ref(), pubkey, message);
+        s = s.add(&z.mul(&s_i));
+        out.extend_from_slice(signature.r_ref());
+    }
+    out.extend_from_slice(&s.to_bytes());
+    Ok(out)
+}
+
+/// ### Verify Aggregate
+///
+/// Verifies a half-aggregate of BIP340 signatures over `(pubkey, message)` pairs in the order
+/// they were aggregated, checking 𝑠⋅𝐺 = Σ𝑧ᵢ⋅(𝑅ᵢ + 𝑒ᵢ⋅𝑃ᵢ). This costs `2𝑢 - 1` calls to
+/// `secp256k1_recover` and `2𝑢 - 2` point additions, in exchange for halving the signature data
+/// carried by the transaction. An empty aggregate of zero signatures is valid.
+///
+/// Fails with `InvalidSignature` if the aggregate is not `32⋅(𝑢+1)` bytes or does not verify,
+/// `InvalidSignatureR` or `InvalidSignatureS` if any 𝑟ᵢ is not on the curve or 𝑠 is not a valid
+/// scalar, and `InvalidPublicKey` if any public key is not on the curve. A partial sum may also
+/// cancel out with `PointAtInfinity`, which honestly aggregated signatures do with negligible
+/// probability.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::halfagg::verify_aggregate;
+///
+/// verify_aggregate(&[(pubkey_a, message_a), (pubkey_b, message_b)], &aggregate)
+///     .expect("Invalid aggregate signature");
+/// ```
+pub fn verify_aggregate(
+    entries: &[([u8; 32], [u8; 32])],
+    aggregate: &[u8],
+) -> Result<(), Secp256k1SchnorrError> {
+    if entries.len() > HALFAGG_MAX_SIGNATURES {
+        return Err(Secp256k1SchnorrError::LimitExceeded);
+    }
+    if aggregate.len() != 32 * (entries.len() + 1) {
+        return Err(Secp256k1SchnorrError::InvalidSignature);
+    }
+    let (rs, s) = aggregate.split_at(32 * entries.len());
+    let s =
+        Scalar::new(s.try_into().unwrap()).map_err(|_| Secp256k1SchnorrError::InvalidSignatureS)?;
+    if entries.is_empty() {
+        return match s.is_zero() {
+            true => Ok(()),
+            false => Err(Secp256k1SchnorrError::InvalidSignature),
+        };
+    }
+
+    // With z₀ = 1, the equation becomes Σ(zᵢ⋅eᵢ⋅Pᵢ) + Σᵢ₌₁(zᵢ⋅Rᵢ) - s⋅G = -R₀, accumulated
+    // without ever reaching the point at infinity of a valid aggregate
+    let mut transcript = Vec::with_capacity(96 * entries.len());
+    let mut sum: Option<UncompressedPoint> = None;
+    let mut r_0 = [0u8; 32];
+    for (i, ((pubkey, message), r)) in entries.iter().zip(rs.chunks_exact(32)).enumerate() {
+        let r: &[u8; 32] = r.try_into().unwrap();
+        let p = lift_x(pubkey)?;
+        let r_point = lift_x(r).map_err(|_| Secp256k1SchnorrError::InvalidSignatureR)?;
+        let e = Scalar::reduce(&BIP340Challenge::challenge(r, &p, message));
+        let z = randomizer(&mut transcript, r, pubkey, message);
+
+        // zᵢ⋅eᵢ⋅Pᵢ, minus s⋅G for the first entry
+        let term = match i {
+            0 => ecmul::mul_add_g(&p, &z.mul(&e).to_bytes(), &s.negate().to_bytes()),
+            _ => ecmul::mul(&p, &z.mul(&e).to_bytes()),
+        }
+        .map_err(|_| Secp256k1SchnorrError::InvalidSignature)?;
+        sum = Some(match sum {
+            None => term,
+            Some(sum) => add_points(sum, term)?,
+        });
+
+        match i {
+            0 => r_0 = *r,
+            _ => {
+                let term = ecmul::mul(&r_point, &z.to_bytes())
+                    .map_err(|_| Secp256k1SchnorrError::InvalidSignature)?;
+                sum = sum.map(|sum| add_points(sum, term)).transpose()?;
+            }
+        }
+    }
+
+    // -R₀ has the X-coordinate r₀ and an odd Y-coordinate
+    match sum {
+        Some(sum) if sum.x().eq(&r_0) && sum.is_odd() => Ok(()),
+        _ => Err(Secp256k1SchnorrError::InvalidSignature),
+    }
+}
@@ -0,0 +1,64 @@
+use core::{hint::black_box, ptr::write_volatile, sync::atomic::compiler_fence};
+
+/// ### Stack Scrub Size
+///
+/// Size in bytes of the region below the caller's frame zeroed by `scrub_stack`. It is scrubbed
+/// from a single frame, kept well within the 4KiB SBF stack frame limit, so scrubbing adds one
+/// frame to the call depth.
+pub const STACK_SCRUB_SIZE: usize = 3072;
+
+/// ### Zeroize
+///
+/// Overwrites a buffer with zeros using volatile writes, which the compiler may not elide even
+/// though the buffer is never read again.
+#[inline(never)]
+pub fn zeroize(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut() {
+        // Safety: `byte` is a valid, aligned and exclusive reference.
+        unsafe { write_volatile(byte, 0) };
+    }
+    compiler_fence(core::sync::atomic::Ordering::SeqCst);
+}
+
+/// ### Zeroizing
+///
+/// A secret buffer that is overwritten with `zeroize` when dropped, so that it is wiped on every
+/// exit path of the function holding it, including early returns and `?`.
+pub struct Zeroizing<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> Drop for Zeroizing<N> {
+    fn drop(&mut self) {
+        zeroize(&mut self.0);
+    }
+}
+
+impl<const N: usize> core::ops::Deref for Zeroizing<N> {
+    type Target = [u8; N];
+
+    fn deref(&self) -> &[u8; N] {
+        &self.0
+    }
+}
+
+impl<const N: usize> core::ops::DerefMut for Zeroizing<N> {
+    fn deref_mut(&mut self) -> &mut [u8; N] {
+        &mut self.0
+    }
+}
+
+/// ### Scrub Stack
+///
+/// Best-effort scrubbing of the stack region below the caller's frame, where the locals of any
+/// functions it has just returned from lived. A scratch buffer of `STACK_SCRUB_SIZE` bytes is
+/// zeroed with volatile writes, so that secret intermediates spilled to the stack during signing
+/// do not outlive the signing call.
+///
+/// This cannot reach values held in registers, copied elsewhere by the compiler, or spilled deeper
+/// than `STACK_SCRUB_SIZE` bytes below the caller, and is only meaningful when invoked immediately
+/// after the sensitive call returns.
+#[inline(never)]
+pub fn scrub_stack() {
+    let mut scratch = [0xffu8; STACK_SCRUB_SIZE];
+    zeroize(&mut scratch);
+    black_box(&scratch);
+}
@@ -0,0 +1,261 @@
+use solana_secp256k1::{
+    CompressedPoint, Curve, Secp256k1Point, UncompressedPoint, SEC1_OCTET_COMPRESSED_EVEN,
+    SEC1_OCTET_COMPRESSED_ODD, SEC1_OCTET_UNCOMPRESSED,
+};
+
+use crate::{ecmul, errors::Secp256k1SchnorrError, scalar::Scalar};
+
+/// ### Lift 𝑋
+///
+/// Lifts a 32-byte 𝑋-coordinate to the unique point on the curve with an even 𝑌-coordinate, as
+/// defined by `lift_x` in BIP340.
+///
+/// Fails with `InvalidPublicKey` if 𝑋 is not a valid field element or if 𝑋³ + 7 has no square root
+/// modulo 𝑃, in which case there is no point on the curve with that 𝑋-coordinate.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::keys::lift_x;
+///
+/// let point = lift_x(&Curve::G.x()).expect("Invalid X coordinate");
+/// assert!(point.is_even());
+/// ```
+pub fn lift_x(x: &[u8; 32]) -> Result<UncompressedPoint, Secp256k1SchnorrError> {
+    if x.ge(&Curve::P) {
+        return Err(Secp256k1SchnorrError::InvalidPublicKey);
+    }
+    let mut point =
+        UncompressedPoint::lift_x(x).map_err(|_| Secp256k1SchnorrError::InvalidPublicKey)?;
+    if point.is_odd() {
+        point.invert();
+    }
+    Ok(point)
+}
+
+/// ### Parse SEC1
+///
+/// Parses a public key from its 33-byte compressed (`0x02`/`0x03`) or 65-byte uncompressed
+/// (`0x04`) SEC1 encoding, as commonly produced by other secp256k1 libraries.
+///
+/// Fails with `InvalidPublicKey` for any other length or prefix, including the hybrid `0x06`/`0x07`
+/// encodings, if a coordinate is not a valid field element, or if the point is not on the curve.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::keys::parse_sec1;
+///
+/// let pubkey = parse_sec1(&sec1_bytes).expect("Invalid SEC1 public key");
+/// ```
+pub fn parse_sec1(bytes: &[u8]) -> Result<UncompressedPoint, Secp256k1SchnorrError> {
+    match (bytes.len(), bytes.first()) {
+        (33, Some(&(SEC1_OCTET_COMPRESSED_EVEN | SEC1_OCTET_COMPRESSED_ODD))) => {
+            let mut x = [0u8; 32];
+            x.clone_from_slice(&bytes[1..]);
+            let mut point = lift_x(&x)?;
+            if bytes[0] == SEC1_OCTET_COMPRESSED_ODD {
+                point.invert();
+            }
+            Ok(point)
+        }
+        (65, Some(&SEC1_OCTET_UNCOMPRESSED)) => {
+            let mut point = [0u8; 65];
+            point.clone_from_slice(bytes);
+            let point = UncompressedPoint::from(point);
+            // 𝑌 must be one of the two square roots of 𝑋³ + 7 lifted from 𝑋
+            let even = lift_x(&point.x())?;
+            if point.y().ne(&even.y()) && point.y().ne(&Curve::negate_p(&even.y())) {
+                return Err(Secp256k1SchnorrError::InvalidPublicKey);
+            }
+            Ok(point)
+        }
+        _ => Err(Secp256k1SchnorrError::InvalidPublicKey),
+    }
+}
+
+/// ### Parse SEC1 Compressed
+///
+/// Parses a public key from either SEC1 encoding, as with `parse_sec1`, returning it compressed.
+pub fn parse_sec1_compressed(bytes: &[u8]) -> Result<CompressedPoint, Secp256k1SchnorrError> {
+    parse_sec1(bytes).map(|point| point.compress())
+}
+
+/// ### To SEC1 Compressed
+///
+/// Formats a public key as its 33-byte compressed SEC1 encoding.
+pub fn to_sec1_compressed<T: Secp256k1Point>(pubkey: &T) -> [u8; 33] {
+    pubkey.compress().0
+}
+
+/// ### To SEC1 Uncompressed
+///
+/// Formats a public key as its 65-byte uncompressed SEC1 encoding.
+pub fn to_sec1_uncompressed<T: Secp256k1Point>(pubkey: &T) -> [u8; 65] {
+    pubkey.decompress().to_sec1_bytes()
+}
+
+/// ### Ethereum Address
+///
+/// Derives the 20-byte Ethereum address of a public key, the last 20 bytes of the Keccak256 hash
+/// of its 64-byte uncompressed encoding without the SEC1 prefix.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::keys::eth_address;
+///
+/// // 0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf
+/// let address = eth_address(&Curve::G);
+/// ```
+#[cfg(feature = "keccak256")]
+pub fn eth_address<T: Secp256k1Point>(pubkey: &T) -> [u8; 20] {
+    let hash = solana_nostd_keccak::hash(&pubkey.decompress().0);
+    let mut address = [0u8; 20];
+    address.clone_from_slice(&hash[12..]);
+    address
+}
+
+/// ### Pubkey Tweak Add
+///
+/// Tweaks a public key additively into 𝑄 = 𝑃 + 𝑡⋅𝐺 with a single call to `secp256k1_recover`,
+/// as pay-to-contract and commitment schemes do, yielding the public key of the secret key
+/// tweaked with `seckey_tweak_add`. 𝑃 is used with its parity as given, so 𝑋-only keys must
+/// first be lifted with `lift_x`, as BIP341 does.
+///
+/// Fails with `ArithmeticOverflow` if `tweak` is not a valid scalar, and `PointAtInfinity` if
+/// 𝑄 is.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::keys::pubkey_tweak_add;
+///
+/// let tweak = solana_nostd_sha256::hashv(&[&pubkey.compress().0, contract]);
+/// let tweaked = pubkey_tweak_add(&pubkey, &tweak)?;
+/// ```
+pub fn pubkey_tweak_add<T: Secp256k1Point>(
+    pubkey: &T,
+    tweak: &[u8; 32],
+) -> Result<UncompressedPoint, Secp256k1SchnorrError> {
+    if tweak.ge(&Curve::N) {
+        return Err(Secp256k1SchnorrError::ArithmeticOverflow);
+    }
+    ecmul::mul_add_g(pubkey, &Scalar::ONE.to_bytes(), tweak)
+}
+
+/// ### Pubkey Tweak Mul
+///
+/// Tweaks a public key multiplicatively into 𝑄 = 𝑡⋅𝑃 with a single call to `secp256k1_recover`,
+/// yielding the public key of the secret key tweaked with `seckey_tweak_mul`.
+///
+/// Fails with `ArithmeticOverflow` if `tweak` is not a valid scalar, and `PointAtInfinity` if it
+/// is zero.
+pub fn pubkey_tweak_mul<T: Secp256k1Point>(
+    pubkey: &T,
+    tweak: &[u8; 32],
+) -> Result<UncompressedPoint, Secp256k1SchnorrError> {
+    ecmul::mul(pubkey, tweak)
+}
+
+/// ### Seckey Tweak Add
+///
+/// Tweaks a secret key additively into 𝑑 + 𝑡, the secret key of the public key tweaked with
+/// `pubkey_tweak_add`.
+///
+/// Fails with `InvalidSecretKey` if `privkey` or the tweaked key is not a valid secret key, and
+/// `ArithmeticOverflow` if `tweak` is not a valid scalar.
+#[cfg(feature = "sign")]
+pub fn seckey_tweak_add(
+    privkey: &[u8; 32],
+    tweak: &[u8; 32],
+) -> Result<[u8; 32], Secp256k1SchnorrError> {
+    let d = Scalar::new_nonzero(*privkey)?;
+    let t = Scalar::new(*tweak).map_err(|_| Secp256k1SchnorrError::ArithmeticOverflow)?;
+    let tweaked = d.add(&t);
+    if tweaked.is_zero() {
+        return Err(Secp256k1SchnorrError::InvalidSecretKey);
+    }
+    Ok(tweaked.to_bytes())
+}
+
+/// ### Seckey Tweak Mul
+///
+/// Tweaks a secret key multiplicatively into 𝑡⋅𝑑, the secret key of the public key tweaked with
+/// `pubkey_tweak_mul`.
+///
+/// Fails with `InvalidSecretKey` if `privkey` is not a valid secret key, and `ArithmeticOverflow`
+/// if `tweak` is not a nonzero scalar.
+#[cfg(feature = "sign")]
+pub fn seckey_tweak_mul(
+    privkey: &[u8; 32],
+    tweak: &[u8; 32],
+) -> Result<[u8; 32], Secp256k1SchnorrError> {
+    let d = Scalar::new_nonzero(*privkey)?;
+    let t = Scalar::new(*tweak).map_err(|_| Secp256k1SchnorrError::ArithmeticOverflow)?;
+    if t.is_zero() {
+        return Err(Secp256k1SchnorrError::ArithmeticOverflow);
+    }
+    Ok(d.mul(&t).to_bytes())
+}
+
+/// # XOnlyPublicKey
+///
+/// A 32-byte 𝑋-only public key, as used by BIP340, Taproot and Nostr, standing for the point with
+/// that 𝑋-coordinate and an even 𝑌-coordinate. No validation is performed on construction, so a key
+/// that is not on the curve fails when lifted or verified against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct XOnlyPublicKey(pub [u8; 32]);
+
+impl XOnlyPublicKey {
+    /// ### Lift 𝑋
+    ///
+    /// Lifts the key to its point with an even 𝑌-coordinate, as the free function `lift_x`.
+    pub fn lift_x(&self) -> Result<UncompressedPoint, Secp256k1SchnorrError> {
+        lift_x(&self.0)
+    }
+
+    /// ### To Compressed
+    ///
+    /// Returns the compressed encoding of the key's point, prefixing it with `0x02`. Unlike
+    /// `lift_x`, this is free and does not check that the key is on the curve.
+    pub fn to_compressed(&self) -> CompressedPoint {
+        let mut point = [SEC1_OCTET_COMPRESSED_EVEN; 33];
+        point[1..].clone_from_slice(&self.0);
+        CompressedPoint(point)
+    }
+
+    /// ### From Point
+    ///
+    /// Returns the 𝑋-only key of a point, and whether its 𝑌-coordinate is odd, in which case the
+    /// key stands for its negation.
+    pub fn from_point<T: Secp256k1Point>(pubkey: &T) -> (Self, bool) {
+        (Self(pubkey.x()), pubkey.is_odd())
+    }
+}
+
+impl From<[u8; 32]> for XOnlyPublicKey {
+    fn from(x: [u8; 32]) -> Self {
+        Self(x)
+    }
+}
+
+impl From<CompressedPoint> for XOnlyPublicKey {
+    fn from(pubkey: CompressedPoint) -> Self {
+        Self(pubkey.x())
+    }
+}
+
+impl From<UncompressedPoint> for XOnlyPublicKey {
+    fn from(pubkey: UncompressedPoint) -> Self {
+        Self(pubkey.x())
+    }
+}
+
+impl From<XOnlyPublicKey> for CompressedPoint {
+    fn from(xonly: XOnlyPublicKey) -> Self {
+        xonly.to_compressed()
+    }
+}
+
+impl core::borrow::Borrow<[u8; 32]> for XOnlyPublicKey {
+    fn borrow(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{Secp256k1SchnorrSignature, SECP256K1_SCHNORR_SIGNATURE_LENGTH};
+
+/// ### Layout
+///
+/// The byte layout of a 64-byte signature emitted by an external system. This crate always
+/// encodes signatures as big-endian 𝑟 followed by big-endian 𝑠, which is `Layout::RS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// Big-endian 𝑟 followed by big-endian 𝑠, as used by this crate and BIP340
+    RS,
+    /// Big-endian 𝑠 followed by big-endian 𝑟
+    SR,
+    /// Little-endian 𝑟 followed by little-endian 𝑠
+    RSLittleEndian,
+    /// Little-endian 𝑠 followed by little-endian 𝑟
+    SRLittleEndian,
+}
+
+impl Layout {
+    fn swapped(&self) -> bool {
+        matches!(self, Self::SR | Self::SRLittleEndian)
+    }
+
+    fn little_endian(&self) -> bool {
+        matches!(self, Self::RSLittleEndian | Self::SRLittleEndian)
+    }
+}
+
+/// Converts between a layout and `Layout::RS`, which is its own inverse.
+fn convert(
+    bytes: &[u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH],
+    layout: Layout,
+) -> [u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH] {
+    let mut out = *bytes;
+    if layout.swapped() {
+        out[..32].clone_from_slice(&bytes[32..]);
+        out[32..].clone_from_slice(&bytes[..32]);
+    }
+    if layout.little_endian() {
+        out[..32].reverse();
+        out[32..].reverse();
+    }
+    out
+}
+
+impl Secp256k1SchnorrSignature {
+    /// ### From Legacy Layout
+    /// Decodes a signature emitted by an external system in a layout other than this crate's
+    /// `𝑟∥𝑠`, rather than shuffling its bytes by hand.
+    ///
+    /// No validation is performed, so a signature decoded with the wrong layout simply fails
+    /// verification.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::{layout::Layout, Secp256k1SchnorrSignature};
+    ///
+    /// let signature = Secp256k1SchnorrSignature::from_legacy_layout(&s_r_bytes, Layout::SR);
+    /// ```
+    pub fn from_legacy_layout(
+        bytes: &[u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH],
+        layout: Layout,
+    ) -> Self {
+        Self(convert(bytes, layout))
+    }
+
+    /// ### To Legacy Layout
+    /// Encodes a signature in the layout expected by an external system.
+    pub fn to_legacy_layout(&self, layout: Layout) -> [u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH] {
+        convert(&self.0, layout)
+    }
+}
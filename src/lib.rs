@@ -1,15 +1,285 @@
+#[cfg(feature = "adaptor")]
+pub mod adaptor;
+#[cfg(feature = "keccak256")]
+pub mod address;
+#[cfg(feature = "anchor")]
+pub mod anchor;
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+#[cfg(feature = "audit")]
+pub mod audit;
+pub mod backend;
+#[cfg(feature = "bip32")]
+pub mod bip32;
+#[cfg(feature = "bip322")]
+pub mod bip322;
+#[cfg(feature = "blind")]
+pub mod blind;
 pub mod challenges;
+pub mod compute;
+#[cfg(feature = "debug-logs")]
+mod debug;
+#[cfg(feature = "dlc")]
+pub mod dlc;
+#[cfg(feature = "dleq")]
+pub mod dleq;
+#[cfg(feature = "dudect")]
+pub mod dudect;
+pub mod ecmul;
+#[cfg(feature = "ecvrf")]
+pub mod ecvrf;
 pub mod errors;
+#[cfg(feature = "evm")]
+pub mod evm;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "frost")]
+pub mod frost;
+#[cfg(feature = "halfagg")]
+pub mod halfagg;
+#[cfg(feature = "hardening")]
+pub mod hardening;
+pub mod keys;
+pub mod layout;
+#[cfg(feature = "messages")]
+pub mod messages;
+#[cfg(feature = "musig2")]
+pub mod musig2;
+#[cfg(feature = "nostr")]
+pub mod nostr;
+pub mod prelude;
+#[cfg(feature = "program")]
+pub mod program;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(any(feature = "bch", all(feature = "ecvrf", feature = "sign")))]
+mod rfc6979;
+#[cfg(feature = "rng")]
+pub mod rng;
+#[cfg(feature = "s2c")]
+pub mod s2c;
+pub mod scalar;
+#[cfg(feature = "serde")]
+mod serde;
+#[cfg(feature = "signature")]
+pub mod signature;
+#[cfg(feature = "taproot")]
+pub mod taproot;
 #[cfg(test)]
 mod tests;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "zilliqa")]
+pub mod zilliqa;
 
+use backend::{DefaultRecover, Secp256k1RecoverBackend};
 use challenges::{Secp256k1SchnorrSign, Secp256k1SchnorrVerify};
 use errors::Secp256k1SchnorrError;
-use solana_nostd_secp256k1_recover::secp256k1_recover;
-use solana_secp256k1::{Curve, Secp256k1Point, UncompressedPoint};
+use solana_secp256k1::{
+    CompressedPoint, Curve, Secp256k1Point, UncompressedPoint, SEC1_OCTET_COMPRESSED_EVEN,
+};
 
 pub const SECP256K1_SCHNORR_SIGNATURE_LENGTH: usize = 64;
 
+/// Returns true if two byte strings are equal. With the "constant-time" feature flag enabled, the
+/// comparison runs in constant time with `subtle`, so that comparisons of secret values such as
+/// nonces leak nothing through timing.
+#[inline(always)]
+pub(crate) fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    #[cfg(feature = "constant-time")]
+    return subtle::ConstantTimeEq::ct_eq(a, b).into();
+    #[cfg(not(feature = "constant-time"))]
+    a.eq(b)
+}
+
+/// Returns true if a 32-byte scalar is congruent to zero modulo the curve order 𝑁. As 2𝑁 exceeds
+/// 2²⁵⁶, the only such values are 0 and 𝑁 itself. Both are always compared, as signing passes
+/// secret nonces.
+#[inline(always)]
+pub(crate) fn is_zero_mod_n(k: &[u8; 32]) -> bool {
+    bytes_eq(k, &[0u8; 32]) | bytes_eq(k, &Curve::N)
+}
+
+/// Holds a secret scalar of signing, which with the "hardening" feature flag enabled is zeroized
+/// when dropped, on every exit path including failures.
+#[cfg(all(feature = "sign", feature = "hardening"))]
+#[inline(always)]
+fn secret(bytes: [u8; 32]) -> hardening::Zeroizing<32> {
+    hardening::Zeroizing(bytes)
+}
+
+#[cfg(all(feature = "sign", not(feature = "hardening")))]
+#[inline(always)]
+fn secret(bytes: [u8; 32]) -> [u8; 32] {
+    bytes
+}
+
+/// Returns true if s*G == e*P, in which case R = s*G - e*P is the point at infinity.
+#[inline(always)]
+fn is_point_at_infinity<T: Secp256k1Point>(s: &[u8; 32], e: &[u8; 32], pubkey: &T) -> bool {
+    matches!(
+        (Curve::mul_g(s), Curve::ecmul(pubkey, e)),
+        (Ok(s_g), Ok(e_p)) if s_g.eq(&e_p)
+    )
+}
+
+/// Returns the point with an even 𝑌-coordinate sharing the 𝑋-coordinate of `pubkey`, which is the
+/// public key x-only schemes verify against. Only the parity byte changes, so this is free.
+#[inline(always)]
+pub(crate) fn xonly_point<T: Secp256k1Point>(pubkey: &T) -> CompressedPoint {
+    let mut point = [SEC1_OCTET_COMPRESSED_EVEN; 33];
+    point[1..].clone_from_slice(&pubkey.x());
+    CompressedPoint(point)
+}
+
+/// Computes the operands `(𝑚, 𝑟∥𝑠)` for which `secp256k1_recover` returns 𝑅 = 𝑠⋅𝐺 - 𝑒⋅𝑃.
+#[inline(always)]
+fn recover_operands<T: Secp256k1Point>(
+    s: &[u8; 32],
+    e: &[u8; 32],
+    pubkey: &T,
+) -> Result<([u8; 32], [u8; 64]), Secp256k1SchnorrError> {
+    let px = pubkey.x();
+    // m = -s*Px
+    let m = Curve::negate_n(&Curve::mul_mod_n(s, &px));
+    // -e*Px
+    let e_px = Curve::negate_n(&Curve::mul_mod_n(e, &px));
+
+    // R and S are made up of Px and and -e*Px
+    let mut r_s = [0u8; 64];
+    r_s[..32].clone_from_slice(&px);
+    r_s[32..].clone_from_slice(&e_px);
+
+    #[cfg(feature = "audit")]
+    {
+        audit::record("verify.m", &m);
+        audit::record("verify.r_s", &r_s);
+    }
+
+    if m.eq(&[0u8; 32]) {
+        #[cfg(feature = "debug-logs")]
+        debug::log("recover operand m is zero");
+        return Err(Secp256k1SchnorrError::InvalidSignature);
+    }
+
+    // R = s*G - e*P degenerates to the point at infinity when s*G == e*P, which the recover
+    // trick cannot represent. The syscall and the "host" backend reject it, so with them we only
+    // pay to identify it on the failure path, whereas the host implementation of the syscall
+    // panics and must be guarded up front.
+    #[cfg(not(any(target_os = "solana", feature = "host")))]
+    if is_point_at_infinity(s, e, pubkey) {
+        return Err(Secp256k1SchnorrError::PointAtInfinity);
+    }
+
+    Ok((m, r_s))
+}
+
+/// Recovers the nonce point 𝑅 = 𝑠⋅𝐺 - 𝑒⋅𝑃 with a single call to `secp256k1_recover`.
+#[inline(always)]
+pub(crate) fn recover_nonce<T: Secp256k1Point>(
+    s: &[u8; 32],
+    e: &[u8; 32],
+    pubkey: &T,
+) -> Result<[u8; 64], Secp256k1SchnorrError> {
+    let (m, r_s) = recover_operands(s, e, pubkey)?;
+
+    let r = DefaultRecover::recover(&m, pubkey.is_odd(), &r_s).map_err(|_| {
+        #[cfg(feature = "debug-logs")]
+        debug::log("secp256k1_recover failed");
+        #[cfg(any(target_os = "solana", feature = "host"))]
+        if is_point_at_infinity(s, e, pubkey) {
+            return Secp256k1SchnorrError::PointAtInfinity;
+        }
+        Secp256k1SchnorrError::InvalidSignature
+    })?;
+
+    #[cfg(feature = "audit")]
+    audit::record("verify.R", &r);
+
+    Ok(r)
+}
+
+// (𝑃-1)/2, the exponent of Euler's criterion
+const P_MINUS_ONE_HALF: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f, 0xff, 0xfe, 0x17,
+];
+
+/// Returns true if a field element is a nonzero quadratic residue modulo 𝑃, by Euler's criterion
+/// 𝑦^((𝑃-1)/2) = 1. This costs some 500 field multiplications, so only schemes that commit to a
+/// square 𝑅 pay for it.
+pub(crate) fn is_square_mod_p(y: &[u8; 32]) -> bool {
+    let mut one = [0u8; 32];
+    one[31] = 1;
+    let mut acc = one;
+    for byte in P_MINUS_ONE_HALF {
+        for bit in (0..8).rev() {
+            acc = Curve::mul_mod_p(&acc, &acc);
+            if (byte >> bit) & 1 == 1 {
+                acc = Curve::mul_mod_p(&acc, y);
+            }
+        }
+    }
+    acc.eq(&one)
+}
+
+/// Checks a recovered nonce point against the 𝑟 of a signature.
+#[inline(always)]
+fn check_nonce(
+    expected_r: &[u8; 32],
+    even_r: bool,
+    square_r: bool,
+    r: &[u8; 64],
+) -> Result<(), Secp256k1SchnorrError> {
+    if !bytes_eq(expected_r, &r[..32]) {
+        #[cfg(feature = "debug-logs")]
+        debug::log("r mismatch");
+        return Err(Secp256k1SchnorrError::InvalidSignature);
+    }
+
+    // Schemes that commit to an even R must also reject its odd counterpart
+    if even_r && r[63] & 1 != 0 {
+        #[cfg(feature = "debug-logs")]
+        debug::log("R has an odd Y");
+        return Err(Secp256k1SchnorrError::InvalidSignature);
+    }
+
+    // Likewise for schemes that commit to an R whose Y-coordinate is a square
+    if square_r && !is_square_mod_p(r[32..].first_chunk().unwrap()) {
+        #[cfg(feature = "debug-logs")]
+        debug::log("R has a non-square Y");
+        return Err(Secp256k1SchnorrError::InvalidSignature);
+    }
+    Ok(())
+}
+
+/// # PreparedRecover
+/// The operands of the single `secp256k1_recover` call that verifies a signature, as returned by
+/// `prepare_verify`. Passing `m`, `recovery_id` and `r_s` to any implementation of
+/// `secp256k1_recover` yields the signature's nonce point, which `finish` then checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreparedRecover {
+    pub m: [u8; 32],
+    pub recovery_id: u8,
+    pub r_s: [u8; 64],
+    pub expected_r: [u8; 32],
+    /// Whether the challenge scheme commits to an even 𝑅
+    pub even_r: bool,
+    /// Whether the challenge scheme commits to an 𝑅 with a square 𝑌-coordinate
+    pub square_r: bool,
+}
+
+impl PreparedRecover {
+    /// ### Finish
+    ///
+    /// Completes verification with the 64-byte point `recovered` from the operands, failing with
+    /// `InvalidSignature` if it is not the signature's nonce point.
+    #[inline]
+    pub fn finish(&self, recovered: &[u8; 64]) -> Result<(), Secp256k1SchnorrError> {
+        check_nonce(&self.expected_r, self.even_r, self.square_r, recovered)
+    }
+}
+
 /// # Secp256k1SchnorrSignature
 /// A Schnorr signature used for signature verification purposes.
 ///
@@ -21,23 +291,105 @@ pub struct Secp256k1SchnorrSignature(pub [u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH
 
 impl Secp256k1SchnorrSignature {
     pub fn r(&self) -> [u8; 32] {
-        [
-            self.0[0], self.0[1], self.0[2], self.0[3], self.0[4], self.0[5], self.0[6], self.0[7],
-            self.0[8], self.0[9], self.0[10], self.0[11], self.0[12], self.0[13], self.0[14],
-            self.0[15], self.0[16], self.0[17], self.0[18], self.0[19], self.0[20], self.0[21],
-            self.0[22], self.0[23], self.0[24], self.0[25], self.0[26], self.0[27], self.0[28],
-            self.0[29], self.0[30], self.0[31],
-        ]
+        *self.r_ref()
     }
 
     pub fn s(&self) -> [u8; 32] {
-        [
-            self.0[32], self.0[33], self.0[34], self.0[35], self.0[36], self.0[37], self.0[38],
-            self.0[39], self.0[40], self.0[41], self.0[42], self.0[43], self.0[44], self.0[45],
-            self.0[46], self.0[47], self.0[48], self.0[49], self.0[50], self.0[51], self.0[52],
-            self.0[53], self.0[54], self.0[55], self.0[56], self.0[57], self.0[58], self.0[59],
-            self.0[60], self.0[61], self.0[62], self.0[63],
-        ]
+        *self.s_ref()
+    }
+
+    /// ### R Ref
+    /// Borrows the 𝑟 component of the signature in place, without copying it.
+    #[inline(always)]
+    pub fn r_ref(&self) -> &[u8; 32] {
+        self.0.first_chunk().unwrap()
+    }
+
+    /// ### S Ref
+    /// Borrows the 𝑠 component of the signature in place, without copying it.
+    #[inline(always)]
+    pub fn s_ref(&self) -> &[u8; 32] {
+        self.0.last_chunk().unwrap()
+    }
+
+    /// ### From Parts
+    /// Assembles a signature from its 𝑟 and 𝑠 components.
+    pub fn from_parts(r: [u8; 32], s: [u8; 32]) -> Self {
+        let mut bytes = [0u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH];
+        bytes[..32].clone_from_slice(&r);
+        bytes[32..].clone_from_slice(&s);
+        Self(bytes)
+    }
+
+    /// ### As Bytes
+    /// Borrows the 64-byte `𝑟∥𝑠` encoding of the signature.
+    pub fn as_bytes(&self) -> &[u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH] {
+        &self.0
+    }
+
+    /// ### To Bytes
+    /// Returns the 64-byte `𝑟∥𝑠` encoding of the signature.
+    pub fn to_bytes(&self) -> [u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH] {
+        self.0
+    }
+}
+
+/// Decodes a signature from a slice, such as instruction or account data, failing with
+/// `InvalidSignature` unless it is exactly 64 bytes long. No other validation is performed.
+impl TryFrom<&[u8]> for Secp256k1SchnorrSignature {
+    type Error = Secp256k1SchnorrError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        bytes
+            .try_into()
+            .map(Self)
+            .map_err(|_| Secp256k1SchnorrError::InvalidSignature)
+    }
+}
+
+/// Formats a signature as 128 lowercase hex digits.
+impl core::fmt::LowerHex for Secp256k1SchnorrSignature {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        self.0.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+    }
+}
+
+/// Formats a signature as 128 lowercase hex digits, as `LowerHex`.
+impl core::fmt::Display for Secp256k1SchnorrSignature {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::LowerHex::fmt(self, f)
+    }
+}
+
+/// Parses a signature from 128 hex digits of either case, failing with `InvalidSignature` for any
+/// other string.
+impl core::str::FromStr for Secp256k1SchnorrSignature {
+    type Err = Secp256k1SchnorrError;
+
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        fn nibble(c: u8) -> Option<u8> {
+            match c {
+                b'0'..=b'9' => Some(c - b'0'),
+                b'a'..=b'f' => Some(c - b'a' + 10),
+                b'A'..=b'F' => Some(c - b'A' + 10),
+                _ => None,
+            }
+        }
+        let hex = hex.as_bytes();
+        if hex.len() != SECP256K1_SCHNORR_SIGNATURE_LENGTH * 2 {
+            return Err(Secp256k1SchnorrError::InvalidSignature);
+        }
+        let mut bytes = [0u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH];
+        for (byte, pair) in bytes.iter_mut().zip(hex.chunks_exact(2)) {
+            *byte = match (nibble(pair[0]), nibble(pair[1])) {
+                (Some(high), Some(low)) => (high << 4) | low,
+                _ => return Err(Secp256k1SchnorrError::InvalidSignature),
+            };
+        }
+        Ok(Self(bytes))
     }
 }
 
@@ -48,6 +400,9 @@ impl Secp256k1SchnorrSignature {
     /// Under the hood, it abuses the `sol_secp256k1_ecrecover` syscall to perform efficient elliptic curve multiplication
     /// over the Secp256k1 curve, enabling on-chain Schnorr signature verification.
     ///
+    /// Signatures are strictly range checked, failing with `InvalidSignatureR` if 𝑟 ≥ 𝑝 and with
+    /// `InvalidSignatureS` if 𝑠 ≥ 𝑛, so every signature has exactly one accepted encoding.
+    ///
     /// Example:
     /// ```rs
     /// use solana_secp256k1_schnorr::{Secp256k1SchnorrSignature, CompressedPoint, BIP340Challenge},
@@ -79,30 +434,415 @@ impl Secp256k1SchnorrSignature {
         message: &[u8],
         pubkey: &T,
     ) -> Result<(), Secp256k1SchnorrError> {
-        // Calculate challenge from pubkey and message:
-        let e = C::challenge(&self.r(), pubkey, message);
-        // m = -s*Px
-        let m = Curve::negate_n(&Curve::mul_mod_n(&self.s(), &pubkey.x()));
-        // s = -e*Px
-        let s = Curve::negate_n(&Curve::mul_mod_n(&e, &pubkey.x()));
-
-        // R and S are made up of Px and and -e*Px
-        let mut r_s = [0u8; 64];
-        r_s[..32].clone_from_slice(&pubkey.x());
-        r_s[32..].clone_from_slice(&s);
-
-        if m.eq(&[0u8; 32]) {
-            return Err(Secp256k1SchnorrError::InvalidSignature);
+        self.verify_parts::<C, T>(&[message], pubkey)
+    }
+
+    /// ### Verify Parts
+    /// Verifies a signature over the concatenation of message `parts`, such as
+    /// `program_id ∥ nonce ∥ payload`, as `verify` over the concatenated message. The built-in
+    /// schemes hash the parts in place, so no contiguous buffer is allocated or copied.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::{Secp256k1SchnorrSignature, CompressedPoint, BIP340Challenge},
+    ///
+    /// schnorr_signature.verify_parts::<BIP340Challenge, CompressedPoint>(
+    ///     &[program_id.as_ref(), &nonce.to_le_bytes(), payload],
+    ///     &pubkey,
+    /// )
+    /// .expect("Invalid signature");
+    /// ```
+    #[inline]
+    pub fn verify_parts<C: Secp256k1SchnorrVerify, T: Secp256k1Point>(
+        &self,
+        parts: &[&[u8]],
+        pubkey: &T,
+    ) -> Result<(), Secp256k1SchnorrError> {
+        self.verify_parts_recover_r::<C, T>(parts, pubkey)
+            .map(|_| ())
+    }
+
+    /// ### Verify Recover R
+    /// Verifies a signature as `verify`, returning the full nonce point 𝑅 = 𝑠⋅𝐺 - 𝑒⋅𝑃 recovered
+    /// along the way rather than only checking its 𝑋-coordinate against 𝑟. Protocols built on top
+    /// of signatures, such as adaptor signatures and DLEQ proofs, can then use 𝑅 in further
+    /// computation without lifting 𝑟 or paying for another call to `secp256k1_recover`.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::{Secp256k1SchnorrSignature, CompressedPoint, BIP340Challenge},
+    ///
+    /// let r = schnorr_signature.verify_recover_r::<BIP340Challenge, CompressedPoint>(&message, &pubkey)
+    ///     .expect("Invalid signature");
+    /// ```
+    #[inline]
+    pub fn verify_recover_r<C: Secp256k1SchnorrVerify, T: Secp256k1Point>(
+        &self,
+        message: &[u8],
+        pubkey: &T,
+    ) -> Result<UncompressedPoint, Secp256k1SchnorrError> {
+        self.verify_parts_recover_r::<C, T>(&[message], pubkey)
+    }
+
+    /// ### Verify Parts Recover R
+    /// Verifies a signature over the concatenation of message `parts` as `verify_parts`,
+    /// returning the recovered nonce point as `verify_recover_r`.
+    #[inline]
+    pub fn verify_parts_recover_r<C: Secp256k1SchnorrVerify, T: Secp256k1Point>(
+        &self,
+        parts: &[&[u8]],
+        pubkey: &T,
+    ) -> Result<UncompressedPoint, Secp256k1SchnorrError> {
+        if C::XONLY && pubkey.is_odd() {
+            return self.verify_parts_recover_r::<C, CompressedPoint>(parts, &xonly_point(pubkey));
         }
+        let e = self.checked_challenge::<C, T>(pubkey, parts)?;
 
-        let r = secp256k1_recover(&m, pubkey.is_odd(), &r_s)
-            .map_err(|_| Secp256k1SchnorrError::InvalidSignature)?;
+        // R = s*G - e*P
+        let r = recover_nonce(self.s_ref(), &e, pubkey)?;
 
-        if self.r().ne(&r[..32]) {
-            return Err(Secp256k1SchnorrError::InvalidSignature);
+        check_nonce(self.r_ref(), C::EVEN_R, C::SQUARE_R, &r)?;
+        Ok(UncompressedPoint(r))
+    }
+
+    /// ### Verify Prehashed
+    /// Verifies a signature over a 32-byte `digest` of a payload hashed elsewhere, such as by a
+    /// previous instruction, so that the payload itself is never hashed on-chain again. The digest
+    /// is the signed message, and signers must sign it with `sign_prehashed` rather than the
+    /// payload.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::{Secp256k1SchnorrSignature, CompressedPoint, BIP340Challenge},
+    ///
+    /// schnorr_signature.verify_prehashed::<BIP340Challenge, CompressedPoint>(&digest, &pubkey)
+    ///     .expect("Invalid signature");
+    /// ```
+    #[inline]
+    pub fn verify_prehashed<C: Secp256k1SchnorrVerify, T: Secp256k1Point>(
+        &self,
+        digest: &[u8; 32],
+        pubkey: &T,
+    ) -> Result<(), Secp256k1SchnorrError> {
+        self.verify::<C, T>(digest, pubkey)
+    }
+
+    /// ### Verify X-Only
+    /// Verifies a signature against a 32-byte 𝑋-only public key, as stored by most BIP340
+    /// consumers, assuming the point with an even 𝑌-coordinate. Either a `[u8; 32]` or a
+    /// `keys::XOnlyPublicKey` may be passed.
+    ///
+    /// The key is passed to `secp256k1_recover` with an even parity as is, without computing its
+    /// 𝑌-coordinate, so this costs no more than `verify`.
+    ///
+    /// Fails with `InvalidPublicKey` if `xonly` is not the 𝑋-coordinate of a point on the curve,
+    /// which is only checked once verification has failed.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::{Secp256k1SchnorrSignature, BIP340Challenge},
+    ///
+    /// schnorr_signature.verify_xonly::<BIP340Challenge>(&message, &xonly)
+    ///     .expect("Invalid signature");
+    /// ```
+    #[inline]
+    pub fn verify_xonly<C: Secp256k1SchnorrVerify>(
+        &self,
+        message: &[u8],
+        xonly: &impl core::borrow::Borrow<[u8; 32]>,
+    ) -> Result<(), Secp256k1SchnorrError> {
+        self.verify_xonly_parity::<C>(message, xonly.borrow(), false)
+    }
+
+    /// ### Verify X-Only Relaxed
+    /// Verifies a signature as `verify_xonly`, retrying with the point with an odd 𝑌-coordinate
+    /// should the even one fail. This suits schemes other than BIP340, which sign with either
+    /// point, when only the 𝑋-coordinate of the signer's key is known.
+    ///
+    /// X-only schemes verify identically under both points, so they are never retried.
+    #[inline]
+    pub fn verify_xonly_relaxed<C: Secp256k1SchnorrVerify>(
+        &self,
+        message: &[u8],
+        xonly: &impl core::borrow::Borrow<[u8; 32]>,
+    ) -> Result<(), Secp256k1SchnorrError> {
+        let xonly = xonly.borrow();
+        match self.verify_xonly_parity::<C>(message, xonly, false) {
+            Err(Secp256k1SchnorrError::InvalidSignature) if !C::XONLY => {
+                self.verify_xonly_parity::<C>(message, xonly, true)
+            }
+            result => result,
+        }
+    }
+
+    #[inline(always)]
+    fn verify_xonly_parity<C: Secp256k1SchnorrVerify>(
+        &self,
+        message: &[u8],
+        xonly: &[u8; 32],
+        is_odd: bool,
+    ) -> Result<(), Secp256k1SchnorrError> {
+        let mut pubkey = keys::XOnlyPublicKey(*xonly).to_compressed();
+        if is_odd {
+            pubkey.invert();
+        }
+        self.verify::<C, CompressedPoint>(message, &pubkey)
+            .map_err(|error| match error {
+                Secp256k1SchnorrError::InvalidSignature if keys::lift_x(xonly).is_err() => {
+                    Secp256k1SchnorrError::InvalidPublicKey
+                }
+                error => error,
+            })
+    }
+
+    /// ### Verify Tweaked
+    /// Verifies a signature against the public key tweaked additively with `tweak` into
+    /// 𝑄 = 𝑃 + 𝑡⋅𝐺, as with `keys::pubkey_tweak_add`, at the cost of one more call to
+    /// `secp256k1_recover`. This suits pay-to-contract and commitment schemes, where signers
+    /// sign with the secret key tweaked by `keys::seckey_tweak_add` and programs only store the
+    /// untweaked key.
+    ///
+    /// Fails as `keys::pubkey_tweak_add`, and otherwise as `verify`.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::{Secp256k1SchnorrSignature, CompressedPoint, BIP340Challenge},
+    ///
+    /// schnorr_signature.verify_tweaked::<BIP340Challenge, CompressedPoint>(&message, &pubkey, &tweak)
+    ///     .expect("Invalid signature");
+    /// ```
+    #[inline]
+    pub fn verify_tweaked<C: Secp256k1SchnorrVerify, T: Secp256k1Point>(
+        &self,
+        message: &[u8],
+        pubkey: &T,
+        tweak: &[u8; 32],
+    ) -> Result<(), Secp256k1SchnorrError> {
+        let tweaked = keys::pubkey_tweak_add(pubkey, tweak)?;
+        self.verify::<C, UncompressedPoint>(message, &tweaked)
+    }
+
+    /// ### Estimated Compute Units
+    /// Estimates the compute units consumed by `verify` over a `message_len`-byte message under
+    /// the challenge scheme `C`, so that programs and clients can request an appropriate compute
+    /// budget up front. The estimate adds the recover syscall, the scheme's challenge hashing from
+    /// `challenge_compute_units`, an allowance for the verifier's own arithmetic, and the square
+    /// check of schemes committing to a square 𝑅.
+    ///
+    /// Syscall costs follow the runtime's cost schedule, while the arithmetic allowances in
+    /// `compute` are deliberately conservative, so the estimate errs on the high side.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::{Secp256k1SchnorrSignature, BIP340Challenge},
+    ///
+    /// let units = Secp256k1SchnorrSignature::estimated_compute_units::<BIP340Challenge>(message.len());
+    /// let instruction = ComputeBudgetInstruction::set_compute_unit_limit(units as u32 + base_units);
+    /// ```
+    pub fn estimated_compute_units<C: Secp256k1SchnorrVerify>(message_len: usize) -> u64 {
+        let mut units = compute::SECP256K1_RECOVER_COMPUTE_UNITS
+            + compute::VERIFY_ARITHMETIC_COMPUTE_UNITS
+            + C::challenge_compute_units(message_len);
+        if C::SQUARE_R {
+            units += compute::square_check_compute_units();
+        }
+        units
+    }
+
+    /// ### Verify Account Data
+    /// Verifies a signature over the `len` bytes at `offset` in borrowed account data, hashing
+    /// them in place rather than copying the message into instruction data.
+    ///
+    /// Fails with `InvalidMessage` if the range is out of bounds.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::{Secp256k1SchnorrSignature, CompressedPoint, BIP340Challenge},
+    ///
+    /// let data = attestation_account.try_borrow_data()?;
+    /// schnorr_signature.verify_account_data::<BIP340Challenge, CompressedPoint>(&data, 8, 512, &pubkey)
+    ///     .expect("Invalid signature");
+    /// ```
+    #[inline]
+    pub fn verify_account_data<C: Secp256k1SchnorrVerify, T: Secp256k1Point>(
+        &self,
+        data: &[u8],
+        offset: usize,
+        len: usize,
+        pubkey: &T,
+    ) -> Result<(), Secp256k1SchnorrError> {
+        let message = offset
+            .checked_add(len)
+            .and_then(|end| data.get(offset..end))
+            .ok_or(Secp256k1SchnorrError::InvalidMessage)?;
+        self.verify::<C, T>(message, pubkey)
+    }
+
+    /// ### Verify Batch
+    /// Verifies many signatures in one call, such as the attestations ingested by an oracle in a
+    /// single instruction, failing with the error of the first invalid item.
+    ///
+    /// The cheap range checks of every item run before any challenge is hashed or syscall is
+    /// made, so a malformed batch is rejected without paying for the valid signatures within it.
+    /// Each remaining signature then costs exactly one `secp256k1_recover` call. An empty batch
+    /// is trivially valid.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::{Secp256k1SchnorrSignature, CompressedPoint, BIP340Challenge},
+    ///
+    /// Secp256k1SchnorrSignature::verify_batch::<BIP340Challenge, CompressedPoint>(&[
+    ///     (first_signature, &first_message, &first_pubkey),
+    ///     (second_signature, &second_message, &second_pubkey),
+    /// ])
+    /// .expect("Invalid signature");
+    /// ```
+    #[inline]
+    pub fn verify_batch<C: Secp256k1SchnorrVerify, T: Secp256k1Point>(
+        items: &[(Secp256k1SchnorrSignature, &[u8], &T)],
+    ) -> Result<(), Secp256k1SchnorrError> {
+        for (signature, _, pubkey) in items {
+            signature.check_range()?;
+            if pubkey.x().ge(&Curve::P) {
+                return Err(Secp256k1SchnorrError::InvalidPublicKey);
+            }
+        }
+        items
+            .iter()
+            .try_for_each(|(signature, message, pubkey)| signature.verify::<C, T>(message, *pubkey))
+    }
+
+    /// ### Verify Threshold
+    /// Verifies independent signatures over the same message, such as the approvals of a DAO
+    /// authority's members, succeeding once at least `m` distinct public keys have a valid
+    /// signature.
+    ///
+    /// Fails with `LimitExceeded` if `m` is zero, and with `DuplicateSigner` if any public key
+    /// appears more than once, before any signature is verified. Keys are compared by their
+    /// 𝑋-coordinate alone under x-only schemes, where both parities verify identically. Invalid
+    /// signatures are not counted, failing with `Unauthorized` if fewer than `m` remain.
+    /// Verification stops as soon as the threshold is reached, so signatures past the `m`th valid
+    /// one cost no `secp256k1_recover` call.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::{Secp256k1SchnorrSignature, CompressedPoint, BIP340Challenge},
+    ///
+    /// Secp256k1SchnorrSignature::verify_threshold::<BIP340Challenge, CompressedPoint>(
+    ///     &[(first_signature, &first_pubkey), (second_signature, &second_pubkey)],
+    ///     &proposal,
+    ///     2,
+    /// )?;
+    /// ```
+    pub fn verify_threshold<C: Secp256k1SchnorrVerify, T: Secp256k1Point>(
+        signatures: &[(Secp256k1SchnorrSignature, &T)],
+        message: &[u8],
+        m: usize,
+    ) -> Result<(), Secp256k1SchnorrError> {
+        if m == 0 {
+            return Err(Secp256k1SchnorrError::LimitExceeded);
+        }
+        let same_signer = |a: &T, b: &T| a.x() == b.x() && (C::XONLY || a.is_odd() == b.is_odd());
+        for (i, (_, pubkey)) in signatures.iter().enumerate() {
+            if signatures[..i]
+                .iter()
+                .any(|(_, other)| same_signer(pubkey, other))
+            {
+                return Err(Secp256k1SchnorrError::DuplicateSigner);
+            }
+        }
+        let mut valid = 0;
+        for (signature, pubkey) in signatures {
+            if signature.verify::<C, T>(message, *pubkey).is_ok() {
+                valid += 1;
+                if valid == m {
+                    return Ok(());
+                }
+            }
+        }
+        Err(Secp256k1SchnorrError::Unauthorized)
+    }
+
+    /// ### Prepare Verify
+    /// Performs every step of verification except the `secp256k1_recover` call itself, returning
+    /// its operands. This allows advanced users to schedule or batch the syscalls themselves, or to
+    /// route them through a different recover implementation, before finishing verification with
+    /// a cheap comparison.
+    ///
+    /// Off-chain, signatures whose nonce point would be the point at infinity are rejected here
+    /// with `PointAtInfinity`. On-chain, the syscall itself fails for them.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::{Secp256k1SchnorrSignature, CompressedPoint, BIP340Challenge},
+    ///
+    /// let prepared = schnorr_signature.prepare_verify::<BIP340Challenge, CompressedPoint>(&message, &pubkey)?;
+    /// let recovered = secp256k1_recover(&prepared.m, prepared.recovery_id == 1, &prepared.r_s)
+    ///     .map_err(|_| Secp256k1SchnorrError::InvalidSignature)?;
+    /// prepared.finish(&recovered)?;
+    /// ```
+    #[inline]
+    pub fn prepare_verify<C: Secp256k1SchnorrVerify, T: Secp256k1Point>(
+        &self,
+        message: &[u8],
+        pubkey: &T,
+    ) -> Result<PreparedRecover, Secp256k1SchnorrError> {
+        if C::XONLY && pubkey.is_odd() {
+            return self.prepare_verify::<C, CompressedPoint>(message, &xonly_point(pubkey));
+        }
+        let e = self.checked_challenge::<C, T>(pubkey, &[message])?;
+        let (m, r_s) = recover_operands(self.s_ref(), &e, pubkey)?;
+        Ok(PreparedRecover {
+            m,
+            recovery_id: pubkey.is_odd() as u8,
+            r_s,
+            expected_r: self.r(),
+            even_r: C::EVEN_R,
+            square_r: C::SQUARE_R,
+        })
+    }
+
+    /// Rejects signatures whose 𝑟 is not a valid field element or whose 𝑠 is not a valid scalar,
+    /// as mandated by BIP340. Without this, 𝑠 + 𝑁 would verify as a second encoding of 𝑠.
+    #[inline(always)]
+    pub(crate) fn check_range(&self) -> Result<(), Secp256k1SchnorrError> {
+        if self.r_ref().ge(&Curve::P) {
+            #[cfg(feature = "debug-logs")]
+            debug::log("range check: r >= P");
+            return Err(Secp256k1SchnorrError::InvalidSignatureR);
+        }
+        if self.s_ref().ge(&Curve::N) {
+            #[cfg(feature = "debug-logs")]
+            debug::log("range check: s >= N");
+            return Err(Secp256k1SchnorrError::InvalidSignatureS);
         }
         Ok(())
     }
+
+    #[inline(always)]
+    fn checked_challenge<C: Secp256k1SchnorrVerify, T: Secp256k1Point>(
+        &self,
+        pubkey: &T,
+        parts: &[&[u8]],
+    ) -> Result<[u8; 32], Secp256k1SchnorrError> {
+        self.check_range()?;
+        // Px must be a valid field element
+        if pubkey.x().ge(&Curve::P) {
+            #[cfg(feature = "debug-logs")]
+            debug::log("range check: Px >= P");
+            return Err(Secp256k1SchnorrError::InvalidPublicKey);
+        }
+        // Calculate challenge from pubkey and message:
+        let e = C::challenge_scalar_parts(self.r_ref(), pubkey, parts);
+        #[cfg(feature = "audit")]
+        audit::record("verify.e", &e);
+        if is_zero_mod_n(&e) {
+            #[cfg(feature = "debug-logs")]
+            debug::log("challenge is zero");
+            return Err(Secp256k1SchnorrError::InvalidChallenge);
+        }
+        Ok(e)
+    }
 }
 
 #[cfg(feature = "sign")]
@@ -112,6 +852,9 @@ impl Secp256k1SchnorrSignature {
     ///
     /// Challenges must provide a valid implementation of the Secp256k1SchnorrSign trait.
     ///
+    /// With the "verify-after-sign" feature flag enabled, every signature is verified against the
+    /// signer's public key before being returned, guarding against fault injection.
+    ///
     /// Example:
     /// ```rs
     /// use solana_secp256k1_schnorr::{Secp256k1SchnorrSignature, BIP340Challenge},
@@ -131,21 +874,153 @@ impl Secp256k1SchnorrSignature {
         message: &[u8],
         privkey: &[u8; 32],
     ) -> Result<Secp256k1SchnorrSignature, Secp256k1SchnorrError> {
-        // aux represents the tagged-sha256 hash of our auxiliary randomness. In our default signing, this will be zero.
-        let aux = C::aux_randomness(privkey, &[0u8; 32]);
+        Self::sign_with_aux::<C>(message, privkey, &[0u8; 32])
+    }
+
+    /// ### Sign With Aux
+    /// Signs a message as `sign`, mixing 32 bytes of fresh auxiliary randomness into nonce
+    /// generation as recommended by BIP340. This protects against side-channel attacks on the
+    /// deterministic nonce, while a weak or repeated `aux` remains no worse than `sign`.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::{Secp256k1SchnorrSignature, BIP340Challenge},
+    ///
+    /// let mut aux = [0u8; 32];
+    /// getrandom::getrandom(&mut aux)?;
+    /// let schnorr_signature = Secp256k1SchnorrSignature::sign_with_aux::<BIP340Challenge>(message.as_slice(), &privkey, &aux)
+    ///     .expect("Invalid signature");
+    /// ```
+    #[inline]
+    pub fn sign_with_aux<C: Secp256k1SchnorrSign>(
+        message: &[u8],
+        privkey: &[u8; 32],
+        aux: &[u8; 32],
+    ) -> Result<Secp256k1SchnorrSignature, Secp256k1SchnorrError> {
+        Self::sign_parts::<C>(&[message], privkey, aux)
+    }
 
+    /// ### Sign With Rng
+    /// Signs a message as `sign_with_aux`, drawing the auxiliary randomness from `rng` so that
+    /// host-side signers get BIP340's recommended defense-in-depth without managing aux bytes
+    /// themselves. Requires activating the "rand_core" feature flag.
+    ///
+    /// Example:
+    /// ```rs
+    /// use rand_core::OsRng;
+    /// use solana_secp256k1_schnorr::{Secp256k1SchnorrSignature, BIP340Challenge},
+    ///
+    /// let schnorr_signature = Secp256k1SchnorrSignature::sign_with_rng::<BIP340Challenge, _>(message.as_slice(), &privkey, &mut OsRng)
+    ///     .expect("Invalid signature");
+    /// ```
+    #[cfg(feature = "rand_core")]
+    #[inline]
+    pub fn sign_with_rng<C: Secp256k1SchnorrSign, R: rand_core::CryptoRngCore + ?Sized>(
+        message: &[u8],
+        privkey: &[u8; 32],
+        rng: &mut R,
+    ) -> Result<Secp256k1SchnorrSignature, Secp256k1SchnorrError> {
+        let mut aux = [0u8; 32];
+        rng.fill_bytes(&mut aux);
+        Self::sign_with_aux::<C>(message, privkey, &aux)
+    }
+
+    /// ### Sign Parts
+    /// Signs the concatenation of message `parts` as `sign_with_aux`, for verification with
+    /// `verify_parts` or with `verify` over the concatenated message.
+    #[inline]
+    pub fn sign_parts<C: Secp256k1SchnorrSign>(
+        parts: &[&[u8]],
+        privkey: &[u8; 32],
+        aux: &[u8; 32],
+    ) -> Result<Secp256k1SchnorrSignature, Secp256k1SchnorrError> {
+        let signature = Self::sign_inner::<C>(parts, privkey, aux);
+        // Scrub the stack frames used by signing now that they have been popped
+        #[cfg(feature = "hardening")]
+        hardening::scrub_stack();
+        signature
+    }
+
+    /// ### Sign Prehashed
+    /// Signs a 32-byte `digest` of a payload as `sign_with_aux`, for verification with
+    /// `verify_prehashed`. The digest itself is the signed message, so it must be computed with a
+    /// collision-resistant hash agreed upon with the verifier.
+    #[inline]
+    pub fn sign_prehashed<C: Secp256k1SchnorrSign>(
+        digest: &[u8; 32],
+        privkey: &[u8; 32],
+        aux: &[u8; 32],
+    ) -> Result<Secp256k1SchnorrSignature, Secp256k1SchnorrError> {
+        Self::sign_with_aux::<C>(digest, privkey, aux)
+    }
+
+    #[cfg_attr(feature = "hardening", inline(never))]
+    #[cfg_attr(not(feature = "hardening"), inline(always))]
+    fn sign_inner<C: Secp256k1SchnorrSign>(
+        parts: &[&[u8]],
+        privkey: &[u8; 32],
+        aux: &[u8; 32],
+    ) -> Result<Secp256k1SchnorrSignature, Secp256k1SchnorrError> {
         // p is the X-only public key of our Privkey
-        let pubkey = Curve::mul_g(privkey).map_err(|_| Secp256k1SchnorrError::InvalidPublicKey)?;
+        let mut pubkey =
+            backend::mul_g(privkey).map_err(|_| Secp256k1SchnorrError::InvalidPublicKey)?;
+
+        // X-only schemes sign with the secret key of the even-Y point sharing P's X-coordinate
+        let mut d = secret(*privkey);
+        if C::XONLY && pubkey.is_odd() {
+            Curve::negate_n_assign(&mut d);
+            pubkey.invert();
+        }
+        let privkey: &[u8; 32] = &d;
+
+        // aux represents the tagged-sha256 hash of our auxiliary randomness. In our default signing, this will be zero.
+        let aux = secret(C::aux_randomness(privkey, aux));
+        #[cfg(feature = "audit")]
+        {
+            audit::record("sign.aux", &aux[..]);
+            audit::record("sign.P", &pubkey.0);
+        }
 
         // k is our ephemeral key
-        let (k, r) = C::nonce::<UncompressedPoint>(&pubkey, message, &aux)?;
+        let (k, r) = C::nonce_parts::<UncompressedPoint>(&pubkey, parts, &aux)?;
+        let k = secret(k);
+        #[cfg(feature = "audit")]
+        {
+            audit::record("sign.k", &k[..]);
+            audit::record("sign.R", &r.0);
+        }
+
+        // A zero nonce would produce s = e*d, leaking the private key
+        if is_zero_mod_n(&k) {
+            return Err(Secp256k1SchnorrError::InvalidNonce);
+        }
 
         // e is the challenge message
-        let e = C::challenge(&r.x(), &pubkey, message);
+        let e = C::challenge_scalar_parts(&r.x(), &pubkey, parts);
+        #[cfg(feature = "audit")]
+        audit::record("sign.e", &e);
+
+        // A zero challenge produces a signature that is independent of the private key
+        if is_zero_mod_n(&e) {
+            return Err(Secp256k1SchnorrError::InvalidChallenge);
+        }
 
         let mut sig_bytes = [0; 64];
         sig_bytes[..32].clone_from_slice(&r.x());
         sig_bytes[32..].clone_from_slice(&Curve::add_mod_n(&k, &Curve::mul_mod_n(&e, privkey)));
-        Ok(Secp256k1SchnorrSignature(sig_bytes))
+        #[cfg(feature = "audit")]
+        audit::record("sign.signature", &sig_bytes);
+
+        let signature = Secp256k1SchnorrSignature(sig_bytes);
+
+        // Re-verify against our own public key so that a faulted or miscomputed signature, which
+        // could leak information about the private key, is never released. The check is kept out
+        // of audit transcripts, which record the signing path alone.
+        #[cfg(all(feature = "verify-after-sign", feature = "audit"))]
+        audit::suspend(|| signature.verify_parts::<C, UncompressedPoint>(parts, &pubkey))?;
+        #[cfg(all(feature = "verify-after-sign", not(feature = "audit")))]
+        signature.verify_parts::<C, UncompressedPoint>(parts, &pubkey)?;
+
+        Ok(signature)
     }
 }
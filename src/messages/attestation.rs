@@ -0,0 +1,113 @@
+use solana_nostd_sha256::hash;
+use solana_secp256k1::CompressedPoint;
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify,
+    errors::Secp256k1SchnorrError,
+    messages::{key_id, Domain, Envelope, Reader},
+    Secp256k1SchnorrSignature,
+};
+
+/// ### Attestation
+///
+/// A general-purpose signed claim by an issuer about a `subject`, such as a KYC pass, a score or
+/// membership of an allow-list. `schema_id` identifies the format of the attested payload, of which
+/// only the SHA256 hash is signed, and the attestation is valid from `issued_at` until `expiry`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Attestation {
+    pub issuer_key_id: [u8; 32],
+    pub schema_id: [u8; 32],
+    pub subject: [u8; 32],
+    pub payload_hash: [u8; 32],
+    pub issued_at: i64,
+    pub expiry: i64,
+}
+
+impl Envelope for Attestation {
+    const TAG: &'static [u8] = b"solana-secp256k1-schnorr/attestation";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.issuer_key_id);
+        out.extend_from_slice(&self.schema_id);
+        out.extend_from_slice(&self.subject);
+        out.extend_from_slice(&self.payload_hash);
+        out.extend_from_slice(&self.issued_at.to_le_bytes());
+        out.extend_from_slice(&self.expiry.to_le_bytes());
+    }
+}
+
+impl Attestation {
+    /// ### New
+    ///
+    /// Constructs an attestation by `issuer` of `payload` about `subject`.
+    pub fn new(
+        issuer: &CompressedPoint,
+        schema_id: [u8; 32],
+        subject: [u8; 32],
+        payload: &[u8],
+        issued_at: i64,
+        expiry: i64,
+    ) -> Self {
+        Self {
+            issuer_key_id: key_id(issuer),
+            schema_id,
+            subject,
+            payload_hash: hash(payload),
+            issued_at,
+            expiry,
+        }
+    }
+
+    /// ### From Bytes
+    ///
+    /// Decodes an attestation from its canonical encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Secp256k1SchnorrError> {
+        let mut reader = Reader::new(bytes);
+        let attestation = Self {
+            issuer_key_id: reader.read_array()?,
+            schema_id: reader.read_array()?,
+            subject: reader.read_array()?,
+            payload_hash: reader.read_array()?,
+            issued_at: reader.read_i64()?,
+            expiry: reader.read_i64()?,
+        };
+        reader.finish()?;
+        Ok(attestation)
+    }
+}
+
+/// ### Verify Attestation
+///
+/// Program-side verification of an off-chain attestation. Succeeds only if the attestation was
+/// issued by `issuer` under `schema_id` about `subject`, commits to `payload`, is valid at time
+/// `now`, and is signed by the issuer for `domain`.
+///
+/// The caller is responsible for deciding which issuers it trusts for each schema.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_attestation<C: Secp256k1SchnorrVerify>(
+    attestation: &Attestation,
+    domain: &Domain,
+    signature: &Secp256k1SchnorrSignature,
+    issuer: &CompressedPoint,
+    schema_id: &[u8; 32],
+    subject: &[u8; 32],
+    payload: &[u8],
+    now: i64,
+) -> Result<(), Secp256k1SchnorrError> {
+    if attestation.issuer_key_id.ne(&key_id(issuer)) {
+        return Err(Secp256k1SchnorrError::Unauthorized);
+    }
+    if attestation.schema_id.ne(schema_id)
+        || attestation.subject.ne(subject)
+        || attestation.payload_hash.ne(&hash(payload))
+    {
+        return Err(Secp256k1SchnorrError::InvalidMessage);
+    }
+    if now < attestation.issued_at {
+        return Err(Secp256k1SchnorrError::NotYetValid);
+    }
+    if now > attestation.expiry {
+        return Err(Secp256k1SchnorrError::Expired);
+    }
+    attestation.verify::<C, CompressedPoint>(domain, signature, issuer)
+}
@@ -0,0 +1,118 @@
+use solana_secp256k1::CompressedPoint;
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify,
+    errors::Secp256k1SchnorrError,
+    messages::{encode_bytes, Domain, Envelope, Reader},
+    Secp256k1SchnorrSignature,
+};
+
+/// ### AuthChallenge
+///
+/// A challenge issued by a server or program to a party wishing to prove control of a key, for
+/// login or device pairing. The issuer chooses a fresh random `nonce`, an application-defined
+/// `context` such as a session or device identifier, and an `expiry` after which responses are no
+/// longer accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthChallenge {
+    pub nonce: [u8; 32],
+    pub context: Vec<u8>,
+    pub expiry: i64,
+}
+
+impl AuthChallenge {
+    pub fn new(nonce: [u8; 32], context: &[u8], expiry: i64) -> Self {
+        Self {
+            nonce,
+            context: context.to_vec(),
+            expiry,
+        }
+    }
+
+    /// ### To Bytes
+    ///
+    /// Returns the canonical encoding of the challenge, suitable for transmission to the responder.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(44 + self.context.len());
+        out.extend_from_slice(&self.nonce);
+        encode_bytes(&self.context, &mut out);
+        out.extend_from_slice(&self.expiry.to_le_bytes());
+        out
+    }
+
+    /// ### From Bytes
+    ///
+    /// Decodes a challenge from its canonical encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Secp256k1SchnorrError> {
+        let mut reader = Reader::new(bytes);
+        let challenge = Self {
+            nonce: reader.read_array()?,
+            context: reader.read_bytes()?.to_vec(),
+            expiry: reader.read_i64()?,
+        };
+        reader.finish()?;
+        Ok(challenge)
+    }
+}
+
+/// ### AuthResponse
+///
+/// The message signed in response to a challenge. It commits to both the challenge and the key of
+/// the responder, so a response cannot be replayed to authenticate a different key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuthResponse<'a> {
+    pub challenge: &'a AuthChallenge,
+    pub responder: CompressedPoint,
+}
+
+impl Envelope for AuthResponse<'_> {
+    const TAG: &'static [u8] = b"solana-secp256k1-schnorr/auth";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.challenge.to_bytes());
+        out.extend_from_slice(&self.responder.0);
+    }
+}
+
+/// ### Respond
+///
+/// Signs a response to `challenge` with the responder's private key.
+#[cfg(feature = "sign")]
+pub fn respond<C: crate::challenges::Secp256k1SchnorrSign>(
+    challenge: &AuthChallenge,
+    domain: &Domain,
+    privkey: &[u8; 32],
+) -> Result<Secp256k1SchnorrSignature, Secp256k1SchnorrError> {
+    let responder = CompressedPoint::from(
+        solana_secp256k1::Curve::mul_g(privkey)
+            .map_err(|_| Secp256k1SchnorrError::InvalidSecretKey)?,
+    );
+    AuthResponse {
+        challenge,
+        responder,
+    }
+    .sign::<C>(domain, privkey)
+}
+
+/// ### Verify Response
+///
+/// Verifies that `responder` answered the challenge the verifier `issued` before it expired at
+/// time `now`.
+///
+/// The verifier must track issued challenges and accept a response to each at most once.
+pub fn verify_response<C: Secp256k1SchnorrVerify>(
+    issued: &AuthChallenge,
+    domain: &Domain,
+    responder: &CompressedPoint,
+    signature: &Secp256k1SchnorrSignature,
+    now: i64,
+) -> Result<(), Secp256k1SchnorrError> {
+    if now > issued.expiry {
+        return Err(Secp256k1SchnorrError::Expired);
+    }
+    AuthResponse {
+        challenge: issued,
+        responder: *responder,
+    }
+    .verify::<C, CompressedPoint>(domain, signature, responder)
+}
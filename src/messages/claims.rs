@@ -0,0 +1,114 @@
+use solana_secp256k1::Secp256k1Point;
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify,
+    errors::Secp256k1SchnorrError,
+    messages::{
+        merkle::{leaf_hash, merkle_proof, merkle_root, verify_merkle_proof},
+        Domain, Envelope, Reader,
+    },
+    Secp256k1SchnorrSignature,
+};
+
+/// ### ClaimLeaf
+///
+/// A single entitlement in a distribution, canonically encoded as `index ∥ recipient ∥ amount`
+/// with little-endian integers. The `index` uniquely identifies the leaf, allowing programs to
+/// record claims in a bitmap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClaimLeaf {
+    pub index: u64,
+    pub recipient: [u8; 32],
+    pub amount: u64,
+}
+
+impl ClaimLeaf {
+    pub const LEN: usize = 48;
+
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut out = [0u8; Self::LEN];
+        out[..8].clone_from_slice(&self.index.to_le_bytes());
+        out[8..40].clone_from_slice(&self.recipient);
+        out[40..].clone_from_slice(&self.amount.to_le_bytes());
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Secp256k1SchnorrError> {
+        let mut reader = Reader::new(bytes);
+        let leaf = Self {
+            index: reader.read_u64()?,
+            recipient: reader.read_array()?,
+            amount: reader.read_u64()?,
+        };
+        reader.finish()?;
+        Ok(leaf)
+    }
+
+    /// The merkle leaf hash of the canonical encoding
+    pub fn hash(&self) -> [u8; 32] {
+        leaf_hash(&self.to_bytes())
+    }
+}
+
+/// ### ClaimRoot
+///
+/// The message signed by an issuer to commit to a distribution: the merkle root over the hashes of
+/// every `ClaimLeaf`, identified by a `distribution_id` unique within the domain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClaimRoot {
+    pub distribution_id: u64,
+    pub root: [u8; 32],
+}
+
+impl Envelope for ClaimRoot {
+    const TAG: &'static [u8] = b"solana-secp256k1-schnorr/claims";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.distribution_id.to_le_bytes());
+        out.extend_from_slice(&self.root);
+    }
+}
+
+impl ClaimRoot {
+    /// ### From Leaves
+    ///
+    /// Client-side construction of a distribution's root. Returns `None` if there are no leaves.
+    pub fn from_leaves(distribution_id: u64, leaves: &[ClaimLeaf]) -> Option<Self> {
+        let hashes: Vec<[u8; 32]> = leaves.iter().map(ClaimLeaf::hash).collect();
+        Some(Self {
+            distribution_id,
+            root: merkle_root(&hashes)?,
+        })
+    }
+
+    /// ### Proof
+    ///
+    /// Client-side construction of the inclusion proof for the leaf at `index`.
+    pub fn proof(leaves: &[ClaimLeaf], index: usize) -> Option<Vec<[u8; 32]>> {
+        let hashes: Vec<[u8; 32]> = leaves.iter().map(ClaimLeaf::hash).collect();
+        merkle_proof(&hashes, index)
+    }
+}
+
+/// ### Verify Claim
+///
+/// Program-side verification of a claim in a single call. Decodes `leaf` from its canonical
+/// encoding, checks its inclusion in `claim_root` via `proof`, and verifies the issuer's signature
+/// over the root for `domain`, returning the decoded leaf on success.
+///
+/// The caller must check that the leaf has not been claimed before paying it out.
+pub fn verify_claim<C: Secp256k1SchnorrVerify, T: Secp256k1Point>(
+    issuer: &T,
+    domain: &Domain,
+    claim_root: &ClaimRoot,
+    signature: &Secp256k1SchnorrSignature,
+    leaf: &[u8],
+    proof: &[[u8; 32]],
+) -> Result<ClaimLeaf, Secp256k1SchnorrError> {
+    let claim = ClaimLeaf::from_bytes(leaf)?;
+    if !verify_merkle_proof(&claim_root.root, &claim.hash(), proof) {
+        return Err(Secp256k1SchnorrError::InvalidProof);
+    }
+    claim_root.verify::<C, T>(domain, signature, issuer)?;
+    Ok(claim)
+}
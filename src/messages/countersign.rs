@@ -0,0 +1,105 @@
+use solana_secp256k1::CompressedPoint;
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify,
+    errors::Secp256k1SchnorrError,
+    messages::{Domain, Envelope, Reader},
+    Secp256k1SchnorrSignature,
+};
+
+/// ### Verdict
+///
+/// The decision of a counter-signer on the action it counter-signs.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Approve = 0,
+    Cancel = 1,
+}
+
+impl TryFrom<u8> for Verdict {
+    type Error = Secp256k1SchnorrError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Approve),
+            1 => Ok(Self::Cancel),
+            _ => Err(Secp256k1SchnorrError::InvalidMessage),
+        }
+    }
+}
+
+/// ### CounterSignature
+///
+/// The message signed by a second key, such as a risk desk, to approve or cancel an action that has
+/// already been signed. It commits to the original signature and signer along with the verdict, so
+/// a verdict on one action can never be applied to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CounterSignature {
+    pub signature: [u8; 64],
+    pub signer: CompressedPoint,
+    pub verdict: Verdict,
+}
+
+impl Envelope for CounterSignature {
+    const TAG: &'static [u8] = b"solana-secp256k1-schnorr/countersign";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.signature);
+        out.extend_from_slice(&self.signer.0);
+        out.push(self.verdict as u8);
+    }
+}
+
+impl CounterSignature {
+    pub fn new(
+        signature: &Secp256k1SchnorrSignature,
+        signer: CompressedPoint,
+        verdict: Verdict,
+    ) -> Self {
+        Self {
+            signature: signature.0,
+            signer,
+            verdict,
+        }
+    }
+
+    /// ### From Bytes
+    ///
+    /// Decodes a counter-signature from its canonical encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Secp256k1SchnorrError> {
+        let mut reader = Reader::new(bytes);
+        let counter_signature = Self {
+            signature: reader.read_array()?,
+            signer: reader.read_point()?,
+            verdict: Verdict::try_from(reader.read_u8()?)?,
+        };
+        reader.finish()?;
+        Ok(counter_signature)
+    }
+}
+
+/// ### Verify Countersigned
+///
+/// Program-side verification of a counter-signed action. Verifies the `signer`'s `signature` over
+/// `message`, then the `countersigner`'s signature over its verdict on it for `domain`, returning
+/// the verdict.
+///
+/// The caller must only execute the action if the verdict is `Approve`.
+pub fn verify_countersigned<C: Secp256k1SchnorrVerify>(
+    message: &[u8],
+    signature: &Secp256k1SchnorrSignature,
+    signer: &CompressedPoint,
+    domain: &Domain,
+    verdict: Verdict,
+    counter_signature: &Secp256k1SchnorrSignature,
+    countersigner: &CompressedPoint,
+) -> Result<Verdict, Secp256k1SchnorrError> {
+    signature.verify::<C, CompressedPoint>(message, signer)?;
+    CounterSignature::new(signature, *signer, verdict).verify::<C, CompressedPoint>(
+        domain,
+        counter_signature,
+        countersigner,
+    )?;
+    Ok(verdict)
+}
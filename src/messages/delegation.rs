@@ -0,0 +1,265 @@
+use solana_secp256k1::CompressedPoint;
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify,
+    errors::Secp256k1SchnorrError,
+    messages::{encode_bytes, replay::NonceBitmap, Domain, Envelope, Reader},
+    Secp256k1SchnorrSignature,
+};
+
+/// Maximum number of instruction-data prefixes a single delegation may authorize
+pub const MAX_DELEGATION_SCOPE: usize = 32;
+
+/// ### Delegation
+///
+/// A single hop in a delegation chain, in which `delegator` authorizes `delegate` to act within
+/// `scope` until `expiry_slot`. `max_subdelegations` bounds how many further hops may follow this
+/// one, allowing a cold key to delegate to an operational key which may itself only delegate to
+/// end-of-chain automation keys.
+///
+/// Scopes are sets of instruction-data prefixes. An instruction is authorized by a chain only if
+/// every hop authorizes it, so the effective scope is the intersection of every hop's scope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delegation {
+    pub delegator: CompressedPoint,
+    pub delegate: CompressedPoint,
+    pub scope: Vec<Vec<u8>>,
+    pub expiry_slot: u64,
+    pub max_subdelegations: u8,
+}
+
+impl Envelope for Delegation {
+    const TAG: &'static [u8] = b"solana-secp256k1-schnorr/delegation";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.delegator.0);
+        out.extend_from_slice(&self.delegate.0);
+        out.push(self.scope.len() as u8);
+        for prefix in &self.scope {
+            encode_bytes(prefix, out);
+        }
+        out.extend_from_slice(&self.expiry_slot.to_le_bytes());
+        out.push(self.max_subdelegations);
+    }
+}
+
+impl Delegation {
+    /// ### Validate
+    ///
+    /// Delegations must authorize at least one and at most `MAX_DELEGATION_SCOPE` non-empty
+    /// prefixes.
+    pub fn validate(&self) -> Result<(), Secp256k1SchnorrError> {
+        if self.scope.is_empty()
+            || self.scope.len() > MAX_DELEGATION_SCOPE
+            || self.scope.iter().any(|p| p.is_empty())
+        {
+            return Err(Secp256k1SchnorrError::InvalidMessage);
+        }
+        Ok(())
+    }
+
+    /// ### From Bytes
+    ///
+    /// Decodes a delegation from its canonical encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Secp256k1SchnorrError> {
+        let mut reader = Reader::new(bytes);
+        let delegator = reader.read_point()?;
+        let delegate = reader.read_point()?;
+        let count = reader.read_u8()? as usize;
+        let scope = (0..count)
+            .map(|_| reader.read_bytes().map(|p| p.to_vec()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let delegation = Self {
+            delegator,
+            delegate,
+            scope,
+            expiry_slot: reader.read_u64()?,
+            max_subdelegations: reader.read_u8()?,
+        };
+        reader.finish()?;
+        delegation.validate()?;
+        Ok(delegation)
+    }
+}
+
+/// ### Intersect Scopes
+///
+/// Intersects two prefix scopes. Data matching a prefix in both `a` and `b` must match the longer
+/// of the two prefixes, so the intersection consists of every prefix from either scope that
+/// extends a prefix in the other. Fails with `LimitExceeded` if the intersection holds more than
+/// `MAX_DELEGATION_SCOPE` prefixes.
+pub fn intersect_scopes(
+    a: &[Vec<u8>],
+    b: &[Vec<u8>],
+) -> Result<Vec<Vec<u8>>, Secp256k1SchnorrError> {
+    let mut scope: Vec<Vec<u8>> = Vec::new();
+    for x in a {
+        for y in b {
+            let longer = match (x.starts_with(y), y.starts_with(x)) {
+                (true, _) => x,
+                (_, true) => y,
+                _ => continue,
+            };
+            if !scope.contains(longer) {
+                if scope.len() == MAX_DELEGATION_SCOPE {
+                    return Err(Secp256k1SchnorrError::LimitExceeded);
+                }
+                scope.push(longer.clone());
+            }
+        }
+    }
+    Ok(scope)
+}
+
+/// ### Verified Delegation
+///
+/// The result of validating a delegation chain: the key at the end of the chain, the scope it may
+/// act within, and the slot after which the chain expires.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedDelegation {
+    pub delegate: CompressedPoint,
+    pub scope: Vec<Vec<u8>>,
+    pub expiry_slot: u64,
+}
+
+impl VerifiedDelegation {
+    /// Returns true if `instruction_data` falls within the effective scope of the chain.
+    pub fn authorizes(&self, instruction_data: &[u8]) -> bool {
+        self.scope.iter().any(|p| instruction_data.starts_with(p))
+    }
+}
+
+/// ### Verify Delegation Chain
+///
+/// Validates a chain of signed delegations rooted at `root`. Succeeds only if:
+///
+/// - the chain is non-empty and no longer than `max_depth`
+/// - the first hop is delegated by `root`, and each subsequent hop by the previous delegate
+/// - every hop is signed by its delegator for `domain` and has not expired at `slot`
+/// - no hop is followed by more sub-delegations than it allows
+/// - the intersection of every hop's scope is non-empty
+pub fn verify_delegation_chain<C: Secp256k1SchnorrVerify>(
+    root: &CompressedPoint,
+    chain: &[(Delegation, Secp256k1SchnorrSignature)],
+    domain: &Domain,
+    slot: u64,
+    max_depth: usize,
+) -> Result<VerifiedDelegation, Secp256k1SchnorrError> {
+    if chain.is_empty() || chain.len() > max_depth {
+        return Err(Secp256k1SchnorrError::LimitExceeded);
+    }
+
+    let mut delegator = *root;
+    let mut scope: Option<Vec<Vec<u8>>> = None;
+    let mut expiry_slot = u64::MAX;
+
+    for (i, (delegation, signature)) in chain.iter().enumerate() {
+        delegation.validate()?;
+        if delegation.delegator.ne(&delegator) {
+            return Err(Secp256k1SchnorrError::Unauthorized);
+        }
+        if slot > delegation.expiry_slot {
+            return Err(Secp256k1SchnorrError::Expired);
+        }
+        if chain.len() - 1 - i > delegation.max_subdelegations as usize {
+            return Err(Secp256k1SchnorrError::LimitExceeded);
+        }
+        delegation.verify::<C, CompressedPoint>(domain, signature, &delegation.delegator)?;
+
+        scope = Some(match scope {
+            Some(scope) => intersect_scopes(&scope, &delegation.scope)?,
+            None => delegation.scope.clone(),
+        });
+        expiry_slot = expiry_slot.min(delegation.expiry_slot);
+        delegator = delegation.delegate;
+    }
+
+    let scope = scope.unwrap_or_default();
+    if scope.is_empty() {
+        return Err(Secp256k1SchnorrError::Unauthorized);
+    }
+
+    Ok(VerifiedDelegation {
+        delegate: delegator,
+        scope,
+        expiry_slot,
+    })
+}
+
+/// ### DelegatedCall
+///
+/// The message signed by the key at the end of a delegation chain for each instruction, binding
+/// the instruction data to the digest of the final hop and to a single-use `nonce`, without which
+/// a delegated call could be replayed verbatim until the chain expires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DelegatedCall<'a> {
+    pub delegation: [u8; 32],
+    pub nonce: u64,
+    pub instruction_data: &'a [u8],
+}
+
+impl Envelope for DelegatedCall<'_> {
+    const TAG: &'static [u8] = b"solana-secp256k1-schnorr/delegated-call";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.delegation);
+        out.extend_from_slice(&self.nonce.to_le_bytes());
+        encode_bytes(self.instruction_data, out);
+    }
+}
+
+/// ### Sign Delegated Call
+///
+/// Signs `instruction_data` under a fresh `nonce` with the private key of the delegate of
+/// `delegation`, the final hop in its chain.
+#[cfg(feature = "sign")]
+pub fn sign_delegated_call<C: crate::challenges::Secp256k1SchnorrSign>(
+    delegation: &Delegation,
+    domain: &Domain,
+    instruction_data: &[u8],
+    nonce: u64,
+    delegate_privkey: &[u8; 32],
+) -> Result<Secp256k1SchnorrSignature, Secp256k1SchnorrError> {
+    let call = DelegatedCall {
+        delegation: delegation.digest(domain),
+        nonce,
+        instruction_data,
+    };
+    call.sign::<C>(domain, delegate_privkey)
+}
+
+/// ### Verify Delegated Call
+///
+/// Validates a delegation chain as in `verify_delegation_chain`, then checks that the chain
+/// authorizes `instruction_data`, that it is signed with `nonce` by the key at the end of the
+/// chain, and that `nonce` has not been consumed in `nonces`, the replay bitmap of `root`.
+///
+/// On success, `nonce` is consumed, so the same call cannot be submitted twice.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_delegated_call<C: Secp256k1SchnorrVerify, B: AsRef<[u8]> + AsMut<[u8]>>(
+    root: &CompressedPoint,
+    chain: &[(Delegation, Secp256k1SchnorrSignature)],
+    domain: &Domain,
+    instruction_data: &[u8],
+    signature: &Secp256k1SchnorrSignature,
+    nonce: u64,
+    nonces: &mut NonceBitmap<B>,
+    slot: u64,
+    max_depth: usize,
+) -> Result<VerifiedDelegation, Secp256k1SchnorrError> {
+    nonces.check(nonce)?;
+    let verified = verify_delegation_chain::<C>(root, chain, domain, slot, max_depth)?;
+    if !verified.authorizes(instruction_data) {
+        return Err(Secp256k1SchnorrError::Unauthorized);
+    }
+    // The chain is non-empty once verified
+    let (last, _) = &chain[chain.len() - 1];
+    let call = DelegatedCall {
+        delegation: last.digest(domain),
+        nonce,
+        instruction_data,
+    };
+    call.verify::<C, CompressedPoint>(domain, signature, &verified.delegate)?;
+    nonces.consume(nonce)?;
+    Ok(verified)
+}
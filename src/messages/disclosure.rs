@@ -0,0 +1,177 @@
+use solana_secp256k1::CompressedPoint;
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify,
+    errors::Secp256k1SchnorrError,
+    messages::{
+        encode_bytes,
+        merkle::{leaf_hash, merkle_proof, merkle_root, verify_merkle_proof},
+        Domain, Envelope, Reader,
+    },
+    Secp256k1SchnorrSignature,
+};
+
+/// ### Attribute
+///
+/// A single named attribute of a credential. Each attribute carries a random `salt`, chosen by the
+/// issuer, so that undisclosed attributes cannot be recovered by hashing guesses of their values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attribute {
+    pub salt: [u8; 32],
+    pub name: Vec<u8>,
+    pub value: Vec<u8>,
+}
+
+impl Attribute {
+    pub fn new(salt: [u8; 32], name: &[u8], value: &[u8]) -> Self {
+        Self {
+            salt,
+            name: name.to_vec(),
+            value: value.to_vec(),
+        }
+    }
+
+    /// ### To Bytes
+    ///
+    /// Returns the canonical encoding of the attribute, `salt ∥ name ∥ value`, with length-prefixed
+    /// name and value.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(40 + self.name.len() + self.value.len());
+        out.extend_from_slice(&self.salt);
+        encode_bytes(&self.name, &mut out);
+        encode_bytes(&self.value, &mut out);
+        out
+    }
+
+    /// ### From Bytes
+    ///
+    /// Decodes an attribute from its canonical encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Secp256k1SchnorrError> {
+        let mut reader = Reader::new(bytes);
+        let attribute = Self {
+            salt: reader.read_array()?,
+            name: reader.read_bytes()?.to_vec(),
+            value: reader.read_bytes()?.to_vec(),
+        };
+        reader.finish()?;
+        Ok(attribute)
+    }
+
+    /// The merkle leaf hash of the canonical encoding
+    pub fn hash(&self) -> [u8; 32] {
+        leaf_hash(&self.to_bytes())
+    }
+}
+
+/// ### Disclosure
+///
+/// An attribute revealed by the holder of a credential, with its merkle path to the credential's
+/// root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disclosure {
+    pub attribute: Attribute,
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// ### Credential
+///
+/// The message signed by an issuer, committing to the merkle `root` over the hashes of every
+/// `Attribute` of `subject`, valid from `issued_at` until `expiry`. The holder may then prove any
+/// subset of attributes without revealing the rest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Credential {
+    pub subject: [u8; 32],
+    pub root: [u8; 32],
+    pub issued_at: i64,
+    pub expiry: i64,
+}
+
+impl Envelope for Credential {
+    const TAG: &'static [u8] = b"solana-secp256k1-schnorr/disclosure";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.subject);
+        out.extend_from_slice(&self.root);
+        out.extend_from_slice(&self.issued_at.to_le_bytes());
+        out.extend_from_slice(&self.expiry.to_le_bytes());
+    }
+}
+
+impl Credential {
+    /// ### Issue
+    ///
+    /// Issuer-side construction of a credential over `attributes`. Returns `None` if there are no
+    /// attributes.
+    pub fn issue(
+        subject: [u8; 32],
+        attributes: &[Attribute],
+        issued_at: i64,
+        expiry: i64,
+    ) -> Option<Self> {
+        let hashes: Vec<[u8; 32]> = attributes.iter().map(Attribute::hash).collect();
+        Some(Self {
+            subject,
+            root: merkle_root(&hashes)?,
+            issued_at,
+            expiry,
+        })
+    }
+
+    /// ### Disclose
+    ///
+    /// Holder-side construction of the disclosure of the attribute at `index`.
+    pub fn disclose(attributes: &[Attribute], index: usize) -> Option<Disclosure> {
+        let hashes: Vec<[u8; 32]> = attributes.iter().map(Attribute::hash).collect();
+        Some(Disclosure {
+            attribute: attributes.get(index)?.clone(),
+            proof: merkle_proof(&hashes, index)?,
+        })
+    }
+
+    /// ### From Bytes
+    ///
+    /// Decodes a credential from its canonical encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Secp256k1SchnorrError> {
+        let mut reader = Reader::new(bytes);
+        let credential = Self {
+            subject: reader.read_array()?,
+            root: reader.read_array()?,
+            issued_at: reader.read_i64()?,
+            expiry: reader.read_i64()?,
+        };
+        reader.finish()?;
+        Ok(credential)
+    }
+}
+
+/// ### Verify Disclosure
+///
+/// Verifies that every `disclosed` attribute belongs to `credential`, that the credential is valid
+/// at time `now`, and that it is signed by `issuer` for `domain`.
+///
+/// The caller is responsible for checking the subject and the values of the disclosed attributes.
+pub fn verify_disclosure<C: Secp256k1SchnorrVerify>(
+    credential: &Credential,
+    domain: &Domain,
+    signature: &Secp256k1SchnorrSignature,
+    issuer: &CompressedPoint,
+    disclosed: &[Disclosure],
+    now: i64,
+) -> Result<(), Secp256k1SchnorrError> {
+    if now < credential.issued_at {
+        return Err(Secp256k1SchnorrError::NotYetValid);
+    }
+    if now > credential.expiry {
+        return Err(Secp256k1SchnorrError::Expired);
+    }
+    if disclosed.iter().any(|disclosure| {
+        !verify_merkle_proof(
+            &credential.root,
+            &disclosure.attribute.hash(),
+            &disclosure.proof,
+        )
+    }) {
+        return Err(Secp256k1SchnorrError::InvalidProof);
+    }
+    credential.verify::<C, CompressedPoint>(domain, signature, issuer)
+}
@@ -0,0 +1,183 @@
+use solana_secp256k1::CompressedPoint;
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify,
+    errors::Secp256k1SchnorrError,
+    messages::{encode_bytes, tagged_hash, Reader},
+    Secp256k1SchnorrSignature,
+};
+
+/// Tag under which framed messages are hashed before signing
+pub const FRAMED_MESSAGE_TAG: &[u8] = b"solana-secp256k1-schnorr/framed";
+
+/// ### FramedMessage
+///
+/// An arbitrary payload framed with everything a program needs to reject replays, in exactly one
+/// canonical encoding:
+///
+/// `program_id (32) ∥ signer (33) ∥ nonce (u64) ∥ expiry (i64) ∥ len (u32) ∥ payload`
+///
+/// The signer is framed alongside the nonce, scoping nonces to each signer, so that programs may
+/// keep one nonce per key. The signed message is `TaggedHash(FRAMED_MESSAGE_TAG, encoding)`.
+///
+/// `nonce` must match the signer's current nonce, which the program increments on every accepted
+/// message, and the message may not be used after `expiry`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FramedMessage {
+    pub program_id: [u8; 32],
+    pub signer: CompressedPoint,
+    pub nonce: u64,
+    pub expiry: i64,
+    pub payload: Vec<u8>,
+}
+
+impl FramedMessage {
+    /// ### Builder
+    ///
+    /// Starts building a message from `signer` to the program `program_id`.
+    pub fn builder(program_id: [u8; 32], signer: CompressedPoint) -> MessageBuilder {
+        MessageBuilder {
+            program_id,
+            signer,
+            nonce: 0,
+            expiry: None,
+            payload: Vec::new(),
+        }
+    }
+
+    /// ### To Bytes
+    ///
+    /// Returns the canonical encoding of the message.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(32 + 33 + 8 + 8 + 4 + self.payload.len());
+        out.extend_from_slice(&self.program_id);
+        out.extend_from_slice(&self.signer.0);
+        out.extend_from_slice(&self.nonce.to_le_bytes());
+        out.extend_from_slice(&self.expiry.to_le_bytes());
+        encode_bytes(&self.payload, &mut out);
+        out
+    }
+
+    /// ### From Bytes
+    ///
+    /// Decodes a message from its canonical encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Secp256k1SchnorrError> {
+        let mut reader = Reader::new(bytes);
+        let message = Self {
+            program_id: reader.read_array()?,
+            signer: reader.read_point()?,
+            nonce: reader.read_u64()?,
+            expiry: reader.read_i64()?,
+            payload: reader.read_bytes()?.to_vec(),
+        };
+        reader.finish()?;
+        Ok(message)
+    }
+
+    /// ### Digest
+    ///
+    /// The digest of the framed message. This is the message that is signed.
+    pub fn digest(&self) -> [u8; 32] {
+        tagged_hash(FRAMED_MESSAGE_TAG, &[&self.to_bytes()])
+    }
+
+    /// ### Verify
+    ///
+    /// Verifies a signature over the message by its signer, without checking its nonce or expiry.
+    pub fn verify<C: Secp256k1SchnorrVerify>(
+        &self,
+        signature: &Secp256k1SchnorrSignature,
+    ) -> Result<(), Secp256k1SchnorrError> {
+        signature.verify::<C, CompressedPoint>(&self.digest(), &self.signer)
+    }
+
+    /// ### Sign
+    ///
+    /// Signs the message with the private key of its signer.
+    #[cfg(feature = "sign")]
+    pub fn sign<C: crate::challenges::Secp256k1SchnorrSign>(
+        &self,
+        privkey: &[u8; 32],
+    ) -> Result<Secp256k1SchnorrSignature, Secp256k1SchnorrError> {
+        Secp256k1SchnorrSignature::sign::<C>(&self.digest(), privkey)
+    }
+}
+
+/// ### MessageBuilder
+///
+/// Client-side builder for `FramedMessage`, requiring an explicit expiry so that no message is
+/// valid forever.
+#[derive(Debug, Clone)]
+pub struct MessageBuilder {
+    program_id: [u8; 32],
+    signer: CompressedPoint,
+    nonce: u64,
+    expiry: Option<i64>,
+    payload: Vec<u8>,
+}
+
+impl MessageBuilder {
+    pub fn nonce(mut self, nonce: u64) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    pub fn expiry(mut self, expiry: i64) -> Self {
+        self.expiry = Some(expiry);
+        self
+    }
+
+    pub fn payload(mut self, payload: &[u8]) -> Self {
+        self.payload = payload.to_vec();
+        self
+    }
+
+    pub fn build(self) -> Result<FramedMessage, Secp256k1SchnorrError> {
+        let expiry = self.expiry.ok_or(Secp256k1SchnorrError::InvalidMessage)?;
+        if self.payload.len() > u32::MAX as usize {
+            return Err(Secp256k1SchnorrError::InvalidMessage);
+        }
+        Ok(FramedMessage {
+            program_id: self.program_id,
+            signer: self.signer,
+            nonce: self.nonce,
+            expiry,
+            payload: self.payload,
+        })
+    }
+}
+
+/// ### Verify Framed Message
+///
+/// Program-side verification of a framed message. Succeeds only if the message is addressed to
+/// `program_id`, carries the signer's current `nonce`, has not expired at time `now`, and is
+/// signed by its signer.
+///
+/// On success, the caller must act on the payload and increment the signer's nonce.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::{messages::framed::{verify_framed_message, FramedMessage}, BIP340Challenge};
+///
+/// let message = FramedMessage::from_bytes(data)?;
+/// verify_framed_message::<BIP340Challenge>(&message, &signature, &crate::ID, state.nonce, clock.unix_timestamp)?;
+/// state.nonce += 1;
+/// ```
+pub fn verify_framed_message<C: Secp256k1SchnorrVerify>(
+    message: &FramedMessage,
+    signature: &Secp256k1SchnorrSignature,
+    program_id: &[u8; 32],
+    nonce: u64,
+    now: i64,
+) -> Result<(), Secp256k1SchnorrError> {
+    if message.program_id.ne(program_id) {
+        return Err(Secp256k1SchnorrError::DomainMismatch);
+    }
+    if message.nonce != nonce {
+        return Err(Secp256k1SchnorrError::NonceMismatch);
+    }
+    if now > message.expiry {
+        return Err(Secp256k1SchnorrError::Expired);
+    }
+    message.verify::<C>(signature)
+}
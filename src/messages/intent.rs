@@ -0,0 +1,115 @@
+use solana_nostd_sha256::hash;
+use solana_secp256k1::CompressedPoint;
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify,
+    errors::Secp256k1SchnorrError,
+    messages::{Domain, Envelope, Reader},
+    Secp256k1SchnorrSignature,
+};
+
+/// ### Intent
+///
+/// A user-signed authorization for a relayer to execute an instruction on the user's behalf,
+/// independent of whoever pays the transaction fee.
+///
+/// The intent commits to the target program and the SHA256 hash of the instruction payload, caps
+/// the fee the relayer may charge, and carries an expiry and a replay nonce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Intent {
+    pub signer: CompressedPoint,
+    pub target_program: [u8; 32],
+    pub payload_hash: [u8; 32],
+    pub fee_cap: u64,
+    pub expiry: i64,
+    pub nonce: u64,
+}
+
+impl Envelope for Intent {
+    const TAG: &'static [u8] = b"solana-secp256k1-schnorr/intent";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.signer.0);
+        out.extend_from_slice(&self.target_program);
+        out.extend_from_slice(&self.payload_hash);
+        out.extend_from_slice(&self.fee_cap.to_le_bytes());
+        out.extend_from_slice(&self.expiry.to_le_bytes());
+        out.extend_from_slice(&self.nonce.to_le_bytes());
+    }
+}
+
+impl Intent {
+    /// ### New
+    ///
+    /// Constructs an intent to execute `payload` against `target_program`.
+    pub fn new(
+        signer: CompressedPoint,
+        target_program: [u8; 32],
+        payload: &[u8],
+        fee_cap: u64,
+        expiry: i64,
+        nonce: u64,
+    ) -> Self {
+        Self {
+            signer,
+            target_program,
+            payload_hash: hash(payload),
+            fee_cap,
+            expiry,
+            nonce,
+        }
+    }
+
+    /// ### From Bytes
+    ///
+    /// Decodes an intent from its canonical encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Secp256k1SchnorrError> {
+        let mut reader = Reader::new(bytes);
+        let intent = Self {
+            signer: reader.read_point()?,
+            target_program: reader.read_array()?,
+            payload_hash: reader.read_array()?,
+            fee_cap: reader.read_u64()?,
+            expiry: reader.read_i64()?,
+            nonce: reader.read_u64()?,
+        };
+        reader.finish()?;
+        Ok(intent)
+    }
+}
+
+/// ### Verify Intent
+///
+/// Program-side verification of a relayed intent. Succeeds only if:
+///
+/// - `target_program` and `payload` are exactly what the user authorized
+/// - the relayer's `fee` does not exceed the user's fee cap
+/// - the intent carries the user's expected replay `nonce` and has not expired at time `now`
+/// - the intent is signed by its signer for `domain`
+///
+/// On success, the caller must advance the user's nonce before executing the payload.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_intent<C: Secp256k1SchnorrVerify>(
+    intent: &Intent,
+    domain: &Domain,
+    signature: &Secp256k1SchnorrSignature,
+    target_program: &[u8; 32],
+    payload: &[u8],
+    fee: u64,
+    nonce: u64,
+    now: i64,
+) -> Result<(), Secp256k1SchnorrError> {
+    if intent.target_program.ne(target_program) || intent.payload_hash.ne(&hash(payload)) {
+        return Err(Secp256k1SchnorrError::InvalidMessage);
+    }
+    if fee > intent.fee_cap {
+        return Err(Secp256k1SchnorrError::LimitExceeded);
+    }
+    if intent.nonce != nonce {
+        return Err(Secp256k1SchnorrError::NonceMismatch);
+    }
+    if now > intent.expiry {
+        return Err(Secp256k1SchnorrError::Expired);
+    }
+    intent.verify::<C, CompressedPoint>(domain, signature, &intent.signer)
+}
@@ -0,0 +1,81 @@
+use crate::{
+    challenges::Secp256k1SchnorrVerify,
+    errors::Secp256k1SchnorrError,
+    keys::lift_x,
+    messages::{Domain, Envelope, Reader},
+    Secp256k1SchnorrSignature,
+};
+
+/// ### LinkProof
+///
+/// A proof binding a Bitcoin taproot key to a Solana wallet, allowing programs to gate features on
+/// verified BTC ownership. The taproot key, given as its 32-byte 𝑋-coordinate, signs a message
+/// naming the Solana wallet, while the wallet completes the binding in the opposite direction by
+/// signing the transaction that submits the proof. Wallets linking off-chain may instead sign the
+/// proof's `digest` with their ed25519 key.
+///
+/// `nonce` must match the program's current link nonce for the wallet, which it increments on
+/// every accepted link, and the proof may not be used after `expiry`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LinkProof {
+    pub bitcoin_key: [u8; 32],
+    pub solana_key: [u8; 32],
+    pub nonce: u64,
+    pub expiry: i64,
+}
+
+impl Envelope for LinkProof {
+    const TAG: &'static [u8] = b"solana-secp256k1-schnorr/link";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.bitcoin_key);
+        out.extend_from_slice(&self.solana_key);
+        out.extend_from_slice(&self.nonce.to_le_bytes());
+        out.extend_from_slice(&self.expiry.to_le_bytes());
+    }
+}
+
+impl LinkProof {
+    /// ### From Bytes
+    ///
+    /// Decodes a link proof from its canonical encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Secp256k1SchnorrError> {
+        let mut reader = Reader::new(bytes);
+        let link = Self {
+            bitcoin_key: reader.read_array()?,
+            solana_key: reader.read_array()?,
+            nonce: reader.read_u64()?,
+            expiry: reader.read_i64()?,
+        };
+        reader.finish()?;
+        Ok(link)
+    }
+}
+
+/// ### Verify Link
+///
+/// Program-side verification of a link proof. Succeeds only if the proof names `solana_signer`,
+/// which the caller must have checked signed the transaction, carries the wallet's current `nonce`,
+/// has not expired at time `now`, and is signed by the taproot key for `domain`.
+///
+/// On success, the caller must record the link and increment the wallet's nonce.
+pub fn verify_link<C: Secp256k1SchnorrVerify>(
+    link: &LinkProof,
+    domain: &Domain,
+    signature: &Secp256k1SchnorrSignature,
+    solana_signer: &[u8; 32],
+    nonce: u64,
+    now: i64,
+) -> Result<(), Secp256k1SchnorrError> {
+    if link.solana_key.ne(solana_signer) {
+        return Err(Secp256k1SchnorrError::Unauthorized);
+    }
+    if link.nonce != nonce {
+        return Err(Secp256k1SchnorrError::NonceMismatch);
+    }
+    if now > link.expiry {
+        return Err(Secp256k1SchnorrError::Expired);
+    }
+    let bitcoin_key = lift_x(&link.bitcoin_key)?;
+    link.verify::<C, _>(domain, signature, &bitcoin_key)
+}
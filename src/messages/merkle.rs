@@ -0,0 +1,78 @@
+use crate::messages::tagged_hash;
+
+/// Tag under which merkle leaves are hashed
+pub const MERKLE_LEAF_TAG: &[u8] = b"solana-secp256k1-schnorr/merkle/leaf";
+
+/// Tag under which merkle nodes are hashed
+pub const MERKLE_NODE_TAG: &[u8] = b"solana-secp256k1-schnorr/merkle/node";
+
+/// ### Leaf Hash
+///
+/// Hashes the canonical encoding of a leaf. Leaves and interior nodes are hashed under distinct
+/// tags, so an interior node can never be passed off as a leaf.
+pub fn leaf_hash(leaf: &[u8]) -> [u8; 32] {
+    tagged_hash(MERKLE_LEAF_TAG, &[leaf])
+}
+
+/// ### Node Hash
+///
+/// Hashes a pair of child nodes. Children are sorted before hashing, so proofs need not encode
+/// whether each sibling sits to the left or the right.
+pub fn node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    tagged_hash(MERKLE_NODE_TAG, &[left, right])
+}
+
+/// Computes the next level of the tree. An unpaired trailing node is promoted unchanged.
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [a, b] => node_hash(a, b),
+            [a] => *a,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+/// ### Merkle Root
+///
+/// Computes the root of the tree over already-hashed `leaves`. Returns `None` if there are no
+/// leaves.
+pub fn merkle_root(leaves: &[[u8; 32]]) -> Option<[u8; 32]> {
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level.first().copied()
+}
+
+/// ### Merkle Proof
+///
+/// Computes the proof of inclusion of the leaf at `index`, ordered from the leaf up to the root.
+/// Returns `None` if `index` is out of bounds.
+pub fn merkle_proof(leaves: &[[u8; 32]], mut index: usize) -> Option<Vec<[u8; 32]>> {
+    if index >= leaves.len() {
+        return None;
+    }
+    let mut proof = Vec::new();
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        level = next_level(&level);
+        index /= 2;
+    }
+    Some(proof)
+}
+
+/// ### Verify Merkle Proof
+///
+/// Returns true if `proof` proves the inclusion of the hashed `leaf` in the tree with `root`.
+pub fn verify_merkle_proof(root: &[u8; 32], leaf: &[u8; 32], proof: &[[u8; 32]]) -> bool {
+    proof
+        .iter()
+        .fold(*leaf, |node, sibling| node_hash(&node, sibling))
+        .eq(root)
+}
@@ -0,0 +1,293 @@
+use solana_nostd_sha256::{hash, hashv};
+use solana_secp256k1::{
+    CompressedPoint, Secp256k1Point, SEC1_OCTET_COMPRESSED_EVEN, SEC1_OCTET_COMPRESSED_ODD,
+};
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify, errors::Secp256k1SchnorrError, Secp256k1SchnorrSignature,
+};
+
+/// Tag under which domain separators are hashed
+pub const DOMAIN_TAG: &[u8] = b"solana-secp256k1-schnorr/domain";
+
+/// ### Tagged Hash
+///
+/// Computes a BIP340-style tagged hash, `SHA256(SHA256(tag) ∥ SHA256(tag) ∥ parts)`, over an
+/// arbitrary number of message parts.
+///
+/// Every structured message format in this module hashes its canonical encoding under its own tag,
+/// ensuring a signature over one format can never be replayed as a valid signature over another.
+pub fn tagged_hash(tag: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let tag = hash(tag);
+    let mut data: Vec<&[u8]> = Vec::with_capacity(parts.len() + 2);
+    data.push(&tag);
+    data.push(&tag);
+    data.extend_from_slice(parts);
+    hashv(&data)
+}
+
+/// ### Hex Encode Into
+///
+/// Appends the lowercase hex encoding of `bytes` to `out`.
+pub fn hex_encode_into(bytes: &[u8], out: &mut String) {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    for byte in bytes {
+        out.push(HEX[(byte >> 4) as usize] as char);
+        out.push(HEX[(byte & 0x0f) as usize] as char);
+    }
+}
+
+/// ### Hex Decode
+///
+/// Decodes lowercase hex into a fixed-size array, rejecting uppercase digits so that every value
+/// has exactly one canonical encoding.
+pub fn hex_decode<const N: usize>(hex: &str) -> Option<[u8; N]> {
+    fn nibble(c: u8) -> Option<u8> {
+        match c {
+            b'0'..=b'9' => Some(c - b'0'),
+            b'a'..=b'f' => Some(c - b'a' + 10),
+            _ => None,
+        }
+    }
+    let hex = hex.as_bytes();
+    if hex.len() != N * 2 {
+        return None;
+    }
+    let mut out = [0u8; N];
+    for (byte, pair) in out.iter_mut().zip(hex.chunks_exact(2)) {
+        *byte = (nibble(pair[0])? << 4) | nibble(pair[1])?;
+    }
+    Some(out)
+}
+
+/// Tag under which keys are hashed to derive their key id
+pub const KEY_ID_TAG: &[u8] = b"solana-secp256k1-schnorr/key-id";
+
+/// ### Key Id
+///
+/// Derives the 32-byte identifier of a key, allowing programs to refer to issuers and notaries in a
+/// registry without storing or transmitting their full public key.
+pub fn key_id(pubkey: &CompressedPoint) -> [u8; 32] {
+    tagged_hash(KEY_ID_TAG, &[&pubkey.0])
+}
+
+/// ### Domain
+///
+/// Identifies the application a structured message is intended for, analogous to an EIP-712
+/// domain. Binding signatures to a domain prevents a message signed for one program, or one
+/// version of a program, from being accepted by another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Domain<'a> {
+    pub name: &'a [u8],
+    pub version: u32,
+    pub program_id: [u8; 32],
+}
+
+impl Domain<'_> {
+    /// ### Separator
+    ///
+    /// The 32-byte domain separator mixed into the digest of every message signed for this domain.
+    pub fn separator(&self) -> [u8; 32] {
+        tagged_hash(
+            DOMAIN_TAG,
+            &[
+                &(self.name.len() as u32).to_le_bytes(),
+                self.name,
+                &self.version.to_le_bytes(),
+                &self.program_id,
+            ],
+        )
+    }
+}
+
+/// ### Envelope
+///
+/// The domain-separation envelope shared by all structured message formats. Implementors define
+/// a unique tag and a canonical binary encoding, and inherit a digest of the form:
+///
+/// `TaggedHash(TAG, separator ∥ encoding)`
+///
+/// along with signing and verification over that digest with any challenge scheme.
+pub trait Envelope {
+    /// Tag under which messages of this type are hashed
+    const TAG: &'static [u8];
+
+    /// Appends the canonical encoding of the message to `out`
+    fn encode(&self, out: &mut Vec<u8>);
+
+    /// ### To Bytes
+    ///
+    /// Returns the canonical encoding of the message.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.encode(&mut out);
+        out
+    }
+
+    /// ### Digest
+    ///
+    /// The domain-bound digest of the message. This is the message that is signed.
+    fn digest(&self, domain: &Domain) -> [u8; 32] {
+        tagged_hash(Self::TAG, &[&domain.separator(), &self.to_bytes()])
+    }
+
+    /// ### Verify
+    ///
+    /// Verifies a signature over the message for `domain` by `pubkey`.
+    fn verify<C: Secp256k1SchnorrVerify, T: Secp256k1Point>(
+        &self,
+        domain: &Domain,
+        signature: &Secp256k1SchnorrSignature,
+        pubkey: &T,
+    ) -> Result<(), Secp256k1SchnorrError> {
+        signature.verify::<C, T>(&self.digest(domain), pubkey)
+    }
+
+    /// ### Sign
+    ///
+    /// Signs the message for `domain`.
+    #[cfg(feature = "sign")]
+    fn sign<C: crate::challenges::Secp256k1SchnorrSign>(
+        &self,
+        domain: &Domain,
+        privkey: &[u8; 32],
+    ) -> Result<Secp256k1SchnorrSignature, Secp256k1SchnorrError> {
+        Secp256k1SchnorrSignature::sign::<C>(&self.digest(domain), privkey)
+    }
+}
+
+/// ### Reader
+///
+/// A strict cursor over a canonical binary encoding. Integers are little-endian, variable-length
+/// byte strings are prefixed with a little-endian `u32` length, and points are 33-byte SEC1
+/// compressed encodings.
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    pub fn read_slice(&mut self, len: usize) -> Result<&'a [u8], Secp256k1SchnorrError> {
+        if self.bytes.len() < len {
+            return Err(Secp256k1SchnorrError::InvalidMessage);
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N], Secp256k1SchnorrError> {
+        let mut out = [0u8; N];
+        out.clone_from_slice(self.read_slice(N)?);
+        Ok(out)
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, Secp256k1SchnorrError> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, Secp256k1SchnorrError> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, Secp256k1SchnorrError> {
+        Ok(u64::from_le_bytes(self.read_array()?))
+    }
+
+    pub fn read_i64(&mut self) -> Result<i64, Secp256k1SchnorrError> {
+        Ok(i64::from_le_bytes(self.read_array()?))
+    }
+
+    pub fn read_bytes(&mut self) -> Result<&'a [u8], Secp256k1SchnorrError> {
+        let len = self.read_u32()? as usize;
+        self.read_slice(len)
+    }
+
+    pub fn read_point(&mut self) -> Result<CompressedPoint, Secp256k1SchnorrError> {
+        let point = CompressedPoint(self.read_array()?);
+        if point.0[0] != SEC1_OCTET_COMPRESSED_EVEN && point.0[0] != SEC1_OCTET_COMPRESSED_ODD {
+            return Err(Secp256k1SchnorrError::InvalidPublicKey);
+        }
+        Ok(point)
+    }
+
+    /// Fails unless every byte has been consumed, so each message has exactly one encoding.
+    pub fn finish(self) -> Result<(), Secp256k1SchnorrError> {
+        match self.bytes.is_empty() {
+            true => Ok(()),
+            false => Err(Secp256k1SchnorrError::InvalidMessage),
+        }
+    }
+}
+
+/// Appends a `u32` length-prefixed byte string to `out`.
+pub fn encode_bytes(bytes: &[u8], out: &mut Vec<u8>) {
+    out.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    out.extend_from_slice(bytes);
+}
+
+pub mod merkle;
+pub mod replay;
+
+#[cfg(feature = "attestation")]
+pub mod attestation;
+
+#[cfg(feature = "auth")]
+pub mod auth;
+
+#[cfg(feature = "claims")]
+pub mod claims;
+
+#[cfg(feature = "countersign")]
+pub mod countersign;
+
+#[cfg(feature = "delegation")]
+pub mod delegation;
+
+#[cfg(feature = "disclosure")]
+pub mod disclosure;
+
+#[cfg(feature = "framed")]
+pub mod framed;
+
+#[cfg(feature = "intent")]
+pub mod intent;
+
+#[cfg(feature = "link")]
+pub mod link;
+
+#[cfg(feature = "multisig")]
+pub mod multisig;
+
+#[cfg(feature = "notary")]
+pub mod notary;
+
+#[cfg(feature = "order")]
+pub mod order;
+
+#[cfg(feature = "permit")]
+pub mod permit;
+
+#[cfg(feature = "recovery")]
+pub mod recovery;
+
+#[cfg(feature = "session")]
+pub mod session;
+
+#[cfg(feature = "siwx")]
+pub mod siwx;
+
+#[cfg(feature = "subscription")]
+pub mod subscription;
+
+#[cfg(feature = "swap")]
+pub mod swap;
+
+#[cfg(feature = "twofactor")]
+pub mod twofactor;
+
+#[cfg(feature = "wallet")]
+pub mod wallet;
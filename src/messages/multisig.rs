@@ -0,0 +1,230 @@
+use solana_secp256k1::CompressedPoint;
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify,
+    errors::Secp256k1SchnorrError,
+    messages::{encode_bytes, Domain, Envelope, Reader},
+    Secp256k1SchnorrSignature,
+};
+
+/// Maximum number of signer and veto keys a single policy may hold
+pub const MAX_POLICY_KEYS: usize = 32;
+
+/// ### Policy
+///
+/// A weighted multisig policy for account storage. A message is approved once the combined weight
+/// of the `signers` approving it reaches `threshold`, unless any of the `vetoes` keys has vetoed it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Policy {
+    pub signers: Vec<(CompressedPoint, u32)>,
+    pub threshold: u64,
+    pub vetoes: Vec<CompressedPoint>,
+}
+
+impl Envelope for Policy {
+    const TAG: &'static [u8] = b"solana-secp256k1-schnorr/multisig-policy";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(self.signers.len() as u8);
+        for (signer, weight) in &self.signers {
+            out.extend_from_slice(&signer.0);
+            out.extend_from_slice(&weight.to_le_bytes());
+        }
+        out.extend_from_slice(&self.threshold.to_le_bytes());
+        out.push(self.vetoes.len() as u8);
+        for veto in &self.vetoes {
+            out.extend_from_slice(&veto.0);
+        }
+    }
+}
+
+impl Policy {
+    /// ### Validate
+    ///
+    /// Policies must hold at least one signer and no key more than once. Every signer must carry a
+    /// non-zero weight, and the threshold must be non-zero and reachable by the combined weight of
+    /// all signers. Policies holding more than `MAX_POLICY_KEYS` keys in total, which the one-byte
+    /// counts of the encoding cannot represent faithfully, fail with `LimitExceeded`.
+    pub fn validate(&self) -> Result<(), Secp256k1SchnorrError> {
+        if self.signers.len() + self.vetoes.len() > MAX_POLICY_KEYS {
+            return Err(Secp256k1SchnorrError::LimitExceeded);
+        }
+        let keys: Vec<&CompressedPoint> = self
+            .signers
+            .iter()
+            .map(|(signer, _)| signer)
+            .chain(self.vetoes.iter())
+            .collect();
+        if self.signers.is_empty()
+            || keys
+                .iter()
+                .enumerate()
+                .any(|(i, key)| keys[..i].iter().any(|k| k.0 == key.0))
+            || self.signers.iter().any(|(_, weight)| *weight == 0)
+            || self.threshold == 0
+            || self.threshold > self.total_weight()
+        {
+            return Err(Secp256k1SchnorrError::InvalidMessage);
+        }
+        Ok(())
+    }
+
+    /// ### From Bytes
+    ///
+    /// Decodes a policy from its canonical encoding, as stored in account data.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Secp256k1SchnorrError> {
+        let mut reader = Reader::new(bytes);
+        let count = reader.read_u8()? as usize;
+        let signers = (0..count)
+            .map(|_| Ok((reader.read_point()?, reader.read_u32()?)))
+            .collect::<Result<Vec<_>, Secp256k1SchnorrError>>()?;
+        let threshold = reader.read_u64()?;
+        let count = reader.read_u8()? as usize;
+        let vetoes = (0..count)
+            .map(|_| reader.read_point())
+            .collect::<Result<Vec<_>, _>>()?;
+        reader.finish()?;
+        let policy = Self {
+            signers,
+            threshold,
+            vetoes,
+        };
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    /// The combined weight of all signers
+    pub fn total_weight(&self) -> u64 {
+        self.signers.iter().map(|(_, weight)| *weight as u64).sum()
+    }
+
+    /// ### Evaluate
+    ///
+    /// Evaluates the `signatures` submitted by each key over `message` against the policy for
+    /// `domain`, accounting for every submission individually. Fails if the policy itself does not
+    /// validate.
+    pub fn evaluate<C: Secp256k1SchnorrVerify>(
+        &self,
+        domain: &Domain,
+        message: &[u8],
+        signatures: &[(CompressedPoint, Secp256k1SchnorrSignature)],
+    ) -> Result<Evaluation, Secp256k1SchnorrError> {
+        self.validate()?;
+        let policy = self.digest(domain);
+        let mut evaluation = Evaluation {
+            approved: false,
+            weight: 0,
+            threshold: self.threshold,
+            votes: Vec::with_capacity(signatures.len()),
+        };
+        for (i, (key, signature)) in signatures.iter().enumerate() {
+            let signer = self.signers.iter().find(|(signer, _)| signer.0 == key.0);
+            let veto = self.vetoes.iter().any(|veto| veto.0 == key.0);
+            let status = if signatures[..i].iter().any(|(k, _)| k.0 == key.0) {
+                VoteStatus::Duplicate
+            } else if signer.is_none() && !veto {
+                VoteStatus::UnknownKey
+            } else if (PolicyVote {
+                policy,
+                message,
+                veto,
+            })
+            .verify::<C, CompressedPoint>(domain, signature, key)
+            .is_err()
+            {
+                VoteStatus::InvalidSignature
+            } else if let Some((_, weight)) = signer {
+                evaluation.weight += *weight as u64;
+                VoteStatus::Approved(*weight)
+            } else {
+                VoteStatus::Vetoed
+            };
+            evaluation.votes.push(status);
+        }
+        evaluation.approved =
+            evaluation.weight >= self.threshold && !evaluation.votes.contains(&VoteStatus::Vetoed);
+        Ok(evaluation)
+    }
+}
+
+/// ### PolicyVote
+///
+/// The message signed by a policy key, committing to the digest of the policy, the message voted
+/// on and whether the vote is a veto. Signer keys sign approvals and veto keys sign vetoes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PolicyVote<'a> {
+    pub policy: [u8; 32],
+    pub message: &'a [u8],
+    pub veto: bool,
+}
+
+impl Envelope for PolicyVote<'_> {
+    const TAG: &'static [u8] = b"solana-secp256k1-schnorr/multisig-vote";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.policy);
+        encode_bytes(self.message, out);
+        out.push(self.veto as u8);
+    }
+}
+
+/// ### Sign Vote
+///
+/// Signs an approval of `message` under `policy`, or a veto if `veto` is set. Fails if the policy
+/// does not validate.
+#[cfg(feature = "sign")]
+pub fn sign_vote<C: crate::challenges::Secp256k1SchnorrSign>(
+    policy: &Policy,
+    domain: &Domain,
+    message: &[u8],
+    veto: bool,
+    privkey: &[u8; 32],
+) -> Result<Secp256k1SchnorrSignature, Secp256k1SchnorrError> {
+    policy.validate()?;
+    PolicyVote {
+        policy: policy.digest(domain),
+        message,
+        veto,
+    }
+    .sign::<C>(domain, privkey)
+}
+
+/// ### VoteStatus
+///
+/// The outcome of evaluating a single submitted signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoteStatus {
+    /// A valid approval by a signer, contributing its weight
+    Approved(u32),
+    /// A valid veto by a veto key
+    Vetoed,
+    /// A key that appears in neither list of the policy
+    UnknownKey,
+    /// A key that already submitted a signature
+    Duplicate,
+    /// A signature that failed to verify
+    InvalidSignature,
+}
+
+/// ### Evaluation
+///
+/// The result of evaluating a policy, with the status of each submitted signature in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Evaluation {
+    pub approved: bool,
+    pub weight: u64,
+    pub threshold: u64,
+    pub votes: Vec<VoteStatus>,
+}
+
+impl Evaluation {
+    /// ### Result
+    ///
+    /// Converts the evaluation into a result, failing with `Unauthorized` if it was denied.
+    pub fn result(&self) -> Result<(), Secp256k1SchnorrError> {
+        match self.approved {
+            true => Ok(()),
+            false => Err(Secp256k1SchnorrError::Unauthorized),
+        }
+    }
+}
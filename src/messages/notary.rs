@@ -0,0 +1,110 @@
+use solana_secp256k1::CompressedPoint;
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify,
+    errors::Secp256k1SchnorrError,
+    messages::{key_id, Domain, Envelope, Reader},
+    Secp256k1SchnorrSignature,
+};
+
+/// ### Receipt
+///
+/// A notary's signed receipt anchoring a document, identified by its hash, at `timestamp`. Each
+/// notary numbers its receipts with a strictly increasing `sequence`, starting from 1, allowing
+/// programs settling receipts on-chain to detect replayed or reordered receipts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Receipt {
+    pub document_hash: [u8; 32],
+    pub timestamp: i64,
+    pub notary_key_id: [u8; 32],
+    pub sequence: u64,
+}
+
+impl Envelope for Receipt {
+    const TAG: &'static [u8] = b"solana-secp256k1-schnorr/notary";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.document_hash);
+        out.extend_from_slice(&self.timestamp.to_le_bytes());
+        out.extend_from_slice(&self.notary_key_id);
+        out.extend_from_slice(&self.sequence.to_le_bytes());
+    }
+}
+
+impl Receipt {
+    /// ### From Bytes
+    ///
+    /// Decodes a receipt from its canonical encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Secp256k1SchnorrError> {
+        let mut reader = Reader::new(bytes);
+        let receipt = Self {
+            document_hash: reader.read_array()?,
+            timestamp: reader.read_i64()?,
+            notary_key_id: reader.read_array()?,
+            sequence: reader.read_u64()?,
+        };
+        reader.finish()?;
+        Ok(receipt)
+    }
+}
+
+/// ### NotaryState
+///
+/// The sequence number and timestamp of the last receipt accepted from a notary, as stored in
+/// account data. A fresh notary starts from the default state.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NotaryState {
+    pub sequence: u64,
+    pub timestamp: i64,
+}
+
+impl NotaryState {
+    /// ### Check
+    ///
+    /// Receipts must advance the notary's sequence, failing with `NonceMismatch` otherwise, and
+    /// may not be timestamped before the last accepted receipt, failing with `InvalidMessage`.
+    pub fn check(&self, receipt: &Receipt) -> Result<(), Secp256k1SchnorrError> {
+        if receipt.sequence <= self.sequence {
+            return Err(Secp256k1SchnorrError::NonceMismatch);
+        }
+        if receipt.timestamp < self.timestamp {
+            return Err(Secp256k1SchnorrError::InvalidMessage);
+        }
+        Ok(())
+    }
+
+    /// ### Advance
+    ///
+    /// Checks `receipt` and records it as the last accepted receipt.
+    pub fn advance(&mut self, receipt: &Receipt) -> Result<(), Secp256k1SchnorrError> {
+        self.check(receipt)?;
+        self.sequence = receipt.sequence;
+        self.timestamp = receipt.timestamp;
+        Ok(())
+    }
+}
+
+/// ### Verify Receipt
+///
+/// Program-side verification of a notary receipt. Succeeds only if the receipt was issued by
+/// `notary`, is not timestamped after `now`, advances the notary's `state` and is signed by the
+/// notary for `domain`.
+///
+/// On success, the caller must `advance` the stored state with the receipt.
+pub fn verify_receipt<C: Secp256k1SchnorrVerify>(
+    receipt: &Receipt,
+    domain: &Domain,
+    signature: &Secp256k1SchnorrSignature,
+    notary: &CompressedPoint,
+    state: &NotaryState,
+    now: i64,
+) -> Result<(), Secp256k1SchnorrError> {
+    if receipt.notary_key_id.ne(&key_id(notary)) {
+        return Err(Secp256k1SchnorrError::Unauthorized);
+    }
+    if receipt.timestamp > now {
+        return Err(Secp256k1SchnorrError::NotYetValid);
+    }
+    state.check(receipt)?;
+    receipt.verify::<C, CompressedPoint>(domain, signature, notary)
+}
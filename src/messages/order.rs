@@ -0,0 +1,147 @@
+use solana_secp256k1::CompressedPoint;
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify,
+    errors::Secp256k1SchnorrError,
+    messages::{Domain, Envelope, Reader},
+    Secp256k1SchnorrSignature,
+};
+
+/// ### Side
+///
+/// The side of the book an order rests on.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Bid = 0,
+    Ask = 1,
+}
+
+impl TryFrom<u8> for Side {
+    type Error = Secp256k1SchnorrError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Bid),
+            1 => Ok(Self::Ask),
+            _ => Err(Secp256k1SchnorrError::InvalidMessage),
+        }
+    }
+}
+
+/// ### Order
+///
+/// A signed off-chain limit order for DEX settlement, offering to trade `size` base units on
+/// `market` at `price` quote units per base unit until `expiry`. The `salt` distinguishes otherwise
+/// identical orders from the same maker.
+///
+/// Orders are canonically encoded with a single byte for `side` and little-endian integers, and
+/// decoding rejects any other encoding, so each order has exactly one digest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Order {
+    pub maker: CompressedPoint,
+    pub market: [u8; 32],
+    pub side: Side,
+    pub price: u64,
+    pub size: u64,
+    pub expiry: i64,
+    pub salt: u64,
+}
+
+impl Envelope for Order {
+    const TAG: &'static [u8] = b"solana-secp256k1-schnorr/order";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.maker.0);
+        out.extend_from_slice(&self.market);
+        out.push(self.side as u8);
+        out.extend_from_slice(&self.price.to_le_bytes());
+        out.extend_from_slice(&self.size.to_le_bytes());
+        out.extend_from_slice(&self.expiry.to_le_bytes());
+        out.extend_from_slice(&self.salt.to_le_bytes());
+    }
+}
+
+impl Order {
+    /// ### Validate
+    ///
+    /// Orders must have a non-zero price and size.
+    pub fn validate(&self) -> Result<(), Secp256k1SchnorrError> {
+        if self.price == 0 || self.size == 0 {
+            return Err(Secp256k1SchnorrError::InvalidMessage);
+        }
+        Ok(())
+    }
+
+    /// ### From Bytes
+    ///
+    /// Decodes an order from its canonical encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Secp256k1SchnorrError> {
+        let mut reader = Reader::new(bytes);
+        let order = Self {
+            maker: reader.read_point()?,
+            market: reader.read_array()?,
+            side: Side::try_from(reader.read_u8()?)?,
+            price: reader.read_u64()?,
+            size: reader.read_u64()?,
+            expiry: reader.read_i64()?,
+            salt: reader.read_u64()?,
+        };
+        reader.finish()?;
+        order.validate()?;
+        Ok(order)
+    }
+}
+
+/// ### SignedOrder
+///
+/// An order together with its maker's signature.
+pub struct SignedOrder {
+    pub order: Order,
+    pub signature: Secp256k1SchnorrSignature,
+}
+
+impl SignedOrder {
+    /// ### Verify
+    ///
+    /// Verifies that the order is valid, has not expired at time `now`, and is signed by its maker
+    /// for `domain`.
+    pub fn verify<C: Secp256k1SchnorrVerify>(
+        &self,
+        domain: &Domain,
+        now: i64,
+    ) -> Result<(), Secp256k1SchnorrError> {
+        self.order.validate()?;
+        if now > self.order.expiry {
+            return Err(Secp256k1SchnorrError::Expired);
+        }
+        self.order
+            .verify::<C, CompressedPoint>(domain, &self.signature, &self.order.maker)
+    }
+}
+
+/// ### Verify Matches
+///
+/// Program-side verification of a batch of matched `(bid, ask)` order pairs at time `now`. Each
+/// pair must be a valid, unexpired bid and ask on the same market whose prices cross, each signed
+/// by its maker for `domain`. Fails on the first invalid pair.
+///
+/// The caller remains responsible for tracking the filled size of each order across settlements.
+pub fn verify_matches<C: Secp256k1SchnorrVerify>(
+    domain: &Domain,
+    matches: &[(SignedOrder, SignedOrder)],
+    now: i64,
+) -> Result<(), Secp256k1SchnorrError> {
+    for (bid, ask) in matches {
+        if bid.order.side != Side::Bid
+            || ask.order.side != Side::Ask
+            || bid.order.market.ne(&ask.order.market)
+            || bid.order.price < ask.order.price
+        {
+            return Err(Secp256k1SchnorrError::InvalidMessage);
+        }
+        bid.verify::<C>(domain, now)?;
+        ask.verify::<C>(domain, now)?;
+    }
+    Ok(())
+}
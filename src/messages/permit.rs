@@ -0,0 +1,79 @@
+use solana_secp256k1::CompressedPoint;
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify,
+    errors::Secp256k1SchnorrError,
+    messages::{Domain, Envelope, Reader},
+    Secp256k1SchnorrSignature,
+};
+
+/// ### Permit
+///
+/// An EIP-2612-style signed token approval. The owner's key authorizes `spender` to transfer up to
+/// `amount` of `mint` on their behalf, allowing token programs to accept gasless approvals.
+///
+/// `nonce` must match the owner's current permit nonce, which the token program increments on
+/// every accepted permit, and the permit may not be used after `deadline`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Permit {
+    pub owner: CompressedPoint,
+    pub spender: [u8; 32],
+    pub mint: [u8; 32],
+    pub amount: u64,
+    pub nonce: u64,
+    pub deadline: i64,
+}
+
+impl Envelope for Permit {
+    const TAG: &'static [u8] = b"solana-secp256k1-schnorr/permit";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.owner.0);
+        out.extend_from_slice(&self.spender);
+        out.extend_from_slice(&self.mint);
+        out.extend_from_slice(&self.amount.to_le_bytes());
+        out.extend_from_slice(&self.nonce.to_le_bytes());
+        out.extend_from_slice(&self.deadline.to_le_bytes());
+    }
+}
+
+impl Permit {
+    /// ### From Bytes
+    ///
+    /// Decodes a permit from its canonical encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Secp256k1SchnorrError> {
+        let mut reader = Reader::new(bytes);
+        let permit = Self {
+            owner: reader.read_point()?,
+            spender: reader.read_array()?,
+            mint: reader.read_array()?,
+            amount: reader.read_u64()?,
+            nonce: reader.read_u64()?,
+            deadline: reader.read_i64()?,
+        };
+        reader.finish()?;
+        Ok(permit)
+    }
+}
+
+/// ### Verify Permit
+///
+/// Program-side verification of a permit. Succeeds only if the permit carries the owner's current
+/// `nonce`, has not passed its deadline at time `now`, and is signed by its owner for `domain`.
+///
+/// On success, the caller must record the approval and increment the owner's nonce.
+pub fn verify_permit<C: Secp256k1SchnorrVerify>(
+    permit: &Permit,
+    domain: &Domain,
+    signature: &Secp256k1SchnorrSignature,
+    nonce: u64,
+    now: i64,
+) -> Result<(), Secp256k1SchnorrError> {
+    if permit.nonce != nonce {
+        return Err(Secp256k1SchnorrError::NonceMismatch);
+    }
+    if now > permit.deadline {
+        return Err(Secp256k1SchnorrError::Expired);
+    }
+    permit.verify::<C, CompressedPoint>(domain, signature, &permit.owner)
+}
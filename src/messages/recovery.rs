@@ -0,0 +1,202 @@
+use solana_secp256k1::CompressedPoint;
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify,
+    errors::Secp256k1SchnorrError,
+    messages::{Domain, Envelope, Reader},
+    Secp256k1SchnorrSignature,
+};
+
+/// Maximum number of guardians a wallet may appoint
+pub const MAX_GUARDIANS: usize = 16;
+
+/// ### Guardians
+///
+/// The social recovery configuration of a wallet, as stored in account data. Any `threshold` of
+/// the guardian `keys` may jointly rotate the wallet to a new key, subject to a timelock of at
+/// least `min_delay` seconds during which the current key may cancel the recovery.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guardians {
+    pub keys: Vec<CompressedPoint>,
+    pub threshold: u8,
+    pub min_delay: i64,
+}
+
+impl Guardians {
+    /// ### Validate
+    ///
+    /// Guardian sets must hold at most `MAX_GUARDIANS` distinct keys, with a threshold of at least
+    /// one that the guardians can reach, and a non-negative delay.
+    pub fn validate(&self) -> Result<(), Secp256k1SchnorrError> {
+        if self.keys.len() > MAX_GUARDIANS
+            || self
+                .keys
+                .iter()
+                .enumerate()
+                .any(|(i, key)| self.keys[..i].contains(key))
+            || self.threshold == 0
+            || self.threshold as usize > self.keys.len()
+            || self.min_delay < 0
+        {
+            return Err(Secp256k1SchnorrError::InvalidMessage);
+        }
+        Ok(())
+    }
+
+    /// ### To Bytes
+    ///
+    /// Returns the canonical encoding of the guardian set, for account storage.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(10 + self.keys.len() * 33);
+        out.push(self.keys.len() as u8);
+        for key in &self.keys {
+            out.extend_from_slice(&key.0);
+        }
+        out.push(self.threshold);
+        out.extend_from_slice(&self.min_delay.to_le_bytes());
+        out
+    }
+
+    /// ### From Bytes
+    ///
+    /// Decodes a guardian set from its canonical encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Secp256k1SchnorrError> {
+        let mut reader = Reader::new(bytes);
+        let count = reader.read_u8()? as usize;
+        let keys = (0..count)
+            .map(|_| reader.read_point())
+            .collect::<Result<Vec<_>, _>>()?;
+        let guardians = Self {
+            keys,
+            threshold: reader.read_u8()?,
+            min_delay: reader.read_i64()?,
+        };
+        reader.finish()?;
+        guardians.validate()?;
+        Ok(guardians)
+    }
+
+    /// ### Verify Recovery
+    ///
+    /// Program-side verification of a recovery of `wallet` from `current_key`. Succeeds only if the
+    /// guardian set is valid, the statement is for `wallet` and rotates `current_key`, carries the
+    /// wallet's current recovery `nonce`, imposes at least the minimum delay, and is approved by at
+    /// least `threshold` distinct guardians, each identified by their index in `keys` and signing
+    /// the statement for `domain`.
+    ///
+    /// On success, the caller must increment the nonce and record a `PendingRecovery`.
+    pub fn verify_recovery<C: Secp256k1SchnorrVerify>(
+        &self,
+        domain: &Domain,
+        statement: &RecoveryStatement,
+        approvals: &[(u8, Secp256k1SchnorrSignature)],
+        wallet: &[u8; 32],
+        current_key: &CompressedPoint,
+        nonce: u64,
+    ) -> Result<(), Secp256k1SchnorrError> {
+        // Duplicate keys would let a single guardian count more than once towards the threshold
+        self.validate()?;
+        if statement.wallet.ne(wallet) {
+            return Err(Secp256k1SchnorrError::InvalidMessage);
+        }
+        if statement.old_key.ne(current_key) || statement.new_key.eq(current_key) {
+            return Err(Secp256k1SchnorrError::Unauthorized);
+        }
+        if statement.nonce != nonce {
+            return Err(Secp256k1SchnorrError::NonceMismatch);
+        }
+        if statement.delay < self.min_delay {
+            return Err(Secp256k1SchnorrError::LimitExceeded);
+        }
+        let digest = statement.digest(domain);
+        for (i, (guardian, signature)) in approvals.iter().enumerate() {
+            if approvals[..i].iter().any(|(g, _)| g == guardian) {
+                return Err(Secp256k1SchnorrError::InvalidMessage);
+            }
+            let key = self
+                .keys
+                .get(*guardian as usize)
+                .ok_or(Secp256k1SchnorrError::Unauthorized)?;
+            signature.verify::<C, CompressedPoint>(&digest, key)?;
+        }
+        if approvals.len() < self.threshold as usize {
+            return Err(Secp256k1SchnorrError::Unauthorized);
+        }
+        Ok(())
+    }
+}
+
+/// ### RecoveryStatement
+///
+/// The message each guardian signs to approve rotating `wallet` from `old_key` to `new_key` once
+/// `delay` seconds have passed since the recovery was initiated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecoveryStatement {
+    pub wallet: [u8; 32],
+    pub old_key: CompressedPoint,
+    pub new_key: CompressedPoint,
+    pub delay: i64,
+    pub nonce: u64,
+}
+
+impl Envelope for RecoveryStatement {
+    const TAG: &'static [u8] = b"solana-secp256k1-schnorr/recovery";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.wallet);
+        out.extend_from_slice(&self.old_key.0);
+        out.extend_from_slice(&self.new_key.0);
+        out.extend_from_slice(&self.delay.to_le_bytes());
+        out.extend_from_slice(&self.nonce.to_le_bytes());
+    }
+}
+
+impl RecoveryStatement {
+    /// ### From Bytes
+    ///
+    /// Decodes a recovery statement from its canonical encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Secp256k1SchnorrError> {
+        let mut reader = Reader::new(bytes);
+        let statement = Self {
+            wallet: reader.read_array()?,
+            old_key: reader.read_point()?,
+            new_key: reader.read_point()?,
+            delay: reader.read_i64()?,
+            nonce: reader.read_u64()?,
+        };
+        reader.finish()?;
+        Ok(statement)
+    }
+}
+
+/// ### PendingRecovery
+///
+/// A verified recovery awaiting its timelock, as stored in account data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingRecovery {
+    pub new_key: CompressedPoint,
+    pub executable_at: i64,
+}
+
+impl PendingRecovery {
+    /// Initiates a verified recovery at time `now`.
+    pub fn new(statement: &RecoveryStatement, now: i64) -> Result<Self, Secp256k1SchnorrError> {
+        Ok(Self {
+            new_key: statement.new_key,
+            executable_at: now
+                .checked_add(statement.delay)
+                .ok_or(Secp256k1SchnorrError::ArithmeticOverflow)?,
+        })
+    }
+
+    /// ### Execute
+    ///
+    /// Returns the wallet's new key once the timelock has passed at time `now`, failing with
+    /// `NotYetValid` before then.
+    pub fn execute(&self, now: i64) -> Result<CompressedPoint, Secp256k1SchnorrError> {
+        if now < self.executable_at {
+            return Err(Secp256k1SchnorrError::NotYetValid);
+        }
+        Ok(self.new_key)
+    }
+}
@@ -0,0 +1,55 @@
+use crate::errors::Secp256k1SchnorrError;
+
+/// ### NonceBitmap
+///
+/// A replay-protection bitmap over a byte buffer, typically a region of account data, in which
+/// bit `n` records whether nonce `n` has been used. Unlike a sequential nonce, nonces may be
+/// consumed in any order, allowing several signed messages to be outstanding at once and any of
+/// them to be revoked by consuming its nonce.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonceBitmap<B>(pub B);
+
+impl<B: AsRef<[u8]>> NonceBitmap<B> {
+    /// The number of nonces the bitmap can track
+    pub fn capacity(&self) -> u64 {
+        self.0.as_ref().len() as u64 * 8
+    }
+
+    fn position(&self, nonce: u64) -> Result<(usize, u8), Secp256k1SchnorrError> {
+        if nonce >= self.capacity() {
+            return Err(Secp256k1SchnorrError::LimitExceeded);
+        }
+        Ok(((nonce / 8) as usize, 1 << (nonce % 8)))
+    }
+
+    /// ### Is Used
+    ///
+    /// Returns whether `nonce` has been consumed. Fails with `LimitExceeded` if `nonce` is beyond
+    /// the capacity of the bitmap.
+    pub fn is_used(&self, nonce: u64) -> Result<bool, Secp256k1SchnorrError> {
+        let (byte, mask) = self.position(nonce)?;
+        Ok(self.0.as_ref()[byte] & mask != 0)
+    }
+
+    /// ### Check
+    ///
+    /// Fails with `NonceMismatch` if `nonce` has already been consumed.
+    pub fn check(&self, nonce: u64) -> Result<(), Secp256k1SchnorrError> {
+        match self.is_used(nonce)? {
+            true => Err(Secp256k1SchnorrError::NonceMismatch),
+            false => Ok(()),
+        }
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> NonceBitmap<B> {
+    /// ### Consume
+    ///
+    /// Marks `nonce` as used. Fails with `NonceMismatch` if it has already been consumed.
+    pub fn consume(&mut self, nonce: u64) -> Result<(), Secp256k1SchnorrError> {
+        self.check(nonce)?;
+        let (byte, mask) = self.position(nonce)?;
+        self.0.as_mut()[byte] |= mask;
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use solana_secp256k1::CompressedPoint;
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify,
+    errors::Secp256k1SchnorrError,
+    messages::{
+        delegation::{verify_delegated_call, Delegation},
+        replay::NonceBitmap,
+        Domain,
+    },
+    Secp256k1SchnorrSignature,
+};
+
+/// ### Session Certificate
+///
+/// Builds the certificate in which a `master` key authorizes a short-lived `session` key to sign
+/// instructions on its behalf, scoped to a set of instruction discriminators and valid up to and
+/// including `expiry_slot`. A session certificate is a `Delegation` which may not be delegated
+/// any further, verified as a chain of a single hop, and session calls are signed with
+/// `sign_delegated_call`.
+///
+/// Discriminators are matched as prefixes of the instruction data, accommodating both 8-byte
+/// Anchor discriminators and single-byte native ones.
+pub fn session_certificate(
+    master: CompressedPoint,
+    session: CompressedPoint,
+    discriminators: Vec<Vec<u8>>,
+    expiry_slot: u64,
+) -> Delegation {
+    Delegation {
+        delegator: master,
+        delegate: session,
+        scope: discriminators,
+        expiry_slot,
+        max_subdelegations: 0,
+    }
+}
+
+/// ### Verify Session
+///
+/// Program-side verification of a session-signed instruction in a single call, against the
+/// `expected_master` key stored by the program. Succeeds only if:
+///
+/// - the certificate is well-formed and has not expired at `slot`
+/// - the certificate authorizes the instruction's discriminator
+/// - the certificate is issued and signed by `expected_master` for `domain`
+/// - the instruction data and `nonce` are signed by the certificate's session key
+/// - `nonce` has not been consumed in `nonces`, the replay bitmap of the master key
+///
+/// On success, `nonce` is consumed, so the same call cannot be submitted twice.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_session<C: Secp256k1SchnorrVerify, B: AsRef<[u8]> + AsMut<[u8]>>(
+    expected_master: &CompressedPoint,
+    certificate: &(Delegation, Secp256k1SchnorrSignature),
+    domain: &Domain,
+    instruction_data: &[u8],
+    session_signature: &Secp256k1SchnorrSignature,
+    nonce: u64,
+    nonces: &mut NonceBitmap<B>,
+    slot: u64,
+) -> Result<(), Secp256k1SchnorrError> {
+    verify_delegated_call::<C, B>(
+        expected_master,
+        core::slice::from_ref(certificate),
+        domain,
+        instruction_data,
+        session_signature,
+        nonce,
+        nonces,
+        slot,
+        1,
+    )
+    .map(|_| ())
+}
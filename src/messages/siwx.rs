@@ -0,0 +1,278 @@
+use core::fmt::{Display, Formatter};
+
+use solana_secp256k1::CompressedPoint;
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify,
+    errors::Secp256k1SchnorrError,
+    messages::{hex_decode, hex_encode_into, tagged_hash},
+    Secp256k1SchnorrSignature,
+};
+
+/// Tag under which the canonical login message is hashed before signing
+pub const SIWX_TAG: &[u8] = b"solana-secp256k1-schnorr/siwx";
+
+/// Current version of the login message format
+pub const SIWX_VERSION: u8 = 1;
+
+const HEADER: &str = " wants you to sign in with your secp256k1 key:";
+
+/// ### LoginMessage
+///
+/// A Sign-In-With-X login message, modelled after EIP-4361, binding a secp256k1 key to a login
+/// request from a specific domain for a limited period of time.
+///
+/// Login messages have exactly one canonical text serialization:
+///
+/// ```text
+/// example.com wants you to sign in with your secp256k1 key:
+/// 0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798
+///
+/// Sign in to Example
+///
+/// Version: 1
+/// Nonce: 000102030405060708090a0b0c0d0e0f
+/// Issued At: 1700000000
+/// Expiration Time: 1700000600
+/// ```
+///
+/// The statement and its following blank line are omitted when there is no statement. Timestamps
+/// are Unix timestamps in seconds, directly comparable against the Solana `Clock` sysvar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoginMessage {
+    pub domain: String,
+    pub signer: CompressedPoint,
+    pub statement: Option<String>,
+    pub nonce: [u8; 16],
+    pub issued_at: i64,
+    pub expiration_time: i64,
+}
+
+impl LoginMessage {
+    /// ### Builder
+    ///
+    /// Starts building a login message for `signer` on `domain`.
+    pub fn builder(domain: &str, signer: CompressedPoint) -> LoginMessageBuilder {
+        LoginMessageBuilder {
+            message: LoginMessage {
+                domain: domain.to_string(),
+                signer,
+                statement: None,
+                nonce: [0u8; 16],
+                issued_at: 0,
+                expiration_time: 0,
+            },
+        }
+    }
+
+    /// ### Validate
+    ///
+    /// Checks that every field can be serialized unambiguously and that the validity window is
+    /// well-formed.
+    pub fn validate(&self) -> Result<(), Secp256k1SchnorrError> {
+        let domain_valid = !self.domain.is_empty()
+            && !self
+                .domain
+                .chars()
+                .any(|c| c.is_whitespace() || c.is_control());
+        let statement_valid = self.statement.as_ref().is_none_or(|s| {
+            !s.is_empty() && !s.contains(['\n', '\r']) && !s.starts_with("Version: ")
+        });
+        if !domain_valid || !statement_valid || self.issued_at >= self.expiration_time {
+            return Err(Secp256k1SchnorrError::InvalidMessage);
+        }
+        Ok(())
+    }
+
+    /// ### Parse
+    ///
+    /// Parses a login message from its canonical text serialization, rejecting any text that is
+    /// not byte-for-byte canonical.
+    pub fn parse(text: &str) -> Result<Self, Secp256k1SchnorrError> {
+        let err = Secp256k1SchnorrError::InvalidMessage;
+        let mut lines = text.split('\n');
+
+        let domain = lines
+            .next()
+            .and_then(|line| line.strip_suffix(HEADER))
+            .ok_or(err.clone())?;
+        let signer = lines
+            .next()
+            .and_then(hex_decode::<33>)
+            .map(CompressedPoint)
+            .ok_or(err.clone())?;
+        if lines.next() != Some("") {
+            return Err(err);
+        }
+
+        let mut line = lines.next().ok_or(err.clone())?;
+        let statement = match line.starts_with("Version: ") {
+            true => None,
+            false => {
+                let statement = line.to_string();
+                if lines.next() != Some("") {
+                    return Err(err);
+                }
+                line = lines.next().ok_or(err.clone())?;
+                Some(statement)
+            }
+        };
+
+        if line.strip_prefix("Version: ") != Some(SIWX_VERSION.to_string().as_str()) {
+            return Err(err);
+        }
+        let nonce = lines
+            .next()
+            .and_then(|line| line.strip_prefix("Nonce: "))
+            .and_then(hex_decode::<16>)
+            .ok_or(err.clone())?;
+        let issued_at = lines
+            .next()
+            .and_then(|line| line.strip_prefix("Issued At: "))
+            .and_then(|t| t.parse::<i64>().ok())
+            .ok_or(err.clone())?;
+        let expiration_time = lines
+            .next()
+            .and_then(|line| line.strip_prefix("Expiration Time: "))
+            .and_then(|t| t.parse::<i64>().ok())
+            .ok_or(err.clone())?;
+        if lines.next().is_some() {
+            return Err(err);
+        }
+
+        let message = LoginMessage {
+            domain: domain.to_string(),
+            signer,
+            statement,
+            nonce,
+            issued_at,
+            expiration_time,
+        };
+        message.validate()?;
+
+        // Reject non-canonical encodings of the same fields, such as "+1" or "01" timestamps
+        if message.to_string() != text {
+            return Err(err);
+        }
+        Ok(message)
+    }
+
+    /// ### Digest
+    ///
+    /// The tagged hash of the canonical text serialization. This is the message that is signed.
+    pub fn digest(&self) -> [u8; 32] {
+        tagged_hash(SIWX_TAG, &[self.to_string().as_bytes()])
+    }
+
+    /// ### Verify
+    ///
+    /// Verifies a signature over this login message by its signer, without checking the domain,
+    /// nonce, or validity window. Most callers should use `verify_login` instead.
+    pub fn verify<C: Secp256k1SchnorrVerify>(
+        &self,
+        signature: &Secp256k1SchnorrSignature,
+    ) -> Result<(), Secp256k1SchnorrError> {
+        self.validate()?;
+        signature.verify::<C, CompressedPoint>(&self.digest(), &self.signer)
+    }
+
+    /// ### Sign
+    ///
+    /// Signs the login message. The private key must correspond to `signer`.
+    #[cfg(feature = "sign")]
+    pub fn sign<C: crate::challenges::Secp256k1SchnorrSign>(
+        &self,
+        privkey: &[u8; 32],
+    ) -> Result<Secp256k1SchnorrSignature, Secp256k1SchnorrError> {
+        self.validate()?;
+        Secp256k1SchnorrSignature::sign::<C>(&self.digest(), privkey)
+    }
+}
+
+impl Display for LoginMessage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut signer = String::with_capacity(66);
+        hex_encode_into(&self.signer.0, &mut signer);
+        let mut nonce = String::with_capacity(32);
+        hex_encode_into(&self.nonce, &mut nonce);
+
+        write!(f, "{}{}\n{}\n\n", self.domain, HEADER, signer)?;
+        if let Some(statement) = &self.statement {
+            write!(f, "{}\n\n", statement)?;
+        }
+        write!(
+            f,
+            "Version: {}\nNonce: {}\nIssued At: {}\nExpiration Time: {}",
+            SIWX_VERSION, nonce, self.issued_at, self.expiration_time
+        )
+    }
+}
+
+/// ### LoginMessageBuilder
+///
+/// Client-side builder for `LoginMessage`, validating the message once all fields are set.
+#[derive(Debug, Clone)]
+pub struct LoginMessageBuilder {
+    message: LoginMessage,
+}
+
+impl LoginMessageBuilder {
+    pub fn statement(mut self, statement: &str) -> Self {
+        self.message.statement = Some(statement.to_string());
+        self
+    }
+
+    pub fn nonce(mut self, nonce: [u8; 16]) -> Self {
+        self.message.nonce = nonce;
+        self
+    }
+
+    pub fn issued_at(mut self, issued_at: i64) -> Self {
+        self.message.issued_at = issued_at;
+        self
+    }
+
+    pub fn expiration_time(mut self, expiration_time: i64) -> Self {
+        self.message.expiration_time = expiration_time;
+        self
+    }
+
+    pub fn build(self) -> Result<LoginMessage, Secp256k1SchnorrError> {
+        self.message.validate()?;
+        Ok(self.message)
+    }
+}
+
+/// ### Verify Login
+///
+/// Program-side verification of a login. Succeeds only if the message is well-formed, was issued
+/// for `domain` with the expected `nonce`, is valid at time `now`, and is signed by its signer.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::{messages::siwx::{verify_login, LoginMessage}, BIP340Challenge};
+///
+/// let message = LoginMessage::parse(text)?;
+/// verify_login::<BIP340Challenge>(&message, &signature, "example.com", &nonce, clock.unix_timestamp)?;
+/// ```
+pub fn verify_login<C: Secp256k1SchnorrVerify>(
+    message: &LoginMessage,
+    signature: &Secp256k1SchnorrSignature,
+    domain: &str,
+    nonce: &[u8; 16],
+    now: i64,
+) -> Result<(), Secp256k1SchnorrError> {
+    if message.domain != domain {
+        return Err(Secp256k1SchnorrError::DomainMismatch);
+    }
+    if message.nonce.ne(nonce) {
+        return Err(Secp256k1SchnorrError::NonceMismatch);
+    }
+    if now < message.issued_at {
+        return Err(Secp256k1SchnorrError::NotYetValid);
+    }
+    if now >= message.expiration_time {
+        return Err(Secp256k1SchnorrError::Expired);
+    }
+    message.verify::<C>(signature)
+}
@@ -0,0 +1,122 @@
+use solana_secp256k1::CompressedPoint;
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify,
+    errors::Secp256k1SchnorrError,
+    messages::{replay::NonceBitmap, Domain, Envelope, Reader},
+    Secp256k1SchnorrSignature,
+};
+
+/// ### Mandate
+///
+/// A signed recurring payment authorization. The subscriber's key allows `merchant` to pull up to
+/// `amount_ceiling` of `mint` in each `period` seconds, counted from `valid_from`, until
+/// `valid_until`.
+///
+/// `nonce` is the mandate's slot in the subscriber's `NonceBitmap`. The subscriber cancels the
+/// mandate by consuming its nonce, after which no further pulls are accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mandate {
+    pub subscriber: CompressedPoint,
+    pub merchant: [u8; 32],
+    pub mint: [u8; 32],
+    pub amount_ceiling: u64,
+    pub period: i64,
+    pub valid_from: i64,
+    pub valid_until: i64,
+    pub nonce: u64,
+}
+
+impl Envelope for Mandate {
+    const TAG: &'static [u8] = b"solana-secp256k1-schnorr/subscription";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.subscriber.0);
+        out.extend_from_slice(&self.merchant);
+        out.extend_from_slice(&self.mint);
+        out.extend_from_slice(&self.amount_ceiling.to_le_bytes());
+        out.extend_from_slice(&self.period.to_le_bytes());
+        out.extend_from_slice(&self.valid_from.to_le_bytes());
+        out.extend_from_slice(&self.valid_until.to_le_bytes());
+        out.extend_from_slice(&self.nonce.to_le_bytes());
+    }
+}
+
+impl Mandate {
+    /// ### From Bytes
+    ///
+    /// Decodes a mandate from its canonical encoding. Fails with `InvalidMessage` if the period is
+    /// not positive.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Secp256k1SchnorrError> {
+        let mut reader = Reader::new(bytes);
+        let mandate = Self {
+            subscriber: reader.read_point()?,
+            merchant: reader.read_array()?,
+            mint: reader.read_array()?,
+            amount_ceiling: reader.read_u64()?,
+            period: reader.read_i64()?,
+            valid_from: reader.read_i64()?,
+            valid_until: reader.read_i64()?,
+            nonce: reader.read_u64()?,
+        };
+        reader.finish()?;
+        if mandate.period <= 0 {
+            return Err(Secp256k1SchnorrError::InvalidMessage);
+        }
+        Ok(mandate)
+    }
+
+    /// ### Period Index
+    ///
+    /// Returns the index of the period containing time `now`. Fails with `NotYetValid` if `now` is
+    /// before the mandate becomes valid, and with `InvalidMessage` if the period is not positive or
+    /// the elapsed time overflows.
+    pub fn period_index(&self, now: i64) -> Result<u64, Secp256k1SchnorrError> {
+        if now < self.valid_from {
+            return Err(Secp256k1SchnorrError::NotYetValid);
+        }
+        if self.period <= 0 {
+            return Err(Secp256k1SchnorrError::InvalidMessage);
+        }
+        now.checked_sub(self.valid_from)
+            .and_then(|elapsed| elapsed.checked_div(self.period))
+            .map(|index| index as u64)
+            .ok_or(Secp256k1SchnorrError::InvalidMessage)
+    }
+}
+
+/// ### Verify Pull
+///
+/// Program-side verification of a pull of `amount` by `merchant` at time `now`, given that
+/// `pulled` has already been pulled in the current period. Succeeds only if the mandate names
+/// `merchant`, is within its validity window, has not been cancelled in `cancellations`, would not
+/// exceed its ceiling for the period, and is signed by its subscriber for `domain`.
+///
+/// On success, returns the index of the current period. The caller must add `amount` to the
+/// total pulled in that period, resetting the total whenever the index changes.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_pull<C: Secp256k1SchnorrVerify, B: AsRef<[u8]>>(
+    mandate: &Mandate,
+    domain: &Domain,
+    signature: &Secp256k1SchnorrSignature,
+    merchant: &[u8; 32],
+    amount: u64,
+    pulled: u64,
+    now: i64,
+    cancellations: &NonceBitmap<B>,
+) -> Result<u64, Secp256k1SchnorrError> {
+    if mandate.merchant.ne(merchant) {
+        return Err(Secp256k1SchnorrError::Unauthorized);
+    }
+    let period = mandate.period_index(now)?;
+    if now > mandate.valid_until {
+        return Err(Secp256k1SchnorrError::Expired);
+    }
+    cancellations.check(mandate.nonce)?;
+    match pulled.checked_add(amount) {
+        Some(total) if total <= mandate.amount_ceiling => (),
+        _ => return Err(Secp256k1SchnorrError::LimitExceeded),
+    }
+    mandate.verify::<C, CompressedPoint>(domain, signature, &mandate.subscriber)?;
+    Ok(period)
+}
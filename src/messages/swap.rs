@@ -0,0 +1,308 @@
+use solana_secp256k1::{CompressedPoint, Curve};
+
+use crate::{
+    adaptor::Secp256k1SchnorrPresignature,
+    challenges::Secp256k1SchnorrVerify,
+    errors::Secp256k1SchnorrError,
+    messages::{Domain, Envelope, Reader},
+    Secp256k1SchnorrSignature,
+};
+
+/// ### SwapOffer
+///
+/// The terms of an atomic swap between a `maker`, who holds an asset on another chain, and a
+/// `taker`, who holds `amount` of `mint` on Solana. Both legs are locked to the same adaptor point
+/// 𝑇 = 𝑡⋅𝐺, whose secret 𝑡 is known only to the maker:
+///
+/// 1. The maker locks the counter-chain asset, publishes the offer, and sends the taker a
+///    pre-signature over `counter_message`, the counter-chain transaction paying the taker.
+/// 2. The taker verifies it, funds `escrow` with the offer, and sends the maker a pre-signature over
+///    the `EscrowRelease` paying `recipient`.
+/// 3. The maker verifies it and adapts it with 𝑡, submitting the release signature to the escrow
+///    program before `refund_after`.
+/// 4. The taker reads the release signature from the chain, extracts 𝑡, and adapts the maker's
+///    pre-signature to claim the counter-chain asset.
+///
+/// If the maker never claims, the taker may refund the escrow after `refund_after`. The
+/// counter-chain lock must expire well after `refund_after`, so that the taker always has time to
+/// complete step 4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapOffer {
+    pub maker: CompressedPoint,
+    pub taker: CompressedPoint,
+    pub adaptor_point: CompressedPoint,
+    pub escrow: [u8; 32],
+    pub recipient: [u8; 32],
+    pub mint: [u8; 32],
+    pub amount: u64,
+    pub counter_message: [u8; 32],
+    pub refund_after: i64,
+}
+
+impl Envelope for SwapOffer {
+    const TAG: &'static [u8] = b"solana-secp256k1-schnorr/swap-offer";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.maker.0);
+        out.extend_from_slice(&self.taker.0);
+        out.extend_from_slice(&self.adaptor_point.0);
+        out.extend_from_slice(&self.escrow);
+        out.extend_from_slice(&self.recipient);
+        out.extend_from_slice(&self.mint);
+        out.extend_from_slice(&self.amount.to_le_bytes());
+        out.extend_from_slice(&self.counter_message);
+        out.extend_from_slice(&self.refund_after.to_le_bytes());
+    }
+}
+
+impl SwapOffer {
+    /// ### From Bytes
+    ///
+    /// Decodes an offer from its canonical encoding, as stored in the escrow account.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Secp256k1SchnorrError> {
+        let mut reader = Reader::new(bytes);
+        let offer = Self {
+            maker: reader.read_point()?,
+            taker: reader.read_point()?,
+            adaptor_point: reader.read_point()?,
+            escrow: reader.read_array()?,
+            recipient: reader.read_array()?,
+            mint: reader.read_array()?,
+            amount: reader.read_u64()?,
+            counter_message: reader.read_array()?,
+            refund_after: reader.read_i64()?,
+        };
+        reader.finish()?;
+        Ok(offer)
+    }
+}
+
+/// ### EscrowRelease
+///
+/// The message the taker pre-signs to release the escrow to the maker's `recipient`. It commits to
+/// the digest of the offer, as well as repeating the terms the escrow program pays out on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EscrowRelease {
+    pub offer: [u8; 32],
+    pub escrow: [u8; 32],
+    pub recipient: [u8; 32],
+    pub mint: [u8; 32],
+    pub amount: u64,
+}
+
+impl Envelope for EscrowRelease {
+    const TAG: &'static [u8] = b"solana-secp256k1-schnorr/swap-release";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.offer);
+        out.extend_from_slice(&self.escrow);
+        out.extend_from_slice(&self.recipient);
+        out.extend_from_slice(&self.mint);
+        out.extend_from_slice(&self.amount.to_le_bytes());
+    }
+}
+
+impl EscrowRelease {
+    pub fn new(offer: &SwapOffer, domain: &Domain) -> Self {
+        Self {
+            offer: offer.digest(domain),
+            escrow: offer.escrow,
+            recipient: offer.recipient,
+            mint: offer.mint,
+            amount: offer.amount,
+        }
+    }
+}
+
+/// ### Maker
+///
+/// The maker's side of a swap, holding the adaptor secret.
+pub struct Maker {
+    pub offer: SwapOffer,
+    secret: [u8; 32],
+}
+
+impl Maker {
+    /// Fails with `InvalidSecretKey` if `secret` is not the discrete logarithm of the offer's
+    /// adaptor point.
+    pub fn new(offer: SwapOffer, secret: [u8; 32]) -> Result<Self, Secp256k1SchnorrError> {
+        match Curve::mul_g(&secret) {
+            Ok(point) if CompressedPoint::from(point).0 == offer.adaptor_point.0 => {
+                Ok(Self { offer, secret })
+            }
+            _ => Err(Secp256k1SchnorrError::InvalidSecretKey),
+        }
+    }
+
+    /// ### Presign Counter
+    ///
+    /// Step 1: pre-signs the counter-chain transaction paying the taker.
+    #[cfg(feature = "sign")]
+    pub fn presign_counter<C: crate::challenges::Secp256k1SchnorrSign>(
+        &self,
+        privkey: &[u8; 32],
+    ) -> Result<Secp256k1SchnorrPresignature, Secp256k1SchnorrError> {
+        Secp256k1SchnorrPresignature::presign::<C, _>(
+            &self.offer.counter_message,
+            privkey,
+            &self.offer.adaptor_point,
+        )
+    }
+
+    /// ### Complete Release
+    ///
+    /// Step 3: verifies the taker's pre-signature over the release and adapts it into the
+    /// signature to submit to the escrow program.
+    pub fn complete_release<C: Secp256k1SchnorrVerify>(
+        &self,
+        domain: &Domain,
+        presignature: &Secp256k1SchnorrPresignature,
+    ) -> Result<Secp256k1SchnorrSignature, Secp256k1SchnorrError> {
+        let digest = EscrowRelease::new(&self.offer, domain).digest(domain);
+        presignature.verify_adaptor::<C, _, _>(
+            &digest,
+            &self.offer.taker,
+            &self.offer.adaptor_point,
+        )?;
+        let signature = presignature.adapt::<C>(&self.secret);
+        signature.verify::<C, CompressedPoint>(&digest, &self.offer.taker)?;
+        Ok(signature)
+    }
+}
+
+/// ### Taker
+///
+/// The taker's side of a swap.
+pub struct Taker {
+    pub offer: SwapOffer,
+}
+
+impl Taker {
+    pub fn new(offer: SwapOffer) -> Self {
+        Self { offer }
+    }
+
+    /// ### Verify Counter
+    ///
+    /// Step 2: verifies the maker's pre-signature over the counter-chain transaction. The taker
+    /// must not fund the escrow unless this succeeds.
+    pub fn verify_counter<C: Secp256k1SchnorrVerify>(
+        &self,
+        presignature: &Secp256k1SchnorrPresignature,
+    ) -> Result<(), Secp256k1SchnorrError> {
+        presignature.verify_adaptor::<C, _, _>(
+            &self.offer.counter_message,
+            &self.offer.maker,
+            &self.offer.adaptor_point,
+        )
+    }
+
+    /// ### Presign Release
+    ///
+    /// Step 2: pre-signs the release of the escrow to the maker.
+    #[cfg(feature = "sign")]
+    pub fn presign_release<C: crate::challenges::Secp256k1SchnorrSign>(
+        &self,
+        domain: &Domain,
+        privkey: &[u8; 32],
+    ) -> Result<Secp256k1SchnorrPresignature, Secp256k1SchnorrError> {
+        Secp256k1SchnorrPresignature::presign::<C, _>(
+            &EscrowRelease::new(&self.offer, domain).digest(domain),
+            privkey,
+            &self.offer.adaptor_point,
+        )
+    }
+
+    /// ### Complete Counter
+    ///
+    /// Step 4: extracts the adaptor secret from the release `signature` published on-chain, then
+    /// adapts the maker's `counter` pre-signature into a signature over the counter-chain
+    /// transaction.
+    pub fn complete_counter<C: Secp256k1SchnorrVerify>(
+        &self,
+        release: &Secp256k1SchnorrPresignature,
+        signature: &Secp256k1SchnorrSignature,
+        counter: &Secp256k1SchnorrPresignature,
+    ) -> Result<Secp256k1SchnorrSignature, Secp256k1SchnorrError> {
+        let secret = release.extract_secret::<C>(signature)?;
+        match Curve::mul_g(&secret) {
+            Ok(point) if CompressedPoint::from(point).0 == self.offer.adaptor_point.0 => (),
+            _ => return Err(Secp256k1SchnorrError::InvalidSecretKey),
+        }
+        let signature = counter.adapt::<C>(&secret);
+        signature.verify::<C, CompressedPoint>(&self.offer.counter_message, &self.offer.maker)?;
+        Ok(signature)
+    }
+}
+
+/// ### EscrowStatus
+///
+/// The state of the Solana-side escrow, as stored alongside its offer.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscrowStatus {
+    Funded = 0,
+    Released = 1,
+    Refunded = 2,
+}
+
+impl TryFrom<u8> for EscrowStatus {
+    type Error = Secp256k1SchnorrError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Funded),
+            1 => Ok(Self::Released),
+            2 => Ok(Self::Refunded),
+            _ => Err(Secp256k1SchnorrError::InvalidState),
+        }
+    }
+}
+
+impl EscrowStatus {
+    /// ### Release
+    ///
+    /// Program-side validation of a release of a funded escrow at time `now`. Succeeds only if the
+    /// escrow has not yet become refundable and `signature` is the taker's signature over the
+    /// release for `domain`, transitioning the escrow to `Released`.
+    ///
+    /// On success, the caller must pay the escrowed funds to the offer's recipient.
+    pub fn release<C: Secp256k1SchnorrVerify>(
+        &mut self,
+        offer: &SwapOffer,
+        domain: &Domain,
+        signature: &Secp256k1SchnorrSignature,
+        now: i64,
+    ) -> Result<(), Secp256k1SchnorrError> {
+        if *self != Self::Funded {
+            return Err(Secp256k1SchnorrError::InvalidState);
+        }
+        if now >= offer.refund_after {
+            return Err(Secp256k1SchnorrError::Expired);
+        }
+        EscrowRelease::new(offer, domain).verify::<C, CompressedPoint>(
+            domain,
+            signature,
+            &offer.taker,
+        )?;
+        *self = Self::Released;
+        Ok(())
+    }
+
+    /// ### Refund
+    ///
+    /// Program-side validation of a refund of a funded escrow at time `now`, transitioning it to
+    /// `Refunded` once `refund_after` has been reached.
+    ///
+    /// On success, the caller must return the escrowed funds to the taker.
+    pub fn refund(&mut self, offer: &SwapOffer, now: i64) -> Result<(), Secp256k1SchnorrError> {
+        if *self != Self::Funded {
+            return Err(Secp256k1SchnorrError::InvalidState);
+        }
+        if now < offer.refund_after {
+            return Err(Secp256k1SchnorrError::NotYetValid);
+        }
+        *self = Self::Refunded;
+        Ok(())
+    }
+}
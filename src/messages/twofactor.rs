@@ -0,0 +1,93 @@
+use solana_secp256k1::CompressedPoint;
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify,
+    errors::Secp256k1SchnorrError,
+    messages::{encode_bytes, Domain, Envelope, Reader},
+    Secp256k1SchnorrSignature,
+};
+
+/// ### TwoFactor
+///
+/// The keys of a wallet protected by a second factor, as stored in account data: the user's
+/// `primary` key and the `cosigner` key of a service that only co-signs once the user has passed a
+/// second authentication factor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TwoFactor {
+    pub primary: CompressedPoint,
+    pub cosigner: CompressedPoint,
+}
+
+/// ### TwoFactorMessage
+///
+/// The message signed by both keys to authorize `payload`. The primary key and the co-signer each
+/// track their own replay nonce, so that neither party can reuse or invalidate the other's
+/// signatures by advancing a shared counter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TwoFactorMessage<'a> {
+    pub signers: TwoFactor,
+    pub payload: &'a [u8],
+    pub primary_nonce: u64,
+    pub cosigner_nonce: u64,
+}
+
+impl Envelope for TwoFactorMessage<'_> {
+    const TAG: &'static [u8] = b"solana-secp256k1-schnorr/two-factor";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.signers.primary.0);
+        out.extend_from_slice(&self.signers.cosigner.0);
+        encode_bytes(self.payload, out);
+        out.extend_from_slice(&self.primary_nonce.to_le_bytes());
+        out.extend_from_slice(&self.cosigner_nonce.to_le_bytes());
+    }
+}
+
+impl<'a> TwoFactorMessage<'a> {
+    /// ### From Bytes
+    ///
+    /// Decodes a message from its canonical encoding, borrowing the payload.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, Secp256k1SchnorrError> {
+        let mut reader = Reader::new(bytes);
+        let message = Self {
+            signers: TwoFactor {
+                primary: reader.read_point()?,
+                cosigner: reader.read_point()?,
+            },
+            payload: reader.read_bytes()?,
+            primary_nonce: reader.read_u64()?,
+            cosigner_nonce: reader.read_u64()?,
+        };
+        reader.finish()?;
+        Ok(message)
+    }
+}
+
+impl TwoFactor {
+    /// ### Verify
+    ///
+    /// Program-side verification of a two-factor authorization in one call. Succeeds only if
+    /// `message` names this wallet's keys, carries the current `primary_nonce` and
+    /// `cosigner_nonce`, and is signed by both keys for `domain`.
+    ///
+    /// On success, the caller must increment both nonces.
+    pub fn verify<C: Secp256k1SchnorrVerify>(
+        &self,
+        domain: &Domain,
+        message: &TwoFactorMessage,
+        primary_signature: &Secp256k1SchnorrSignature,
+        cosigner_signature: &Secp256k1SchnorrSignature,
+        primary_nonce: u64,
+        cosigner_nonce: u64,
+    ) -> Result<(), Secp256k1SchnorrError> {
+        if message.signers.ne(self) {
+            return Err(Secp256k1SchnorrError::Unauthorized);
+        }
+        if message.primary_nonce != primary_nonce || message.cosigner_nonce != cosigner_nonce {
+            return Err(Secp256k1SchnorrError::NonceMismatch);
+        }
+        let digest = message.digest(domain);
+        primary_signature.verify::<C, CompressedPoint>(&digest, &self.primary)?;
+        cosigner_signature.verify::<C, CompressedPoint>(&digest, &self.cosigner)
+    }
+}
@@ -0,0 +1,227 @@
+use solana_secp256k1::CompressedPoint;
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify,
+    errors::Secp256k1SchnorrError,
+    messages::{encode_bytes, tagged_hash, Domain, Envelope, Reader},
+    Secp256k1SchnorrSignature,
+};
+
+/// Tag under which a single instruction is hashed
+pub const WALLET_INSTRUCTION_TAG: &[u8] = b"solana-secp256k1-schnorr/wallet/instruction";
+
+/// Tag under which the hashes of an instruction set are hashed
+pub const WALLET_INSTRUCTIONS_TAG: &[u8] = b"solana-secp256k1-schnorr/wallet/instructions";
+
+/// Maximum number of instructions a single execution may authorize
+pub const MAX_WALLET_INSTRUCTIONS: usize = 64;
+
+const SIGNER_FLAG: u8 = 1 << 0;
+const WRITABLE_FLAG: u8 = 1 << 1;
+
+/// ### WalletAccountMeta
+///
+/// An account referenced by a wallet instruction, mirroring Solana's `AccountMeta`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalletAccountMeta {
+    pub pubkey: [u8; 32],
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// ### WalletInstruction
+///
+/// A Solana instruction to be executed by a smart wallet, in a framework-agnostic form that can
+/// be built identically by clients and by the wallet program.
+///
+/// Its canonical encoding is `program_id ∥ u32 account count ∥ (pubkey ∥ flags)* ∥ data`, where
+/// `flags` has bit 0 set for signers and bit 1 set for writable accounts, and `data` is prefixed
+/// with its `u32` length.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalletInstruction {
+    pub program_id: [u8; 32],
+    pub accounts: Vec<WalletAccountMeta>,
+    pub data: Vec<u8>,
+}
+
+impl WalletInstruction {
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.program_id);
+        out.extend_from_slice(&(self.accounts.len() as u32).to_le_bytes());
+        for account in &self.accounts {
+            out.extend_from_slice(&account.pubkey);
+            out.push(
+                (account.is_signer as u8 * SIGNER_FLAG)
+                    | (account.is_writable as u8 * WRITABLE_FLAG),
+            );
+        }
+        encode_bytes(&self.data, out);
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, Secp256k1SchnorrError> {
+        let program_id = reader.read_array()?;
+        let count = reader.read_u32()? as usize;
+        let mut accounts = Vec::new();
+        for _ in 0..count {
+            let pubkey = reader.read_array()?;
+            let flags = reader.read_u8()?;
+            if flags & !(SIGNER_FLAG | WRITABLE_FLAG) != 0 {
+                return Err(Secp256k1SchnorrError::InvalidMessage);
+            }
+            accounts.push(WalletAccountMeta {
+                pubkey,
+                is_signer: flags & SIGNER_FLAG != 0,
+                is_writable: flags & WRITABLE_FLAG != 0,
+            });
+        }
+        Ok(Self {
+            program_id,
+            accounts,
+            data: reader.read_bytes()?.to_vec(),
+        })
+    }
+
+    /// ### Hash
+    ///
+    /// The canonical hash of the instruction, committing to its program id, every account with its
+    /// signer and writable flags, and its data.
+    pub fn hash(&self) -> [u8; 32] {
+        let mut encoding = Vec::new();
+        self.encode(&mut encoding);
+        tagged_hash(WALLET_INSTRUCTION_TAG, &[&encoding])
+    }
+}
+
+/// ### Encode Instructions
+///
+/// Encodes an instruction set as a `u32` count followed by each instruction, as passed to the
+/// wallet program in instruction data.
+pub fn encode_instructions(instructions: &[WalletInstruction]) -> Vec<u8> {
+    let mut out = (instructions.len() as u32).to_le_bytes().to_vec();
+    for instruction in instructions {
+        instruction.encode(&mut out);
+    }
+    out
+}
+
+/// ### Decode Instructions
+///
+/// Decodes an instruction set encoded by `encode_instructions`, failing with `LimitExceeded` for
+/// more than `MAX_WALLET_INSTRUCTIONS` instructions.
+pub fn decode_instructions(bytes: &[u8]) -> Result<Vec<WalletInstruction>, Secp256k1SchnorrError> {
+    let mut reader = Reader::new(bytes);
+    let count = reader.read_u32()? as usize;
+    if count > MAX_WALLET_INSTRUCTIONS {
+        return Err(Secp256k1SchnorrError::LimitExceeded);
+    }
+    let mut instructions = Vec::with_capacity(count);
+    for _ in 0..count {
+        instructions.push(WalletInstruction::read(&mut reader)?);
+    }
+    reader.finish()?;
+    Ok(instructions)
+}
+
+/// ### Hash Instructions
+///
+/// The canonical hash of an ordered instruction set, over the hash of each instruction.
+pub fn hash_instructions(instructions: &[WalletInstruction]) -> [u8; 32] {
+    let hashes: Vec<[u8; 32]> = instructions.iter().map(WalletInstruction::hash).collect();
+    let count = (instructions.len() as u32).to_le_bytes();
+    let mut parts: Vec<&[u8]> = vec![&count];
+    parts.extend(hashes.iter().map(|hash| hash.as_slice()));
+    tagged_hash(WALLET_INSTRUCTIONS_TAG, &parts)
+}
+
+/// ### WalletExecution
+///
+/// An owner's authorization for a smart wallet to execute an ordered instruction set, allowing a
+/// secp256k1 Schnorr key rather than an ed25519 transaction signer to control the wallet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalletExecution {
+    pub wallet: [u8; 32],
+    pub instructions_hash: [u8; 32],
+    pub nonce: u64,
+    pub expiry: i64,
+}
+
+impl Envelope for WalletExecution {
+    const TAG: &'static [u8] = b"solana-secp256k1-schnorr/wallet";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.wallet);
+        out.extend_from_slice(&self.instructions_hash);
+        out.extend_from_slice(&self.nonce.to_le_bytes());
+        out.extend_from_slice(&self.expiry.to_le_bytes());
+    }
+}
+
+impl WalletExecution {
+    /// ### New
+    ///
+    /// Constructs an authorization for `wallet` to execute `instructions`.
+    pub fn new(
+        wallet: [u8; 32],
+        instructions: &[WalletInstruction],
+        nonce: u64,
+        expiry: i64,
+    ) -> Self {
+        Self {
+            wallet,
+            instructions_hash: hash_instructions(instructions),
+            nonce,
+            expiry,
+        }
+    }
+
+    /// ### From Bytes
+    ///
+    /// Decodes an execution from its canonical encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Secp256k1SchnorrError> {
+        let mut reader = Reader::new(bytes);
+        let execution = Self {
+            wallet: reader.read_array()?,
+            instructions_hash: reader.read_array()?,
+            nonce: reader.read_u64()?,
+            expiry: reader.read_i64()?,
+        };
+        reader.finish()?;
+        Ok(execution)
+    }
+}
+
+/// ### Verify Execution
+///
+/// Program-side verification of a smart wallet execution. Succeeds only if:
+///
+/// - the execution targets `wallet` and authorizes exactly `instructions`, in order
+/// - it carries the wallet's expected replay `nonce` and has not expired at time `now`
+/// - it is signed by the wallet's `owner` for `domain`
+///
+/// On success, the caller must advance the wallet's nonce before invoking the instructions.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_execution<C: Secp256k1SchnorrVerify>(
+    execution: &WalletExecution,
+    domain: &Domain,
+    signature: &Secp256k1SchnorrSignature,
+    owner: &CompressedPoint,
+    wallet: &[u8; 32],
+    instructions: &[WalletInstruction],
+    nonce: u64,
+    now: i64,
+) -> Result<(), Secp256k1SchnorrError> {
+    if execution.wallet.ne(wallet)
+        || execution
+            .instructions_hash
+            .ne(&hash_instructions(instructions))
+    {
+        return Err(Secp256k1SchnorrError::InvalidMessage);
+    }
+    if execution.nonce != nonce {
+        return Err(Secp256k1SchnorrError::NonceMismatch);
+    }
+    if now > execution.expiry {
+        return Err(Secp256k1SchnorrError::Expired);
+    }
+    execution.verify::<C, CompressedPoint>(domain, signature, owner)
+}
@@ -0,0 +1,325 @@
+use solana_nostd_sha256::hashv;
+use solana_secp256k1::{CompressedPoint, Secp256k1Point, UncompressedPoint};
+
+use crate::{
+    ecmul::{self, accumulate},
+    errors::Secp256k1SchnorrError,
+    keys::parse_sec1,
+    scalar::Scalar,
+};
+
+// sha256(KeyAgg list) tagged hash
+const KEYAGG_LIST: [u8; 32] = [
+    0x48, 0x1c, 0x97, 0x1c, 0x3c, 0x0b, 0x46, 0xd7, 0xf0, 0xb2, 0x75, 0xae, 0x59, 0x8d, 0x4e, 0x2c,
+    0x7e, 0xd7, 0x31, 0x9c, 0x59, 0x4a, 0x5c, 0x6e, 0xc7, 0x9e, 0xa0, 0xd4, 0x99, 0x02, 0x94, 0xf0,
+];
+
+// sha256(KeyAgg coefficient) tagged hash
+const KEYAGG_COEFFICIENT: [u8; 32] = [
+    0xbf, 0xc9, 0x04, 0x03, 0x4d, 0x1c, 0x88, 0xe8, 0xc8, 0x0e, 0x22, 0xe5, 0x3d, 0x24, 0x56, 0x6d,
+    0x64, 0x82, 0x4e, 0xd6, 0x42, 0x72, 0x81, 0xc0, 0x91, 0x00, 0xf9, 0x4d, 0xcd, 0x52, 0xc9, 0x81,
+];
+
+// sha256(MuSig/aux) tagged hash
+#[cfg(feature = "sign")]
+const MUSIG_AUX: [u8; 32] = [
+    0x40, 0x8f, 0x8c, 0x1f, 0x29, 0x24, 0x21, 0xb5, 0x56, 0x9e, 0xbc, 0x6c, 0xb5, 0xf2, 0xe2, 0x0c,
+    0xf1, 0xe3, 0x84, 0x1b, 0x47, 0x43, 0x9f, 0xcc, 0x58, 0x7d, 0x20, 0xe3, 0xc1, 0x7f, 0x08, 0x37,
+];
+
+// sha256(MuSig/nonce) tagged hash
+#[cfg(feature = "sign")]
+const MUSIG_NONCE: [u8; 32] = [
+    0xf8, 0xc1, 0x0c, 0xbc, 0x61, 0x4e, 0xd1, 0xa0, 0x84, 0xb4, 0x37, 0x05, 0x2b, 0x5d, 0x2c, 0x4b,
+    0x50, 0x1a, 0x9d, 0xe7, 0xaa, 0xfb, 0xe3, 0x48, 0xac, 0xe8, 0x02, 0x6c, 0xa7, 0xfc, 0xb1, 0x7b,
+];
+
+pub const MUSIG2_SECNONCE_LENGTH: usize = 97;
+pub const MUSIG2_PUBNONCE_LENGTH: usize = 66;
+pub const MUSIG2_AGGNONCE_LENGTH: usize = 66;
+
+/// Computes the coefficient of `pubkey` given the hash of the key list and its second distinct key.
+fn key_agg_coeff(
+    list_hash: &[u8; 32],
+    second_key: Option<&CompressedPoint>,
+    pubkey: &CompressedPoint,
+) -> [u8; 32] {
+    if second_key == Some(pubkey) {
+        return Scalar::ONE.to_bytes();
+    }
+    let a = hashv(&[
+        KEYAGG_COEFFICIENT.as_ref(),
+        KEYAGG_COEFFICIENT.as_ref(),
+        list_hash.as_ref(),
+        pubkey.0.as_ref(),
+    ]);
+    Scalar::reduce(&a).to_bytes()
+}
+
+/// ### Key Sort
+///
+/// Sorts public keys lexicographically by their compressed encoding, as defined by `KeySort` in
+/// BIP327, so that every participant derives the same aggregate key regardless of the order in
+/// which the keys were collected.
+pub fn key_sort(pubkeys: &mut [CompressedPoint]) {
+    pubkeys.sort_unstable_by_key(|pubkey| pubkey.0);
+}
+
+/// # KeyAggContext
+/// The BIP327 aggregation of a list of public keys into a single MuSig2 public key 𝑄, the sum of
+/// each key 𝑃ᵢ weighted by its coefficient 𝑎ᵢ. An n-of-n signature produced by the participants
+/// is an ordinary BIP340 signature under the 𝑋-only key of 𝑄, so it verifies on-chain with the
+/// existing `verify`.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::{musig2::KeyAggContext, BIP340Challenge};
+///
+/// let context = KeyAggContext::new(&[alice, bob, carol]).expect("Invalid public keys");
+/// schnorr_signature.verify_xonly::<BIP340Challenge>(&message, &context.xonly())
+///     .expect("Invalid signature");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyAggContext {
+    pubkeys: Vec<CompressedPoint>,
+    list_hash: [u8; 32],
+    second_key: Option<CompressedPoint>,
+    q: UncompressedPoint,
+}
+
+impl KeyAggContext {
+    /// ### New
+    ///
+    /// Aggregates `pubkeys` in the order given, as defined by `KeyAgg` in BIP327. Sort them with
+    /// `key_sort` first if the participants have not agreed upon an order.
+    ///
+    /// Fails with `InvalidPublicKey` if a key is not a point on the curve, and `PointAtInfinity` if
+    /// `pubkeys` is empty or the keys cancel out.
+    pub fn new(pubkeys: &[CompressedPoint]) -> Result<Self, Secp256k1SchnorrError> {
+        let list_hash = {
+            let mut keys: Vec<&[u8]> = vec![KEYAGG_LIST.as_ref(), KEYAGG_LIST.as_ref()];
+            keys.extend(pubkeys.iter().map(|pubkey| pubkey.0.as_ref()));
+            hashv(&keys)
+        };
+        // The first key differing from the first, whose coefficient is fixed to 1
+        let second_key = pubkeys
+            .iter()
+            .skip(1)
+            .find(|pubkey| pubkey.ne(&&pubkeys[0]))
+            .copied();
+
+        let mut q = None;
+        for pubkey in pubkeys {
+            let point = parse_sec1(&pubkey.0)?;
+            let a = key_agg_coeff(&list_hash, second_key.as_ref(), pubkey);
+            let weighted = match a.eq(&Scalar::ONE.to_bytes()) {
+                true => point,
+                false => ecmul::mul(&point, &a)?,
+            };
+            q = accumulate(q, weighted);
+        }
+
+        Ok(Self {
+            pubkeys: pubkeys.to_vec(),
+            list_hash,
+            second_key,
+            q: q.ok_or(Secp256k1SchnorrError::PointAtInfinity)?,
+        })
+    }
+
+    /// The public keys in aggregation order
+    pub fn pubkeys(&self) -> &[CompressedPoint] {
+        &self.pubkeys
+    }
+
+    /// ### Coefficient
+    ///
+    /// Returns the coefficient 𝑎ᵢ by which `pubkey` is weighted in the aggregate key, as defined by
+    /// `KeyAggCoeff` in BIP327. The second distinct key is weighted by 1, saving a multiplication.
+    pub fn coefficient(&self, pubkey: &CompressedPoint) -> [u8; 32] {
+        key_agg_coeff(&self.list_hash, self.second_key.as_ref(), pubkey)
+    }
+
+    /// The aggregate public key 𝑄, with its full 𝑌-coordinate
+    pub fn aggregate_pubkey(&self) -> UncompressedPoint {
+        self.q
+    }
+
+    /// The 32-byte 𝑋-only aggregate key, against which BIP340 signatures are verified
+    pub fn xonly(&self) -> [u8; 32] {
+        self.q.x()
+    }
+}
+
+/// # SecNonce
+/// A participant's secret nonce `𝑘₁∥𝑘₂∥𝑃`, as generated by `nonce_gen`. It must be used to sign at
+/// most once and never be revealed, otherwise the participant's secret key is leaked.
+pub struct SecNonce(pub [u8; MUSIG2_SECNONCE_LENGTH]);
+
+impl SecNonce {
+    pub fn k1(&self) -> [u8; 32] {
+        let mut k = [0u8; 32];
+        k.clone_from_slice(&self.0[..32]);
+        k
+    }
+
+    pub fn k2(&self) -> [u8; 32] {
+        let mut k = [0u8; 32];
+        k.clone_from_slice(&self.0[32..64]);
+        k
+    }
+
+    /// The compressed public key of the participant the nonce was generated for
+    pub fn pubkey(&self) -> CompressedPoint {
+        let mut pubkey = [0u8; 33];
+        pubkey.clone_from_slice(&self.0[64..]);
+        CompressedPoint(pubkey)
+    }
+}
+
+#[cfg(feature = "hardening")]
+impl Drop for SecNonce {
+    fn drop(&mut self) {
+        crate::hardening::zeroize(&mut self.0);
+    }
+}
+
+/// # PubNonce
+/// A participant's public nonce `𝑅₁∥𝑅₂`, the compressed points 𝑘₁⋅𝐺 and 𝑘₂⋅𝐺, which is shared
+/// with the other participants ahead of signing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PubNonce(pub [u8; MUSIG2_PUBNONCE_LENGTH]);
+
+impl PubNonce {
+    pub fn r1(&self) -> CompressedPoint {
+        let mut r = [0u8; 33];
+        r.clone_from_slice(&self.0[..33]);
+        CompressedPoint(r)
+    }
+
+    pub fn r2(&self) -> CompressedPoint {
+        let mut r = [0u8; 33];
+        r.clone_from_slice(&self.0[33..]);
+        CompressedPoint(r)
+    }
+}
+
+/// # AggNonce
+/// The aggregate nonce `𝑅₁∥𝑅₂` of every participant, as computed by `nonce_agg`. Either point may
+/// be the point at infinity, which is encoded as 33 zero bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AggNonce(pub [u8; MUSIG2_AGGNONCE_LENGTH]);
+
+impl AggNonce {
+    pub fn r1(&self) -> [u8; 33] {
+        let mut r = [0u8; 33];
+        r.clone_from_slice(&self.0[..33]);
+        r
+    }
+
+    pub fn r2(&self) -> [u8; 33] {
+        let mut r = [0u8; 33];
+        r.clone_from_slice(&self.0[33..]);
+        r
+    }
+}
+
+/// ### Nonce Gen
+///
+/// Generates a secret and public nonce pair for the participant with public key `pubkey`, as
+/// defined by `NonceGen` in BIP327.
+///
+/// WARNING: `rand` must be 32 bytes of fresh randomness from a secure source for every call. The
+/// optional `privkey`, 𝑋-only `aggregate_xonly` key, `message` and `extra` inputs are mixed in as
+/// defense in depth against a weak source, but do not make reusing `rand` safe.
+///
+/// Fails with `InvalidNonce` in the negligible event that either nonce is zero.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::musig2::nonce_gen;
+///
+/// let (secnonce, pubnonce) = nonce_gen(&rand, Some(&privkey), &pubkey, None, Some(&message), &[])?;
+/// ```
+#[cfg(feature = "sign")]
+pub fn nonce_gen(
+    rand: &[u8; 32],
+    privkey: Option<&[u8; 32]>,
+    pubkey: &CompressedPoint,
+    aggregate_xonly: Option<&[u8; 32]>,
+    message: Option<&[u8]>,
+    extra: &[u8],
+) -> Result<(SecNonce, PubNonce), Secp256k1SchnorrError> {
+    #[allow(unused_mut)]
+    let mut rand = match privkey {
+        Some(privkey) => {
+            let mut t = hashv(&[MUSIG_AUX.as_ref(), MUSIG_AUX.as_ref(), rand]);
+            for (a, b) in t.iter_mut().zip(privkey.iter()) {
+                *a ^= b
+            }
+            t
+        }
+        None => *rand,
+    };
+    let aggregate_xonly: &[u8] = aggregate_xonly.map_or(&[], |xonly| xonly.as_ref());
+    let message_length = message.map(|message| (message.len() as u64).to_be_bytes());
+    let extra_length = (extra.len() as u32).to_be_bytes();
+
+    let mut secnonce = [0u8; MUSIG2_SECNONCE_LENGTH];
+    let mut pubnonce = [0u8; MUSIG2_PUBNONCE_LENGTH];
+    for i in 0..2u8 {
+        let k = hashv(&[
+            MUSIG_NONCE.as_ref(),
+            MUSIG_NONCE.as_ref(),
+            rand.as_ref(),
+            &[pubkey.0.len() as u8],
+            pubkey.0.as_ref(),
+            &[aggregate_xonly.len() as u8],
+            aggregate_xonly,
+            &[message.is_some() as u8],
+            message_length
+                .as_ref()
+                .map_or(&[], |length| length.as_ref()),
+            message.unwrap_or_default(),
+            extra_length.as_ref(),
+            extra,
+            &[i],
+        ]);
+        let k = Scalar::reduce(&k);
+        if k.is_zero() {
+            return Err(Secp256k1SchnorrError::InvalidNonce);
+        }
+        let r = CompressedPoint::from(ecmul::mul_g(&k.to_bytes())?);
+        let i = i as usize;
+        secnonce[32 * i..32 * (i + 1)].clone_from_slice(&k.to_bytes());
+        pubnonce[33 * i..33 * (i + 1)].clone_from_slice(&r.0);
+    }
+    secnonce[64..].clone_from_slice(&pubkey.0);
+
+    #[cfg(feature = "hardening")]
+    crate::hardening::zeroize(&mut rand);
+
+    Ok((SecNonce(secnonce), PubNonce(pubnonce)))
+}
+
+/// ### Nonce Agg
+///
+/// Aggregates the public nonces of every participant into the aggregate nonce, as defined by
+/// `NonceAgg` in BIP327.
+///
+/// Fails with `InvalidNonce` if any point of any public nonce is not a point on the curve.
+pub fn nonce_agg(pubnonces: &[PubNonce]) -> Result<AggNonce, Secp256k1SchnorrError> {
+    let mut aggnonce = [0u8; MUSIG2_AGGNONCE_LENGTH];
+    for j in 0..2 {
+        let mut r = None;
+        for pubnonce in pubnonces {
+            let point = parse_sec1(&pubnonce.0[33 * j..33 * (j + 1)])
+                .map_err(|_| Secp256k1SchnorrError::InvalidNonce)?;
+            r = accumulate(r, point);
+        }
+        if let Some(r) = r {
+            aggnonce[33 * j..33 * (j + 1)].clone_from_slice(&CompressedPoint::from(r).0);
+        }
+    }
+    Ok(AggNonce(aggnonce))
+}
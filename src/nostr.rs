@@ -0,0 +1,111 @@
+use solana_nostd_sha256::hash;
+
+use crate::{
+    challenges::bip340::BIP340Challenge, errors::Secp256k1SchnorrError, messages::hex_encode_into,
+    Secp256k1SchnorrSignature,
+};
+
+/// Appends `value` to `out` as a JSON string, escaping only the characters NIP-01 requires.
+fn json_string_into(value: &str, out: &mut String) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// # Event
+///
+/// The signed fields of a NIP-01 Nostr event. The event id and signature are not part of the
+/// event itself, as the id commits to these fields and the signature to the id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Event<'a> {
+    pub pubkey: [u8; 32],
+    pub created_at: u64,
+    pub kind: u16,
+    pub tags: &'a [&'a [&'a str]],
+    pub content: &'a str,
+}
+
+impl Event<'_> {
+    /// ### Serialize
+    ///
+    /// Returns the canonical NIP-01 serialization of the event, the JSON array
+    /// `[0,<pubkey>,<created_at>,<kind>,<tags>,<content>]` without whitespace.
+    pub fn serialize(&self) -> String {
+        let mut out = String::with_capacity(96 + self.content.len());
+        out.push_str("[0,\"");
+        hex_encode_into(&self.pubkey, &mut out);
+        out.push_str("\",");
+        out.push_str(&self.created_at.to_string());
+        out.push(',');
+        out.push_str(&self.kind.to_string());
+        out.push_str(",[");
+        for (i, tag) in self.tags.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push('[');
+            for (j, value) in tag.iter().enumerate() {
+                if j > 0 {
+                    out.push(',');
+                }
+                json_string_into(value, &mut out);
+            }
+            out.push(']');
+        }
+        out.push_str("],");
+        json_string_into(self.content, &mut out);
+        out.push(']');
+        out
+    }
+
+    /// ### Id
+    ///
+    /// Computes the event id, the SHA256 hash of the canonical serialization of the event.
+    pub fn id(&self) -> [u8; 32] {
+        hash(self.serialize().as_bytes())
+    }
+
+    /// ### Verify
+    ///
+    /// Verifies the `sig` of an event against its author's 32-byte 𝑋-only `pubkey`, as relays and
+    /// clients do before accepting it.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::nostr::Event;
+    ///
+    /// let event = Event { pubkey, created_at, kind: 1, tags: &[], content: "gm" };
+    /// event.verify(&sig).expect("Invalid signature");
+    /// ```
+    pub fn verify(&self, sig: &Secp256k1SchnorrSignature) -> Result<(), Secp256k1SchnorrError> {
+        sig.verify_xonly::<BIP340Challenge>(&self.id(), &self.pubkey)
+    }
+
+    /// ### Verify With Id
+    ///
+    /// Verifies an event as transmitted, checking its claimed `id` before its `sig`.
+    ///
+    /// Fails with `InvalidMessage` if `id` is not the id of the event.
+    pub fn verify_with_id(
+        &self,
+        id: &[u8; 32],
+        sig: &Secp256k1SchnorrSignature,
+    ) -> Result<(), Secp256k1SchnorrError> {
+        let expected = self.id();
+        if expected.ne(id) {
+            return Err(Secp256k1SchnorrError::InvalidMessage);
+        }
+        sig.verify_xonly::<BIP340Challenge>(&expected, &self.pubkey)
+    }
+}
@@ -0,0 +1,33 @@
+//! Re-exports the types needed to sign and verify Schnorr signatures, so that downstream programs
+//! need not depend on `solana-secp256k1` directly:
+//!
+//! ```rs
+//! use solana_secp256k1_schnorr::prelude::*;
+//! ```
+
+pub use solana_secp256k1::{CompressedPoint, Curve, Secp256k1Point, UncompressedPoint};
+
+#[cfg(feature = "bch")]
+pub use crate::challenges::bch::BchSchnorrChallenge;
+#[cfg(feature = "bip340")]
+pub use crate::challenges::bip340::BIP340Challenge;
+#[cfg(feature = "bsm")]
+pub use crate::challenges::bsm::BsmChallenge;
+#[cfg(feature = "digest")]
+pub use crate::challenges::digest::{DigestChallenge, DigestTag, TaggedDigestChallenge};
+#[cfg(feature = "eip191")]
+pub use crate::challenges::eip191::Eip191Challenge;
+#[cfg(feature = "eip712")]
+pub use crate::challenges::eip712::Eip712Challenge;
+#[cfg(feature = "keccak256")]
+pub use crate::challenges::keccak256::Keccak256Challenge;
+#[cfg(feature = "sha256")]
+pub use crate::challenges::sha256::Sha256Challenge;
+#[cfg(feature = "tagged")]
+pub use crate::challenges::tagged::{ChallengeTag, TaggedChallenge};
+pub use crate::{
+    challenges::{dynamic::DynChallenge, Secp256k1SchnorrSign, Secp256k1SchnorrVerify},
+    errors::Secp256k1SchnorrError,
+    keys::{lift_x, XOnlyPublicKey},
+    Secp256k1SchnorrSignature, SECP256K1_SCHNORR_SIGNATURE_LENGTH,
+};
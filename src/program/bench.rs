@@ -0,0 +1,330 @@
+use solana_account_info::AccountInfo;
+use solana_program_error::ProgramResult;
+use solana_pubkey::Pubkey;
+use solana_secp256k1::{CompressedPoint, Secp256k1Point, UncompressedPoint};
+
+use crate::{
+    challenges::{
+        bch::BchSchnorrChallenge, bip340::BIP340Challenge, bsm::BsmChallenge,
+        eip191::Eip191Challenge, eip712::Eip712Challenge, keccak256::Keccak256Challenge,
+        sha256::Sha256Challenge, Secp256k1SchnorrVerify,
+    },
+    errors::Secp256k1SchnorrError,
+    Secp256k1SchnorrSignature,
+};
+
+/// Length of the bench instruction header: the scheme, the point type and the signature
+pub const BENCH_INSTRUCTION_HEADER_LENGTH: usize = 2 + 64;
+
+/// Compute units charged by the `sol_remaining_compute_units` call closing a measurement, which
+/// is deducted from every result
+const REMAINING_COMPUTE_UNITS_COST: u64 = 100;
+
+crate::define_tagged_challenge!(
+    /// ### BenchTaggedChallenge
+    ///
+    /// The tagged challenge scheme measured by the benchmark program.
+    pub BenchTaggedChallenge,
+    "solana-secp256k1-schnorr/bench"
+);
+
+/// ### BenchScheme
+///
+/// A challenge scheme measured by the benchmark program.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BenchScheme {
+    Bip340 = 0,
+    Sha256 = 1,
+    Keccak256 = 2,
+    Bch = 3,
+    Bsm = 4,
+    Eip191 = 5,
+    Eip712 = 6,
+    Tagged = 7,
+}
+
+impl TryFrom<u8> for BenchScheme {
+    type Error = Secp256k1SchnorrError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Bip340),
+            1 => Ok(Self::Sha256),
+            2 => Ok(Self::Keccak256),
+            3 => Ok(Self::Bch),
+            4 => Ok(Self::Bsm),
+            5 => Ok(Self::Eip191),
+            6 => Ok(Self::Eip712),
+            7 => Ok(Self::Tagged),
+            _ => Err(Secp256k1SchnorrError::InvalidMessage),
+        }
+    }
+}
+
+impl BenchScheme {
+    pub const ALL: [Self; 8] = [
+        Self::Bip340,
+        Self::Sha256,
+        Self::Keccak256,
+        Self::Bch,
+        Self::Bsm,
+        Self::Eip191,
+        Self::Eip712,
+        Self::Tagged,
+    ];
+
+    /// ### Estimated Compute Units
+    ///
+    /// Returns the estimate of `Secp256k1SchnorrSignature::estimated_compute_units` for the
+    /// scheme, the budget a measurement must stay within.
+    pub fn estimated_compute_units(&self, message_len: usize) -> u64 {
+        match self {
+            Self::Bip340 => {
+                Secp256k1SchnorrSignature::estimated_compute_units::<BIP340Challenge>(message_len)
+            }
+            Self::Sha256 => {
+                Secp256k1SchnorrSignature::estimated_compute_units::<Sha256Challenge>(message_len)
+            }
+            Self::Keccak256 => Secp256k1SchnorrSignature::estimated_compute_units::<
+                Keccak256Challenge,
+            >(message_len),
+            Self::Bch => Secp256k1SchnorrSignature::estimated_compute_units::<BchSchnorrChallenge>(
+                message_len,
+            ),
+            Self::Bsm => {
+                Secp256k1SchnorrSignature::estimated_compute_units::<BsmChallenge>(message_len)
+            }
+            Self::Eip191 => {
+                Secp256k1SchnorrSignature::estimated_compute_units::<Eip191Challenge>(message_len)
+            }
+            Self::Eip712 => {
+                Secp256k1SchnorrSignature::estimated_compute_units::<Eip712Challenge>(message_len)
+            }
+            Self::Tagged => Secp256k1SchnorrSignature::estimated_compute_units::<
+                BenchTaggedChallenge,
+            >(message_len),
+        }
+    }
+
+    /// ### Verify
+    ///
+    /// Verifies a signature under the scheme, returning the compute units the verification
+    /// consumed on-chain, or zero off-chain.
+    pub fn verify<T: Secp256k1Point>(
+        &self,
+        signature: &Secp256k1SchnorrSignature,
+        message: &[u8],
+        pubkey: &T,
+    ) -> Result<u64, Secp256k1SchnorrError> {
+        match self {
+            Self::Bip340 => measure::<BIP340Challenge, T>(signature, message, pubkey),
+            Self::Sha256 => measure::<Sha256Challenge, T>(signature, message, pubkey),
+            Self::Keccak256 => measure::<Keccak256Challenge, T>(signature, message, pubkey),
+            Self::Bch => measure::<BchSchnorrChallenge, T>(signature, message, pubkey),
+            Self::Bsm => measure::<BsmChallenge, T>(signature, message, pubkey),
+            Self::Eip191 => measure::<Eip191Challenge, T>(signature, message, pubkey),
+            Self::Eip712 => measure::<Eip712Challenge, T>(signature, message, pubkey),
+            Self::Tagged => measure::<BenchTaggedChallenge, T>(signature, message, pubkey),
+        }
+    }
+}
+
+#[cfg(feature = "sign")]
+impl BenchScheme {
+    /// ### Sign
+    ///
+    /// Signs a message under the scheme, as the harness does before measuring it.
+    pub fn sign(
+        &self,
+        message: &[u8],
+        privkey: &[u8; 32],
+    ) -> Result<Secp256k1SchnorrSignature, Secp256k1SchnorrError> {
+        match self {
+            Self::Bip340 => Secp256k1SchnorrSignature::sign::<BIP340Challenge>(message, privkey),
+            Self::Sha256 => Secp256k1SchnorrSignature::sign::<Sha256Challenge>(message, privkey),
+            Self::Keccak256 => {
+                Secp256k1SchnorrSignature::sign::<Keccak256Challenge>(message, privkey)
+            }
+            Self::Bch => Secp256k1SchnorrSignature::sign::<BchSchnorrChallenge>(message, privkey),
+            Self::Bsm => Secp256k1SchnorrSignature::sign::<BsmChallenge>(message, privkey),
+            Self::Eip191 => Secp256k1SchnorrSignature::sign::<Eip191Challenge>(message, privkey),
+            Self::Eip712 => Secp256k1SchnorrSignature::sign::<Eip712Challenge>(message, privkey),
+            Self::Tagged => {
+                Secp256k1SchnorrSignature::sign::<BenchTaggedChallenge>(message, privkey)
+            }
+        }
+    }
+}
+
+/// ### BenchPoint
+///
+/// The encoding of the public key a measurement verifies against.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BenchPoint {
+    Compressed = 0,
+    Uncompressed = 1,
+}
+
+impl TryFrom<u8> for BenchPoint {
+    type Error = Secp256k1SchnorrError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Compressed),
+            1 => Ok(Self::Uncompressed),
+            _ => Err(Secp256k1SchnorrError::InvalidMessage),
+        }
+    }
+}
+
+impl BenchPoint {
+    pub const ALL: [Self; 2] = [Self::Compressed, Self::Uncompressed];
+
+    /// The length of the encoded public key
+    pub const fn pubkey_len(&self) -> usize {
+        match self {
+            Self::Compressed => 33,
+            Self::Uncompressed => 65,
+        }
+    }
+}
+
+/// ### BenchInstruction
+///
+/// The instruction data of the benchmark program, encoded as
+/// `scheme (u8) ∥ point (u8) ∥ signature (64) ∥ pubkey (33 or 65) ∥ message`. The public key
+/// is passed to verification as is, so that only the cost of verifying is measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchInstruction<'a> {
+    pub scheme: BenchScheme,
+    pub point: BenchPoint,
+    pub signature: [u8; 64],
+    pub pubkey: &'a [u8],
+    pub message: &'a [u8],
+}
+
+impl<'a> BenchInstruction<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Self, Secp256k1SchnorrError> {
+        if data.len() < BENCH_INSTRUCTION_HEADER_LENGTH {
+            return Err(Secp256k1SchnorrError::InvalidMessage);
+        }
+        let point = BenchPoint::try_from(data[1])?;
+        let rest = &data[BENCH_INSTRUCTION_HEADER_LENGTH..];
+        if rest.len() < point.pubkey_len() {
+            return Err(Secp256k1SchnorrError::InvalidMessage);
+        }
+        let (pubkey, message) = rest.split_at(point.pubkey_len());
+        let mut signature = [0u8; 64];
+        signature.clone_from_slice(&data[2..BENCH_INSTRUCTION_HEADER_LENGTH]);
+        Ok(Self {
+            scheme: BenchScheme::try_from(data[0])?,
+            point,
+            signature,
+            pubkey,
+            message,
+        })
+    }
+
+    /// ### Encode
+    ///
+    /// Encodes the instruction data measuring the verification of `signature` over `message`,
+    /// with `pubkey` in the encoding of `point`.
+    pub fn encode<T: Secp256k1Point>(
+        scheme: BenchScheme,
+        point: BenchPoint,
+        signature: &Secp256k1SchnorrSignature,
+        pubkey: &T,
+        message: &[u8],
+    ) -> Vec<u8> {
+        let mut data = vec![scheme as u8, point as u8];
+        data.extend_from_slice(&signature.0);
+        match point {
+            BenchPoint::Compressed => data.extend_from_slice(&pubkey.compress().0),
+            BenchPoint::Uncompressed => {
+                data.extend_from_slice(&pubkey.decompress().to_sec1_bytes())
+            }
+        }
+        data.extend_from_slice(message);
+        data
+    }
+
+    /// ### Verify
+    ///
+    /// Verifies the signature, returning the compute units the verification consumed on-chain,
+    /// or zero off-chain.
+    pub fn verify(&self) -> Result<u64, Secp256k1SchnorrError> {
+        let signature = Secp256k1SchnorrSignature(self.signature);
+        match self.point {
+            BenchPoint::Compressed => {
+                let mut pubkey = [0u8; 33];
+                pubkey.clone_from_slice(self.pubkey);
+                self.scheme
+                    .verify(&signature, self.message, &CompressedPoint(pubkey))
+            }
+            BenchPoint::Uncompressed => {
+                let mut pubkey = [0u8; 65];
+                pubkey.clone_from_slice(self.pubkey);
+                self.scheme
+                    .verify(&signature, self.message, &UncompressedPoint::from(pubkey))
+            }
+        }
+    }
+}
+
+/// Verifies a signature under `C`, measuring the compute units remaining before and after.
+fn measure<C: Secp256k1SchnorrVerify, T: Secp256k1Point>(
+    signature: &Secp256k1SchnorrSignature,
+    message: &[u8],
+    pubkey: &T,
+) -> Result<u64, Secp256k1SchnorrError> {
+    let before = remaining_compute_units();
+    signature.verify::<C, T>(message, pubkey)?;
+    let after = remaining_compute_units();
+    Ok(before
+        .saturating_sub(after)
+        .saturating_sub(REMAINING_COMPUTE_UNITS_COST))
+}
+
+#[cfg(target_os = "solana")]
+fn remaining_compute_units() -> u64 {
+    unsafe { solana_define_syscall::definitions::sol_remaining_compute_units() }
+}
+
+#[cfg(not(target_os = "solana"))]
+fn remaining_compute_units() -> u64 {
+    REMAINING_COMPUTE_UNITS_COST
+}
+
+#[cfg(target_os = "solana")]
+fn set_return_data(data: &[u8]) {
+    unsafe {
+        solana_define_syscall::definitions::sol_set_return_data(data.as_ptr(), data.len() as u64)
+    }
+}
+
+#[cfg(not(target_os = "solana"))]
+fn set_return_data(_data: &[u8]) {}
+
+/// ### Process Instruction
+///
+/// The processor of the benchmark program, verifying the signature of its `BenchInstruction`
+/// data and returning the compute units consumed as a little-endian `u64` in its return data.
+/// It fails the transaction if the signature does not verify, so that a measurement is never
+/// taken over an early return.
+///
+/// With the "bench-sbf" feature flag enabled, it is declared as the program's entrypoint, and
+/// measured by the harness run with `cargo test-sbf --features bench-sbf`.
+#[inline(never)]
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let units = BenchInstruction::parse(instruction_data)?.verify()?;
+    set_return_data(&units.to_le_bytes());
+    Ok(())
+}
+
+solana_program_entrypoint::entrypoint!(process_instruction);
@@ -0,0 +1,49 @@
+use ::pinocchio::{entrypoint::lazy::InstructionContext, ProgramResult};
+
+use crate::{
+    challenges::bip340::BIP340Challenge, errors::Secp256k1SchnorrError, program::read_pubkey,
+    Secp256k1SchnorrSignature, SECP256K1_SCHNORR_SIGNATURE_LENGTH,
+};
+
+/// Length of the example instruction data before its message: the signature and the compressed
+/// public key
+pub const EXAMPLE_INSTRUCTION_HEADER_LENGTH: usize = SECP256K1_SCHNORR_SIGNATURE_LENGTH + 33;
+
+/// ### Verify Example Instruction
+///
+/// Verifies instruction data laid out as `signature (64) ∥ pubkey (33) ∥ message` under BIP340,
+/// borrowing the message in place, failing with `InvalidMessage` if the data is too short to
+/// hold a signature and public key.
+pub fn verify_example_instruction(data: &[u8]) -> Result<(), Secp256k1SchnorrError> {
+    if data.len() < EXAMPLE_INSTRUCTION_HEADER_LENGTH {
+        return Err(Secp256k1SchnorrError::InvalidMessage);
+    }
+    let mut signature = [0u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH];
+    signature.clone_from_slice(&data[..SECP256K1_SCHNORR_SIGNATURE_LENGTH]);
+    let pubkey = read_pubkey(data, SECP256K1_SCHNORR_SIGNATURE_LENGTH)?;
+    Secp256k1SchnorrSignature(signature)
+        .verify::<BIP340Challenge, _>(&data[EXAMPLE_INSTRUCTION_HEADER_LENGTH..], &pubkey)
+}
+
+/// ### Process Instruction
+///
+/// The processor of a minimal BIP340 verifier program, declared with Pinocchio's lazy
+/// entrypoint so that no accounts are deserialized and nothing is allocated before verifying.
+/// It takes no accounts, failing with `InvalidInstructionData` if any are passed, and fails the
+/// transaction unless the signature of its instruction data verifies.
+///
+/// The program itself never allocates, but the scalar arithmetic of verification does, so it
+/// keeps Pinocchio's bump allocator rather than `no_allocator!`.
+///
+/// With the "pinocchio-example" feature flag enabled, it is declared as the program's
+/// entrypoint, and measured by the harness run with
+/// `cargo test-sbf --features pinocchio-example,program-test`.
+#[inline(never)]
+pub fn process_instruction(context: InstructionContext) -> ProgramResult {
+    verify_example_instruction(context.instruction_data()?)?;
+    Ok(())
+}
+
+::pinocchio::lazy_program_entrypoint!(process_instruction);
+::pinocchio::default_allocator!();
+::pinocchio::default_panic_handler!();
@@ -0,0 +1,267 @@
+use solana_secp256k1::{CompressedPoint, SEC1_OCTET_COMPRESSED_EVEN, SEC1_OCTET_COMPRESSED_ODD};
+
+use crate::{errors::Secp256k1SchnorrError, Secp256k1SchnorrSignature};
+
+#[cfg(feature = "bench-sbf")]
+pub mod bench;
+#[cfg(feature = "pinocchio-example")]
+pub mod example;
+pub mod offsets;
+#[cfg(feature = "pinocchio")]
+pub mod pinocchio;
+#[cfg(feature = "solana-program")]
+pub mod solana;
+
+#[cfg(all(
+    feature = "pinocchio-entrypoint",
+    feature = "solana-program-entrypoint"
+))]
+compile_error!(
+    "Only one of \"pinocchio-entrypoint\" and \"solana-program-entrypoint\" may be enabled"
+);
+
+#[cfg(all(
+    feature = "bench-sbf",
+    any(
+        feature = "pinocchio-entrypoint",
+        feature = "solana-program-entrypoint"
+    )
+))]
+compile_error!(
+    "\"bench-sbf\" declares its own entrypoint, and may not be enabled with another entrypoint"
+);
+
+#[cfg(all(
+    feature = "pinocchio-example",
+    any(
+        feature = "bench-sbf",
+        feature = "pinocchio-entrypoint",
+        feature = "solana-program-entrypoint"
+    )
+))]
+compile_error!(
+    "\"pinocchio-example\" declares its own entrypoint, and may not be enabled with another entrypoint"
+);
+
+#[cfg(any(feature = "pinocchio", feature = "solana-program"))]
+impl From<Secp256k1SchnorrError> for solana_program_error::ProgramError {
+    fn from(error: Secp256k1SchnorrError) -> Self {
+        solana_program_error::ProgramError::Custom(error as u32)
+    }
+}
+
+/// Length of the instruction header: the scheme and the number of entries
+pub const VERIFY_INSTRUCTION_HEADER_LENGTH: usize = 2;
+
+/// Length of an entry before its message: the signature, the compressed public key and the
+/// message length
+pub const VERIFY_ENTRY_HEADER_LENGTH: usize = 64 + 33 + 2;
+
+/// ### Scheme
+///
+/// The challenge scheme under which every signature of a verify instruction is checked.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    Bip340 = 0,
+    Sha256 = 1,
+    Keccak256 = 2,
+}
+
+impl TryFrom<u8> for Scheme {
+    type Error = Secp256k1SchnorrError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Bip340),
+            1 => Ok(Self::Sha256),
+            2 => Ok(Self::Keccak256),
+            _ => Err(Secp256k1SchnorrError::InvalidMessage),
+        }
+    }
+}
+
+impl Scheme {
+    /// ### Verify
+    ///
+    /// Verifies a signature under the scheme, failing with `InvalidChallenge` if its feature flag
+    /// is not enabled.
+    #[allow(unused_variables)]
+    pub fn verify(
+        &self,
+        signature: &Secp256k1SchnorrSignature,
+        message: &[u8],
+        pubkey: &CompressedPoint,
+    ) -> Result<(), Secp256k1SchnorrError> {
+        match self {
+            #[cfg(feature = "bip340")]
+            Self::Bip340 => signature
+                .verify::<crate::challenges::bip340::BIP340Challenge, CompressedPoint>(
+                    message, pubkey,
+                ),
+            #[cfg(feature = "sha256")]
+            Self::Sha256 => signature
+                .verify::<crate::challenges::sha256::Sha256Challenge, CompressedPoint>(
+                    message, pubkey,
+                ),
+            #[cfg(feature = "keccak256")]
+            Self::Keccak256 => signature
+                .verify::<crate::challenges::keccak256::Keccak256Challenge, CompressedPoint>(
+                    message, pubkey,
+                ),
+            #[allow(unreachable_patterns)]
+            _ => Err(Secp256k1SchnorrError::InvalidChallenge),
+        }
+    }
+}
+
+#[cfg(feature = "sign")]
+impl Scheme {
+    /// ### Sign
+    ///
+    /// Signs a message under the scheme, failing with `InvalidChallenge` if its feature flag is
+    /// not enabled.
+    #[allow(unused_variables)]
+    pub fn sign(
+        &self,
+        message: &[u8],
+        privkey: &[u8; 32],
+    ) -> Result<Secp256k1SchnorrSignature, Secp256k1SchnorrError> {
+        match self {
+            #[cfg(feature = "bip340")]
+            Self::Bip340 => Secp256k1SchnorrSignature::sign::<
+                crate::challenges::bip340::BIP340Challenge,
+            >(message, privkey),
+            #[cfg(feature = "sha256")]
+            Self::Sha256 => Secp256k1SchnorrSignature::sign::<
+                crate::challenges::sha256::Sha256Challenge,
+            >(message, privkey),
+            #[cfg(feature = "keccak256")]
+            Self::Keccak256 => Secp256k1SchnorrSignature::sign::<
+                crate::challenges::keccak256::Keccak256Challenge,
+            >(message, privkey),
+            #[allow(unreachable_patterns)]
+            _ => Err(Secp256k1SchnorrError::InvalidChallenge),
+        }
+    }
+}
+
+/// ### VerifyEntry
+///
+/// A single signature of a verify instruction, encoded as
+/// `signature (64) ∥ pubkey (33) ∥ message length (u16 LE) ∥ message`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifyEntry<'a> {
+    pub signature: [u8; 64],
+    pub pubkey: CompressedPoint,
+    pub message: &'a [u8],
+}
+
+/// ### VerifyInstruction
+///
+/// The instruction data of the verifier program, encoded as `scheme (u8) ∥ count (u8)` followed
+/// by `count` entries. The instruction succeeds only if every entry verifies.
+///
+/// The format is framework-agnostic, and is processed identically by every entrypoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifyInstruction<'a> {
+    pub scheme: Scheme,
+    pub count: u8,
+    entries: &'a [u8],
+}
+
+impl<'a> VerifyInstruction<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Self, Secp256k1SchnorrError> {
+        if data.len() < VERIFY_INSTRUCTION_HEADER_LENGTH {
+            return Err(Secp256k1SchnorrError::InvalidMessage);
+        }
+        Ok(Self {
+            scheme: Scheme::try_from(data[0])?,
+            count: data[1],
+            entries: &data[VERIFY_INSTRUCTION_HEADER_LENGTH..],
+        })
+    }
+
+    /// ### Verify
+    ///
+    /// Verifies every entry in order, failing on the first invalid one, or with `InvalidMessage`
+    /// if the data does not contain exactly `count` entries.
+    pub fn verify(&self) -> Result<(), Secp256k1SchnorrError> {
+        let mut entries = self.entries;
+        for _ in 0..self.count {
+            let (entry, rest) = read_entry(entries)?;
+            self.scheme.verify(
+                &Secp256k1SchnorrSignature(entry.signature),
+                entry.message,
+                &entry.pubkey,
+            )?;
+            entries = rest;
+        }
+        match entries.is_empty() {
+            true => Ok(()),
+            false => Err(Secp256k1SchnorrError::InvalidMessage),
+        }
+    }
+}
+
+fn read_entry(data: &[u8]) -> Result<(VerifyEntry<'_>, &[u8]), Secp256k1SchnorrError> {
+    if data.len() < VERIFY_ENTRY_HEADER_LENGTH {
+        return Err(Secp256k1SchnorrError::InvalidMessage);
+    }
+    let mut signature = [0u8; 64];
+    signature.clone_from_slice(&data[..64]);
+    let pubkey = read_pubkey(data, 64)?;
+    let len = u16::from_le_bytes([data[97], data[98]]) as usize;
+    let rest = &data[VERIFY_ENTRY_HEADER_LENGTH..];
+    if rest.len() < len {
+        return Err(Secp256k1SchnorrError::InvalidMessage);
+    }
+    let (message, rest) = rest.split_at(len);
+    Ok((
+        VerifyEntry {
+            signature,
+            pubkey,
+            message,
+        },
+        rest,
+    ))
+}
+
+/// ### Encode Verify Instruction
+///
+/// Encodes the instruction data verifying `entries` under `scheme`, failing with
+/// `LimitExceeded` for more than 255 entries or a message longer than 65535 bytes.
+pub fn encode_verify_instruction(
+    scheme: Scheme,
+    entries: &[VerifyEntry],
+) -> Result<Vec<u8>, Secp256k1SchnorrError> {
+    let count = u8::try_from(entries.len()).map_err(|_| Secp256k1SchnorrError::LimitExceeded)?;
+    let mut data = vec![scheme as u8, count];
+    for entry in entries {
+        let len =
+            u16::try_from(entry.message.len()).map_err(|_| Secp256k1SchnorrError::LimitExceeded)?;
+        data.extend_from_slice(&entry.signature);
+        data.extend_from_slice(&entry.pubkey.0);
+        data.extend_from_slice(&len.to_le_bytes());
+        data.extend_from_slice(entry.message);
+    }
+    Ok(data)
+}
+
+/// ### Read Pubkey
+///
+/// Reads a 33-byte compressed public key stored at `offset` in account or instruction data,
+/// failing with `InvalidMessage` if it is out of bounds or `InvalidPublicKey` if its prefix is
+/// not a compressed SEC1 prefix.
+pub fn read_pubkey(data: &[u8], offset: usize) -> Result<CompressedPoint, Secp256k1SchnorrError> {
+    let bytes = offset
+        .checked_add(33)
+        .and_then(|end| data.get(offset..end))
+        .ok_or(Secp256k1SchnorrError::InvalidMessage)?;
+    let mut pubkey = [0u8; 33];
+    pubkey.clone_from_slice(bytes);
+    if pubkey[0] != SEC1_OCTET_COMPRESSED_EVEN && pubkey[0] != SEC1_OCTET_COMPRESSED_ODD {
+        return Err(Secp256k1SchnorrError::InvalidPublicKey);
+    }
+    Ok(CompressedPoint(pubkey))
+}
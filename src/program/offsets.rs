@@ -0,0 +1,251 @@
+use solana_secp256k1::CompressedPoint;
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify, errors::Secp256k1SchnorrError, program::Scheme,
+    Secp256k1SchnorrSignature,
+};
+
+use super::{read_pubkey, VERIFY_INSTRUCTION_HEADER_LENGTH};
+
+/// Length of an encoded `SchnorrSignatureOffsets`
+pub const SIGNATURE_OFFSETS_LENGTH: usize = 11;
+
+/// Instruction index referring to the instruction carrying the offsets themselves
+pub const CURRENT_INSTRUCTION: u8 = u8::MAX;
+
+/// ### SchnorrSignatureOffsets
+///
+/// Locates the signature, compressed public key and message of a single entry in instruction
+/// data, as `SecpSignatureOffsets` does for the native secp256k1 program. Each instruction index
+/// is `CURRENT_INSTRUCTION` or the index of another instruction of the transaction, allowing
+/// several entries to share one public key or message.
+///
+/// Encoded as eleven bytes, with every offset and size `u16` little-endian.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SchnorrSignatureOffsets {
+    pub signature_offset: u16,
+    pub signature_instruction_index: u8,
+    pub pubkey_offset: u16,
+    pub pubkey_instruction_index: u8,
+    pub message_data_offset: u16,
+    pub message_data_size: u16,
+    pub message_instruction_index: u8,
+}
+
+impl SchnorrSignatureOffsets {
+    pub fn from_bytes(bytes: &[u8; SIGNATURE_OFFSETS_LENGTH]) -> Self {
+        Self {
+            signature_offset: u16::from_le_bytes([bytes[0], bytes[1]]),
+            signature_instruction_index: bytes[2],
+            pubkey_offset: u16::from_le_bytes([bytes[3], bytes[4]]),
+            pubkey_instruction_index: bytes[5],
+            message_data_offset: u16::from_le_bytes([bytes[6], bytes[7]]),
+            message_data_size: u16::from_le_bytes([bytes[8], bytes[9]]),
+            message_instruction_index: bytes[10],
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_OFFSETS_LENGTH] {
+        let mut bytes = [0u8; SIGNATURE_OFFSETS_LENGTH];
+        bytes[0..2].clone_from_slice(&self.signature_offset.to_le_bytes());
+        bytes[2] = self.signature_instruction_index;
+        bytes[3..5].clone_from_slice(&self.pubkey_offset.to_le_bytes());
+        bytes[5] = self.pubkey_instruction_index;
+        bytes[6..8].clone_from_slice(&self.message_data_offset.to_le_bytes());
+        bytes[8..10].clone_from_slice(&self.message_data_size.to_le_bytes());
+        bytes[10] = self.message_instruction_index;
+        bytes
+    }
+}
+
+/// Returns `len` bytes at `offset` of `data`, failing with `InvalidMessage` if out of bounds.
+fn slice_at(data: &[u8], offset: u16, len: usize) -> Result<&[u8], Secp256k1SchnorrError> {
+    let offset = offset as usize;
+    data.get(offset..offset + len)
+        .ok_or(Secp256k1SchnorrError::InvalidMessage)
+}
+
+/// ### OffsetsInstruction
+///
+/// Instruction data locating its signatures by offsets, encoded as
+/// `scheme (u8) ∥ count (u8) ∥ offsets (11 × count)` followed by arbitrary data the offsets
+/// refer to. The instruction succeeds only if every entry verifies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OffsetsInstruction<'a> {
+    pub scheme: Scheme,
+    pub count: u8,
+    data: &'a [u8],
+}
+
+impl<'a> OffsetsInstruction<'a> {
+    /// ### Parse
+    ///
+    /// Parses the header of offsets instruction data, failing with `InvalidMessage` if it is too
+    /// short to hold `count` offsets.
+    pub fn parse(data: &'a [u8]) -> Result<Self, Secp256k1SchnorrError> {
+        if data.len() < VERIFY_INSTRUCTION_HEADER_LENGTH {
+            return Err(Secp256k1SchnorrError::InvalidMessage);
+        }
+        let count = data[1];
+        if data.len() < VERIFY_INSTRUCTION_HEADER_LENGTH + count as usize * SIGNATURE_OFFSETS_LENGTH
+        {
+            return Err(Secp256k1SchnorrError::InvalidMessage);
+        }
+        Ok(Self {
+            scheme: Scheme::try_from(data[0])?,
+            count,
+            data,
+        })
+    }
+
+    /// ### Offsets
+    ///
+    /// Iterates over the offsets of every entry in order.
+    pub fn offsets(&self) -> impl Iterator<Item = SchnorrSignatureOffsets> + 'a {
+        self.data[VERIFY_INSTRUCTION_HEADER_LENGTH..]
+            .chunks_exact(SIGNATURE_OFFSETS_LENGTH)
+            .take(self.count as usize)
+            .map(|chunk| {
+                let mut bytes = [0u8; SIGNATURE_OFFSETS_LENGTH];
+                bytes.clone_from_slice(chunk);
+                SchnorrSignatureOffsets::from_bytes(&bytes)
+            })
+    }
+
+    /// ### Verify
+    ///
+    /// Verifies every entry in order, failing on the first invalid one. Every entry must refer to
+    /// `CURRENT_INSTRUCTION`, failing with `InvalidMessage` otherwise or if any entry is out of
+    /// bounds.
+    pub fn verify(&self) -> Result<(), Secp256k1SchnorrError> {
+        self.verify_with(|_| Err(Secp256k1SchnorrError::InvalidMessage))
+    }
+
+    /// ### Verify With
+    ///
+    /// Verifies every entry in order, resolving instruction indices other than
+    /// `CURRENT_INSTRUCTION` through `load_instruction`, such as from the instructions sysvar.
+    pub fn verify_with<'b, F>(&self, load_instruction: F) -> Result<(), Secp256k1SchnorrError>
+    where
+        'a: 'b,
+        F: Fn(u8) -> Result<&'b [u8], Secp256k1SchnorrError>,
+    {
+        let load = |index: u8| match index {
+            CURRENT_INSTRUCTION => Ok(self.data),
+            index => load_instruction(index),
+        };
+        self.offsets().try_for_each(|offsets| {
+            let (signature, pubkey, message) = resolve(&offsets, load)?;
+            self.scheme.verify(&signature, message, &pubkey)
+        })
+    }
+}
+
+/// Resolves the signature, public key and message an entry refers to through `load`.
+fn resolve<'b, F>(
+    offsets: &SchnorrSignatureOffsets,
+    load: F,
+) -> Result<(Secp256k1SchnorrSignature, CompressedPoint, &'b [u8]), Secp256k1SchnorrError>
+where
+    F: Fn(u8) -> Result<&'b [u8], Secp256k1SchnorrError>,
+{
+    let mut signature = [0u8; 64];
+    signature.clone_from_slice(slice_at(
+        load(offsets.signature_instruction_index)?,
+        offsets.signature_offset,
+        64,
+    )?);
+    let pubkey = read_pubkey(
+        load(offsets.pubkey_instruction_index)?,
+        offsets.pubkey_offset as usize,
+    )?;
+    let message = slice_at(
+        load(offsets.message_instruction_index)?,
+        offsets.message_data_offset,
+        offsets.message_data_size as usize,
+    )?;
+    Ok((Secp256k1SchnorrSignature(signature), pubkey, message))
+}
+
+/// ### Verify From Instruction Data
+///
+/// Verifies every signature referenced by instruction data laid out as
+/// `count (u8) ∥ offsets (11 × count) ∥ payload` under the challenge scheme `C`, returning
+/// whether each entry verified, in order. Unlike `OffsetsInstruction::verify`, an invalid
+/// signature or public key does not fail the call, so programs can act on the signers that did
+/// verify, such as counting approvals.
+///
+/// Every entry must refer to `CURRENT_INSTRUCTION`, failing with `InvalidMessage` otherwise, if
+/// the data is too short to hold `count` offsets, or if any entry is out of bounds.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::program::offsets::verify_from_instruction_data;
+///
+/// let verified = verify_from_instruction_data::<BIP340Challenge>(instruction_data)?;
+/// let approvals = verified.iter().filter(|verified| **verified).count();
+/// ```
+pub fn verify_from_instruction_data<C: Secp256k1SchnorrVerify>(
+    data: &[u8],
+) -> Result<Vec<bool>, Secp256k1SchnorrError> {
+    verify_from_instruction_data_with::<C, _>(data, |_| Err(Secp256k1SchnorrError::InvalidMessage))
+}
+
+/// ### Verify From Instruction Data With
+///
+/// Verifies every signature referenced by instruction data as `verify_from_instruction_data`,
+/// resolving instruction indices other than `CURRENT_INSTRUCTION` through `load_instruction`,
+/// such as from the instructions sysvar.
+pub fn verify_from_instruction_data_with<'b, C, F>(
+    data: &'b [u8],
+    load_instruction: F,
+) -> Result<Vec<bool>, Secp256k1SchnorrError>
+where
+    C: Secp256k1SchnorrVerify,
+    F: Fn(u8) -> Result<&'b [u8], Secp256k1SchnorrError>,
+{
+    let count = *data.first().ok_or(Secp256k1SchnorrError::InvalidMessage)? as usize;
+    let offsets = data
+        .get(1..1 + count * SIGNATURE_OFFSETS_LENGTH)
+        .ok_or(Secp256k1SchnorrError::InvalidMessage)?;
+    let load = |index: u8| match index {
+        CURRENT_INSTRUCTION => Ok(data),
+        index => load_instruction(index),
+    };
+    offsets
+        .chunks_exact(SIGNATURE_OFFSETS_LENGTH)
+        .map(|chunk| {
+            let mut bytes = [0u8; SIGNATURE_OFFSETS_LENGTH];
+            bytes.clone_from_slice(chunk);
+            match resolve(&SchnorrSignatureOffsets::from_bytes(&bytes), load) {
+                Ok((signature, pubkey, message)) => Ok(signature
+                    .verify::<C, CompressedPoint>(message, &pubkey)
+                    .is_ok()),
+                Err(Secp256k1SchnorrError::InvalidPublicKey) => Ok(false),
+                Err(error) => Err(error),
+            }
+        })
+        .collect()
+}
+
+/// ### Encode Offsets Instruction
+///
+/// Encodes the header of offsets instruction data followed by `data`, failing with
+/// `LimitExceeded` for more than 255 entries. Offsets into `data` must account for the
+/// `2 + 11 × count` bytes preceding it.
+pub fn encode_offsets_instruction(
+    scheme: Scheme,
+    offsets: &[SchnorrSignatureOffsets],
+    data: &[u8],
+) -> Result<Vec<u8>, Secp256k1SchnorrError> {
+    let count = u8::try_from(offsets.len()).map_err(|_| Secp256k1SchnorrError::LimitExceeded)?;
+    let mut out = Vec::with_capacity(
+        VERIFY_INSTRUCTION_HEADER_LENGTH + offsets.len() * SIGNATURE_OFFSETS_LENGTH + data.len(),
+    );
+    out.extend_from_slice(&[scheme as u8, count]);
+    for entry in offsets {
+        out.extend_from_slice(&entry.to_bytes());
+    }
+    out.extend_from_slice(data);
+    Ok(out)
+}
@@ -0,0 +1,53 @@
+use ::pinocchio::{error::ProgramError, AccountView, Address, ProgramResult};
+use solana_secp256k1::{CompressedPoint, Secp256k1Point};
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify, program::VerifyInstruction, Secp256k1SchnorrSignature,
+};
+
+/// ### Process Instruction
+///
+/// The Pinocchio processor of the verifier program, failing the transaction unless every
+/// signature of its `VerifyInstruction` data verifies. It reads no accounts.
+///
+/// With the "pinocchio-entrypoint" feature flag enabled, it is also declared as the program's
+/// entrypoint.
+#[inline(never)]
+pub fn process_instruction(
+    _program_id: &Address,
+    _accounts: &mut [AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    VerifyInstruction::parse(instruction_data)?.verify()?;
+    Ok(())
+}
+
+#[cfg(feature = "pinocchio-entrypoint")]
+::pinocchio::entrypoint!(process_instruction);
+
+/// ### Pubkey From Account
+///
+/// Reads a compressed public key stored at `offset` in the data of a Pinocchio account, as
+/// `program::read_pubkey`.
+pub fn pubkey_from_account(
+    account: &AccountView,
+    offset: usize,
+) -> Result<CompressedPoint, ProgramError> {
+    let data = account.try_borrow()?;
+    Ok(crate::program::read_pubkey(&data, offset)?)
+}
+
+/// ### Verify Account Message
+///
+/// Verifies a signature over the `len` bytes at `offset` in the data of a Pinocchio account,
+/// borrowing them in place, as `Secp256k1SchnorrSignature::verify_account_data`.
+pub fn verify_account_message<C: Secp256k1SchnorrVerify, T: Secp256k1Point>(
+    signature: &Secp256k1SchnorrSignature,
+    account: &AccountView,
+    offset: usize,
+    len: usize,
+    pubkey: &T,
+) -> ProgramResult {
+    let data = account.try_borrow()?;
+    Ok(signature.verify_account_data::<C, T>(&data, offset, len, pubkey)?)
+}
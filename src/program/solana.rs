@@ -0,0 +1,56 @@
+use solana_account_info::AccountInfo;
+use solana_program_error::{ProgramError, ProgramResult};
+use solana_pubkey::Pubkey;
+use solana_secp256k1::{CompressedPoint, Secp256k1Point};
+
+use crate::{
+    challenges::Secp256k1SchnorrVerify, program::VerifyInstruction, Secp256k1SchnorrSignature,
+};
+
+/// ### Process Instruction
+///
+/// The solana-program processor of the verifier program, failing the transaction unless every
+/// signature of its `VerifyInstruction` data verifies. It reads no accounts, so other programs
+/// may rely on it through instruction introspection rather than linking this crate.
+///
+/// With the "solana-program-entrypoint" feature flag enabled, it is also declared as the
+/// program's entrypoint.
+#[inline(never)]
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    VerifyInstruction::parse(instruction_data)?.verify()?;
+    Ok(())
+}
+
+#[cfg(feature = "solana-program-entrypoint")]
+solana_program_entrypoint::entrypoint!(process_instruction);
+
+/// ### Pubkey From Account
+///
+/// Reads a compressed public key stored at `offset` in the data of an account, as
+/// `program::read_pubkey`.
+pub fn pubkey_from_account(
+    account: &AccountInfo,
+    offset: usize,
+) -> Result<CompressedPoint, ProgramError> {
+    let data = account.try_borrow_data()?;
+    Ok(crate::program::read_pubkey(&data, offset)?)
+}
+
+/// ### Verify Account Message
+///
+/// Verifies a signature over the `len` bytes at `offset` in the data of an account, borrowing
+/// them in place, as `Secp256k1SchnorrSignature::verify_account_data`.
+pub fn verify_account_message<C: Secp256k1SchnorrVerify, T: Secp256k1Point>(
+    signature: &Secp256k1SchnorrSignature,
+    account: &AccountInfo,
+    offset: usize,
+    len: usize,
+    pubkey: &T,
+) -> ProgramResult {
+    let data = account.try_borrow_data()?;
+    Ok(signature.verify_account_data::<C, T>(&data, offset, len, pubkey)?)
+}
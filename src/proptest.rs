@@ -0,0 +1,160 @@
+use ::proptest::{collection::vec, prelude::*};
+use solana_secp256k1::{CompressedPoint, Curve};
+
+use crate::scalar::Scalar;
+#[cfg(feature = "sign")]
+use crate::{challenges::Secp256k1SchnorrSign, Secp256k1SchnorrSignature};
+
+/// Maximum distance from a boundary drawn by `near_boundary`
+pub const NEAR_BOUNDARY_DISTANCE: u8 = 16;
+
+/// Maximum length of the messages drawn by `message`
+pub const MAX_MESSAGE_LENGTH: usize = 256;
+
+/// ### Private Key
+///
+/// Draws valid private keys, nonzero scalars modulo 𝑁, including the smallest and largest ones.
+pub fn privkey() -> impl Strategy<Value = [u8; 32]> {
+    prop_oneof![
+        1 => Just(Scalar::ONE.to_bytes()),
+        1 => Just(Scalar::ONE.negate().to_bytes()),
+        8 => any::<[u8; 32]>().prop_filter_map("invalid private key", |bytes| {
+            Scalar::new_nonzero(bytes).ok().map(|scalar| scalar.to_bytes())
+        }),
+    ]
+}
+
+/// ### Keypair
+///
+/// Draws a valid private key along with its compressed public key, of either parity.
+pub fn keypair() -> impl Strategy<Value = ([u8; 32], CompressedPoint)> {
+    privkey().prop_filter_map("invalid private key", |privkey| {
+        Curve::mul_g(&privkey)
+            .ok()
+            .map(|pubkey| (privkey, CompressedPoint::from(pubkey)))
+    })
+}
+
+/// ### Message
+///
+/// Draws messages of up to `MAX_MESSAGE_LENGTH` bytes, including the empty message.
+pub fn message() -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![
+        1 => Just(Vec::new()),
+        9 => vec(any::<u8>(), 1..=MAX_MESSAGE_LENGTH),
+    ]
+}
+
+/// ### Near Boundary
+///
+/// Draws values within `NEAR_BOUNDARY_DISTANCE` of `bound` on either side, such that about half
+/// are valid and half are not, with `bound - 1` and `bound` themselves drawn most often.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::proptest::{near_boundary, signed_message};
+///
+/// proptest! {
+///     #[test]
+///     fn rejects_unreduced_s(signed in signed_message::<BIP340Challenge>(), s in near_boundary(Curve::N)) {
+///         prop_assume!(s >= Curve::N);
+///         let signature = Secp256k1SchnorrSignature::from_parts(signed.signature().r(), s);
+///         prop_assert!(signature.verify::<BIP340Challenge, _>(&signed.message, &signed.pubkey).is_err());
+///     }
+/// }
+/// ```
+pub fn near_boundary(bound: [u8; 32]) -> impl Strategy<Value = [u8; 32]> {
+    prop_oneof![
+        1 => Just(offset(&bound, -1)),
+        1 => Just(bound),
+        4 => (-(NEAR_BOUNDARY_DISTANCE as i16)..NEAR_BOUNDARY_DISTANCE as i16)
+            .prop_map(move |delta| offset(&bound, delta)),
+    ]
+}
+
+/// ### Near 𝑁
+///
+/// Draws 𝑠 values around the curve order 𝑁, as by `near_boundary`.
+pub fn near_n() -> impl Strategy<Value = [u8; 32]> {
+    near_boundary(Curve::N)
+}
+
+/// ### Near 𝑃
+///
+/// Draws 𝑟 and 𝑃ₓ values around the field prime 𝑃, as by `near_boundary`.
+pub fn near_p() -> impl Strategy<Value = [u8; 32]> {
+    near_boundary(Curve::P)
+}
+
+/// ### Boundary Signature
+///
+/// Draws 64-byte signatures with 𝑟 near 𝑃, 𝑠 near 𝑁, or both, the other being random, exercising
+/// the range checks of verification.
+pub fn boundary_signature() -> impl Strategy<Value = [u8; 64]> {
+    prop_oneof![
+        (near_p(), any::<[u8; 32]>()),
+        (any::<[u8; 32]>(), near_n()),
+        (near_p(), near_n()),
+    ]
+    .prop_map(|(r, s)| {
+        let mut signature = [0u8; 64];
+        signature[..32].clone_from_slice(&r);
+        signature[32..].clone_from_slice(&s);
+        signature
+    })
+}
+
+/// # SignedMessage
+///
+/// A message signed under a known keypair, as drawn by `signed_message`.
+#[cfg(feature = "sign")]
+#[derive(Debug, Clone)]
+pub struct SignedMessage {
+    pub privkey: [u8; 32],
+    pub pubkey: CompressedPoint,
+    pub message: Vec<u8>,
+    pub signature: [u8; 64],
+}
+
+#[cfg(feature = "sign")]
+impl SignedMessage {
+    pub fn signature(&self) -> Secp256k1SchnorrSignature {
+        Secp256k1SchnorrSignature(self.signature)
+    }
+}
+
+/// ### Signed Message
+///
+/// Draws a keypair and message, signed under challenge scheme `C` with random auxiliary data, such
+/// that the signature always verifies.
+#[cfg(feature = "sign")]
+pub fn signed_message<C: Secp256k1SchnorrSign>() -> impl Strategy<Value = SignedMessage> {
+    (keypair(), message(), any::<[u8; 32]>()).prop_filter_map(
+        "signing failed",
+        |((privkey, pubkey), message, aux)| {
+            let signature =
+                Secp256k1SchnorrSignature::sign_with_aux::<C>(&message, &privkey, &aux).ok()?;
+            Some(SignedMessage {
+                privkey,
+                pubkey,
+                message,
+                signature: signature.0,
+            })
+        },
+    )
+}
+
+/// Adds a small signed offset to a big-endian 256-bit value, wrapping modulo 2²⁵⁶.
+fn offset(value: &[u8; 32], delta: i16) -> [u8; 32] {
+    let mut out = *value;
+    let mut carry = delta as i32;
+    for byte in out.iter_mut().rev() {
+        let sum = *byte as i32 + carry;
+        *byte = sum.rem_euclid(256) as u8;
+        carry = sum.div_euclid(256);
+        if carry == 0 {
+            break;
+        }
+    }
+    out
+}
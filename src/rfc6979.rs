@@ -0,0 +1,42 @@
+use solana_nostd_sha256::hashv;
+use solana_secp256k1::Curve;
+
+/// Maximum number of parts of an HMAC message, which internal callers stay well within
+const HMAC_MAX_PARTS: usize = 7;
+
+/// Computes HMAC-SHA256 of the concatenation of `prefix` and `data` under a 32-byte `key`.
+fn hmac_sha256(key: &[u8; 32], prefix: &[&[u8]], data: &[&[u8]]) -> [u8; 32] {
+    let mut ipad = [0x36u8; 64];
+    let mut opad = [0x5cu8; 64];
+    for (i, byte) in key.iter().enumerate() {
+        ipad[i] ^= byte;
+        opad[i] ^= byte;
+    }
+    let mut parts: [&[u8]; HMAC_MAX_PARTS + 1] = [&[]; HMAC_MAX_PARTS + 1];
+    let len = 1 + prefix.len() + data.len();
+    parts[0] = &ipad;
+    parts[1..=prefix.len()].copy_from_slice(prefix);
+    parts[1 + prefix.len()..len].copy_from_slice(data);
+    let inner = hashv(&parts[..len]);
+    hashv(&[&opad, &inner])
+}
+
+/// Derives the first valid scalar from an RFC6979 HMAC-DRBG seeded with `key∥data`, where the
+/// 32-byte `key` is the private key and `data` the octets of the message hash, followed by any
+/// additional data.
+pub(crate) fn rfc6979(key: &[u8; 32], data: &[&[u8]]) -> [u8; 32] {
+    let mut v = [0x01u8; 32];
+    let mut k = [0u8; 32];
+    k = hmac_sha256(&k, &[&v, &[0x00], key], data);
+    v = hmac_sha256(&k, &[&v], &[]);
+    k = hmac_sha256(&k, &[&v, &[0x01], key], data);
+    v = hmac_sha256(&k, &[&v], &[]);
+    loop {
+        v = hmac_sha256(&k, &[&v], &[]);
+        if v.ne(&[0u8; 32]) && v.lt(&Curve::N) {
+            return v;
+        }
+        k = hmac_sha256(&k, &[&v, &[0x00]], &[]);
+        v = hmac_sha256(&k, &[&v], &[]);
+    }
+}
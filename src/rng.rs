@@ -0,0 +1,132 @@
+use solana_secp256k1::{Curve, UncompressedPoint};
+
+use crate::errors::Secp256k1SchnorrError;
+
+/// Domain separator for the `SeededRng` output blocks
+const SEEDED_RNG_TAG: &[u8] = b"solana-secp256k1-schnorr/rng";
+
+/// Domain separator for deriving child generators with `SeededRng::fork`
+const SEEDED_RNG_FORK_TAG: &[u8] = b"solana-secp256k1-schnorr/rng/fork";
+
+/// ### Secp256k1SchnorrRng
+///
+/// A source of randomness injected into every API of this crate that consumes it, such as key
+/// generation, auxiliary randomness and multi-party nonces.
+///
+/// Production signers should implement it over a cryptographically secure generator, such as the
+/// operating system's. Tests can instead inject a `SeededRng`, making every protocol run
+/// reproducible from a single seed.
+pub trait Secp256k1SchnorrRng {
+    fn fill_bytes(&mut self, dest: &mut [u8]);
+}
+
+impl<R: Secp256k1SchnorrRng + ?Sized> Secp256k1SchnorrRng for &mut R {
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        (**self).fill_bytes(dest)
+    }
+}
+
+/// ### SeededRng
+///
+/// A deterministic generator expanding a 32-byte seed into the stream of blocks
+/// `SHA256(tag∥seed∥counter)`, so that a failing multi-party protocol test can be replayed
+/// exactly from the seed it printed.
+///
+/// Its output is only as secret as its seed. Never seed it with a predictable value outside of
+/// tests.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::rng::{keygen, SeededRng};
+///
+/// let mut rng = SeededRng::new([0x42; 32]);
+/// let (alice, bob) = (rng.fork(b"alice"), rng.fork(b"bob"));
+/// let privkey = keygen(&mut rng.fork(b"dealer"))?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    seed: [u8; 32],
+    counter: u64,
+    block: [u8; 32],
+    offset: usize,
+}
+
+impl SeededRng {
+    pub fn new(seed: [u8; 32]) -> Self {
+        Self {
+            seed,
+            counter: 0,
+            block: [0u8; 32],
+            offset: 32,
+        }
+    }
+
+    pub fn seed(&self) -> [u8; 32] {
+        self.seed
+    }
+
+    /// ### Fork
+    ///
+    /// Derives an independent child generator for `label`, such as the name of a protocol
+    /// participant. Forks depend only on the seed and label, not on how much output has been
+    /// drawn, so adding a participant to a test does not perturb the randomness of the others.
+    pub fn fork(&self, label: &[u8]) -> Self {
+        Self::new(solana_nostd_sha256::hashv(&[
+            SEEDED_RNG_FORK_TAG,
+            &self.seed,
+            label,
+        ]))
+    }
+}
+
+impl Secp256k1SchnorrRng for SeededRng {
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for byte in dest.iter_mut() {
+            if self.offset == 32 {
+                self.block = solana_nostd_sha256::hashv(&[
+                    SEEDED_RNG_TAG,
+                    &self.seed,
+                    &self.counter.to_le_bytes(),
+                ]);
+                self.counter += 1;
+                self.offset = 0;
+            }
+            *byte = self.block[self.offset];
+            self.offset += 1;
+        }
+    }
+}
+
+/// ### Aux
+///
+/// Draws 32 bytes of auxiliary randomness for signing.
+pub fn aux<R: Secp256k1SchnorrRng>(rng: &mut R) -> [u8; 32] {
+    let mut aux = [0u8; 32];
+    rng.fill_bytes(&mut aux);
+    aux
+}
+
+/// ### Scalar
+///
+/// Draws a uniformly random nonzero scalar modulo 𝑁 by rejection sampling, as used for private
+/// keys and nonces.
+pub fn scalar<R: Secp256k1SchnorrRng>(rng: &mut R) -> [u8; 32] {
+    loop {
+        let mut k = [0u8; 32];
+        rng.fill_bytes(&mut k);
+        if k.ne(&[0u8; 32]) && k.lt(&Curve::N) {
+            return k;
+        }
+    }
+}
+
+/// ### Keygen
+///
+/// Generates a private key and its public key.
+pub fn keygen<R: Secp256k1SchnorrRng>(
+    rng: &mut R,
+) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
+    let privkey = scalar(rng);
+    let pubkey = Curve::mul_g(&privkey).map_err(|_| Secp256k1SchnorrError::InvalidSecretKey)?;
+    Ok((privkey, pubkey))
+}
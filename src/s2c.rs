@@ -0,0 +1,170 @@
+use solana_nostd_sha256::hashv;
+use solana_secp256k1::{CompressedPoint, Secp256k1Point, UncompressedPoint};
+
+#[cfg(feature = "sign")]
+use solana_secp256k1::Curve;
+
+#[cfg(feature = "sign")]
+use crate::challenges::Secp256k1SchnorrSign;
+use crate::{ecmul, errors::Secp256k1SchnorrError, scalar::Scalar, Secp256k1SchnorrSignature};
+
+/// Domain separator for the commitment tweak 𝑡 = 𝐻(𝑅∥data)
+const S2C_TWEAK_DOMAIN: &[u8] = b"solana-secp256k1-schnorr/s2c/tweak";
+
+/// Domain separator for the host's commitment to its entropy
+const S2C_HOST_DOMAIN: &[u8] = b"solana-secp256k1-schnorr/s2c/host";
+
+/// Returns the commitment tweak 𝑡 of `data` into the original nonce point 𝑅.
+fn tweak(original_nonce: &CompressedPoint, data: &[u8]) -> Scalar {
+    Scalar::reduce(&hashv(&[S2C_TWEAK_DOMAIN, &original_nonce.0, data]))
+}
+
+/// Returns the committed nonce point 𝑅' = 𝑅 + 𝑡⋅𝐺.
+fn commit_nonce(
+    original_nonce: &CompressedPoint,
+    data: &[u8],
+) -> Result<UncompressedPoint, Secp256k1SchnorrError> {
+    let r = UncompressedPoint::try_from(*original_nonce)
+        .map_err(|_| Secp256k1SchnorrError::InvalidNonce)?;
+    ecmul::mul_add_g(
+        &r,
+        &Scalar::ONE.to_bytes(),
+        &tweak(original_nonce, data).to_bytes(),
+    )
+}
+
+/// ### Host Commit
+///
+/// Returns the host's commitment to its `data`, sent to the signing device before it reveals its
+/// original nonce with `signer_commit`, so that the device cannot pick a nonce that depends on it.
+pub fn host_commit(data: &[u8]) -> [u8; 32] {
+    hashv(&[S2C_HOST_DOMAIN, data])
+}
+
+/// ### Verify Commitment
+///
+/// Verifies that `signature` signs with the nonce 𝑅' = 𝑅 + 𝐻(𝑅∥data)⋅𝐺, where 𝑅 is the
+/// `original_nonce` the device committed to with `signer_commit`. Together with verifying the
+/// signature itself, this proves that the nonce holds the host's entropy, so a malicious device
+/// cannot exfiltrate its private key through a chosen nonce. It equally proves that the signature
+/// commits to `data` in sign-to-contract protocols.
+///
+/// Fails with `InvalidNonce` if `original_nonce` is not on the curve, and with `InvalidSignature`
+/// if the signature does not carry the committed nonce.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::{s2c, BIP340Challenge};
+///
+/// s2c::verify_commitment(&signature, &original_nonce, &host_data).expect("Invalid commitment");
+/// signature.verify::<BIP340Challenge, CompressedPoint>(message, &pubkey)?;
+/// ```
+pub fn verify_commitment(
+    signature: &Secp256k1SchnorrSignature,
+    original_nonce: &CompressedPoint,
+    data: &[u8],
+) -> Result<(), Secp256k1SchnorrError> {
+    let r = commit_nonce(original_nonce, data)?;
+    if !crate::bytes_eq(&r.x(), signature.r_ref()) {
+        return Err(Secp256k1SchnorrError::InvalidSignature);
+    }
+    Ok(())
+}
+
+/// Derives the signer's even-adjusted key pair and original nonce, with auxiliary randomness
+/// bound to the host's commitment so that both rounds derive the same nonce.
+#[cfg(feature = "sign")]
+fn original_nonce<C: Secp256k1SchnorrSign>(
+    message: &[u8],
+    privkey: &[u8; 32],
+    host_commitment: &[u8; 32],
+) -> Result<([u8; 32], UncompressedPoint, [u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
+    let mut pubkey = Curve::mul_g(privkey).map_err(|_| Secp256k1SchnorrError::InvalidPublicKey)?;
+
+    // X-only schemes sign with the secret key of the even-Y point sharing P's X-coordinate
+    let mut d = *privkey;
+    if C::XONLY && pubkey.is_odd() {
+        Curve::negate_n_assign(&mut d);
+        pubkey.invert();
+    }
+
+    #[allow(unused_mut)]
+    let mut aux = C::aux_randomness(&d, host_commitment);
+    let (k, r) = C::nonce::<UncompressedPoint>(&pubkey, message, &aux)?;
+    #[cfg(feature = "hardening")]
+    crate::hardening::zeroize(&mut aux);
+    if crate::is_zero_mod_n(&k) {
+        return Err(Secp256k1SchnorrError::InvalidNonce);
+    }
+    Ok((d, pubkey, k, r))
+}
+
+/// ### Signer Commit
+///
+/// Returns the device's original nonce point 𝑅 for signing `message`, in response to the
+/// host's commitment from `host_commit`. The host keeps it to check the signature with
+/// `verify_commitment` once it has revealed its data.
+#[cfg(feature = "sign")]
+pub fn signer_commit<C: Secp256k1SchnorrSign>(
+    message: &[u8],
+    privkey: &[u8; 32],
+    host_commitment: &[u8; 32],
+) -> Result<CompressedPoint, Secp256k1SchnorrError> {
+    #[allow(unused_mut, unused_variables)]
+    let (mut d, _, mut k, r) = original_nonce::<C>(message, privkey, host_commitment)?;
+    #[cfg(feature = "hardening")]
+    {
+        crate::hardening::zeroize(&mut d);
+        crate::hardening::zeroize(&mut k);
+    }
+    Ok(r.compress())
+}
+
+/// ### Sign With Commitment
+///
+/// Signs `message` with the nonce 𝑘 + 𝐻(𝑅∥data), committing the host's revealed `data` into
+/// the original nonce 𝑅 returned by `signer_commit`, which the signature can then be checked
+/// against with `verify_commitment`.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::{s2c, BIP340Challenge};
+///
+/// // Host
+/// let host_commitment = s2c::host_commit(&host_data);
+/// // Device
+/// let original_nonce = s2c::signer_commit::<BIP340Challenge>(message, &privkey, &host_commitment)?;
+/// // Host reveals host_data, device signs
+/// let signature = s2c::sign_with_commitment::<BIP340Challenge>(message, &privkey, &host_data)?;
+/// ```
+#[cfg(feature = "sign")]
+pub fn sign_with_commitment<C: Secp256k1SchnorrSign>(
+    message: &[u8],
+    privkey: &[u8; 32],
+    data: &[u8],
+) -> Result<Secp256k1SchnorrSignature, Secp256k1SchnorrError> {
+    #[allow(unused_mut)]
+    let (mut d, pubkey, mut k, r) = original_nonce::<C>(message, privkey, &host_commit(data))?;
+    let original_nonce = r.compress();
+
+    // k' = k + t commits to the data, negated for schemes expecting an even or square R'
+    let r = commit_nonce(&original_nonce, data)?;
+    let mut nonce = Scalar::reduce(&k).add(&tweak(&original_nonce, data));
+    if (C::EVEN_R && r.is_odd()) || (C::SQUARE_R && !crate::is_square_mod_p(&r.y())) {
+        nonce = nonce.negate();
+    }
+    #[cfg(feature = "hardening")]
+    crate::hardening::zeroize(&mut k);
+    if nonce.is_zero() {
+        return Err(Secp256k1SchnorrError::InvalidNonce);
+    }
+
+    let e = C::challenge_scalar(&r.x(), &pubkey, message);
+    if crate::is_zero_mod_n(&e) {
+        return Err(Secp256k1SchnorrError::InvalidChallenge);
+    }
+    let s = nonce.add(&Scalar::reduce(&e).mul(&Scalar::reduce(&d)));
+    #[cfg(feature = "hardening")]
+    crate::hardening::zeroize(&mut d);
+    Ok(Secp256k1SchnorrSignature::from_parts(r.x(), s.to_bytes()))
+}
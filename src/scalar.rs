@@ -0,0 +1,105 @@
+use solana_secp256k1::Curve;
+
+use crate::errors::Secp256k1SchnorrError;
+
+/// ### Scalar
+///
+/// A scalar modulo the curve order 𝑁, checked to be reduced on construction so that protocol
+/// code built on this crate, such as adaptors, key recovery or blind signatures, can perform
+/// arithmetic on private keys, nonces and challenges without bypassing range checks.
+///
+/// Every operation returns a reduced scalar, with inversion the only fallible one.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::scalar::Scalar;
+///
+/// // s = k + e*d
+/// let s = Scalar::new(k)?.add(&Scalar::new(e)?.mul(&Scalar::new(privkey)?));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scalar([u8; 32]);
+
+impl Scalar {
+    pub const ZERO: Self = Self([0u8; 32]);
+
+    pub const ONE: Self = Self([
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x01,
+    ]);
+
+    /// Fails with `ArithmeticOverflow` if `bytes` is not reduced modulo 𝑁.
+    pub fn new(bytes: [u8; 32]) -> Result<Self, Secp256k1SchnorrError> {
+        match bytes.lt(&Curve::N) {
+            true => Ok(Self(bytes)),
+            false => Err(Secp256k1SchnorrError::ArithmeticOverflow),
+        }
+    }
+
+    /// Fails with `InvalidSecretKey` unless `bytes` is a valid private key, a nonzero scalar
+    /// modulo 𝑁.
+    pub fn new_nonzero(bytes: [u8; 32]) -> Result<Self, Secp256k1SchnorrError> {
+        match Self::new(bytes) {
+            Ok(scalar) if !scalar.is_zero() => Ok(scalar),
+            _ => Err(Secp256k1SchnorrError::InvalidSecretKey),
+        }
+    }
+
+    /// Reduces an arbitrary 32-byte value modulo 𝑁, as is done for hash outputs such as
+    /// challenges.
+    pub fn reduce(bytes: &[u8; 32]) -> Self {
+        Self(Curve::add_mod_n(bytes, &[0u8; 32]))
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    pub fn is_zero(&self) -> bool {
+        crate::bytes_eq(&self.0, &[0u8; 32])
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        Self(Curve::add_mod_n(&self.0, &other.0))
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        self.add(&other.negate())
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        Self(Curve::mul_mod_n(&self.0, &other.0))
+    }
+
+    pub fn negate(&self) -> Self {
+        Self(Curve::negate_n(&self.0))
+    }
+
+    /// ### Invert
+    ///
+    /// Computes the modular inverse 𝑘⁻¹ mod 𝑁, failing with `ArithmeticOverflow` for zero, which
+    /// has no inverse.
+    pub fn invert(&self) -> Result<Self, Secp256k1SchnorrError> {
+        if self.is_zero() {
+            return Err(Secp256k1SchnorrError::ArithmeticOverflow);
+        }
+        Curve::mod_inv_n(&self.0)
+            .map(Self)
+            .map_err(|_| Secp256k1SchnorrError::ArithmeticOverflow)
+    }
+}
+
+impl TryFrom<[u8; 32]> for Scalar {
+    type Error = Secp256k1SchnorrError;
+
+    fn try_from(bytes: [u8; 32]) -> Result<Self, Self::Error> {
+        Self::new(bytes)
+    }
+}
+
+impl From<Scalar> for [u8; 32] {
+    fn from(scalar: Scalar) -> Self {
+        scalar.0
+    }
+}
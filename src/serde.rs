@@ -0,0 +1,70 @@
+use core::fmt;
+
+use ::serde::{
+    de::{self, SeqAccess, Visitor},
+    ser::SerializeTuple,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{Secp256k1SchnorrSignature, SECP256K1_SCHNORR_SIGNATURE_LENGTH};
+
+/// Serializes signatures as a 128-digit lowercase hex string in human-readable formats such as
+/// JSON, and as a fixed 64-byte array otherwise.
+impl Serialize for Secp256k1SchnorrSignature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            let mut tuple = serializer.serialize_tuple(SECP256K1_SCHNORR_SIGNATURE_LENGTH)?;
+            for byte in self.0.iter() {
+                tuple.serialize_element(byte)?;
+            }
+            tuple.end()
+        }
+    }
+}
+
+struct SignatureVisitor;
+
+impl<'de> Visitor<'de> for SignatureVisitor {
+    type Value = Secp256k1SchnorrSignature;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a 64-byte Schnorr signature")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        value
+            .parse()
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+        let bytes = value
+            .try_into()
+            .map_err(|_| E::invalid_length(value.len(), &self))?;
+        Ok(Secp256k1SchnorrSignature(bytes))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = [0u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        Ok(Secp256k1SchnorrSignature(bytes))
+    }
+}
+
+/// Deserializes signatures from hex in human-readable formats, and from a fixed 64-byte array
+/// otherwise. No validation is performed, so a malformed signature simply fails verification.
+impl<'de> Deserialize<'de> for Secp256k1SchnorrSignature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(SignatureVisitor)
+        } else {
+            deserializer.deserialize_tuple(SECP256K1_SCHNORR_SIGNATURE_LENGTH, SignatureVisitor)
+        }
+    }
+}
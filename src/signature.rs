@@ -0,0 +1,154 @@
+use core::{fmt, marker::PhantomData};
+
+use ::signature::{Error, Verifier};
+use solana_secp256k1::{CompressedPoint, Secp256k1Point};
+
+#[cfg(feature = "sign")]
+use crate::{challenges::Secp256k1SchnorrSign, errors::Secp256k1SchnorrError, scalar::Scalar};
+use crate::{challenges::Secp256k1SchnorrVerify, Secp256k1SchnorrSignature};
+
+/// # VerifyingKey
+///
+/// A public key bound to the challenge scheme `C`, implementing `signature::Verifier` so that it
+/// can be used by frameworks generic over the RustCrypto signature traits. The underlying error
+/// is not preserved, as `signature::Error` is opaque by design.
+///
+/// Example:
+/// ```rs
+/// use signature::Verifier;
+/// use solana_secp256k1_schnorr::{signature::VerifyingKey, BIP340Challenge};
+///
+/// let verifying_key = VerifyingKey::<BIP340Challenge>::new(&pubkey);
+/// verifying_key.verify(b"test", &schnorr_signature).expect("Invalid signature");
+/// ```
+pub struct VerifyingKey<C> {
+    pubkey: CompressedPoint,
+    challenge: PhantomData<C>,
+}
+
+impl<C> VerifyingKey<C> {
+    pub fn new<T: Secp256k1Point>(pubkey: &T) -> Self {
+        Self {
+            pubkey: pubkey.compress(),
+            challenge: PhantomData,
+        }
+    }
+
+    pub fn pubkey(&self) -> &CompressedPoint {
+        &self.pubkey
+    }
+}
+
+// Implemented by hand so that the scheme marker need not implement these traits itself
+impl<C> Clone for VerifyingKey<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for VerifyingKey<C> {}
+
+impl<C> PartialEq for VerifyingKey<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.pubkey.0.eq(&other.pubkey.0)
+    }
+}
+
+impl<C> Eq for VerifyingKey<C> {}
+
+impl<C> fmt::Debug for VerifyingKey<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("VerifyingKey").field(&self.pubkey.0).finish()
+    }
+}
+
+impl<C: Secp256k1SchnorrVerify> Verifier<Secp256k1SchnorrSignature> for VerifyingKey<C> {
+    fn verify(&self, msg: &[u8], signature: &Secp256k1SchnorrSignature) -> Result<(), Error> {
+        signature
+            .verify::<C, CompressedPoint>(msg, &self.pubkey)
+            .map_err(|_| Error::new())
+    }
+}
+
+/// # SigningKey
+///
+/// A private key bound to the challenge scheme `C`, implementing `signature::Signer` and
+/// `signature::Keypair`. Signing is deterministic, as `Secp256k1SchnorrSignature::sign`, while
+/// with the "rand_core" feature flag enabled it also implements `signature::RandomizedSigner`.
+///
+/// With the "hardening" feature flag enabled, the private key is zeroized on drop.
+///
+/// Example:
+/// ```rs
+/// use signature::{Keypair, Signer, Verifier};
+/// use solana_secp256k1_schnorr::{signature::SigningKey, BIP340Challenge};
+///
+/// let signing_key = SigningKey::<BIP340Challenge>::new(privkey)?;
+/// let schnorr_signature = signing_key.sign(b"test");
+/// signing_key.verifying_key().verify(b"test", &schnorr_signature).expect("Invalid signature");
+/// ```
+#[cfg(feature = "sign")]
+pub struct SigningKey<C> {
+    privkey: [u8; 32],
+    verifying_key: VerifyingKey<C>,
+}
+
+#[cfg(feature = "sign")]
+impl<C> SigningKey<C> {
+    /// Fails with `InvalidSecretKey` unless `privkey` is a nonzero scalar modulo 𝑁.
+    pub fn new(privkey: [u8; 32]) -> Result<Self, Secp256k1SchnorrError> {
+        Scalar::new_nonzero(privkey)?;
+        let pubkey = crate::ecmul::mul_g(&privkey)?;
+        Ok(Self {
+            privkey,
+            verifying_key: VerifyingKey::new(&pubkey),
+        })
+    }
+}
+
+#[cfg(feature = "sign")]
+impl<C> Clone for SigningKey<C> {
+    fn clone(&self) -> Self {
+        Self {
+            privkey: self.privkey,
+            verifying_key: self.verifying_key,
+        }
+    }
+}
+
+#[cfg(feature = "sign")]
+impl<C: Secp256k1SchnorrSign> ::signature::Signer<Secp256k1SchnorrSignature> for SigningKey<C> {
+    fn try_sign(&self, msg: &[u8]) -> Result<Secp256k1SchnorrSignature, Error> {
+        Secp256k1SchnorrSignature::sign::<C>(msg, &self.privkey).map_err(|_| Error::new())
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl<C: Secp256k1SchnorrSign> ::signature::RandomizedSigner<Secp256k1SchnorrSignature>
+    for SigningKey<C>
+{
+    fn try_sign_with_rng(
+        &self,
+        rng: &mut impl rand_core::CryptoRngCore,
+        msg: &[u8],
+    ) -> Result<Secp256k1SchnorrSignature, Error> {
+        Secp256k1SchnorrSignature::sign_with_rng::<C, _>(msg, &self.privkey, rng)
+            .map_err(|_| Error::new())
+    }
+}
+
+#[cfg(feature = "sign")]
+impl<C> ::signature::Keypair for SigningKey<C> {
+    type VerifyingKey = VerifyingKey<C>;
+
+    fn verifying_key(&self) -> VerifyingKey<C> {
+        self.verifying_key
+    }
+}
+
+#[cfg(all(feature = "sign", feature = "hardening"))]
+impl<C> Drop for SigningKey<C> {
+    fn drop(&mut self) {
+        crate::hardening::zeroize(&mut self.privkey);
+    }
+}
@@ -0,0 +1,84 @@
+use solana_nostd_sha256::hashv;
+use solana_secp256k1::{Curve, Secp256k1Point};
+
+use crate::{ecmul, errors::Secp256k1SchnorrError, keys::lift_x, scalar::Scalar};
+
+// sha256(TapTweak) tagged hash
+const TAP_TWEAK: [u8; 32] = [
+    0xe8, 0x0f, 0xe1, 0x63, 0x9c, 0x9c, 0xa0, 0x50, 0xe3, 0xaf, 0x1b, 0x39, 0xc1, 0x43, 0xc6, 0x3e,
+    0x42, 0x9c, 0xbc, 0xeb, 0x15, 0xd9, 0x40, 0xfb, 0xb5, 0xc5, 0xa1, 0xf4, 0xaf, 0x57, 0xc5, 0xe9,
+];
+
+/// Computes the tweak 𝑡 = hash_TapTweak(𝑃ₓ∥ℎ), failing with `ArithmeticOverflow` if 𝑡 ≥ 𝑁.
+fn tap_tweak(
+    internal_key: &[u8; 32],
+    merkle_root: Option<&[u8; 32]>,
+) -> Result<[u8; 32], Secp256k1SchnorrError> {
+    let t = hashv(&[
+        TAP_TWEAK.as_ref(),
+        TAP_TWEAK.as_ref(),
+        internal_key.as_ref(),
+        merkle_root.map_or(&[], |root| root.as_ref()),
+    ]);
+    if t.ge(&Curve::N) {
+        return Err(Secp256k1SchnorrError::ArithmeticOverflow);
+    }
+    Ok(t)
+}
+
+/// ### Taproot Tweak Pubkey
+///
+/// Tweaks the 𝑋-only `internal_key` of a Taproot output with the `merkle_root` of its script
+/// tree, or with no script tree if `None`, as defined by `taproot_tweak_pubkey` in BIP341.
+///
+/// Returns the 𝑋-only output key 𝑄 = 𝑃 + 𝑡⋅𝐺 against which key-spend signatures verify, and
+/// whether 𝑄 has an odd 𝑌-coordinate, as committed to by script-spend control blocks. 𝑄 is
+/// computed with a single call to `secp256k1_recover`.
+///
+/// Fails with `InvalidPublicKey` if `internal_key` is not the 𝑋-coordinate of a point on the
+/// curve, `ArithmeticOverflow` if the tweak is not a valid scalar, and `PointAtInfinity` if 𝑄 is.
+///
+/// Example:
+/// ```rs
+/// use solana_secp256k1_schnorr::{taproot::taproot_tweak_pubkey, BIP340Challenge};
+///
+/// let (output_key, _) = taproot_tweak_pubkey(&internal_key, None)?;
+/// schnorr_signature.verify_xonly::<BIP340Challenge>(&sighash, &output_key)
+///     .expect("Invalid signature");
+/// ```
+pub fn taproot_tweak_pubkey(
+    internal_key: &[u8; 32],
+    merkle_root: Option<&[u8; 32]>,
+) -> Result<([u8; 32], bool), Secp256k1SchnorrError> {
+    let p = lift_x(internal_key)?;
+    let t = tap_tweak(internal_key, merkle_root)?;
+    let q = ecmul::mul_add_g(&p, &Scalar::ONE.to_bytes(), &t)?;
+    Ok((q.x(), q.is_odd()))
+}
+
+/// ### Taproot Tweak Seckey
+///
+/// Tweaks a secret key with the `merkle_root` of its output's script tree, as defined by
+/// `taproot_tweak_seckey` in BIP341, yielding the secret key of the output key returned by
+/// `taproot_tweak_pubkey` for key-spend signing.
+///
+/// Fails with `InvalidSecretKey` if `privkey` or the tweaked key is not a valid secret key, and
+/// `ArithmeticOverflow` if the tweak is not a valid scalar.
+#[cfg(feature = "sign")]
+pub fn taproot_tweak_seckey(
+    privkey: &[u8; 32],
+    merkle_root: Option<&[u8; 32]>,
+) -> Result<[u8; 32], Secp256k1SchnorrError> {
+    let d = Scalar::new_nonzero(*privkey)?;
+    let p = ecmul::mul_g(privkey).map_err(|_| Secp256k1SchnorrError::InvalidSecretKey)?;
+    let d = match p.is_odd() {
+        true => d.negate(),
+        false => d,
+    };
+    let t = tap_tweak(&p.x(), merkle_root)?;
+    let tweaked = d.add(&Scalar::reduce(&t));
+    if tweaked.is_zero() {
+        return Err(Secp256k1SchnorrError::InvalidSecretKey);
+    }
+    Ok(tweaked.to_bytes())
+}
@@ -1,10 +1,87 @@
-use solana_secp256k1::CompressedPoint;
+use solana_secp256k1::{CompressedPoint, Curve, Secp256k1Point, UncompressedPoint};
 
 use crate::{
-    challenges::{bip340::BIP340Challenge, keccak256::Keccak256Challenge, sha256::Sha256Challenge},
+    challenges::{
+        bip340::BIP340Challenge, keccak256::Keccak256Challenge, sha256::Sha256Challenge,
+        Secp256k1SchnorrSign, Secp256k1SchnorrVerify,
+    },
+    errors::Secp256k1SchnorrError,
+    keys::lift_x,
+    scalar::Scalar,
     Secp256k1SchnorrSignature, SECP256K1_SCHNORR_SIGNATURE_LENGTH,
 };
 
+const PRIVKEY_ONE: [u8; 32] = [
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+];
+
+/// Implements signing for a test scheme with the fixed nonce scalar `$k`, whose nonce point is
+/// always reported as 𝐺
+macro_rules! fixed_nonce {
+    ($scheme:ty, $k:expr) => {
+        impl Secp256k1SchnorrSign for $scheme {
+            fn aux_randomness(privkey: &[u8; 32], _aux: &[u8; 32]) -> [u8; 32] {
+                *privkey
+            }
+
+            fn nonce<T: Secp256k1Point>(
+                _pubkey: &T,
+                _message: &[u8],
+                _aux: &[u8; 32],
+            ) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
+                Ok(($k, Curve::G))
+            }
+        }
+    };
+}
+
+/// A degenerate scheme whose challenge is always 𝑁, which is congruent to zero
+struct ZeroChallenge;
+
+impl Secp256k1SchnorrVerify for ZeroChallenge {
+    fn challenge<T: Secp256k1Point>(_r: &[u8; 32], _pubkey: &T, _message: &[u8]) -> [u8; 32] {
+        Curve::N
+    }
+}
+
+fixed_nonce!(ZeroChallenge, PRIVKEY_ONE);
+
+/// A scheme whose challenge is always 𝑁 + 1, an unreduced encoding of 1
+struct UnreducedChallenge;
+
+impl Secp256k1SchnorrVerify for UnreducedChallenge {
+    fn challenge<T: Secp256k1Point>(_r: &[u8; 32], _pubkey: &T, _message: &[u8]) -> [u8; 32] {
+        let mut e = Curve::N;
+        e[31] += 1;
+        e
+    }
+}
+
+fixed_nonce!(UnreducedChallenge, PRIVKEY_ONE);
+
+/// A scheme whose challenge is always 1, equivalent to `UnreducedChallenge`
+struct OneChallenge;
+
+impl Secp256k1SchnorrVerify for OneChallenge {
+    fn challenge<T: Secp256k1Point>(_r: &[u8; 32], _pubkey: &T, _message: &[u8]) -> [u8; 32] {
+        PRIVKEY_ONE
+    }
+}
+
+fixed_nonce!(OneChallenge, PRIVKEY_ONE);
+
+/// A degenerate scheme whose nonce is always zero
+struct ZeroNonceChallenge;
+
+impl Secp256k1SchnorrVerify for ZeroNonceChallenge {
+    fn challenge<T: Secp256k1Point>(r: &[u8; 32], pubkey: &T, message: &[u8]) -> [u8; 32] {
+        BIP340Challenge::challenge(r, pubkey, message)
+    }
+}
+
+fixed_nonce!(ZeroNonceChallenge, [0u8; 32]);
+
 #[test]
 fn test_signature_verification_bip340() {
     let signature: [u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH] = [
@@ -93,15 +170,39 @@ fn test_signature_generation_keccak256() {
     assert_eq!(
         schnorr_signature.0,
         [
-            0xee, 0x30, 0x11, 0xa0, 0x61, 0xf1, 0x2b, 0x5c, 0x6d, 0x14, 0xb8, 0xbc, 0x6d, 0x4b,
-            0xb4, 0x77, 0x91, 0xca, 0xd9, 0x15, 0x2e, 0xb6, 0x0a, 0x7e, 0x76, 0x9f, 0x27, 0x86,
-            0x9f, 0xf3, 0x89, 0xd2, 0x04, 0xa5, 0xb0, 0x61, 0x5f, 0x39, 0xc7, 0x77, 0x1a, 0x14,
-            0xf7, 0xce, 0x17, 0xce, 0x8b, 0x92, 0x51, 0xfd, 0x41, 0x18, 0xd9, 0xde, 0x06, 0x37,
-            0xc7, 0x5c, 0x04, 0x46, 0x09, 0xfc, 0x9c, 0x04,
+            0x61, 0x14, 0xc8, 0xed, 0x4e, 0xb6, 0x97, 0x95, 0x0c, 0x46, 0xc4, 0xfb, 0x94, 0xf0,
+            0x42, 0xa1, 0x03, 0x17, 0xa9, 0x07, 0x36, 0xd4, 0x2d, 0xd4, 0xe4, 0x26, 0x3e, 0x50,
+            0xd3, 0xcb, 0x51, 0x53, 0x3c, 0xd7, 0xe9, 0x06, 0xa7, 0x67, 0xa6, 0x8c, 0xb5, 0x0b,
+            0xb5, 0x05, 0xc8, 0x36, 0x20, 0x74, 0x44, 0x91, 0xa9, 0xa8, 0x1b, 0x0c, 0xcb, 0x8e,
+            0x0f, 0xa0, 0x46, 0x22, 0x88, 0x0c, 0xc0, 0x28,
         ]
     );
 }
 
+// Signed after nonce derivation was domain-separated
+#[cfg_attr(feature = "keccak256", test)]
+fn test_signature_verification_keccak256_tagged_nonce() {
+    let signature: [u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH] = [
+        0x61, 0x14, 0xc8, 0xed, 0x4e, 0xb6, 0x97, 0x95, 0x0c, 0x46, 0xc4, 0xfb, 0x94, 0xf0, 0x42,
+        0xa1, 0x03, 0x17, 0xa9, 0x07, 0x36, 0xd4, 0x2d, 0xd4, 0xe4, 0x26, 0x3e, 0x50, 0xd3, 0xcb,
+        0x51, 0x53, 0x3c, 0xd7, 0xe9, 0x06, 0xa7, 0x67, 0xa6, 0x8c, 0xb5, 0x0b, 0xb5, 0x05, 0xc8,
+        0x36, 0x20, 0x74, 0x44, 0x91, 0xa9, 0xa8, 0x1b, 0x0c, 0xcb, 0x8e, 0x0f, 0xa0, 0x46, 0x22,
+        0x88, 0x0c, 0xc0, 0x28,
+    ];
+    let message = *b"test";
+    let pubkey = CompressedPoint([
+        0x02, 0x79, 0xbe, 0x66, 0x7e, 0xf9, 0xdc, 0xbb, 0xac, 0x55, 0xa0, 0x62, 0x95, 0xce, 0x87,
+        0x0b, 0x07, 0x02, 0x9b, 0xfc, 0xdb, 0x2d, 0xce, 0x28, 0xd9, 0x59, 0xf2, 0x81, 0x5b, 0x16,
+        0xf8, 0x17, 0x98,
+    ]);
+
+    let schnorr_signature = Secp256k1SchnorrSignature(signature);
+
+    schnorr_signature
+        .verify::<Keccak256Challenge, CompressedPoint>(&message, &pubkey)
+        .expect("Invalid signature");
+}
+
 #[cfg(feature = "sign")]
 #[cfg_attr(feature = "sha256", test)]
 fn test_signature_verification_sha256() {
@@ -142,11 +243,5361 @@ fn test_signature_generation_sha256() {
     assert_eq!(
         schnorr_signature.0,
         [
-            0x36, 0x4d, 0x7d, 0x6a, 0x77, 0xca, 0xe2, 0xf7, 0x88, 0x28, 0x79, 0xfe, 0x0b, 0xd5,
-            0x03, 0x57, 0x03, 0x39, 0x31, 0xa8, 0x05, 0x6d, 0xa5, 0x23, 0x86, 0xcf, 0x7b, 0x16,
-            0x52, 0x0e, 0xe6, 0x66, 0xf6, 0xfb, 0xac, 0xa7, 0xa4, 0xd3, 0x1b, 0xdf, 0x29, 0x3c,
-            0xd8, 0xe9, 0x9a, 0x93, 0x5e, 0x0e, 0x9a, 0xf3, 0xf9, 0xdd, 0xdf, 0xef, 0xbc, 0x85,
-            0xf6, 0x0a, 0xb9, 0x5f, 0x00, 0x06, 0x8a, 0x31
+            0xd0, 0x1f, 0xa4, 0xc9, 0x84, 0x4d, 0x0b, 0x5a, 0x3d, 0x15, 0x14, 0x54, 0xa2, 0xce,
+            0xbc, 0xbd, 0x1e, 0x57, 0xe1, 0x37, 0x7b, 0x62, 0x85, 0x8c, 0x9c, 0x07, 0xb8, 0x43,
+            0x60, 0x2c, 0xc3, 0x7e, 0x28, 0xd2, 0x38, 0x06, 0x8e, 0x13, 0x73, 0x49, 0xb1, 0x07,
+            0x73, 0xda, 0xdc, 0xd8, 0x80, 0xd0, 0xa7, 0xb4, 0xa6, 0x6e, 0x60, 0x76, 0x9e, 0x2c,
+            0x20, 0x96, 0x4b, 0x0a, 0x26, 0xc0, 0xf1, 0x87,
+        ]
+    );
+}
+
+// Signed after nonce derivation was domain-separated
+#[cfg(feature = "sign")]
+#[cfg_attr(feature = "sha256", test)]
+fn test_signature_verification_sha256_tagged_nonce() {
+    let signature: [u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH] = [
+        0xd0, 0x1f, 0xa4, 0xc9, 0x84, 0x4d, 0x0b, 0x5a, 0x3d, 0x15, 0x14, 0x54, 0xa2, 0xce, 0xbc,
+        0xbd, 0x1e, 0x57, 0xe1, 0x37, 0x7b, 0x62, 0x85, 0x8c, 0x9c, 0x07, 0xb8, 0x43, 0x60, 0x2c,
+        0xc3, 0x7e, 0x28, 0xd2, 0x38, 0x06, 0x8e, 0x13, 0x73, 0x49, 0xb1, 0x07, 0x73, 0xda, 0xdc,
+        0xd8, 0x80, 0xd0, 0xa7, 0xb4, 0xa6, 0x6e, 0x60, 0x76, 0x9e, 0x2c, 0x20, 0x96, 0x4b, 0x0a,
+        0x26, 0xc0, 0xf1, 0x87,
+    ];
+    let message = *b"test";
+    let pubkey = CompressedPoint([
+        0x02, 0x79, 0xbe, 0x66, 0x7e, 0xf9, 0xdc, 0xbb, 0xac, 0x55, 0xa0, 0x62, 0x95, 0xce, 0x87,
+        0x0b, 0x07, 0x02, 0x9b, 0xfc, 0xdb, 0x2d, 0xce, 0x28, 0xd9, 0x59, 0xf2, 0x81, 0x5b, 0x16,
+        0xf8, 0x17, 0x98,
+    ]);
+
+    let schnorr_signature = Secp256k1SchnorrSignature(signature);
+
+    schnorr_signature
+        .verify::<Sha256Challenge, CompressedPoint>(&message, &pubkey)
+        .expect("Invalid signature");
+}
+
+#[cfg(feature = "sign")]
+#[test]
+fn test_signature_generation_zero_challenge() {
+    assert_eq!(
+        Secp256k1SchnorrSignature::sign::<ZeroChallenge>(b"test", &PRIVKEY_ONE).err(),
+        Some(Secp256k1SchnorrError::InvalidChallenge)
+    );
+}
+
+#[cfg(feature = "sign")]
+#[test]
+fn test_signature_generation_zero_nonce() {
+    assert_eq!(
+        Secp256k1SchnorrSignature::sign::<ZeroNonceChallenge>(b"test", &PRIVKEY_ONE).err(),
+        Some(Secp256k1SchnorrError::InvalidNonce)
+    );
+}
+
+#[test]
+fn test_signature_verification_zero_challenge() {
+    let pubkey = CompressedPoint::from(Curve::G);
+    let mut signature = [0u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH];
+    signature[..32].clone_from_slice(&Curve::G.x());
+    signature[63] = 0x01;
+
+    assert_eq!(
+        Secp256k1SchnorrSignature(signature)
+            .verify::<ZeroChallenge, CompressedPoint>(b"test", &pubkey)
+            .err(),
+        Some(Secp256k1SchnorrError::InvalidChallenge)
+    );
+}
+
+#[test]
+fn test_signature_verification_pubkey_x_overflow() {
+    let mut pubkey = [0u8; 33];
+    pubkey[0] = 0x02;
+    pubkey[1..].clone_from_slice(&Curve::P);
+
+    let mut signature = [0u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH];
+    signature[..32].clone_from_slice(&Curve::G.x());
+    signature[63] = 0x01;
+
+    assert_eq!(
+        Secp256k1SchnorrSignature(signature)
+            .verify::<BIP340Challenge, CompressedPoint>(b"test", &CompressedPoint(pubkey))
+            .err(),
+        Some(Secp256k1SchnorrError::InvalidPublicKey)
+    );
+}
+
+#[test]
+fn test_lift_x() {
+    assert_eq!(lift_x(&Curve::G.x()), Ok(Curve::G));
+
+    // The square root of 2³ + 7 is odd, so the lift must be negated
+    let mut x = [0u8; 32];
+    x[31] = 0x02;
+    let point = lift_x(&x).expect("Invalid X coordinate");
+    assert!(point.is_even());
+    assert_eq!(point.x(), x);
+}
+
+#[test]
+fn test_lift_x_invalid() {
+    // 0³ + 7 is not a quadratic residue modulo 𝑃
+    assert_eq!(
+        lift_x(&[0u8; 32]),
+        Err(Secp256k1SchnorrError::InvalidPublicKey)
+    );
+    assert_eq!(
+        lift_x(&Curve::P),
+        Err(Secp256k1SchnorrError::InvalidPublicKey)
+    );
+}
+
+#[test]
+fn test_signature_verification_point_at_infinity() {
+    // With P = G, choosing s = e forces s*G == e*P, so R = s*G - e*P is the identity
+    let pubkey = CompressedPoint::from(Curve::G);
+    let message = *b"test";
+    let r = Curve::G.x();
+    let e = BIP340Challenge::challenge(&r, &pubkey, &message);
+
+    let mut signature = [0u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH];
+    signature[..32].clone_from_slice(&r);
+    signature[32..].clone_from_slice(&Curve::add_mod_n(&e, &[0u8; 32]));
+
+    assert_eq!(
+        Secp256k1SchnorrSignature(signature)
+            .verify::<BIP340Challenge, CompressedPoint>(&message, &pubkey)
+            .err(),
+        Some(Secp256k1SchnorrError::PointAtInfinity)
+    );
+}
+
+#[cfg_attr(feature = "sha256", test)]
+fn test_signature_verification_point_at_infinity_odd_pubkey() {
+    // The same construction against an odd-Y key, P = -G, requires s = -e. BIP340 treats -G as G,
+    // so a scheme that verifies against the full point is used.
+    let mut pubkey = CompressedPoint::from(Curve::G);
+    pubkey.invert();
+    let message = *b"test";
+    let r = Curve::G.x();
+    let e = Sha256Challenge::challenge(&r, &pubkey, &message);
+
+    let mut signature = [0u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH];
+    signature[..32].clone_from_slice(&r);
+    signature[32..].clone_from_slice(&Curve::negate_n(&e));
+
+    assert_eq!(
+        Secp256k1SchnorrSignature(signature)
+            .verify::<Sha256Challenge, CompressedPoint>(&message, &pubkey)
+            .err(),
+        Some(Secp256k1SchnorrError::PointAtInfinity)
+    );
+}
+
+#[cfg(feature = "audit")]
+#[test]
+fn test_audit_kat_suite_is_deterministic() {
+    assert_eq!(crate::audit::kat_suite(), crate::audit::kat_suite());
+    assert_eq!(
+        crate::audit::kat_suite_json(),
+        crate::audit::kat_suite_json()
+    );
+}
+
+#[cfg(feature = "audit")]
+#[test]
+fn test_audit_transcript_records_signing_path() {
+    let (transcript, signature) = crate::audit::capture("bip340", || {
+        Secp256k1SchnorrSignature::sign::<BIP340Challenge>(b"test", &PRIVKEY_ONE)
+    });
+    let signature = signature.expect("Invalid signature");
+
+    let labels: Vec<&str> = transcript.entries.iter().map(|e| e.label).collect();
+    assert_eq!(
+        labels,
+        [
+            "sign.aux",
+            "sign.P",
+            "sign.k",
+            "sign.R",
+            "sign.e",
+            "sign.signature"
         ]
     );
-}
\ No newline at end of file
+    assert_eq!(transcript.entries[5].value, signature.0);
+    assert!(transcript
+        .to_json()
+        .starts_with("{\"name\":\"bip340\",\"entries\":["));
+}
+
+#[cfg(all(feature = "audit", feature = "verify-after-sign"))]
+#[test]
+fn test_audit_transcript_excludes_self_check() {
+    let pubkey = Curve::mul_g(&PRIVKEY_ONE).unwrap();
+    let (transcript, _) = crate::audit::capture("bip340", || {
+        let signature =
+            Secp256k1SchnorrSignature::sign::<BIP340Challenge>(b"test", &PRIVKEY_ONE).unwrap();
+        signature.verify::<BIP340Challenge, UncompressedPoint>(b"test", &pubkey)
+    });
+
+    // Only the explicit verification is recorded, and recording resumes after signing
+    let labels: Vec<&str> = transcript.entries.iter().map(|e| e.label).collect();
+    assert_eq!(labels.iter().filter(|l| l.starts_with("sign.")).count(), 6);
+    assert_eq!(labels.iter().filter(|l| **l == "verify.e").count(), 1);
+}
+
+#[test]
+fn test_signature_verification_bip340_odd_r() {
+    // Find a nonce whose R has an odd Y-coordinate
+    let mut k = PRIVKEY_ONE;
+    let mut r = Curve::mul_g(&k).unwrap();
+    while r.is_even() {
+        k[31] += 1;
+        r = Curve::mul_g(&k).unwrap();
+    }
+
+    // Sign with P = G, so s = k + e
+    let pubkey = CompressedPoint::from(Curve::G);
+    let message = *b"test";
+    let e = BIP340Challenge::challenge(&r.x(), &pubkey, &message);
+
+    let mut signature = [0u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH];
+    signature[..32].clone_from_slice(&r.x());
+    signature[32..].clone_from_slice(&Curve::add_mod_n(&k, &e));
+
+    // The X-coordinates match, but BIP340 only accepts an even R
+    assert_eq!(
+        Secp256k1SchnorrSignature(signature)
+            .verify::<BIP340Challenge, CompressedPoint>(&message, &pubkey)
+            .err(),
+        Some(Secp256k1SchnorrError::InvalidSignature)
+    );
+}
+
+#[cfg(feature = "dudect")]
+#[test]
+fn test_dudect_no_samples() {
+    let report = crate::dudect::test_signing::<BIP340Challenge>(&PRIVKEY_ONE, b"test", 0, 7);
+    assert_eq!(report.samples, 0);
+    assert_eq!(report.max_t, 0.0);
+}
+
+// Wall-clock statistics are slow and sensitive to machine load, so run explicitly with
+// `cargo test --release --features dudect -- --ignored`
+#[cfg(feature = "dudect")]
+#[test]
+#[ignore]
+fn test_dudect_signing_bip340() {
+    let report = crate::dudect::test_signing::<BIP340Challenge>(&PRIVKEY_ONE, b"test", 5000, 7);
+    assert!(report.passed(), "Timing leak detected: {:?}", report);
+}
+
+#[cfg(feature = "hardening")]
+#[test]
+fn test_hardening_zeroize() {
+    let mut secret = [0xffu8; 32];
+    crate::hardening::zeroize(&mut secret);
+    assert_eq!(secret, [0u8; 32]);
+}
+
+#[cfg(feature = "hardening")]
+#[test]
+fn test_hardening_zeroizing() {
+    use crate::hardening::Zeroizing;
+
+    let mut guard = core::mem::MaybeUninit::new(Zeroizing([0xffu8; 32]));
+    assert_eq!(**unsafe { guard.assume_init_ref() }, [0xff; 32]);
+    // Safety: the guard is initialized, and its bytes remain readable after being dropped in place
+    let wiped = unsafe {
+        guard.assume_init_drop();
+        *(guard.as_ptr() as *const [u8; 32])
+    };
+    assert_eq!(wiped, [0u8; 32]);
+}
+
+#[cfg(feature = "hardening")]
+#[test]
+fn test_hardening_signing_paths() {
+    // Scrubbing must not alter the signatures produced
+    let signature = Secp256k1SchnorrSignature::sign::<BIP340Challenge>(b"test", &PRIVKEY_ONE)
+        .expect("Invalid signature");
+    crate::hardening::scrub_stack();
+    let scrubbed = Secp256k1SchnorrSignature::sign::<BIP340Challenge>(b"test", &PRIVKEY_ONE)
+        .expect("Invalid signature");
+    assert_eq!(scrubbed.0, signature.0);
+    scrubbed
+        .verify::<BIP340Challenge, CompressedPoint>(b"test", &CompressedPoint::from(Curve::G))
+        .expect("Invalid signature");
+
+    // Secrets are held by drop guards, so failures past their derivation wipe them too
+    assert_eq!(
+        Secp256k1SchnorrSignature::sign::<ZeroNonceChallenge>(b"test", &PRIVKEY_ONE).err(),
+        Some(Secp256k1SchnorrError::InvalidNonce)
+    );
+    assert_eq!(
+        Secp256k1SchnorrSignature::sign::<ZeroChallenge>(b"test", &PRIVKEY_ONE).err(),
+        Some(Secp256k1SchnorrError::InvalidChallenge)
+    );
+}
+
+/// A faulty scheme whose nonce point R does not correspond to its nonce scalar k
+#[cfg(feature = "verify-after-sign")]
+struct FaultyNonceChallenge;
+
+#[cfg(feature = "verify-after-sign")]
+impl Secp256k1SchnorrVerify for FaultyNonceChallenge {
+    fn challenge<T: Secp256k1Point>(r: &[u8; 32], pubkey: &T, message: &[u8]) -> [u8; 32] {
+        Sha256Challenge::challenge(r, pubkey, message)
+    }
+}
+
+#[cfg(feature = "verify-after-sign")]
+impl Secp256k1SchnorrSign for FaultyNonceChallenge {
+    fn aux_randomness(privkey: &[u8; 32], aux: &[u8; 32]) -> [u8; 32] {
+        Sha256Challenge::aux_randomness(privkey, aux)
+    }
+
+    fn nonce<T: Secp256k1Point>(
+        pubkey: &T,
+        message: &[u8],
+        aux: &[u8; 32],
+    ) -> Result<([u8; 32], UncompressedPoint), Secp256k1SchnorrError> {
+        let (k, _) = Sha256Challenge::nonce(pubkey, message, aux)?;
+        Ok((k, Curve::G))
+    }
+}
+
+#[cfg(feature = "verify-after-sign")]
+#[test]
+fn test_verify_after_sign_rejects_faulty_signature() {
+    assert!(Secp256k1SchnorrSignature::sign::<Sha256Challenge>(b"test", &PRIVKEY_ONE).is_ok());
+    assert_eq!(
+        Secp256k1SchnorrSignature::sign::<FaultyNonceChallenge>(b"test", &PRIVKEY_ONE).err(),
+        Some(Secp256k1SchnorrError::InvalidSignature)
+    );
+}
+
+#[cfg(feature = "siwx")]
+fn siwx_login_message() -> crate::messages::siwx::LoginMessage {
+    crate::messages::siwx::LoginMessage::builder("example.com", CompressedPoint::from(Curve::G))
+        .statement("Sign in to Example")
+        .nonce([
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
+            0x0e, 0x0f,
+        ])
+        .issued_at(1_700_000_000)
+        .expiration_time(1_700_000_600)
+        .build()
+        .expect("Invalid login message")
+}
+
+#[cfg(feature = "siwx")]
+#[test]
+fn test_siwx_canonical_serialization() {
+    use crate::messages::siwx::LoginMessage;
+
+    let message = siwx_login_message();
+    let text = message.to_string();
+    assert_eq!(
+        text,
+        "example.com wants you to sign in with your secp256k1 key:\n\
+         0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\n\
+         \n\
+         Sign in to Example\n\
+         \n\
+         Version: 1\n\
+         Nonce: 000102030405060708090a0b0c0d0e0f\n\
+         Issued At: 1700000000\n\
+         Expiration Time: 1700000600"
+    );
+    assert_eq!(LoginMessage::parse(&text), Ok(message.clone()));
+
+    // The statement is optional
+    let mut without_statement = message;
+    without_statement.statement = None;
+    assert_eq!(
+        LoginMessage::parse(&without_statement.to_string()),
+        Ok(without_statement)
+    );
+
+    // Non-canonical encodings are rejected
+    assert_eq!(
+        LoginMessage::parse(&text.replace("1700000000", "+1700000000")),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+    assert_eq!(
+        LoginMessage::parse(&format!("{}\n", text)),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+}
+
+#[cfg(all(feature = "siwx", feature = "sign"))]
+#[test]
+fn test_siwx_verify_login() {
+    use crate::messages::siwx::verify_login;
+
+    let message = siwx_login_message();
+    let nonce = message.nonce;
+    let signature = message
+        .sign::<BIP340Challenge>(&PRIVKEY_ONE)
+        .expect("Invalid signature");
+
+    let verify = |domain: &str, nonce: &[u8; 16], now: i64| {
+        verify_login::<BIP340Challenge>(&message, &signature, domain, nonce, now)
+    };
+    assert_eq!(verify("example.com", &nonce, 1_700_000_000), Ok(()));
+    assert_eq!(
+        verify("evil.com", &nonce, 1_700_000_000),
+        Err(Secp256k1SchnorrError::DomainMismatch)
+    );
+    assert_eq!(
+        verify("example.com", &[0u8; 16], 1_700_000_000),
+        Err(Secp256k1SchnorrError::NonceMismatch)
+    );
+    assert_eq!(
+        verify("example.com", &nonce, 1_699_999_999),
+        Err(Secp256k1SchnorrError::NotYetValid)
+    );
+    assert_eq!(
+        verify("example.com", &nonce, 1_700_000_600),
+        Err(Secp256k1SchnorrError::Expired)
+    );
+
+    // Signatures do not transfer to altered messages
+    let mut altered = message.clone();
+    altered.expiration_time += 1;
+    assert_eq!(
+        altered.verify::<BIP340Challenge>(&signature),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+}
+
+#[cfg(feature = "messages")]
+#[allow(dead_code)]
+const TEST_DOMAIN: crate::messages::Domain = crate::messages::Domain {
+    name: b"Test Program",
+    version: 1,
+    program_id: [0x42; 32],
+};
+
+#[cfg(all(feature = "permit", feature = "sign"))]
+#[test]
+fn test_permit() {
+    use crate::messages::{
+        permit::{verify_permit, Permit},
+        Domain, Envelope,
+    };
+
+    let permit = Permit {
+        owner: CompressedPoint::from(Curve::G),
+        spender: [0x01; 32],
+        mint: [0x02; 32],
+        amount: 1_000_000,
+        nonce: 7,
+        deadline: 1_700_000_000,
+    };
+    assert_eq!(Permit::from_bytes(&permit.to_bytes()), Ok(permit));
+
+    let signature = permit
+        .sign::<BIP340Challenge>(&TEST_DOMAIN, &PRIVKEY_ONE)
+        .expect("Invalid signature");
+    let verify = |permit: &Permit, domain: &Domain, nonce: u64, now: i64| {
+        verify_permit::<BIP340Challenge>(permit, domain, &signature, nonce, now)
+    };
+
+    assert_eq!(verify(&permit, &TEST_DOMAIN, 7, 1_700_000_000), Ok(()));
+    assert_eq!(
+        verify(&permit, &TEST_DOMAIN, 8, 1_700_000_000),
+        Err(Secp256k1SchnorrError::NonceMismatch)
+    );
+    assert_eq!(
+        verify(&permit, &TEST_DOMAIN, 7, 1_700_000_001),
+        Err(Secp256k1SchnorrError::Expired)
+    );
+
+    // Permits are bound to both their contents and their domain
+    let other_domain = Domain {
+        version: 2,
+        ..TEST_DOMAIN
+    };
+    assert_eq!(
+        verify(&permit, &other_domain, 7, 1_700_000_000),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+    let inflated = Permit {
+        amount: u64::MAX,
+        ..permit
+    };
+    assert_eq!(
+        verify(&inflated, &TEST_DOMAIN, 7, 1_700_000_000),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+
+    // Decoding is strict
+    let mut bytes = permit.to_bytes();
+    bytes.push(0);
+    assert_eq!(
+        Permit::from_bytes(&bytes),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+}
+
+#[cfg(all(feature = "framed", feature = "sign"))]
+#[test]
+fn test_framed_message() {
+    use crate::messages::framed::{verify_framed_message, FramedMessage};
+
+    let program_id = [0x42; 32];
+    let signer = CompressedPoint::from(Curve::G);
+    assert_eq!(
+        FramedMessage::builder(program_id, signer).nonce(7).build(),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+    let message = FramedMessage::builder(program_id, signer)
+        .nonce(7)
+        .expiry(1_700_000_000)
+        .payload(b"transfer 100")
+        .build()
+        .expect("Invalid message");
+    assert_eq!(
+        FramedMessage::from_bytes(&message.to_bytes()),
+        Ok(message.clone())
+    );
+
+    let signature = message
+        .sign::<BIP340Challenge>(&PRIVKEY_ONE)
+        .expect("Invalid signature");
+    let verify = |message: &FramedMessage, program_id: &[u8; 32], nonce: u64, now: i64| {
+        verify_framed_message::<BIP340Challenge>(message, &signature, program_id, nonce, now)
+    };
+
+    assert_eq!(verify(&message, &program_id, 7, 1_700_000_000), Ok(()));
+    assert_eq!(
+        verify(&message, &[0x43; 32], 7, 1_700_000_000),
+        Err(Secp256k1SchnorrError::DomainMismatch)
+    );
+    assert_eq!(
+        verify(&message, &program_id, 8, 1_700_000_000),
+        Err(Secp256k1SchnorrError::NonceMismatch)
+    );
+    assert_eq!(
+        verify(&message, &program_id, 7, 1_700_000_001),
+        Err(Secp256k1SchnorrError::Expired)
+    );
+
+    // Every framed field is bound by the signature
+    let readdressed = FramedMessage {
+        program_id: [0x43; 32],
+        ..message.clone()
+    };
+    assert_eq!(
+        verify(&readdressed, &[0x43; 32], 7, 1_700_000_000),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+    let extended = FramedMessage {
+        expiry: i64::MAX,
+        ..message.clone()
+    };
+    assert_eq!(
+        verify(&extended, &program_id, 7, 1_700_000_000),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+    let other_signer = FramedMessage {
+        signer: CompressedPoint::from(Curve::mul_g(&PRIVKEY_THREE).unwrap()),
+        ..message.clone()
+    };
+    assert_eq!(
+        verify(&other_signer, &program_id, 7, 1_700_000_000),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+
+    // Decoding is strict
+    let mut bytes = message.to_bytes();
+    bytes.push(0);
+    assert_eq!(
+        FramedMessage::from_bytes(&bytes),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+}
+
+#[cfg(all(feature = "intent", feature = "sign"))]
+#[test]
+fn test_intent() {
+    use crate::messages::{
+        intent::{verify_intent, Intent},
+        Envelope,
+    };
+
+    let target_program = [0x07; 32];
+    let payload = b"transfer 100";
+    let intent = Intent::new(
+        CompressedPoint::from(Curve::G),
+        target_program,
+        payload,
+        5_000,
+        1_700_000_000,
+        3,
+    );
+    assert_eq!(Intent::from_bytes(&intent.to_bytes()), Ok(intent));
+
+    let signature = intent
+        .sign::<BIP340Challenge>(&TEST_DOMAIN, &PRIVKEY_ONE)
+        .expect("Invalid signature");
+    let verify = |target_program: &[u8; 32], payload: &[u8], fee: u64, nonce: u64, now: i64| {
+        verify_intent::<BIP340Challenge>(
+            &intent,
+            &TEST_DOMAIN,
+            &signature,
+            target_program,
+            payload,
+            fee,
+            nonce,
+            now,
+        )
+    };
+
+    assert_eq!(
+        verify(&target_program, payload, 5_000, 3, 1_700_000_000),
+        Ok(())
+    );
+    assert_eq!(
+        verify(&target_program, b"transfer 999", 5_000, 3, 1_700_000_000),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+    assert_eq!(
+        verify(&[0x08; 32], payload, 5_000, 3, 1_700_000_000),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+    assert_eq!(
+        verify(&target_program, payload, 5_001, 3, 1_700_000_000),
+        Err(Secp256k1SchnorrError::LimitExceeded)
+    );
+    assert_eq!(
+        verify(&target_program, payload, 5_000, 4, 1_700_000_000),
+        Err(Secp256k1SchnorrError::NonceMismatch)
+    );
+    assert_eq!(
+        verify(&target_program, payload, 5_000, 3, 1_700_000_001),
+        Err(Secp256k1SchnorrError::Expired)
+    );
+}
+
+#[cfg(feature = "sign")]
+#[allow(dead_code)]
+const PRIVKEY_THREE: [u8; 32] = [
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03,
+];
+
+#[cfg(all(feature = "session", feature = "sign"))]
+#[test]
+fn test_session_certificate() {
+    use crate::messages::{
+        delegation::{sign_delegated_call, Delegation},
+        replay::NonceBitmap,
+        session::{session_certificate, verify_session},
+        Envelope,
+    };
+
+    let session_pubkey = CompressedPoint::from(Curve::mul_g(&PRIVKEY_THREE).unwrap());
+    let certificate = session_certificate(
+        CompressedPoint::from(Curve::G),
+        session_pubkey,
+        vec![vec![0x01], vec![0xaa, 0xbb]],
+        1_000,
+    );
+    assert_eq!(
+        Delegation::from_bytes(&certificate.to_bytes()),
+        Ok(certificate.clone())
+    );
+
+    let certificate_signature = certificate
+        .sign::<BIP340Challenge>(&TEST_DOMAIN, &PRIVKEY_ONE)
+        .expect("Invalid signature");
+    let sign = |data: &[u8], nonce: u64, privkey: &[u8; 32]| {
+        sign_delegated_call::<BIP340Challenge>(&certificate, &TEST_DOMAIN, data, nonce, privkey)
+            .expect("Invalid signature")
+    };
+    let master = certificate.delegator;
+    let signed = (certificate.clone(), certificate_signature);
+    let mut nonces = NonceBitmap([0u8; 4]);
+    let mut verify = |data: &[u8], signature, nonce, slot| {
+        verify_session::<BIP340Challenge, _>(
+            &master,
+            &signed,
+            &TEST_DOMAIN,
+            data,
+            &signature,
+            nonce,
+            &mut nonces,
+            slot,
+        )
+    };
+
+    let data = [0xaa, 0xbb, 0x10, 0x20];
+    assert_eq!(
+        verify(&data, sign(&data, 0, &PRIVKEY_THREE), 0, 1_001),
+        Err(Secp256k1SchnorrError::Expired)
+    );
+    assert_eq!(
+        verify(&data, sign(&data, 0, &PRIVKEY_THREE), 0, 1_000),
+        Ok(())
+    );
+    // Calls cannot be replayed, nor moved to another nonce
+    assert_eq!(
+        verify(&data, sign(&data, 0, &PRIVKEY_THREE), 0, 1_000),
+        Err(Secp256k1SchnorrError::NonceMismatch)
+    );
+    assert_eq!(
+        verify(&data, sign(&data, 0, &PRIVKEY_THREE), 1, 1_000),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        verify(&[0x02], sign(&[0x02], 1, &PRIVKEY_THREE), 1, 1_000),
+        Err(Secp256k1SchnorrError::Unauthorized)
+    );
+    // The master key is not the session key
+    assert_eq!(
+        verify(&data, sign(&data, 1, &PRIVKEY_ONE), 1, 1_000),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        verify(&data, sign(&data, 1, &PRIVKEY_THREE), 1, 1_000),
+        Ok(())
+    );
+
+    // A certificate validly signed by a foreign master is not trusted
+    let foreign = session_certificate(
+        CompressedPoint::from(Curve::mul_g(&PRIVKEY_THREE).unwrap()),
+        session_pubkey,
+        vec![vec![0xaa, 0xbb]],
+        1_000,
+    );
+    let foreign_signature = foreign
+        .sign::<BIP340Challenge>(&TEST_DOMAIN, &PRIVKEY_THREE)
+        .expect("Invalid signature");
+    let call =
+        sign_delegated_call::<BIP340Challenge>(&foreign, &TEST_DOMAIN, &data, 2, &PRIVKEY_THREE)
+            .expect("Invalid signature");
+    assert_eq!(
+        verify_session::<BIP340Challenge, _>(
+            &master,
+            &(foreign, foreign_signature),
+            &TEST_DOMAIN,
+            &data,
+            &call,
+            2,
+            &mut NonceBitmap([0u8; 4]),
+            1_000,
+        ),
+        Err(Secp256k1SchnorrError::Unauthorized)
+    );
+
+    // Certificates must be scoped
+    let unscoped = Delegation {
+        scope: vec![],
+        ..certificate.clone()
+    };
+    assert_eq!(
+        Delegation::from_bytes(&unscoped.to_bytes()),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+}
+
+#[cfg(all(feature = "delegation", feature = "sign"))]
+#[test]
+fn test_delegation_chain() {
+    use crate::messages::{
+        delegation::{
+            intersect_scopes, sign_delegated_call, verify_delegated_call, verify_delegation_chain,
+            Delegation, MAX_DELEGATION_SCOPE,
+        },
+        replay::NonceBitmap,
+        Envelope,
+    };
+
+    // Cold (1) -> operational (3) -> automation (5)
+    let mut privkey_five = PRIVKEY_ONE;
+    privkey_five[31] = 0x05;
+    let cold = CompressedPoint::from(Curve::G);
+    let operational = CompressedPoint::from(Curve::mul_g(&PRIVKEY_THREE).unwrap());
+    let automation = CompressedPoint::from(Curve::mul_g(&privkey_five).unwrap());
+
+    let first = Delegation {
+        delegator: cold,
+        delegate: operational,
+        scope: vec![vec![0x01], vec![0x02]],
+        expiry_slot: 2_000,
+        max_subdelegations: 1,
+    };
+    let second = Delegation {
+        delegator: operational,
+        delegate: automation,
+        scope: vec![vec![0x01, 0xff], vec![0x03]],
+        expiry_slot: 1_000,
+        max_subdelegations: 0,
+    };
+    assert_eq!(Delegation::from_bytes(&first.to_bytes()), Ok(first.clone()));
+
+    let chain = vec![
+        (
+            first.clone(),
+            first
+                .sign::<BIP340Challenge>(&TEST_DOMAIN, &PRIVKEY_ONE)
+                .unwrap(),
+        ),
+        (
+            second.clone(),
+            second
+                .sign::<BIP340Challenge>(&TEST_DOMAIN, &PRIVKEY_THREE)
+                .unwrap(),
+        ),
+    ];
+
+    let verified =
+        verify_delegation_chain::<BIP340Challenge>(&cold, &chain, &TEST_DOMAIN, 1_000, 3)
+            .expect("Invalid delegation chain");
+    assert_eq!(verified.delegate, automation);
+    assert_eq!(verified.scope, vec![vec![0x01, 0xff]]);
+    assert_eq!(verified.expiry_slot, 1_000);
+
+    // Scope intersection is symmetric
+    assert_eq!(
+        intersect_scopes(&second.scope, &first.scope),
+        Ok(verified.scope)
+    );
+    // Intersections may not grow beyond the bound on a single hop's scope
+    let narrow = (0..=MAX_DELEGATION_SCOPE as u8)
+        .map(|i| vec![0x01, i])
+        .collect::<Vec<_>>();
+    assert_eq!(
+        intersect_scopes(&first.scope, &narrow),
+        Err(Secp256k1SchnorrError::LimitExceeded)
+    );
+
+    let data = [0x01, 0xff, 0x00];
+    let signature =
+        sign_delegated_call::<BIP340Challenge>(&second, &TEST_DOMAIN, &data, 0, &privkey_five)
+            .unwrap();
+    let mut nonces = NonceBitmap([0u8; 4]);
+    let mut verify_call = |data: &[u8], nonce, slot, max_depth| {
+        verify_delegated_call::<BIP340Challenge, _>(
+            &cold,
+            &chain,
+            &TEST_DOMAIN,
+            data,
+            &signature,
+            nonce,
+            &mut nonces,
+            slot,
+            max_depth,
+        )
+        .map(|_| ())
+    };
+    assert_eq!(
+        verify_call(&data, 0, 1_001, 2),
+        Err(Secp256k1SchnorrError::Expired)
+    );
+    assert_eq!(
+        verify_call(&data, 0, 1_000, 1),
+        Err(Secp256k1SchnorrError::LimitExceeded)
+    );
+    // The call is bound to its nonce
+    assert_eq!(
+        verify_call(&data, 1, 1_000, 2),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+    assert_eq!(verify_call(&data, 0, 1_000, 2), Ok(()));
+    // Calls cannot be replayed
+    assert_eq!(
+        verify_call(&data, 0, 1_000, 2),
+        Err(Secp256k1SchnorrError::NonceMismatch)
+    );
+    // Authorized by the first hop, but not the second
+    assert_eq!(
+        verify_call(&[0x02], 1, 1_000, 2),
+        Err(Secp256k1SchnorrError::Unauthorized)
+    );
+
+    // The chain must be rooted at the expected key
+    assert_eq!(
+        verify_delegation_chain::<BIP340Challenge>(&automation, &chain, &TEST_DOMAIN, 1_000, 3),
+        Err(Secp256k1SchnorrError::Unauthorized)
+    );
+
+    // The first hop does not allow a second level of sub-delegation
+    let restricted = Delegation {
+        max_subdelegations: 0,
+        ..first.clone()
+    };
+    let restricted_chain = vec![
+        (
+            restricted.clone(),
+            restricted
+                .sign::<BIP340Challenge>(&TEST_DOMAIN, &PRIVKEY_ONE)
+                .unwrap(),
+        ),
+        (
+            second.clone(),
+            second
+                .sign::<BIP340Challenge>(&TEST_DOMAIN, &PRIVKEY_THREE)
+                .unwrap(),
+        ),
+    ];
+    assert_eq!(
+        verify_delegation_chain::<BIP340Challenge>(
+            &cold,
+            &restricted_chain,
+            &TEST_DOMAIN,
+            1_000,
+            3
+        ),
+        Err(Secp256k1SchnorrError::LimitExceeded)
+    );
+}
+
+#[cfg(all(feature = "auth", feature = "sign"))]
+#[test]
+fn test_auth_challenge_response() {
+    use crate::messages::auth::{respond, verify_response, AuthChallenge};
+
+    let challenge = AuthChallenge::new([0x5a; 32], b"device-pairing", 1_700_000_000);
+    assert_eq!(
+        AuthChallenge::from_bytes(&challenge.to_bytes()),
+        Ok(challenge.clone())
+    );
+
+    let signature = respond::<BIP340Challenge>(&challenge, &TEST_DOMAIN, &PRIVKEY_ONE)
+        .expect("Invalid signature");
+    let responder = CompressedPoint::from(Curve::G);
+
+    assert_eq!(
+        verify_response::<BIP340Challenge>(
+            &challenge,
+            &TEST_DOMAIN,
+            &responder,
+            &signature,
+            1_700_000_000
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        verify_response::<BIP340Challenge>(
+            &challenge,
+            &TEST_DOMAIN,
+            &responder,
+            &signature,
+            1_700_000_001
+        ),
+        Err(Secp256k1SchnorrError::Expired)
+    );
+
+    // A response to one challenge does not answer another
+    let other = AuthChallenge::new([0x5b; 32], b"device-pairing", 1_700_000_000);
+    assert_eq!(
+        verify_response::<BIP340Challenge>(
+            &other,
+            &TEST_DOMAIN,
+            &responder,
+            &signature,
+            1_700_000_000
+        ),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+}
+
+#[cfg(feature = "messages")]
+#[test]
+fn test_merkle_proofs() {
+    use crate::messages::merkle::{leaf_hash, merkle_proof, merkle_root, verify_merkle_proof};
+
+    assert_eq!(merkle_root(&[]), None);
+    for count in 1..=9u8 {
+        let leaves: Vec<[u8; 32]> = (0..count).map(|i| leaf_hash(&[i])).collect();
+        let root = merkle_root(&leaves).unwrap();
+        for (i, leaf) in leaves.iter().enumerate() {
+            let proof = merkle_proof(&leaves, i).unwrap();
+            assert!(verify_merkle_proof(&root, leaf, &proof));
+            assert!(!verify_merkle_proof(&root, &leaf_hash(&[0xff]), &proof));
+        }
+        assert_eq!(merkle_proof(&leaves, count as usize), None);
+    }
+}
+
+#[cfg(all(feature = "claims", feature = "sign"))]
+#[test]
+fn test_claims() {
+    use crate::messages::{
+        claims::{verify_claim, ClaimLeaf, ClaimRoot},
+        Envelope,
+    };
+
+    let leaves: Vec<ClaimLeaf> = (0..5u64)
+        .map(|index| ClaimLeaf {
+            index,
+            recipient: [index as u8; 32],
+            amount: 100 * (index + 1),
+        })
+        .collect();
+    let claim_root = ClaimRoot::from_leaves(1, &leaves).unwrap();
+    let signature = claim_root
+        .sign::<BIP340Challenge>(&TEST_DOMAIN, &PRIVKEY_ONE)
+        .expect("Invalid signature");
+    let issuer = CompressedPoint::from(Curve::G);
+
+    let proof = ClaimRoot::proof(&leaves, 3).unwrap();
+    let verify = |leaf: &[u8], proof: &[[u8; 32]]| {
+        verify_claim::<BIP340Challenge, CompressedPoint>(
+            &issuer,
+            &TEST_DOMAIN,
+            &claim_root,
+            &signature,
+            leaf,
+            proof,
+        )
+    };
+    assert_eq!(verify(&leaves[3].to_bytes(), &proof), Ok(leaves[3]));
+
+    // Inflating the amount invalidates the proof
+    let inflated = ClaimLeaf {
+        amount: 1_000_000,
+        ..leaves[3]
+    };
+    assert_eq!(
+        verify(&inflated.to_bytes(), &proof),
+        Err(Secp256k1SchnorrError::InvalidProof)
+    );
+    assert_eq!(
+        verify(&leaves[3].to_bytes()[..47], &proof),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+
+    // Roots not signed by the issuer are rejected
+    let forged_root = ClaimRoot {
+        distribution_id: 2,
+        ..claim_root
+    };
+    assert_eq!(
+        verify_claim::<BIP340Challenge, CompressedPoint>(
+            &issuer,
+            &TEST_DOMAIN,
+            &forged_root,
+            &signature,
+            &leaves[3].to_bytes(),
+            &proof,
+        ),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+}
+
+#[cfg(all(feature = "link", feature = "sign"))]
+#[test]
+fn test_link() {
+    use crate::messages::{
+        link::{verify_link, LinkProof},
+        Envelope,
+    };
+
+    let wallet = [0x07; 32];
+    let link = LinkProof {
+        bitcoin_key: Curve::G.x(),
+        solana_key: wallet,
+        nonce: 0,
+        expiry: 1_700_000_000,
+    };
+    assert_eq!(LinkProof::from_bytes(&link.to_bytes()), Ok(link));
+
+    let signature = link
+        .sign::<BIP340Challenge>(&TEST_DOMAIN, &PRIVKEY_ONE)
+        .expect("Invalid signature");
+    let verify = |link: &LinkProof, signer: &[u8; 32], nonce: u64, now: i64| {
+        verify_link::<BIP340Challenge>(link, &TEST_DOMAIN, &signature, signer, nonce, now)
+    };
+
+    assert_eq!(verify(&link, &wallet, 0, 1_700_000_000), Ok(()));
+    assert_eq!(
+        verify(&link, &[0x08; 32], 0, 1_700_000_000),
+        Err(Secp256k1SchnorrError::Unauthorized)
+    );
+    assert_eq!(
+        verify(&link, &wallet, 1, 1_700_000_000),
+        Err(Secp256k1SchnorrError::NonceMismatch)
+    );
+    assert_eq!(
+        verify(&link, &wallet, 0, 1_700_000_001),
+        Err(Secp256k1SchnorrError::Expired)
+    );
+
+    // A proof cannot be redirected to another wallet
+    let redirected = LinkProof {
+        solana_key: [0x08; 32],
+        ..link
+    };
+    assert_eq!(
+        verify(&redirected, &[0x08; 32], 0, 1_700_000_000),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+    let invalid_key = LinkProof {
+        bitcoin_key: [0xff; 32],
+        ..link
+    };
+    assert_eq!(
+        verify(&invalid_key, &wallet, 0, 1_700_000_000),
+        Err(Secp256k1SchnorrError::InvalidPublicKey)
+    );
+}
+
+#[cfg(feature = "messages")]
+#[test]
+fn test_nonce_bitmap() {
+    use crate::messages::replay::NonceBitmap;
+
+    let mut bitmap = NonceBitmap([0u8; 4]);
+    assert_eq!(bitmap.capacity(), 32);
+    assert_eq!(bitmap.is_used(9), Ok(false));
+    assert_eq!(bitmap.consume(9), Ok(()));
+    assert_eq!(bitmap.is_used(9), Ok(true));
+    assert_eq!(bitmap.0, [0, 0x02, 0, 0]);
+    assert_eq!(bitmap.consume(9), Err(Secp256k1SchnorrError::NonceMismatch));
+    assert_eq!(bitmap.consume(31), Ok(()));
+    assert_eq!(
+        bitmap.consume(32),
+        Err(Secp256k1SchnorrError::LimitExceeded)
+    );
+}
+
+#[cfg(all(feature = "subscription", feature = "sign"))]
+#[test]
+fn test_subscription() {
+    use crate::messages::{
+        replay::NonceBitmap,
+        subscription::{verify_pull, Mandate},
+        Envelope,
+    };
+
+    let merchant = [0x03; 32];
+    let mandate = Mandate {
+        subscriber: CompressedPoint::from(Curve::G),
+        merchant,
+        mint: [0x04; 32],
+        amount_ceiling: 1_000,
+        period: 86_400,
+        valid_from: 1_700_000_000,
+        valid_until: 1_710_000_000,
+        nonce: 5,
+    };
+    assert_eq!(Mandate::from_bytes(&mandate.to_bytes()), Ok(mandate));
+    assert_eq!(
+        Mandate::from_bytes(
+            &Mandate {
+                period: 0,
+                ..mandate
+            }
+            .to_bytes()
+        ),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+
+    let signature = mandate
+        .sign::<BIP340Challenge>(&TEST_DOMAIN, &PRIVKEY_ONE)
+        .expect("Invalid signature");
+    let mut cancellations = NonceBitmap([0u8; 8]);
+    let verify = |merchant: &[u8; 32],
+                  amount: u64,
+                  pulled: u64,
+                  now: i64,
+                  cancellations: &NonceBitmap<[u8; 8]>| {
+        verify_pull::<BIP340Challenge, _>(
+            &mandate,
+            &TEST_DOMAIN,
+            &signature,
+            merchant,
+            amount,
+            pulled,
+            now,
+            cancellations,
+        )
+    };
+
+    assert_eq!(
+        verify(&merchant, 600, 0, 1_700_000_000, &cancellations),
+        Ok(0)
+    );
+    assert_eq!(
+        verify(&merchant, 400, 600, 1_700_086_400, &cancellations),
+        Ok(1)
+    );
+    assert_eq!(
+        verify(&merchant, 401, 600, 1_700_086_400, &cancellations),
+        Err(Secp256k1SchnorrError::LimitExceeded)
+    );
+    assert_eq!(
+        verify(&[0x05; 32], 1, 0, 1_700_000_000, &cancellations),
+        Err(Secp256k1SchnorrError::Unauthorized)
+    );
+    assert_eq!(
+        verify(&merchant, 1, 0, 1_699_999_999, &cancellations),
+        Err(Secp256k1SchnorrError::NotYetValid)
+    );
+    assert_eq!(
+        verify(&merchant, 1, 0, 1_710_000_001, &cancellations),
+        Err(Secp256k1SchnorrError::Expired)
+    );
+
+    // Elapsed time beyond the range of an i64 is rejected rather than wrapped
+    let unbounded = Mandate {
+        valid_from: i64::MIN,
+        ..mandate
+    };
+    assert_eq!(unbounded.period_index(i64::MIN + 86_400), Ok(1));
+    assert_eq!(
+        unbounded.period_index(i64::MAX),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+
+    // Consuming the mandate's nonce cancels it
+    cancellations.consume(mandate.nonce).unwrap();
+    assert_eq!(
+        verify(&merchant, 1, 0, 1_700_000_000, &cancellations),
+        Err(Secp256k1SchnorrError::NonceMismatch)
+    );
+}
+
+#[cfg(all(feature = "attestation", feature = "sign"))]
+#[test]
+fn test_attestation() {
+    use crate::messages::{
+        attestation::{verify_attestation, Attestation},
+        key_id, Envelope,
+    };
+
+    let issuer = CompressedPoint::from(Curve::G);
+    let (schema, subject, payload) = ([0x0a; 32], [0x0b; 32], b"kyc:pass".as_slice());
+    let attestation = Attestation::new(
+        &issuer,
+        schema,
+        subject,
+        payload,
+        1_700_000_000,
+        1_800_000_000,
+    );
+    assert_eq!(attestation.issuer_key_id, key_id(&issuer));
+    assert_eq!(
+        Attestation::from_bytes(&attestation.to_bytes()),
+        Ok(attestation)
+    );
+
+    let signature = attestation
+        .sign::<BIP340Challenge>(&TEST_DOMAIN, &PRIVKEY_ONE)
+        .expect("Invalid signature");
+    let verify = |issuer: &CompressedPoint, subject: &[u8; 32], payload: &[u8], now: i64| {
+        verify_attestation::<BIP340Challenge>(
+            &attestation,
+            &TEST_DOMAIN,
+            &signature,
+            issuer,
+            &schema,
+            subject,
+            payload,
+            now,
+        )
+    };
+
+    assert_eq!(verify(&issuer, &subject, payload, 1_750_000_000), Ok(()));
+    assert_eq!(
+        verify(
+            &CompressedPoint::from(Curve::mul_g(&PRIVKEY_THREE).unwrap()),
+            &subject,
+            payload,
+            1_750_000_000
+        ),
+        Err(Secp256k1SchnorrError::Unauthorized)
+    );
+    assert_eq!(
+        verify(&issuer, &[0x0c; 32], payload, 1_750_000_000),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+    assert_eq!(
+        verify(&issuer, &subject, b"kyc:fail", 1_750_000_000),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+    assert_eq!(
+        verify(&issuer, &subject, payload, 1_699_999_999),
+        Err(Secp256k1SchnorrError::NotYetValid)
+    );
+    assert_eq!(
+        verify(&issuer, &subject, payload, 1_800_000_001),
+        Err(Secp256k1SchnorrError::Expired)
+    );
+}
+
+#[cfg(all(feature = "multisig", feature = "sign"))]
+#[test]
+fn test_multisig() {
+    use crate::messages::{
+        multisig::{sign_vote, Policy, VoteStatus, MAX_POLICY_KEYS},
+        Envelope,
+    };
+
+    let key = |privkey: u8| {
+        let mut secret = [0u8; 32];
+        secret[31] = privkey;
+        (
+            secret,
+            CompressedPoint::from(Curve::mul_g(&secret).unwrap()),
+        )
+    };
+    let ((a_secret, a), (b_secret, b), (c_secret, c), (veto_secret, veto)) =
+        (key(1), key(2), key(3), key(4));
+    let policy = Policy {
+        signers: vec![(a, 2), (b, 1), (c, 1)],
+        threshold: 3,
+        vetoes: vec![veto],
+    };
+    assert_eq!(Policy::from_bytes(&policy.to_bytes()), Ok(policy.clone()));
+    assert_eq!(
+        Policy::from_bytes(
+            &Policy {
+                threshold: 5,
+                ..policy.clone()
+            }
+            .to_bytes()
+        ),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+    assert_eq!(
+        Policy::from_bytes(
+            &Policy {
+                vetoes: vec![a],
+                ..policy.clone()
+            }
+            .to_bytes()
+        ),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+
+    // Oversize policies are rejected before they can be encoded ambiguously
+    let oversize = Policy {
+        signers: (1..=MAX_POLICY_KEYS as u8).map(|i| (key(i).1, 1)).collect(),
+        ..policy.clone()
+    };
+    assert_eq!(
+        Policy::from_bytes(&oversize.to_bytes()),
+        Err(Secp256k1SchnorrError::LimitExceeded)
+    );
+    assert_eq!(
+        oversize
+            .evaluate::<BIP340Challenge>(&TEST_DOMAIN, b"", &[])
+            .err(),
+        Some(Secp256k1SchnorrError::LimitExceeded)
+    );
+    assert_eq!(
+        sign_vote::<BIP340Challenge>(&oversize, &TEST_DOMAIN, b"", false, &a_secret).err(),
+        Some(Secp256k1SchnorrError::LimitExceeded)
+    );
+
+    let message = b"transfer 100 lamports";
+    let vote = |secret: &[u8; 32], veto: bool| {
+        sign_vote::<BIP340Challenge>(&policy, &TEST_DOMAIN, message, veto, secret).unwrap()
+    };
+
+    let evaluation = policy
+        .evaluate::<BIP340Challenge>(
+            &TEST_DOMAIN,
+            message,
+            &[(a, vote(&a_secret, false)), (b, vote(&b_secret, false))],
+        )
+        .unwrap();
+    assert_eq!(evaluation.result(), Ok(()));
+    assert_eq!(evaluation.weight, 3);
+    assert_eq!(
+        evaluation.votes,
+        vec![VoteStatus::Approved(2), VoteStatus::Approved(1)]
+    );
+
+    // Duplicates, unknown keys and invalid signatures carry no weight
+    let (_, stranger) = key(5);
+    let evaluation = policy
+        .evaluate::<BIP340Challenge>(
+            &TEST_DOMAIN,
+            message,
+            &[
+                (b, vote(&b_secret, false)),
+                (b, vote(&b_secret, false)),
+                (stranger, vote(&b_secret, false)),
+                (c, vote(&c_secret, true)),
+            ],
+        )
+        .unwrap();
+    assert_eq!(
+        evaluation.result(),
+        Err(Secp256k1SchnorrError::Unauthorized)
+    );
+    assert_eq!(evaluation.weight, 1);
+    assert_eq!(
+        evaluation.votes,
+        vec![
+            VoteStatus::Approved(1),
+            VoteStatus::Duplicate,
+            VoteStatus::UnknownKey,
+            VoteStatus::InvalidSignature
+        ]
+    );
+
+    // A single veto denies an otherwise approved message
+    let evaluation = policy
+        .evaluate::<BIP340Challenge>(
+            &TEST_DOMAIN,
+            message,
+            &[
+                (a, vote(&a_secret, false)),
+                (c, vote(&c_secret, false)),
+                (veto, vote(&veto_secret, true)),
+            ],
+        )
+        .unwrap();
+    assert!(!evaluation.approved);
+    assert_eq!(evaluation.weight, 3);
+    assert_eq!(evaluation.votes[2], VoteStatus::Vetoed);
+}
+
+#[cfg(all(feature = "adaptor", feature = "sign"))]
+fn adaptor_roundtrip<C: Secp256k1SchnorrSign>(privkey: &[u8; 32]) {
+    use crate::adaptor::Secp256k1SchnorrPresignature;
+
+    let secret = PRIVKEY_THREE;
+    let adaptor_point = Curve::mul_g(&secret).unwrap();
+    let pubkey = Curve::mul_g(privkey).unwrap();
+    // Cover final nonce points of both parities
+    let mut parities = [false; 2];
+    for i in 0..16u8 {
+        let message = [i; 32];
+        let presignature =
+            Secp256k1SchnorrPresignature::presign::<C, _>(&message, privkey, &adaptor_point)
+                .expect("Invalid pre-signature");
+        parities[presignature.r().is_odd() as usize] = true;
+        presignature
+            .verify_adaptor::<C, _, _>(&message, &pubkey, &adaptor_point)
+            .expect("Invalid pre-signature");
+
+        // A pre-signature is not a valid signature, nor valid under another adaptor point
+        let mut unadapted = [0u8; 64];
+        unadapted[..32].clone_from_slice(&presignature.r().x());
+        unadapted[32..].clone_from_slice(&presignature.s());
+        assert!(Secp256k1SchnorrSignature(unadapted)
+            .verify::<C, UncompressedPoint>(&message, &pubkey)
+            .is_err());
+        assert_eq!(
+            presignature
+                .verify_adaptor::<C, _, _>(&message, &pubkey, &Curve::G)
+                .err(),
+            Some(Secp256k1SchnorrError::InvalidSignature)
+        );
+
+        let signature = presignature.adapt::<C>(&secret);
+        signature
+            .verify::<C, UncompressedPoint>(&message, &pubkey)
+            .expect("Invalid signature");
+        assert_eq!(presignature.extract_secret::<C>(&signature), Ok(secret));
+    }
+    assert_eq!(parities, [true, true]);
+}
+
+#[cfg(all(feature = "adaptor", feature = "sign", feature = "bip340"))]
+#[test]
+fn test_adaptor_bip340() {
+    // Public keys of both parities
+    adaptor_roundtrip::<BIP340Challenge>(&PRIVKEY_ONE);
+    adaptor_roundtrip::<BIP340Challenge>(&Curve::negate_n(&PRIVKEY_ONE));
+}
+
+#[cfg(all(feature = "adaptor", feature = "sign", feature = "sha256"))]
+#[test]
+fn test_adaptor_sha256() {
+    // Public keys of both parities
+    adaptor_roundtrip::<Sha256Challenge>(&PRIVKEY_ONE);
+    adaptor_roundtrip::<Sha256Challenge>(&Curve::negate_n(&PRIVKEY_ONE));
+}
+
+#[cfg(all(feature = "swap", feature = "sign"))]
+#[test]
+fn test_swap() {
+    use crate::messages::swap::{EscrowStatus, Maker, SwapOffer, Taker};
+
+    let maker_privkey = PRIVKEY_ONE;
+    let taker_privkey = PRIVKEY_THREE;
+    let mut secret = [0u8; 32];
+    secret[31] = 7;
+    let offer = SwapOffer {
+        maker: CompressedPoint::from(Curve::mul_g(&maker_privkey).unwrap()),
+        taker: CompressedPoint::from(Curve::mul_g(&taker_privkey).unwrap()),
+        adaptor_point: CompressedPoint::from(Curve::mul_g(&secret).unwrap()),
+        escrow: [0x01; 32],
+        recipient: [0x02; 32],
+        mint: [0x03; 32],
+        amount: 5_000,
+        counter_message: [0x04; 32],
+        refund_after: 1_700_000_000,
+    };
+    assert_eq!(
+        SwapOffer::from_bytes(&crate::messages::Envelope::to_bytes(&offer)),
+        Ok(offer)
+    );
+    assert!(Maker::new(offer, PRIVKEY_ONE).is_err());
+    let maker = Maker::new(offer, secret).unwrap();
+    let taker = Taker::new(offer);
+
+    // 1. The maker pre-signs the counter-chain transaction
+    let counter = maker
+        .presign_counter::<BIP340Challenge>(&maker_privkey)
+        .unwrap();
+    // 2. The taker verifies it and pre-signs the release
+    taker.verify_counter::<BIP340Challenge>(&counter).unwrap();
+    let release = taker
+        .presign_release::<BIP340Challenge>(&TEST_DOMAIN, &taker_privkey)
+        .unwrap();
+    // 3. The maker claims the escrow
+    let signature = maker
+        .complete_release::<BIP340Challenge>(&TEST_DOMAIN, &release)
+        .unwrap();
+    let mut status = EscrowStatus::Funded;
+    assert_eq!(
+        status.release::<BIP340Challenge>(&offer, &TEST_DOMAIN, &signature, 1_700_000_000),
+        Err(Secp256k1SchnorrError::Expired)
+    );
+    assert_eq!(
+        status.release::<BIP340Challenge>(&offer, &TEST_DOMAIN, &signature, 1_699_999_999),
+        Ok(())
+    );
+    assert_eq!(status, EscrowStatus::Released);
+    assert_eq!(
+        status.refund(&offer, 1_700_000_000),
+        Err(Secp256k1SchnorrError::InvalidState)
+    );
+    // 4. The taker completes the counter-chain transaction
+    let counter_signature = taker
+        .complete_counter::<BIP340Challenge>(&release, &signature, &counter)
+        .unwrap();
+    counter_signature
+        .verify::<BIP340Challenge, CompressedPoint>(&offer.counter_message, &offer.maker)
+        .expect("Invalid signature");
+
+    // Unclaimed escrows are refundable only once the timeout is reached
+    let mut status = EscrowStatus::Funded;
+    assert_eq!(
+        status.refund(&offer, 1_699_999_999),
+        Err(Secp256k1SchnorrError::NotYetValid)
+    );
+    assert_eq!(status.refund(&offer, 1_700_000_000), Ok(()));
+    assert_eq!(EscrowStatus::try_from(2), Ok(EscrowStatus::Refunded));
+    assert_eq!(
+        EscrowStatus::try_from(3),
+        Err(Secp256k1SchnorrError::InvalidState)
+    );
+}
+
+#[cfg(all(feature = "countersign", feature = "sign"))]
+#[test]
+fn test_countersign() {
+    use crate::messages::{
+        countersign::{verify_countersigned, CounterSignature, Verdict},
+        Envelope,
+    };
+
+    let message = b"relayed action";
+    let signer = CompressedPoint::from(Curve::G);
+    let countersigner = CompressedPoint::from(Curve::mul_g(&PRIVKEY_THREE).unwrap());
+    let signature =
+        Secp256k1SchnorrSignature::sign::<BIP340Challenge>(message, &PRIVKEY_ONE).unwrap();
+    let counter = |verdict: Verdict| {
+        CounterSignature::new(&signature, signer, verdict)
+            .sign::<BIP340Challenge>(&TEST_DOMAIN, &PRIVKEY_THREE)
+            .unwrap()
+    };
+    let cancel = counter(Verdict::Cancel);
+    assert_eq!(
+        CounterSignature::from_bytes(
+            &CounterSignature::new(&signature, signer, Verdict::Cancel).to_bytes()
+        ),
+        Ok(CounterSignature::new(&signature, signer, Verdict::Cancel))
+    );
+
+    let verify =
+        |message: &[u8], verdict: Verdict, counter_signature: &Secp256k1SchnorrSignature| {
+            verify_countersigned::<BIP340Challenge>(
+                message,
+                &signature,
+                &signer,
+                &TEST_DOMAIN,
+                verdict,
+                counter_signature,
+                &countersigner,
+            )
+        };
+    assert_eq!(
+        verify(message, Verdict::Approve, &counter(Verdict::Approve)),
+        Ok(Verdict::Approve)
+    );
+    assert_eq!(
+        verify(message, Verdict::Cancel, &cancel),
+        Ok(Verdict::Cancel)
+    );
+
+    // A cancellation cannot be presented as an approval, nor applied to another action
+    assert_eq!(
+        verify(message, Verdict::Approve, &cancel),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        verify(b"other action", Verdict::Cancel, &cancel),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        Verdict::try_from(2),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+}
+
+#[cfg(all(feature = "notary", feature = "sign"))]
+#[test]
+fn test_notary() {
+    use crate::messages::{
+        key_id,
+        notary::{verify_receipt, NotaryState, Receipt},
+        Envelope,
+    };
+
+    let notary = CompressedPoint::from(Curve::G);
+    let receipt = |sequence: u64, timestamp: i64| Receipt {
+        document_hash: [0x0d; 32],
+        timestamp,
+        notary_key_id: key_id(&notary),
+        sequence,
+    };
+    let first = receipt(1, 1_700_000_000);
+    assert_eq!(Receipt::from_bytes(&first.to_bytes()), Ok(first));
+
+    let mut state = NotaryState::default();
+    let verify = |receipt: &Receipt, state: &NotaryState, now: i64| {
+        let signature = receipt
+            .sign::<BIP340Challenge>(&TEST_DOMAIN, &PRIVKEY_ONE)
+            .unwrap();
+        verify_receipt::<BIP340Challenge>(receipt, &TEST_DOMAIN, &signature, &notary, state, now)
+    };
+    assert_eq!(verify(&first, &state, 1_700_000_000), Ok(()));
+    assert_eq!(
+        verify(&first, &state, 1_699_999_999),
+        Err(Secp256k1SchnorrError::NotYetValid)
+    );
+    state.advance(&first).unwrap();
+    assert_eq!(state.sequence, 1);
+
+    // Sequences must strictly increase, and timestamps never go backwards
+    assert_eq!(
+        verify(&first, &state, 1_700_000_000),
+        Err(Secp256k1SchnorrError::NonceMismatch)
+    );
+    assert_eq!(
+        verify(&receipt(3, 1_699_999_999), &state, 1_700_000_000),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+    assert_eq!(
+        verify(&receipt(3, 1_700_000_000), &state, 1_700_000_000),
+        Ok(())
+    );
+
+    let impostor = Receipt {
+        notary_key_id: [0; 32],
+        ..receipt(2, 1_700_000_000)
+    };
+    assert_eq!(
+        verify(&impostor, &state, 1_700_000_000),
+        Err(Secp256k1SchnorrError::Unauthorized)
+    );
+}
+
+#[cfg(all(feature = "order", feature = "sign"))]
+#[test]
+fn test_order_matching() {
+    use crate::messages::{
+        order::{verify_matches, Order, Side, SignedOrder},
+        Envelope,
+    };
+
+    let order = |side: Side, price: u64, privkey: &[u8; 32]| {
+        let order = Order {
+            maker: CompressedPoint::from(Curve::mul_g(privkey).unwrap()),
+            market: [0x0e; 32],
+            side,
+            price,
+            size: 10,
+            expiry: 1_700_000_000,
+            salt: 1,
+        };
+        let signature = order
+            .sign::<BIP340Challenge>(&TEST_DOMAIN, privkey)
+            .unwrap();
+        SignedOrder { order, signature }
+    };
+    let bid = order(Side::Bid, 101, &PRIVKEY_ONE);
+    assert_eq!(Order::from_bytes(&bid.order.to_bytes()), Ok(bid.order));
+
+    // Side bytes and zero prices are rejected when decoding
+    let mut bytes = bid.order.to_bytes();
+    bytes[65] = 2;
+    assert_eq!(
+        Order::from_bytes(&bytes),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+    assert_eq!(
+        Order::from_bytes(
+            &Order {
+                price: 0,
+                ..bid.order
+            }
+            .to_bytes()
+        ),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+
+    let matches = [
+        (
+            order(Side::Bid, 101, &PRIVKEY_ONE),
+            order(Side::Ask, 100, &PRIVKEY_THREE),
+        ),
+        (
+            order(Side::Bid, 100, &PRIVKEY_THREE),
+            order(Side::Ask, 100, &PRIVKEY_ONE),
+        ),
+    ];
+    assert_eq!(
+        verify_matches::<BIP340Challenge>(&TEST_DOMAIN, &matches, 1_700_000_000),
+        Ok(())
+    );
+    assert_eq!(
+        verify_matches::<BIP340Challenge>(&TEST_DOMAIN, &matches, 1_700_000_001),
+        Err(Secp256k1SchnorrError::Expired)
+    );
+
+    // Prices must cross, and sides must be in order
+    let uncrossed = [(
+        order(Side::Bid, 99, &PRIVKEY_ONE),
+        order(Side::Ask, 100, &PRIVKEY_THREE),
+    )];
+    assert_eq!(
+        verify_matches::<BIP340Challenge>(&TEST_DOMAIN, &uncrossed, 1_700_000_000),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+    let reversed = [(
+        order(Side::Ask, 100, &PRIVKEY_THREE),
+        order(Side::Bid, 101, &PRIVKEY_ONE),
+    )];
+    assert_eq!(
+        verify_matches::<BIP340Challenge>(&TEST_DOMAIN, &reversed, 1_700_000_000),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+
+    // Tampering with a signed order invalidates it
+    let mut tampered = order(Side::Ask, 100, &PRIVKEY_THREE);
+    tampered.order.size = 1_000;
+    let tampered = [(order(Side::Bid, 101, &PRIVKEY_ONE), tampered)];
+    assert_eq!(
+        verify_matches::<BIP340Challenge>(&TEST_DOMAIN, &tampered, 1_700_000_000),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+}
+
+#[cfg(all(feature = "twofactor", feature = "sign"))]
+#[test]
+fn test_two_factor() {
+    use crate::messages::{
+        twofactor::{TwoFactor, TwoFactorMessage},
+        Envelope,
+    };
+
+    let wallet = TwoFactor {
+        primary: CompressedPoint::from(Curve::G),
+        cosigner: CompressedPoint::from(Curve::mul_g(&PRIVKEY_THREE).unwrap()),
+    };
+    let message = TwoFactorMessage {
+        signers: wallet,
+        payload: b"withdraw",
+        primary_nonce: 4,
+        cosigner_nonce: 9,
+    };
+    let bytes = message.to_bytes();
+    assert_eq!(TwoFactorMessage::from_bytes(&bytes), Ok(message));
+
+    let primary = message
+        .sign::<BIP340Challenge>(&TEST_DOMAIN, &PRIVKEY_ONE)
+        .unwrap();
+    let cosigner = message
+        .sign::<BIP340Challenge>(&TEST_DOMAIN, &PRIVKEY_THREE)
+        .unwrap();
+    let verify = |message: &TwoFactorMessage, primary, cosigner, nonces: (u64, u64)| {
+        wallet.verify::<BIP340Challenge>(
+            &TEST_DOMAIN,
+            message,
+            primary,
+            cosigner,
+            nonces.0,
+            nonces.1,
+        )
+    };
+
+    assert_eq!(verify(&message, &primary, &cosigner, (4, 9)), Ok(()));
+    assert_eq!(
+        verify(&message, &primary, &cosigner, (4, 10)),
+        Err(Secp256k1SchnorrError::NonceMismatch)
+    );
+    // Both factors are required
+    assert_eq!(
+        verify(&message, &primary, &primary, (4, 9)),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        verify(&message, &cosigner, &cosigner, (4, 9)),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+    let other_wallet = TwoFactorMessage {
+        signers: TwoFactor {
+            cosigner: wallet.primary,
+            ..wallet
+        },
+        ..message
+    };
+    assert_eq!(
+        verify(&other_wallet, &primary, &cosigner, (4, 9)),
+        Err(Secp256k1SchnorrError::Unauthorized)
+    );
+}
+
+#[cfg(all(feature = "recovery", feature = "sign"))]
+#[test]
+fn test_social_recovery() {
+    use crate::messages::{
+        recovery::{Guardians, PendingRecovery, RecoveryStatement},
+        Envelope,
+    };
+
+    let key = |privkey: u8| {
+        let mut secret = [0u8; 32];
+        secret[31] = privkey;
+        (
+            secret,
+            CompressedPoint::from(Curve::mul_g(&secret).unwrap()),
+        )
+    };
+    let guardian_keys: Vec<_> = (2..5).map(key).collect();
+    let guardians = Guardians {
+        keys: guardian_keys.iter().map(|(_, key)| *key).collect(),
+        threshold: 2,
+        min_delay: 86_400,
+    };
+    assert_eq!(
+        Guardians::from_bytes(&guardians.to_bytes()),
+        Ok(guardians.clone())
+    );
+    assert_eq!(
+        Guardians::from_bytes(
+            &Guardians {
+                threshold: 4,
+                ..guardians.clone()
+            }
+            .to_bytes()
+        ),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+
+    let (_, old_key) = key(1);
+    let (_, new_key) = key(9);
+    let statement = RecoveryStatement {
+        wallet: [0x0f; 32],
+        old_key,
+        new_key,
+        delay: 86_400,
+        nonce: 0,
+    };
+    assert_eq!(
+        RecoveryStatement::from_bytes(&statement.to_bytes()),
+        Ok(statement)
+    );
+    let approve = |guardian: u8, statement: &RecoveryStatement| {
+        let signature = statement
+            .sign::<BIP340Challenge>(&TEST_DOMAIN, &guardian_keys[guardian as usize].0)
+            .unwrap();
+        (guardian, signature)
+    };
+    let verify_for = |guardians: &Guardians,
+                      wallet: &[u8; 32],
+                      statement: &RecoveryStatement,
+                      approvals: &[(u8, Secp256k1SchnorrSignature)]| {
+        guardians.verify_recovery::<BIP340Challenge>(
+            &TEST_DOMAIN,
+            statement,
+            approvals,
+            wallet,
+            &old_key,
+            0,
+        )
+    };
+    let verify = |statement: &RecoveryStatement, approvals: &[(u8, Secp256k1SchnorrSignature)]| {
+        verify_for(&guardians, &[0x0f; 32], statement, approvals)
+    };
+
+    assert_eq!(
+        verify(
+            &statement,
+            &[approve(0, &statement), approve(2, &statement)]
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        verify(&statement, &[approve(1, &statement)]),
+        Err(Secp256k1SchnorrError::Unauthorized)
+    );
+    assert_eq!(
+        verify(
+            &statement,
+            &[approve(1, &statement), approve(1, &statement)]
+        ),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+    let hasty = RecoveryStatement {
+        delay: 3_600,
+        ..statement
+    };
+    assert_eq!(
+        verify(&hasty, &[approve(0, &hasty), approve(1, &hasty)]),
+        Err(Secp256k1SchnorrError::LimitExceeded)
+    );
+    // An approval of one statement cannot be counted towards another
+    assert_eq!(
+        verify(&statement, &[approve(0, &statement), approve(1, &hasty)]),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+
+    // A statement approved for one wallet cannot recover another sharing its key and guardians
+    let approvals = [approve(0, &statement), approve(2, &statement)];
+    assert_eq!(
+        verify_for(&guardians, &[0x10; 32], &statement, &approvals),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+
+    // A guardian listed twice in a directly constructed set cannot approve twice
+    let duplicated = Guardians {
+        keys: vec![guardians.keys[0], guardians.keys[0], guardians.keys[1]],
+        ..guardians.clone()
+    };
+    assert_eq!(
+        verify_for(
+            &duplicated,
+            &[0x0f; 32],
+            &statement,
+            &[approve(0, &statement), (1, approve(0, &statement).1)]
+        ),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+
+    let pending = PendingRecovery::new(&statement, 1_700_000_000).unwrap();
+    assert_eq!(
+        pending.execute(1_700_086_399),
+        Err(Secp256k1SchnorrError::NotYetValid)
+    );
+    assert_eq!(pending.execute(1_700_086_400), Ok(new_key));
+}
+
+#[cfg(all(feature = "disclosure", feature = "sign"))]
+#[test]
+fn test_selective_disclosure() {
+    use crate::messages::{
+        disclosure::{verify_disclosure, Attribute, Credential},
+        Envelope,
+    };
+
+    let attributes = [
+        Attribute::new([0x01; 32], b"name", b"Satoshi"),
+        Attribute::new([0x02; 32], b"country", b"JP"),
+        Attribute::new([0x03; 32], b"over_18", b"true"),
+    ];
+    assert_eq!(
+        Attribute::from_bytes(&attributes[1].to_bytes()),
+        Ok(attributes[1].clone())
+    );
+    let credential =
+        Credential::issue([0x0a; 32], &attributes, 1_700_000_000, 1_800_000_000).unwrap();
+    assert_eq!(
+        Credential::from_bytes(&credential.to_bytes()),
+        Ok(credential)
+    );
+    let signature = credential
+        .sign::<BIP340Challenge>(&TEST_DOMAIN, &PRIVKEY_ONE)
+        .unwrap();
+    let issuer = CompressedPoint::from(Curve::G);
+    let verify = |disclosed: &[_], now: i64| {
+        verify_disclosure::<BIP340Challenge>(
+            &credential,
+            &TEST_DOMAIN,
+            &signature,
+            &issuer,
+            disclosed,
+            now,
+        )
+    };
+
+    // Only the selected attributes are revealed
+    let disclosed = [
+        Credential::disclose(&attributes, 1).unwrap(),
+        Credential::disclose(&attributes, 2).unwrap(),
+    ];
+    assert_eq!(verify(&disclosed, 1_750_000_000), Ok(()));
+    assert_eq!(
+        verify(&disclosed, 1_800_000_001),
+        Err(Secp256k1SchnorrError::Expired)
+    );
+
+    let mut forged = Credential::disclose(&attributes, 2).unwrap();
+    forged.attribute.value = b"false".to_vec();
+    assert_eq!(
+        verify(&[forged], 1_750_000_000),
+        Err(Secp256k1SchnorrError::InvalidProof)
+    );
+    assert!(Credential::disclose(&attributes, 3).is_none());
+}
+
+#[cfg(all(feature = "bip340", feature = "sign"))]
+#[test]
+fn test_prelude() {
+    use crate::prelude::*;
+
+    let signature =
+        Secp256k1SchnorrSignature::sign::<BIP340Challenge>(b"test", &PRIVKEY_ONE).unwrap();
+    let pubkey: CompressedPoint = lift_x(&Curve::G.x()).unwrap().compress();
+    signature
+        .verify::<BIP340Challenge, CompressedPoint>(b"test", &pubkey)
+        .expect("Invalid signature");
+    assert_eq!(signature.0.len(), SECP256K1_SCHNORR_SIGNATURE_LENGTH);
+}
+
+#[cfg(feature = "sign")]
+#[test]
+fn test_prepare_verify() {
+    use solana_nostd_secp256k1_recover::secp256k1_recover;
+
+    let message = b"test";
+    for privkey in [PRIVKEY_ONE, PRIVKEY_THREE] {
+        let pubkey = CompressedPoint::from(Curve::mul_g(&privkey).unwrap());
+        let signature =
+            Secp256k1SchnorrSignature::sign::<Sha256Challenge>(message, &privkey).unwrap();
+        let prepared = signature
+            .prepare_verify::<Sha256Challenge, CompressedPoint>(message, &pubkey)
+            .unwrap();
+        assert_eq!(prepared.recovery_id, pubkey.is_odd() as u8);
+        assert_eq!(prepared.expected_r, signature.r());
+        let recovered =
+            secp256k1_recover(&prepared.m, prepared.recovery_id == 1, &prepared.r_s).unwrap();
+        assert_eq!(prepared.finish(&recovered), Ok(()));
+
+        // The operands are bound to the message
+        let prepared = signature
+            .prepare_verify::<Sha256Challenge, CompressedPoint>(b"tset", &pubkey)
+            .unwrap();
+        let recovered =
+            secp256k1_recover(&prepared.m, prepared.recovery_id == 1, &prepared.r_s).unwrap();
+        assert_eq!(
+            prepared.finish(&recovered),
+            Err(Secp256k1SchnorrError::InvalidSignature)
+        );
+    }
+}
+
+#[test]
+fn test_sec1() {
+    use crate::keys::{
+        parse_sec1, parse_sec1_compressed, to_sec1_compressed, to_sec1_uncompressed,
+    };
+
+    for point in [Curve::G, Curve::mul_g(&[0x03; 32]).unwrap()] {
+        let compressed = to_sec1_compressed(&point);
+        let uncompressed = to_sec1_uncompressed(&point);
+        assert_eq!(compressed[0], point.is_odd() as u8 + 2);
+        assert_eq!(uncompressed[0], 0x04);
+        assert_eq!(parse_sec1(&compressed), Ok(point));
+        assert_eq!(parse_sec1(&uncompressed), Ok(point));
+        assert_eq!(
+            parse_sec1_compressed(&uncompressed),
+            Ok(CompressedPoint(compressed))
+        );
+
+        // Off-curve points and hybrid encodings are rejected
+        let mut off_curve = uncompressed;
+        off_curve[64] ^= 1;
+        assert_eq!(
+            parse_sec1(&off_curve).err(),
+            Some(Secp256k1SchnorrError::InvalidPublicKey)
+        );
+        let mut hybrid = uncompressed;
+        hybrid[0] = 0x06 | point.is_odd() as u8;
+        assert_eq!(
+            parse_sec1(&hybrid).err(),
+            Some(Secp256k1SchnorrError::InvalidPublicKey)
+        );
+        assert_eq!(
+            parse_sec1(&compressed[..32]).err(),
+            Some(Secp256k1SchnorrError::InvalidPublicKey)
+        );
+    }
+
+    let mut overflow = [0xff; 33];
+    overflow[0] = 0x02;
+    assert_eq!(
+        parse_sec1(&overflow).err(),
+        Some(Secp256k1SchnorrError::InvalidPublicKey)
+    );
+}
+
+#[cfg(feature = "keccak256")]
+#[test]
+fn test_eth_address() {
+    use crate::keys::eth_address;
+
+    let address = hex::decode("7e5f4552091a69125d5dfcb7b8c2659029395bdf").unwrap();
+    assert_eq!(eth_address(&Curve::G).as_slice(), address);
+    assert_eq!(
+        eth_address(&CompressedPoint::from(Curve::G)).as_slice(),
+        address
+    );
+}
+
+#[test]
+fn test_signature_verification_xonly() {
+    // BIP340 test vector 1
+    let xonly: [u8; 32] =
+        hex::decode("dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let message =
+        hex::decode("243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89").unwrap();
+    let signature = Secp256k1SchnorrSignature(
+        hex::decode("6896bd60eeae296db48a229ff71dfe071bde413e6d43f917dc8dcf8c78de33418906d11ac976abccb20b091292bff4ea897efcb639ea871cfa95f6de339e4b0a")
+            .unwrap()
+            .try_into()
+            .unwrap(),
+    );
+
+    signature
+        .verify_xonly::<BIP340Challenge>(&message, &xonly)
+        .expect("Invalid signature");
+    assert_eq!(
+        signature
+            .verify_xonly::<BIP340Challenge>(&message, &Curve::G.x())
+            .err(),
+        Some(Secp256k1SchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        signature
+            .verify_xonly::<BIP340Challenge>(&message, &[0xff; 32])
+            .err(),
+        Some(Secp256k1SchnorrError::InvalidPublicKey)
+    );
+}
+
+#[cfg(feature = "sign")]
+#[test]
+fn test_signature_verification_dyn() {
+    use crate::challenges::dynamic::{scheme_by_name, DynChallenge, FnChallenge};
+
+    let message = b"test";
+    let pubkey = CompressedPoint::from(Curve::mul_g(&PRIVKEY_THREE).unwrap());
+    let bip340 =
+        Secp256k1SchnorrSignature::sign::<BIP340Challenge>(message, &PRIVKEY_THREE).unwrap();
+    let sha256 =
+        Secp256k1SchnorrSignature::sign::<Sha256Challenge>(message, &PRIVKEY_THREE).unwrap();
+
+    let scheme = scheme_by_name("bip340").unwrap();
+    assert!(scheme.even_r());
+    assert_eq!(bip340.verify_dyn(scheme, message, &pubkey), Ok(()));
+    assert_eq!(
+        sha256.verify_dyn(scheme, message, &pubkey),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        sha256.verify_dyn(scheme_by_name("sha256").unwrap(), message, &pubkey),
+        Ok(())
+    );
+    assert!(scheme_by_name("md5").is_none());
+
+    // Runtime-defined schemes behave exactly like their compile-time counterparts
+    let schemes: [&dyn DynChallenge; 2] = [
+        &Sha256Challenge,
+        &FnChallenge {
+            even_r: false,
+            challenge: |r, pubkey, message| Sha256Challenge::challenge(r, pubkey, message),
+        },
+    ];
+    for scheme in schemes {
+        assert_eq!(sha256.verify_dyn(scheme, message, &pubkey), Ok(()));
+        assert_eq!(
+            sha256.verify_dyn(scheme, b"tset", &pubkey),
+            Err(Secp256k1SchnorrError::InvalidSignature)
+        );
+    }
+}
+
+#[cfg(all(feature = "digest", feature = "sign"))]
+#[test]
+fn test_digest_challenge() {
+    use crate::challenges::digest::{DigestChallenge, DigestTag, TaggedDigestChallenge};
+
+    struct Bip340Tag;
+    impl DigestTag for Bip340Tag {
+        const TAG: &'static [u8] = b"BIP0340/challenge";
+    }
+
+    // The adapters reproduce the crate's own challenges over the same digest
+    let (r, message) = (Curve::G.x(), b"test");
+    assert_eq!(
+        DigestChallenge::<sha2::Sha256>::challenge(&r, &Curve::G, message),
+        Sha256Challenge::challenge(&r, &Curve::G, message)
+    );
+    assert_eq!(
+        TaggedDigestChallenge::<sha2::Sha256, Bip340Tag>::challenge(&r, &Curve::G, message),
+        BIP340Challenge::challenge(&r, &Curve::G, message)
+    );
+
+    let pubkey = Curve::mul_g(&PRIVKEY_THREE).unwrap();
+    let signature =
+        Secp256k1SchnorrSignature::sign::<DigestChallenge<sha3::Sha3_256>>(message, &PRIVKEY_THREE)
+            .unwrap();
+    signature
+        .verify::<DigestChallenge<sha3::Sha3_256>, UncompressedPoint>(message, &pubkey)
+        .expect("Invalid signature");
+    assert_eq!(
+        signature
+            .verify::<TaggedDigestChallenge<sha3::Sha3_256, Bip340Tag>, UncompressedPoint>(
+                message, &pubkey
+            )
+            .err(),
+        Some(Secp256k1SchnorrError::InvalidSignature)
+    );
+
+    let signature = Secp256k1SchnorrSignature::sign::<
+        TaggedDigestChallenge<sha3::Sha3_256, Bip340Tag>,
+    >(message, &PRIVKEY_THREE)
+    .unwrap();
+    signature
+        .verify::<TaggedDigestChallenge<sha3::Sha3_256, Bip340Tag>, UncompressedPoint>(
+            message, &pubkey,
+        )
+        .expect("Invalid signature");
+}
+
+#[cfg(all(feature = "keccak256", feature = "sign"))]
+#[test]
+fn test_verify_by_eth_address() {
+    use crate::address::{verify_by_eth_address, Secp256k1SchnorrSignatureBundle};
+
+    let message = b"test";
+    let signer = crate::keys::eth_address(&Curve::mul_g(&PRIVKEY_THREE).unwrap());
+    let bundle =
+        Secp256k1SchnorrSignatureBundle::sign::<Keccak256Challenge>(message, &PRIVKEY_THREE)
+            .unwrap();
+    verify_by_eth_address::<Keccak256Challenge>(message, &bundle.0, &signer)
+        .expect("Invalid signature");
+    assert_eq!(
+        bundle
+            .verify_by_eth_address::<Keccak256Challenge>(b"tEst", &signer)
+            .err(),
+        Some(Secp256k1SchnorrError::InvalidSignature)
+    );
+
+    // A valid signature from any other key is not accepted for the address
+    let other =
+        Secp256k1SchnorrSignatureBundle::sign::<Keccak256Challenge>(message, &PRIVKEY_ONE).unwrap();
+    assert_eq!(
+        other
+            .verify_by_eth_address::<Keccak256Challenge>(message, &signer)
+            .err(),
+        Some(Secp256k1SchnorrError::Unauthorized)
+    );
+
+    let mut tampered = bundle.0;
+    tampered[64] = 0x05;
+    assert_eq!(
+        verify_by_eth_address::<Keccak256Challenge>(message, &tampered, &signer).err(),
+        Some(Secp256k1SchnorrError::InvalidPublicKey)
+    );
+}
+
+#[cfg(all(feature = "rng", feature = "sign"))]
+#[test]
+fn test_seeded_rng() {
+    use crate::rng::{aux, keygen, scalar, Secp256k1SchnorrRng, SeededRng};
+
+    // Replaying a seed reproduces its output exactly, across block boundaries
+    let mut rng = SeededRng::new([0x42; 32]);
+    let mut a = [0u8; 80];
+    rng.fill_bytes(&mut a[..7]);
+    rng.fill_bytes(&mut a[7..]);
+    let mut b = [0u8; 80];
+    SeededRng::new(rng.seed()).fill_bytes(&mut b);
+    assert_eq!(a, b);
+    assert_ne!(a[..32], a[32..64]);
+
+    // Forks are independent of each other and of how much the parent has drawn
+    let alice = rng.fork(b"alice");
+    assert_eq!(
+        aux(&mut alice.clone()),
+        aux(&mut SeededRng::new([0x42; 32]).fork(b"alice"))
+    );
+    assert_ne!(aux(&mut alice.clone()), aux(&mut rng.fork(b"bob")));
+
+    let (privkey, pubkey) = keygen(&mut alice.clone()).unwrap();
+    assert_eq!(privkey, scalar(&mut alice.clone()));
+    assert_eq!(pubkey.0, Curve::mul_g(&privkey).unwrap().0);
+    let signature = Secp256k1SchnorrSignature::sign::<BIP340Challenge>(b"test", &privkey).unwrap();
+    signature
+        .verify::<BIP340Challenge, UncompressedPoint>(b"test", &pubkey)
+        .expect("Invalid signature");
+}
+
+#[cfg(all(feature = "host", feature = "sign"))]
+#[test]
+fn test_host_recover() {
+    use crate::backend::{HostRecover, NostdRecover, Secp256k1RecoverBackend};
+
+    let message = b"test";
+    let pubkey = Curve::mul_g(&PRIVKEY_THREE).unwrap();
+    let signature =
+        Secp256k1SchnorrSignature::sign::<Sha256Challenge>(message, &PRIVKEY_THREE).unwrap();
+
+    // Both backends recover the same nonce point from the same operands
+    let prepared = signature
+        .prepare_verify::<Sha256Challenge, UncompressedPoint>(message, &pubkey)
+        .unwrap();
+    let is_odd = prepared.recovery_id == 1;
+    let recovered = HostRecover::recover(&prepared.m, is_odd, &prepared.r_s).unwrap();
+    assert_eq!(
+        NostdRecover::recover(&prepared.m, is_odd, &prepared.r_s),
+        Ok(recovered)
+    );
+    assert_eq!(
+        HostRecover::recover(&prepared.m, !is_odd, &prepared.r_s),
+        NostdRecover::recover(&prepared.m, !is_odd, &prepared.r_s)
+    );
+    assert_eq!(prepared.finish(&recovered), Ok(()));
+
+    // Zero and unreduced operands are rejected, as is an 𝑟 off the curve
+    let mut r_s = prepared.r_s;
+    r_s[32..].clone_from_slice(&[0u8; 32]);
+    assert_eq!(
+        HostRecover::recover(&prepared.m, is_odd, &r_s),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+    r_s[32..].clone_from_slice(&Curve::N);
+    assert_eq!(
+        HostRecover::recover(&prepared.m, is_odd, &r_s),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+    let mut r_s = prepared.r_s;
+    r_s[..31].clone_from_slice(&[0u8; 31]);
+    r_s[31] = 5;
+    assert_eq!(
+        HostRecover::recover(&prepared.m, is_odd, &r_s),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+
+    // Signing computes 𝑘⋅𝐺 natively, reducing 𝑘 modulo 𝑁 as the recover emulation does
+    let mut n_plus_three = Curve::N;
+    n_plus_three[31] += 3;
+    assert_eq!(crate::backend::mul_g(&PRIVKEY_THREE), Ok(pubkey));
+    assert_eq!(crate::backend::mul_g(&n_plus_three), Ok(pubkey));
+    assert_eq!(
+        crate::backend::mul_g(&Curve::N),
+        Err(Secp256k1SchnorrError::InvalidSecretKey)
+    );
+}
+
+#[cfg(all(feature = "solana-program-recover", feature = "sign"))]
+#[test]
+fn test_solana_program_recover() {
+    use crate::backend::{NostdRecover, Secp256k1RecoverBackend, SolanaProgramRecover};
+
+    let message = b"test";
+    let pubkey = Curve::mul_g(&PRIVKEY_THREE).unwrap();
+    let signature =
+        Secp256k1SchnorrSignature::sign::<Sha256Challenge>(message, &PRIVKEY_THREE).unwrap();
+    signature
+        .verify::<Sha256Challenge, UncompressedPoint>(message, &pubkey)
+        .expect("Invalid signature");
+
+    // Both backends recover the same nonce point from the same operands
+    let prepared = signature
+        .prepare_verify::<Sha256Challenge, UncompressedPoint>(message, &pubkey)
+        .unwrap();
+    let is_odd = prepared.recovery_id == 1;
+    let recovered = SolanaProgramRecover::recover(&prepared.m, is_odd, &prepared.r_s).unwrap();
+    assert_eq!(
+        NostdRecover::recover(&prepared.m, is_odd, &prepared.r_s),
+        Ok(recovered)
+    );
+    assert_eq!(prepared.finish(&recovered), Ok(()));
+}
+
+#[cfg(all(feature = "program", feature = "sign"))]
+#[test]
+fn test_verify_instruction() {
+    use crate::program::{encode_verify_instruction, Scheme, VerifyEntry, VerifyInstruction};
+
+    let entries: Vec<VerifyEntry> = [(PRIVKEY_ONE, &b"test"[..]), (PRIVKEY_THREE, &b""[..])]
+        .iter()
+        .map(|(privkey, message)| VerifyEntry {
+            signature: Secp256k1SchnorrSignature::sign::<Sha256Challenge>(message, privkey)
+                .unwrap()
+                .0,
+            pubkey: Curve::mul_g(privkey).unwrap().compress(),
+            message,
+        })
+        .collect();
+    let data = encode_verify_instruction(Scheme::Sha256, &entries).unwrap();
+    VerifyInstruction::parse(&data)
+        .unwrap()
+        .verify()
+        .expect("Invalid signature");
+
+    // Every entry must verify under the instruction's scheme
+    let mut tampered = data.clone();
+    tampered[0] = Scheme::Keccak256 as u8;
+    assert_eq!(
+        VerifyInstruction::parse(&tampered).unwrap().verify(),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+    let mut tampered = data.clone();
+    tampered[2 + 99] ^= 1;
+    assert_eq!(
+        VerifyInstruction::parse(&tampered).unwrap().verify(),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+
+    // Missing, trailing and malformed entries are rejected
+    let mut tampered = data.clone();
+    tampered[1] = 3;
+    assert_eq!(
+        VerifyInstruction::parse(&tampered).unwrap().verify(),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+    let mut tampered = data.clone();
+    tampered.push(0);
+    assert_eq!(
+        VerifyInstruction::parse(&tampered).unwrap().verify(),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+    let mut tampered = data.clone();
+    tampered[2 + 64] = 0x04;
+    assert_eq!(
+        VerifyInstruction::parse(&tampered).unwrap().verify(),
+        Err(Secp256k1SchnorrError::InvalidPublicKey)
+    );
+    assert_eq!(
+        VerifyInstruction::parse(&[3, 0]).err(),
+        Some(Secp256k1SchnorrError::InvalidMessage)
+    );
+}
+
+#[cfg(all(feature = "program", feature = "sign"))]
+#[test]
+fn test_offsets_instruction() {
+    use crate::program::offsets::{
+        encode_offsets_instruction, OffsetsInstruction, SchnorrSignatureOffsets,
+        CURRENT_INSTRUCTION,
+    };
+    use crate::program::Scheme;
+
+    // Both entries share a message, which follows the header and two offsets
+    let message = b"test";
+    let mut data = message.to_vec();
+    let mut offsets = Vec::new();
+    for privkey in [PRIVKEY_ONE, PRIVKEY_THREE] {
+        let base = (2 + 2 * 11 + data.len()) as u16;
+        data.extend_from_slice(&Curve::mul_g(&privkey).unwrap().compress().0);
+        data.extend_from_slice(
+            &Secp256k1SchnorrSignature::sign::<Sha256Challenge>(message, &privkey)
+                .unwrap()
+                .0,
+        );
+        offsets.push(SchnorrSignatureOffsets {
+            signature_offset: base + 33,
+            signature_instruction_index: CURRENT_INSTRUCTION,
+            pubkey_offset: base,
+            pubkey_instruction_index: CURRENT_INSTRUCTION,
+            message_data_offset: 24,
+            message_data_size: message.len() as u16,
+            message_instruction_index: CURRENT_INSTRUCTION,
+        });
+    }
+    assert_eq!(
+        SchnorrSignatureOffsets::from_bytes(&offsets[1].to_bytes()),
+        offsets[1]
+    );
+    let instruction = encode_offsets_instruction(Scheme::Sha256, &offsets, &data).unwrap();
+    let parsed = OffsetsInstruction::parse(&instruction).unwrap();
+    assert_eq!(parsed.offsets().collect::<Vec<_>>(), offsets);
+    parsed.verify().expect("Invalid signature");
+
+    // Messages may live in other instructions, resolved by the caller
+    let other = [0u8, 0, b't', b'e', b's', b't'];
+    let mut moved = offsets.clone();
+    moved[0].message_data_offset = 2;
+    moved[0].message_instruction_index = 1;
+    let instruction = encode_offsets_instruction(Scheme::Sha256, &moved, &data).unwrap();
+    let parsed = OffsetsInstruction::parse(&instruction).unwrap();
+    assert_eq!(parsed.verify(), Err(Secp256k1SchnorrError::InvalidMessage));
+    parsed
+        .verify_with(|index| match index {
+            1 => Ok(&other[..]),
+            _ => Err(Secp256k1SchnorrError::InvalidMessage),
+        })
+        .expect("Invalid signature");
+
+    // Out of bounds and mismatched entries are rejected
+    let mut tampered = offsets.clone();
+    tampered[1].message_data_size = u16::MAX;
+    let instruction = encode_offsets_instruction(Scheme::Sha256, &tampered, &data).unwrap();
+    assert_eq!(
+        OffsetsInstruction::parse(&instruction).unwrap().verify(),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+    let mut tampered = offsets.clone();
+    tampered[1].pubkey_offset = offsets[0].pubkey_offset;
+    let instruction = encode_offsets_instruction(Scheme::Sha256, &tampered, &data).unwrap();
+    assert_eq!(
+        OffsetsInstruction::parse(&instruction).unwrap().verify(),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        OffsetsInstruction::parse(&[1, 1, 0]).err(),
+        Some(Secp256k1SchnorrError::InvalidMessage)
+    );
+}
+
+#[cfg(all(feature = "pinocchio", feature = "sign"))]
+#[test]
+fn test_pinocchio_processor() {
+    use crate::program::{encode_verify_instruction, pinocchio::process_instruction};
+    use crate::program::{Scheme, VerifyEntry};
+    use pinocchio::{error::ProgramError, Address};
+
+    let message = b"test";
+    let entry = VerifyEntry {
+        signature: Secp256k1SchnorrSignature::sign::<BIP340Challenge>(message, &PRIVKEY_ONE)
+            .unwrap()
+            .0,
+        pubkey: Curve::G.compress(),
+        message,
+    };
+    let mut data = encode_verify_instruction(Scheme::Bip340, &[entry]).unwrap();
+    let program_id = Address::new_from_array([0u8; 32]);
+    assert_eq!(process_instruction(&program_id, &mut [], &data), Ok(()));
+
+    data[2] ^= 1;
+    assert_eq!(
+        process_instruction(&program_id, &mut [], &data),
+        Err(ProgramError::Custom(
+            Secp256k1SchnorrError::InvalidSignature as u32
+        ))
+    );
+}
+
+#[cfg(all(feature = "solana-program", feature = "sign"))]
+#[test]
+fn test_solana_program_processor() {
+    use crate::program::solana::{process_instruction, pubkey_from_account};
+    use crate::program::{encode_verify_instruction, Scheme, VerifyEntry};
+    use solana_account_info::AccountInfo;
+    use solana_program_error::ProgramError;
+    use solana_pubkey::Pubkey;
+
+    let message = b"test";
+    let entry = VerifyEntry {
+        signature: Secp256k1SchnorrSignature::sign::<BIP340Challenge>(message, &PRIVKEY_ONE)
+            .unwrap()
+            .0,
+        pubkey: Curve::G.compress(),
+        message,
+    };
+    let mut data = encode_verify_instruction(Scheme::Bip340, &[entry]).unwrap();
+    let program_id = Pubkey::new_from_array([0u8; 32]);
+    assert_eq!(process_instruction(&program_id, &[], &data), Ok(()));
+
+    data[2] ^= 1;
+    assert_eq!(
+        process_instruction(&program_id, &[], &data),
+        Err(ProgramError::Custom(
+            Secp256k1SchnorrError::InvalidSignature as u32
+        ))
+    );
+
+    // Public keys are read in place from account data
+    let (key, owner) = (Pubkey::new_from_array([1u8; 32]), program_id);
+    let mut lamports = 0;
+    let mut account_data = [0u8; 40];
+    account_data[7..40].clone_from_slice(&Curve::G.compress().0);
+    let account = AccountInfo::new(
+        &key,
+        false,
+        false,
+        &mut lamports,
+        &mut account_data,
+        &owner,
+        false,
+    );
+    assert_eq!(pubkey_from_account(&account, 7), Ok(Curve::G.compress()));
+    assert_eq!(
+        pubkey_from_account(&account, 8),
+        Err(ProgramError::Custom(
+            Secp256k1SchnorrError::InvalidMessage as u32
+        ))
+    );
+}
+
+#[test]
+fn test_ecmul() {
+    use crate::ecmul::{mul, mul_add_g, mul_add_g_operands, mul_g};
+
+    let p = Curve::mul_g(&[0x03; 32]).unwrap();
+    let (a, b) = ([0x05; 32], [0x07; 32]);
+    let a_p = Curve::ecmul(&p, &a).unwrap();
+    let b_g = Curve::mul_g(&b).unwrap();
+    assert_eq!(mul(&p, &a), Ok(a_p));
+    assert_eq!(mul(&p.compress(), &a), Ok(a_p));
+    assert_eq!(mul_g(&b), Ok(b_g));
+
+    // a*P + b*G is computed with a single recover
+    let operands = mul_add_g_operands(&p, &a, &b).unwrap();
+    let sum = solana_nostd_secp256k1_recover::secp256k1_recover(
+        &operands.hash,
+        operands.is_odd,
+        &operands.signature,
+    )
+    .unwrap();
+    assert_eq!(mul_add_g(&p, &a, &b), Ok(UncompressedPoint(sum)));
+    assert_eq!(mul_add_g(&p, &[0u8; 32], &b), Ok(b_g));
+    assert_eq!(
+        mul_add_g(&Curve::G, &a, &b),
+        mul_g(&Curve::add_mod_n(&a, &b))
+    );
+
+    // The point at infinity and unreduced scalars are rejected
+    assert_eq!(
+        mul_add_g(&Curve::G, &a, &Curve::negate_n(&a)),
+        Err(Secp256k1SchnorrError::PointAtInfinity)
+    );
+    assert_eq!(
+        mul(&p, &[0u8; 32]),
+        Err(Secp256k1SchnorrError::PointAtInfinity)
+    );
+    assert_eq!(
+        mul(&p, &Curve::N),
+        Err(Secp256k1SchnorrError::ArithmeticOverflow)
+    );
+    assert_eq!(
+        mul_add_g(&p, &a, &[0xff; 32]),
+        Err(Secp256k1SchnorrError::ArithmeticOverflow)
+    );
+}
+
+#[test]
+fn test_scalar() {
+    use crate::scalar::Scalar;
+
+    let a = Scalar::new([0x05; 32]).unwrap();
+    let b = Scalar::new(Curve::negate_n(&[0x07; 32])).unwrap();
+    assert_eq!(a.add(&b).sub(&b), a);
+    assert_eq!(a.add(&a.negate()), Scalar::ZERO);
+    assert_eq!(a.mul(&a.invert().unwrap()), Scalar::ONE);
+    assert_eq!(Scalar::ZERO.negate(), Scalar::ZERO);
+    assert_eq!(
+        a.mul(&b).to_bytes(),
+        Curve::mul_mod_n(&[0x05; 32], &b.to_bytes())
+    );
+
+    // Unreduced and zero scalars are rejected where they are invalid
+    assert_eq!(
+        Scalar::new(Curve::N).err(),
+        Some(Secp256k1SchnorrError::ArithmeticOverflow)
+    );
+    assert_eq!(Scalar::reduce(&Curve::N), Scalar::ZERO);
+    assert_eq!(
+        Scalar::new_nonzero([0u8; 32]).err(),
+        Some(Secp256k1SchnorrError::InvalidSecretKey)
+    );
+    assert_eq!(
+        Scalar::ZERO.invert().err(),
+        Some(Secp256k1SchnorrError::ArithmeticOverflow)
+    );
+}
+
+#[cfg(all(feature = "ffi", feature = "sign"))]
+#[test]
+fn test_ffi() {
+    use crate::ffi::*;
+    use core::ptr::{null, null_mut};
+
+    let message = b"test";
+    let (mut privkey, mut pubkey) = ([0u8; 32], [0u8; 33]);
+    let (mut signature, mut bundle) = ([0u8; 64], [0u8; SSCH_BUNDLE_LENGTH]);
+    unsafe {
+        assert_eq!(
+            ssch_keygen(
+                [0x42; 32].as_ptr(),
+                privkey.as_mut_ptr(),
+                pubkey.as_mut_ptr()
+            ),
+            SSCH_OK
+        );
+        let mut derived = [0u8; 33];
+        assert_eq!(
+            ssch_pubkey_create(privkey.as_ptr(), derived.as_mut_ptr()),
+            SSCH_OK
+        );
+        assert_eq!(derived, pubkey);
+
+        for scheme in [
+            SSCH_SCHEME_BIP340,
+            SSCH_SCHEME_SHA256,
+            SSCH_SCHEME_KECCAK256,
+        ] {
+            assert_eq!(
+                ssch_sign(
+                    scheme,
+                    message.as_ptr(),
+                    message.len(),
+                    privkey.as_ptr(),
+                    signature.as_mut_ptr()
+                ),
+                SSCH_OK
+            );
+            assert_eq!(
+                ssch_verify(
+                    scheme,
+                    message.as_ptr(),
+                    message.len(),
+                    signature.as_ptr(),
+                    pubkey.as_ptr()
+                ),
+                SSCH_OK
+            );
+            assert_eq!(
+                ssch_verify(scheme, null(), 0, signature.as_ptr(), pubkey.as_ptr()),
+                SSCH_ERR_INVALID_SIGNATURE
+            );
+        }
+
+        // Signatures produced over the FFI verify with the library itself
+        let address = crate::keys::eth_address(&CompressedPoint(pubkey));
+        assert_eq!(
+            ssch_bundle_encode(signature.as_ptr(), pubkey.as_ptr(), bundle.as_mut_ptr()),
+            SSCH_OK
+        );
+        crate::address::verify_by_eth_address::<Keccak256Challenge>(message, &bundle, &address)
+            .expect("Invalid signature");
+        assert_eq!(
+            ssch_verify_by_eth_address(
+                SSCH_SCHEME_KECCAK256,
+                message.as_ptr(),
+                message.len(),
+                bundle.as_ptr(),
+                address.as_ptr()
+            ),
+            SSCH_OK
+        );
+        let (mut decoded_signature, mut decoded_pubkey) = ([0u8; 64], [0u8; 33]);
+        assert_eq!(
+            ssch_bundle_decode(
+                bundle.as_ptr(),
+                decoded_signature.as_mut_ptr(),
+                decoded_pubkey.as_mut_ptr()
+            ),
+            SSCH_OK
+        );
+        assert_eq!((decoded_signature, decoded_pubkey), (signature, pubkey));
+
+        // Invalid arguments are reported rather than dereferenced
+        assert_eq!(
+            ssch_sign(0, message.as_ptr(), 1, null(), signature.as_mut_ptr()),
+            SSCH_ERR_NULL_POINTER
+        );
+        assert_eq!(
+            ssch_pubkey_create(privkey.as_ptr(), null_mut()),
+            SSCH_ERR_NULL_POINTER
+        );
+        assert_eq!(
+            ssch_sign(
+                3,
+                message.as_ptr(),
+                1,
+                privkey.as_ptr(),
+                signature.as_mut_ptr()
+            ),
+            Secp256k1SchnorrError::InvalidMessage as i32
+        );
+    }
+}
+
+#[cfg(all(feature = "wasm", feature = "sign"))]
+#[test]
+fn test_wasm_bindings() {
+    use crate::program::Scheme;
+    use crate::wasm::{bundle_encode, verify, verify_by_eth_address, Keypair};
+
+    // Only the success paths are exercised, as constructing a JsError requires a JS host
+    let message = b"test";
+    let keypair = Keypair::new(&[0x42; 32]).unwrap();
+    let restored = Keypair::from_privkey(&keypair.privkey()).unwrap();
+    assert_eq!(restored.pubkey(), keypair.pubkey());
+    for scheme in [Scheme::Bip340, Scheme::Sha256, Scheme::Keccak256] {
+        let signature = keypair.sign(scheme as u8, message).unwrap();
+        assert!(verify(scheme as u8, message, &signature, &keypair.pubkey()).is_ok());
+
+        // Signatures match those of the library
+        let privkey: [u8; 32] = keypair.privkey().try_into().unwrap();
+        assert_eq!(signature, scheme.sign(message, &privkey).unwrap().0);
+    }
+
+    let bundle = keypair
+        .sign_bundle(Scheme::Keccak256 as u8, message)
+        .unwrap();
+    assert_eq!(
+        bundle,
+        bundle_encode(&bundle[..64], &keypair.pubkey()).unwrap()
+    );
+    assert!(verify_by_eth_address(
+        Scheme::Keccak256 as u8,
+        message,
+        &bundle,
+        &keypair.address()
+    )
+    .is_ok());
+}
+
+#[cfg(feature = "sign")]
+#[test]
+fn test_legacy_layout() {
+    use crate::layout::Layout;
+
+    let signature =
+        Secp256k1SchnorrSignature::sign::<BIP340Challenge>(b"test", &PRIVKEY_ONE).unwrap();
+    let (mut r_le, mut s_le) = (signature.r(), signature.s());
+    r_le.reverse();
+    s_le.reverse();
+    let encodings = [
+        (Layout::RS, [signature.r(), signature.s()].concat()),
+        (Layout::SR, [signature.s(), signature.r()].concat()),
+        (Layout::RSLittleEndian, [r_le, s_le].concat()),
+        (Layout::SRLittleEndian, [s_le, r_le].concat()),
+    ];
+    for (layout, bytes) in encodings {
+        let bytes: [u8; 64] = bytes.try_into().unwrap();
+        assert_eq!(signature.to_legacy_layout(layout), bytes);
+        Secp256k1SchnorrSignature::from_legacy_layout(&bytes, layout)
+            .verify::<BIP340Challenge, UncompressedPoint>(b"test", &Curve::G)
+            .expect("Invalid signature");
+    }
+}
+
+#[cfg(all(feature = "wallet", feature = "sign"))]
+#[test]
+fn test_smart_wallet() {
+    use crate::messages::{
+        wallet::{
+            decode_instructions, encode_instructions, verify_execution, WalletAccountMeta,
+            WalletExecution, WalletInstruction,
+        },
+        Envelope,
+    };
+
+    let wallet = [0x0a; 32];
+    let owner = CompressedPoint::from(Curve::G);
+    let instructions = vec![
+        WalletInstruction {
+            program_id: [0x01; 32],
+            accounts: vec![
+                WalletAccountMeta {
+                    pubkey: wallet,
+                    is_signer: true,
+                    is_writable: true,
+                },
+                WalletAccountMeta {
+                    pubkey: [0x0b; 32],
+                    is_signer: false,
+                    is_writable: true,
+                },
+            ],
+            data: b"transfer 100".to_vec(),
+        },
+        WalletInstruction {
+            program_id: [0x02; 32],
+            accounts: vec![],
+            data: vec![],
+        },
+    ];
+
+    // The client encoding decodes to the same instruction set on the program side
+    let encoded = encode_instructions(&instructions);
+    assert_eq!(decode_instructions(&encoded), Ok(instructions.clone()));
+    let mut invalid_flags = encoded.clone();
+    invalid_flags[4 + 32 + 4 + 32] = 0x04;
+    assert_eq!(
+        decode_instructions(&invalid_flags),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+
+    let execution = WalletExecution::new(wallet, &instructions, 7, 1_700_000_000);
+    assert_eq!(
+        WalletExecution::from_bytes(&execution.to_bytes()),
+        Ok(execution)
+    );
+    let signature = execution
+        .sign::<BIP340Challenge>(&TEST_DOMAIN, &PRIVKEY_ONE)
+        .unwrap();
+    let verify = |instructions: &[WalletInstruction], nonce: u64, now: i64| {
+        verify_execution::<BIP340Challenge>(
+            &execution,
+            &TEST_DOMAIN,
+            &signature,
+            &owner,
+            &wallet,
+            instructions,
+            nonce,
+            now,
+        )
+    };
+    assert_eq!(verify(&instructions, 7, 1_700_000_000), Ok(()));
+
+    // Any change to an account flag, the order or the data invalidates the authorization
+    let mut tampered = instructions.clone();
+    tampered[0].accounts[1].is_signer = true;
+    assert_eq!(
+        verify(&tampered, 7, 1_700_000_000),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+    let reordered = [instructions[1].clone(), instructions[0].clone()];
+    assert_eq!(
+        verify(&reordered, 7, 1_700_000_000),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+    assert_eq!(
+        verify(&instructions, 8, 1_700_000_000),
+        Err(Secp256k1SchnorrError::NonceMismatch)
+    );
+    assert_eq!(
+        verify(&instructions, 7, 1_700_000_001),
+        Err(Secp256k1SchnorrError::Expired)
+    );
+}
+
+#[cfg(feature = "sign")]
+#[test]
+fn test_verify_account_data() {
+    let message = b"attestation payload";
+    let signature =
+        Secp256k1SchnorrSignature::sign::<BIP340Challenge>(message, &PRIVKEY_ONE).unwrap();
+    let mut data = vec![0xaa; 8];
+    data.extend_from_slice(message);
+    data.extend_from_slice(&[0xbb; 16]);
+
+    let verify = |offset: usize, len: usize| {
+        signature.verify_account_data::<BIP340Challenge, UncompressedPoint>(
+            &data,
+            offset,
+            len,
+            &Curve::G,
+        )
+    };
+    assert_eq!(verify(8, message.len()), Ok(()));
+    assert_eq!(
+        verify(7, message.len()),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        verify(8, data.len()),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+    assert_eq!(
+        verify(usize::MAX, 2),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+}
+
+#[cfg(feature = "sign")]
+#[test]
+fn test_signature_generation_bip340_aux() {
+    // BIP340 test vector 1
+    let privkey: [u8; 32] =
+        hex::decode("b7e151628aed2a6abf7158809cf4f3c762e7160f38b4da56a784d9045190cfef")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let message =
+        hex::decode("243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89").unwrap();
+    let mut aux = [0u8; 32];
+    aux[31] = 0x01;
+
+    let signature =
+        Secp256k1SchnorrSignature::sign_with_aux::<BIP340Challenge>(&message, &privkey, &aux)
+            .expect("Invalid signature");
+    assert_eq!(
+        signature.0.to_vec(),
+        hex::decode("6896bd60eeae296db48a229ff71dfe071bde413e6d43f917dc8dcf8c78de33418906d11ac976abccb20b091292bff4ea897efcb639ea871cfa95f6de339e4b0a").unwrap()
+    );
+
+    // A zero aux is the deterministic default
+    assert_eq!(
+        Secp256k1SchnorrSignature::sign_with_aux::<BIP340Challenge>(&message, &privkey, &[0u8; 32])
+            .unwrap()
+            .0,
+        Secp256k1SchnorrSignature::sign::<BIP340Challenge>(&message, &privkey)
+            .unwrap()
+            .0
+    );
+}
+
+#[cfg(feature = "sign")]
+#[test]
+fn test_signature_generation_bip340_odd_pubkey() {
+    // BIP340 test vector 3, whose public key has an odd Y-coordinate
+    let privkey: [u8; 32] =
+        hex::decode("0b432b2677937381aef05bb02a66ecd012773062cf3fa2549e44f58ed2401710")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let xonly: [u8; 32] =
+        hex::decode("25d1dff95105f5253c4022f628a996ad3a0d95fbf21d468a1b33f8c160d8f517")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let message = [0xff; 32];
+    let pubkey = Curve::mul_g(&privkey).unwrap();
+    assert!(pubkey.is_odd());
+    assert_eq!(pubkey.x(), xonly);
+
+    let signature = Secp256k1SchnorrSignature::sign_with_aux::<BIP340Challenge>(
+        &message,
+        &privkey,
+        &[0xff; 32],
+    )
+    .unwrap();
+    assert_eq!(
+        signature.0.to_vec(),
+        hex::decode("7eb0509757e246f19449885651611cb965ecc1a187dd51b64fda1edc9637d5ec97582b9cb13db3933705b32ba982af5af25fd78881ebb32771fc5922efc66ea3").unwrap()
+    );
+
+    // The signature verifies against the x-only key and either parity of its full point
+    signature
+        .verify_xonly::<BIP340Challenge>(&message, &xonly)
+        .expect("Invalid signature");
+    signature
+        .verify::<BIP340Challenge, UncompressedPoint>(&message, &pubkey)
+        .expect("Invalid signature");
+    signature
+        .verify_dyn(&BIP340Challenge, &message, &pubkey)
+        .expect("Invalid signature");
+}
+
+#[test]
+fn test_signature_verification_out_of_range() {
+    let message = *b"test";
+    let pubkey = CompressedPoint::from(Curve::G);
+
+    // r = p is not a valid field element
+    let mut signature = [0u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH];
+    signature[..32].clone_from_slice(&Curve::P);
+    signature[63] = 1;
+    assert_eq!(
+        Secp256k1SchnorrSignature(signature)
+            .verify::<BIP340Challenge, CompressedPoint>(&message, &pubkey)
+            .err(),
+        Some(Secp256k1SchnorrError::InvalidSignatureR)
+    );
+
+    // s = n would otherwise be a second encoding of s = 0
+    let mut signature = [0u8; SECP256K1_SCHNORR_SIGNATURE_LENGTH];
+    signature[..32].clone_from_slice(&Curve::G.x());
+    signature[32..].clone_from_slice(&Curve::N);
+    let signature = Secp256k1SchnorrSignature(signature);
+    assert_eq!(
+        signature
+            .verify::<BIP340Challenge, CompressedPoint>(&message, &pubkey)
+            .err(),
+        Some(Secp256k1SchnorrError::InvalidSignatureS)
+    );
+    assert_eq!(
+        signature
+            .prepare_verify::<BIP340Challenge, CompressedPoint>(&message, &pubkey)
+            .err(),
+        Some(Secp256k1SchnorrError::InvalidSignatureS)
+    );
+    assert_eq!(
+        signature
+            .verify_dyn(&BIP340Challenge, &message, &pubkey)
+            .err(),
+        Some(Secp256k1SchnorrError::InvalidSignatureS)
+    );
+}
+
+#[test]
+fn test_challenge_scalar_reduction() {
+    // Hash outputs at and just above the order reduce to 0 and 1, and those below are unchanged
+    let mut n_minus_one = Curve::N;
+    n_minus_one[31] -= 1;
+    assert_eq!(
+        UnreducedChallenge::challenge_scalar(&Curve::G.x(), &Curve::G, b"test"),
+        PRIVKEY_ONE
+    );
+    assert_eq!(
+        ZeroChallenge::challenge_scalar(&Curve::G.x(), &Curve::G, b"test"),
+        [0u8; 32]
+    );
+    assert_eq!(Scalar::reduce(&n_minus_one).to_bytes(), n_minus_one);
+}
+
+#[cfg(feature = "sign")]
+#[test]
+fn test_signature_generation_unreduced_challenge() {
+    // A challenge of N + 1 must sign and verify exactly as a challenge of 1
+    let message = *b"test";
+    let signature =
+        Secp256k1SchnorrSignature::sign::<UnreducedChallenge>(&message, &PRIVKEY_THREE).unwrap();
+    let expected =
+        Secp256k1SchnorrSignature::sign::<OneChallenge>(&message, &PRIVKEY_THREE).unwrap();
+    assert_eq!(signature.0, expected.0);
+
+    // s = k + e*d = 1 + 3
+    assert_eq!(signature.s()[31], 4);
+
+    let pubkey = Curve::mul_g(&PRIVKEY_THREE).unwrap();
+    signature
+        .verify::<OneChallenge, UncompressedPoint>(&message, &pubkey)
+        .expect("Invalid signature");
+    expected
+        .verify::<UnreducedChallenge, UncompressedPoint>(&message, &pubkey)
+        .expect("Invalid signature");
+    expected
+        .verify_dyn(&UnreducedChallenge, &message, &pubkey)
+        .expect("Invalid signature");
+}
+
+#[cfg(feature = "sign")]
+#[test]
+fn test_signature_verification_batch() {
+    let first_pubkey = Curve::mul_g(&PRIVKEY_ONE).unwrap();
+    let second_pubkey = Curve::mul_g(&PRIVKEY_THREE).unwrap();
+    let first = Secp256k1SchnorrSignature::sign::<BIP340Challenge>(b"first", &PRIVKEY_ONE).unwrap();
+    let second =
+        Secp256k1SchnorrSignature::sign::<BIP340Challenge>(b"second", &PRIVKEY_THREE).unwrap();
+
+    assert_eq!(
+        Secp256k1SchnorrSignature::verify_batch::<BIP340Challenge, UncompressedPoint>(&[]),
+        Ok(())
+    );
+    assert_eq!(
+        Secp256k1SchnorrSignature::verify_batch::<BIP340Challenge, UncompressedPoint>(&[
+            (Secp256k1SchnorrSignature(first.0), b"first", &first_pubkey),
+            (
+                Secp256k1SchnorrSignature(second.0),
+                b"second",
+                &second_pubkey
+            ),
+        ]),
+        Ok(())
+    );
+
+    // A signature over the wrong message fails the whole batch
+    assert_eq!(
+        Secp256k1SchnorrSignature::verify_batch::<BIP340Challenge, UncompressedPoint>(&[
+            (Secp256k1SchnorrSignature(first.0), b"first", &first_pubkey),
+            (
+                Secp256k1SchnorrSignature(second.0),
+                b"first",
+                &second_pubkey
+            ),
+        ]),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+
+    // Out of range items are rejected before any earlier item is verified
+    let mut malformed = second.0;
+    malformed[32..].clone_from_slice(&Curve::N);
+    assert_eq!(
+        Secp256k1SchnorrSignature::verify_batch::<BIP340Challenge, UncompressedPoint>(&[
+            (Secp256k1SchnorrSignature(first.0), b"second", &first_pubkey),
+            (
+                Secp256k1SchnorrSignature(malformed),
+                b"second",
+                &second_pubkey
+            ),
+        ]),
+        Err(Secp256k1SchnorrError::InvalidSignatureS)
+    );
+}
+
+#[cfg(feature = "sign")]
+#[test]
+fn test_signature_verification_threshold() {
+    let privkeys = [PRIVKEY_ONE, PRIVKEY_THREE, [0x07; 32]];
+    let pubkeys = privkeys.map(|privkey| CompressedPoint::from(Curve::mul_g(&privkey).unwrap()));
+    let signatures = privkeys.map(|privkey| {
+        Secp256k1SchnorrSignature::sign::<BIP340Challenge>(b"proposal", &privkey).unwrap()
+    });
+    let verify = |items: &[(usize, usize)], m: usize| {
+        let items: Vec<_> = items
+            .iter()
+            .map(|&(signature, pubkey)| {
+                (
+                    Secp256k1SchnorrSignature(signatures[signature].0),
+                    &pubkeys[pubkey],
+                )
+            })
+            .collect();
+        Secp256k1SchnorrSignature::verify_threshold::<BIP340Challenge, CompressedPoint>(
+            &items,
+            b"proposal",
+            m,
+        )
+    };
+
+    assert_eq!(verify(&[(0, 0), (1, 1), (2, 2)], 3), Ok(()));
+    assert_eq!(verify(&[(0, 0), (1, 1), (2, 2)], 2), Ok(()));
+    assert_eq!(
+        verify(&[(0, 0), (1, 1), (2, 2)], 4),
+        Err(Secp256k1SchnorrError::Unauthorized)
+    );
+    assert_eq!(verify(&[], 0), Err(Secp256k1SchnorrError::LimitExceeded));
+
+    // Invalid signatures are not counted towards the threshold
+    assert_eq!(verify(&[(0, 0), (0, 1), (2, 2)], 2), Ok(()));
+    assert_eq!(
+        verify(&[(0, 0), (0, 1), (2, 2)], 3),
+        Err(Secp256k1SchnorrError::Unauthorized)
+    );
+
+    // A signer may only be counted once, even under the other parity of an x-only key
+    assert_eq!(
+        verify(&[(0, 0), (0, 0)], 2),
+        Err(Secp256k1SchnorrError::DuplicateSigner)
+    );
+    let mut negated = pubkeys[1];
+    negated.0[0] ^= 1;
+    let items = [
+        (Secp256k1SchnorrSignature(signatures[1].0), &pubkeys[1]),
+        (Secp256k1SchnorrSignature(signatures[1].0), &negated),
+    ];
+    assert_eq!(
+        Secp256k1SchnorrSignature::verify_threshold::<BIP340Challenge, CompressedPoint>(
+            &items,
+            b"proposal",
+            2,
+        ),
+        Err(Secp256k1SchnorrError::DuplicateSigner)
+    );
+}
+
+#[cfg(feature = "musig2")]
+fn musig2_pubkeys() -> [CompressedPoint; 3] {
+    [
+        "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+        "03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        "023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66",
+    ]
+    .map(|pubkey| CompressedPoint(hex::decode(pubkey).unwrap().try_into().unwrap()))
+}
+
+#[cfg(feature = "musig2")]
+#[test]
+fn test_musig2_key_agg_vectors() {
+    use crate::musig2::KeyAggContext;
+
+    // BIP327 key_agg_vectors.json
+    let pubkeys = musig2_pubkeys();
+    let vectors: [(&[usize], &str); 4] = [
+        (
+            &[0, 1, 2],
+            "90539EEDE565F5D054F32CC0C220126889ED1E5D193BAF15AEF344FE59D4610C",
+        ),
+        (
+            &[2, 1, 0],
+            "6204DE8B083426DC6EAF9502D27024D53FC826BF7D2012148A0575435DF54B2B",
+        ),
+        (
+            &[0, 0, 0],
+            "B436E3BAD62B8CD409969A224731C193D051162D8C5AE8B109306127DA3AA935",
+        ),
+        (
+            &[0, 0, 1, 1],
+            "69BC22BFA5D106306E48A20679DE1D7389386124D07571D0D872686028C26A3E",
+        ),
+    ];
+    for (indices, expected) in vectors {
+        let keys: Vec<CompressedPoint> = indices.iter().map(|i| pubkeys[*i]).collect();
+        let context = KeyAggContext::new(&keys).unwrap();
+        assert_eq!(context.xonly().to_vec(), hex::decode(expected).unwrap());
+    }
+
+    // A key that is not on the curve
+    let mut invalid = pubkeys;
+    invalid[1].0[1..].clone_from_slice(&[0u8; 32]);
+    invalid[1].0[32] = 5;
+    assert_eq!(
+        KeyAggContext::new(&invalid).err(),
+        Some(Secp256k1SchnorrError::InvalidPublicKey)
+    );
+    assert_eq!(
+        KeyAggContext::new(&[]).err(),
+        Some(Secp256k1SchnorrError::PointAtInfinity)
+    );
+}
+
+#[cfg(all(feature = "musig2", feature = "sign"))]
+#[test]
+fn test_musig2_key_agg_signature() {
+    use crate::musig2::{key_sort, KeyAggContext};
+
+    // The aggregate secret key sum(a_i*d_i) signs for the aggregate key
+    let privkeys = [PRIVKEY_ONE, PRIVKEY_THREE];
+    let mut pubkeys =
+        privkeys.map(|privkey| CompressedPoint::from(Curve::mul_g(&privkey).unwrap()));
+    key_sort(&mut pubkeys);
+    let context = KeyAggContext::new(&pubkeys).unwrap();
+
+    let privkey = privkeys.iter().fold([0u8; 32], |acc, privkey| {
+        let pubkey = CompressedPoint::from(Curve::mul_g(privkey).unwrap());
+        Curve::add_mod_n(
+            &acc,
+            &Curve::mul_mod_n(&context.coefficient(&pubkey), privkey),
+        )
+    });
+    assert_eq!(Curve::mul_g(&privkey).unwrap(), context.aggregate_pubkey());
+
+    let message = *b"test";
+    let signature = Secp256k1SchnorrSignature::sign::<BIP340Challenge>(&message, &privkey).unwrap();
+    signature
+        .verify_xonly::<BIP340Challenge>(&message, &context.xonly())
+        .expect("Invalid signature");
+}
+
+#[cfg(all(feature = "musig2", feature = "sign"))]
+#[test]
+fn test_musig2_nonce_gen() {
+    use crate::musig2::nonce_gen;
+
+    // BIP327 nonce_gen_vectors.json
+    let rand = [0x0f; 32];
+    let pubkey = CompressedPoint(
+        hex::decode("024D4B6CD1361032CA9BD2AEB9D900AA4D45D9EAD80AC9423374C451A7254D0766")
+            .unwrap()
+            .try_into()
+            .unwrap(),
+    );
+    let (secnonce, pubnonce) = nonce_gen(
+        &rand,
+        Some(&[0x02; 32]),
+        &pubkey,
+        Some(&[0x07; 32]),
+        Some(&[0x01; 32]),
+        &[0x08; 32],
+    )
+    .unwrap();
+    assert_eq!(
+        secnonce.0.to_vec(),
+        hex::decode("B114E502BEAA4E301DD08A50264172C84E41650E6CB726B410C0694D59EFFB6495B5CAF28D045B973D63E3C99A44B807BDE375FD6CB39E46DC4A511708D0E9D2024D4B6CD1361032CA9BD2AEB9D900AA4D45D9EAD80AC9423374C451A7254D0766").unwrap()
+    );
+    assert_eq!(secnonce.pubkey(), pubkey);
+    assert_eq!(
+        CompressedPoint::from(Curve::mul_g(&secnonce.k1()).unwrap()),
+        pubnonce.r1()
+    );
+    assert_eq!(
+        CompressedPoint::from(Curve::mul_g(&secnonce.k2()).unwrap()),
+        pubnonce.r2()
+    );
+
+    // Omitting the optional inputs yields a distinct nonce
+    let (secnonce, _) = nonce_gen(&rand, None, &pubkey, None, None, &[]).unwrap();
+    assert_eq!(
+        secnonce.k1().to_vec(),
+        hex::decode("ED5489C1ED712E24E071DF43ABC43841103C946C51AE6611A2E50C885B7A4A33").unwrap()
+    );
+}
+
+#[cfg(feature = "musig2")]
+#[test]
+fn test_musig2_nonce_agg() {
+    use crate::musig2::{nonce_agg, PubNonce};
+
+    // BIP327 nonce_agg_vectors.json
+    let pubnonces = [
+        "020151C80F435648DF67A22B749CD798CE54E0321D034B92B709B567D60A42E66603BA47FBC1834437B3212E89A84D8425E7BF12E0245D98262268EBDCB385D50641",
+        "03FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A60248C264CDD57D3C24D79990B0F865674EB62A0F9018277A95011B41BFC193B833",
+    ]
+    .map(|pubnonce| PubNonce(hex::decode(pubnonce).unwrap().try_into().unwrap()));
+    assert_eq!(
+        nonce_agg(&pubnonces).unwrap().0.to_vec(),
+        hex::decode("035FE1873B4F2967F52FEA4A06AD5A8ECCBE9D0FD73068012C894E2E87CCB5804B024725377345BDE0E9C33AF3C43C0A29A9249F2F2956FA8CFEB55C8573D0262DC8").unwrap()
+    );
+
+    // Cancelling points aggregate to the point at infinity
+    let g = CompressedPoint::from(Curve::G);
+    let mut neg_g = g;
+    neg_g.invert();
+    let mut first = [0u8; 66];
+    first[..33].clone_from_slice(&g.0);
+    first[33..].clone_from_slice(&g.0);
+    let mut second = first;
+    second[..33].clone_from_slice(&neg_g.0);
+    let aggnonce = nonce_agg(&[PubNonce(first), PubNonce(second)]).unwrap();
+    assert_eq!(aggnonce.r1(), [0u8; 33]);
+    assert_eq!(
+        aggnonce.r2(),
+        CompressedPoint::from(Curve::mul_g(&Curve::TWO).unwrap()).0
+    );
+
+    // A point that is not on the curve
+    let mut invalid = pubnonces;
+    invalid[1].0[0] = 0x04;
+    assert_eq!(
+        nonce_agg(&invalid).err(),
+        Some(Secp256k1SchnorrError::InvalidNonce)
+    );
+}
+
+#[cfg(feature = "frost")]
+fn frost_roundtrip<C: Secp256k1SchnorrVerify>(seed: u8) {
+    use crate::{frost, rng::SeededRng};
+
+    let mut rng = SeededRng::new([seed; 32]);
+    let (shares, package) = frost::trusted_dealer_keygen(&mut rng.fork(b"dealer"), 2, 3).unwrap();
+    let message = *b"test";
+
+    // Signers 1 and 3
+    let signers = [&shares[0], &shares[2]];
+    let (nonces, commitments): (Vec<_>, Vec<_>) = signers
+        .iter()
+        .map(|share| frost::commit(&mut rng, share).unwrap())
+        .unzip();
+    let signature_shares: Vec<_> = nonces
+        .into_iter()
+        .zip(signers)
+        .map(|(nonces, share)| frost::sign::<C>(&message, share, nonces, &commitments).unwrap())
+        .collect();
+
+    let signature =
+        frost::aggregate::<C>(&message, &package, &commitments, &signature_shares).unwrap();
+    signature
+        .verify::<C, UncompressedPoint>(&message, &package.group_pubkey)
+        .expect("Invalid signature");
+
+    // A tampered share is attributed to its signer
+    let mut tampered = signature_shares.clone();
+    tampered[1].z = Curve::add_mod_n(&tampered[1].z, &PRIVKEY_ONE);
+    assert_eq!(
+        frost::verify_share::<C>(&message, &package, &commitments, &tampered[0]),
+        Ok(())
+    );
+    assert_eq!(
+        frost::verify_share::<C>(&message, &package, &commitments, &tampered[1]),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        frost::aggregate::<C>(&message, &package, &commitments, &tampered).err(),
+        Some(Secp256k1SchnorrError::InvalidSignature)
+    );
+}
+
+#[cfg(feature = "frost")]
+#[test]
+fn test_frost_trusted_dealer() {
+    use crate::{frost, rng::SeededRng};
+
+    // Cover both parities of the group key and group nonce
+    for seed in 0..4 {
+        frost_roundtrip::<BIP340Challenge>(seed);
+        frost_roundtrip::<Sha256Challenge>(seed);
+    }
+
+    let mut rng = SeededRng::new([0x42; 32]);
+    assert_eq!(
+        frost::trusted_dealer_keygen(&mut rng, 4, 3).err(),
+        Some(Secp256k1SchnorrError::LimitExceeded)
+    );
+
+    // Fewer signers than the threshold, and a signer using another's nonces
+    let (shares, _) = frost::trusted_dealer_keygen(&mut rng, 2, 3).unwrap();
+    let (nonces, commitments) = frost::commit(&mut rng, &shares[0]).unwrap();
+    assert_eq!(
+        frost::sign::<BIP340Challenge>(b"test", &shares[0], nonces, &[commitments]).err(),
+        Some(Secp256k1SchnorrError::InvalidState)
+    );
+    let (_, first) = frost::commit(&mut rng, &shares[0]).unwrap();
+    let (_, second) = frost::commit(&mut rng, &shares[1]).unwrap();
+    let (other, _) = frost::commit(&mut rng, &shares[0]).unwrap();
+    assert_eq!(
+        frost::sign::<BIP340Challenge>(b"test", &shares[0], other, &[first, second]).err(),
+        Some(Secp256k1SchnorrError::NonceMismatch)
+    );
+}
+
+#[cfg(feature = "frost")]
+#[test]
+fn test_frost_lagrange_coefficient() {
+    use crate::frost::lagrange_coefficient;
+
+    let mut two = [0u8; 32];
+    two[31] = 2;
+    assert_eq!(lagrange_coefficient(1, &[1, 2]), Ok(two));
+    assert_eq!(lagrange_coefficient(1, &[2, 1]), Ok(two));
+    // Repeated signers and signers outside the set have no coefficient
+    assert_eq!(
+        lagrange_coefficient(1, &[1, 2, 2]),
+        Err(Secp256k1SchnorrError::DuplicateSigner)
+    );
+    assert_eq!(
+        lagrange_coefficient(1, &[1, 1, 2]),
+        Err(Secp256k1SchnorrError::DuplicateSigner)
+    );
+    assert_eq!(
+        lagrange_coefficient(3, &[1, 2]),
+        Err(Secp256k1SchnorrError::InvalidState)
+    );
+}
+
+#[cfg(feature = "frost")]
+#[test]
+fn test_frost_dkg() {
+    use crate::{frost, rng::SeededRng};
+
+    let mut rng = SeededRng::new([0x42; 32]);
+    let round1: Vec<_> = (1..=3)
+        .map(|index| frost::dkg_round1(&mut rng, index, 2, 3).unwrap())
+        .collect();
+    let others = |index: u32| -> Vec<frost::Round1Package> {
+        round1
+            .iter()
+            .map(|(_, package)| package.clone())
+            .filter(|package| package.index != index)
+            .collect()
+    };
+    let round2: Vec<_> = round1
+        .iter()
+        .flat_map(|(secret, _)| frost::dkg_round2(secret, &others(secret.index)).unwrap())
+        .collect();
+    let received = |index: u32| -> Vec<frost::Round2Package> {
+        round2
+            .iter()
+            .filter(|package| package.receiver == index)
+            .cloned()
+            .collect()
+    };
+    let (shares, packages): (Vec<_>, Vec<_>) = round1
+        .iter()
+        .map(|(secret, _)| {
+            frost::dkg_finalize(secret, &others(secret.index), &received(secret.index)).unwrap()
+        })
+        .unzip();
+
+    // Every participant agrees upon the group key and verifying shares
+    assert!(packages.iter().all(|package| package.eq(&packages[0])));
+    for share in &shares {
+        assert_eq!(
+            packages[0].verifying_share(share.index),
+            Some(&share.verifying_share().unwrap())
+        );
+    }
+
+    // Signers 2 and 3 sign for the group key
+    let message = *b"test";
+    let signers = [&shares[1], &shares[2]];
+    let (nonces, commitments): (Vec<_>, Vec<_>) = signers
+        .iter()
+        .map(|share| frost::commit(&mut rng, share).unwrap())
+        .unzip();
+    let signature_shares: Vec<_> = nonces
+        .into_iter()
+        .zip(signers)
+        .map(|(nonces, share)| {
+            frost::sign::<BIP340Challenge>(&message, share, nonces, &commitments).unwrap()
+        })
+        .collect();
+    frost::aggregate::<BIP340Challenge>(&message, &packages[0], &commitments, &signature_shares)
+        .unwrap()
+        .verify_xonly::<BIP340Challenge>(&message, &packages[0].xonly())
+        .expect("Invalid signature");
+
+    // A forged proof of knowledge, and a share inconsistent with its sender's commitments
+    let mut forged = others(1);
+    forged[0].proof_mu = Curve::add_mod_n(&forged[0].proof_mu, &PRIVKEY_ONE);
+    assert_eq!(
+        frost::dkg_round2(&round1[0].0, &forged).err(),
+        Some(Secp256k1SchnorrError::InvalidProof)
+    );
+    let mut tampered = received(1);
+    tampered[0].share = Curve::add_mod_n(&tampered[0].share, &PRIVKEY_ONE);
+    assert_eq!(
+        frost::dkg_finalize(&round1[0].0, &others(1), &tampered).err(),
+        Some(Secp256k1SchnorrError::InvalidProof)
+    );
+    assert_eq!(
+        frost::dkg_round2(&round1[0].0, &others(1)[..1]).err(),
+        Some(Secp256k1SchnorrError::InvalidState)
+    );
+}
+
+#[cfg(feature = "taproot")]
+#[test]
+fn test_taproot_tweak_pubkey() {
+    use crate::taproot::taproot_tweak_pubkey;
+
+    // BIP341 wallet-test-vectors.json scriptPubKey
+    let decode = |bytes: &str| -> [u8; 32] { hex::decode(bytes).unwrap().try_into().unwrap() };
+    let internal_key = decode("d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d");
+    assert_eq!(
+        taproot_tweak_pubkey(&internal_key, None),
+        Ok((
+            decode("53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343"),
+            true
+        ))
+    );
+    let internal_key = decode("187791b6f712a8ea41c8ecdd0ee77fab3e85263b37e1ec18a3651926b3a6cf27");
+    let merkle_root = decode("5b75adecf53548f3ec6ad7d78383bf84cc57b55a3127c72b9a2481752dd88b21");
+    assert_eq!(
+        taproot_tweak_pubkey(&internal_key, Some(&merkle_root)),
+        Ok((
+            decode("147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3"),
+            true
+        ))
+    );
+    assert_eq!(
+        taproot_tweak_pubkey(&[0u8; 32], None),
+        Err(Secp256k1SchnorrError::InvalidPublicKey)
+    );
+}
+
+#[cfg(all(feature = "taproot", feature = "sign"))]
+#[test]
+fn test_taproot_tweak_seckey() {
+    use crate::taproot::{taproot_tweak_pubkey, taproot_tweak_seckey};
+
+    // BIP341 wallet-test-vectors.json keyPathSpending
+    let decode = |bytes: &str| -> [u8; 32] { hex::decode(bytes).unwrap().try_into().unwrap() };
+    let privkey = decode("6b973d88838f27366ed61c9ad6367663045cb456e28335c109e30717ae0c6baa");
+    let tweaked = taproot_tweak_seckey(&privkey, None).unwrap();
+    assert_eq!(
+        tweaked,
+        decode("2405b971772ad26915c8dcdf10f238753a9b837e5f8e6a86fd7c0cce5b7296d9")
+    );
+
+    // Key-spend signatures verify against the output key
+    let internal_key = Curve::mul_g(&privkey).unwrap().x();
+    let (output_key, _) = taproot_tweak_pubkey(&internal_key, None).unwrap();
+    let message = [0x01; 32];
+    Secp256k1SchnorrSignature::sign::<BIP340Challenge>(&message, &tweaked)
+        .unwrap()
+        .verify_xonly::<BIP340Challenge>(&message, &output_key)
+        .expect("Invalid signature");
+    assert_eq!(
+        taproot_tweak_seckey(&[0u8; 32], None),
+        Err(Secp256k1SchnorrError::InvalidSecretKey)
+    );
+}
+
+#[cfg(feature = "bip322")]
+#[test]
+fn test_bip322_verify_simple() {
+    use crate::bip322::{parse_simple, verify_simple};
+
+    // BIP322 test vector for bc1ppv609nr0vr25u07u95waq5lucwfm6tde4nydujnu8npg4q75mr5sxq8lt3
+    let output_key: [u8; 32] =
+        hex::decode("0b34f2cc6f60d54e3fdc2d1dd053fcc393bd2db9acc8de4a7c3cc28a83d4d8e9")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let witness = hex::decode("0141ddebd3eb25012ffa82937d9f25f9644e047bb2f472ab6c5089bbb53588ada2884cb5bcc53911f32d8dcf9548733b694d120db6a4e485194559e8d8fe668d269f01").unwrap();
+    assert_eq!(verify_simple(b"Hello World", &witness, &output_key), Ok(()));
+    assert_eq!(
+        verify_simple(b"Hello World!", &witness, &output_key),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+
+    // Only SIGHASH_ALL may be appended to the signature
+    let mut single = witness.clone();
+    single[66] = 0x03;
+    assert_eq!(
+        parse_simple(&single).err(),
+        Some(Secp256k1SchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        parse_simple(&witness[..66]).err(),
+        Some(Secp256k1SchnorrError::InvalidSignature)
+    );
+}
+
+#[cfg(feature = "nostr")]
+#[test]
+fn test_nostr_event_verification() {
+    use crate::nostr::Event;
+
+    let pubkey: [u8; 32] =
+        hex::decode("f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let event = Event {
+        pubkey,
+        created_at: 1700000000,
+        kind: 1,
+        tags: &[
+            &[
+                "e",
+                "5c83da77af1dec6d7289834998ad7aafbd9e2191396d75ec3cc27f5a77226f36",
+                "wss://relay.example",
+            ],
+            &["t", "solana"],
+        ],
+        content: "gm \"nostr\"\n\\ é ⚡",
+    };
+    assert_eq!(
+        event.serialize(),
+        "[0,\"f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9\",1700000000,1,[[\"e\",\"5c83da77af1dec6d7289834998ad7aafbd9e2191396d75ec3cc27f5a77226f36\",\"wss://relay.example\"],[\"t\",\"solana\"]],\"gm \\\"nostr\\\"\\n\\\\ é ⚡\"]"
+    );
+    let id: [u8; 32] =
+        hex::decode("84a3d30a432c61f9ff04efa5dccb02e255f3c5d695d80279f2bae329b0a5c36c")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    assert_eq!(event.id(), id);
+
+    let sig = Secp256k1SchnorrSignature(hex::decode("4ed397767c700841de5eb86b696f1543ec8895169cbfeb595ae19355bcbb94cde35ae2101320fe0cc1211290732b5969de30822c34317602c618ecdfb3ef1fbe").unwrap().try_into().unwrap());
+    assert_eq!(event.verify(&sig), Ok(()));
+    assert_eq!(event.verify_with_id(&id, &sig), Ok(()));
+    assert_eq!(
+        event.verify_with_id(&[0u8; 32], &sig),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+
+    let edited = Event {
+        content: "gm",
+        ..event
+    };
+    assert_eq!(
+        edited.verify(&sig),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_signature_serde() {
+    let signature = Secp256k1SchnorrSignature(core::array::from_fn(|i| i as u8));
+
+    // Human-readable formats use lowercase hex, accepting either case
+    let json = serde_json::to_string(&signature).unwrap();
+    assert_eq!(json, format!("\"{}\"", hex::encode(signature.0)));
+    assert_eq!(
+        serde_json::from_str::<Secp256k1SchnorrSignature>(&json)
+            .unwrap()
+            .0,
+        signature.0
+    );
+    assert_eq!(
+        serde_json::from_str::<Secp256k1SchnorrSignature>(&json.to_uppercase())
+            .unwrap()
+            .0,
+        signature.0
+    );
+    assert!(serde_json::from_str::<Secp256k1SchnorrSignature>("\"00\"").is_err());
+    assert!(serde_json::from_str::<Secp256k1SchnorrSignature>(&json.replace('0', "g")).is_err());
+
+    // Binary formats use a fixed 64-byte array
+    let bytes = bincode::serialize(&signature).unwrap();
+    assert_eq!(bytes, signature.0);
+    assert_eq!(
+        bincode::deserialize::<Secp256k1SchnorrSignature>(&bytes)
+            .unwrap()
+            .0,
+        signature.0
+    );
+    assert!(bincode::deserialize::<Secp256k1SchnorrSignature>(&bytes[..63]).is_err());
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn test_arbitrary() {
+    use crate::{
+        arbitrary::{arbitrary_bounded, arbitrary_compressed_point},
+        keys::XOnlyPublicKey,
+    };
+    use ::arbitrary::{Arbitrary, Unstructured};
+
+    let mut below = Curve::N;
+    below[31] -= 1;
+    let bounded = |data: &[u8]| arbitrary_bounded(&mut Unstructured::new(data), &Curve::N).unwrap();
+    assert_eq!(bounded(&[0, 0]), [0u8; 32]);
+    assert_eq!(bounded(&[0, 1]), Scalar::ONE.to_bytes());
+    assert_eq!(bounded(&[2, 0]), Curve::N);
+    assert_eq!(bounded(&[2, 1]), below);
+    assert_eq!(bounded(&[4, 1]), [0xff; 32]);
+    assert_eq!(bounded(&[5; 33]), [5; 32]);
+
+    // Edge selectors reach every range check of verification
+    let signature =
+        |data: &[u8]| Secp256k1SchnorrSignature::arbitrary(&mut Unstructured::new(data)).unwrap();
+    let pubkey = Curve::mul_g(&PRIVKEY_ONE).unwrap();
+    assert_eq!(
+        signature(&[2, 0, 0, 0]).verify::<BIP340Challenge, UncompressedPoint>(b"", &pubkey),
+        Err(Secp256k1SchnorrError::InvalidSignatureR)
+    );
+    assert_eq!(
+        signature(&[0, 1, 2, 0]).verify::<BIP340Challenge, UncompressedPoint>(b"", &pubkey),
+        Err(Secp256k1SchnorrError::InvalidSignatureS)
+    );
+    assert_eq!(signature(&[0, 1, 2, 1]).s(), below);
+
+    // Scalars are always reduced
+    let scalar = |data: &[u8]| Scalar::arbitrary(&mut Unstructured::new(data)).unwrap();
+    assert_eq!(scalar(&[2, 0]), Scalar::ZERO);
+    assert_eq!(scalar(&[2, 1]).to_bytes(), below);
+    assert_eq!(scalar(&[4, 0]), Scalar::reduce(&[0xff; 32]));
+
+    let point =
+        arbitrary_compressed_point(&mut Unstructured::new(&[&[1, 5][..], &[7; 32]].concat()))
+            .unwrap();
+    assert_eq!(point.0[0], 0x03);
+    assert_eq!(point.0[1..], [7; 32]);
+    assert_eq!(
+        XOnlyPublicKey::arbitrary(&mut Unstructured::new(&[3, 1]))
+            .unwrap()
+            .0[31],
+        Curve::P[31] - 1
+    );
+}
+
+#[cfg(all(feature = "proptest", feature = "sign"))]
+::proptest::proptest! {
+    #![proptest_config(::proptest::prelude::ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn test_proptest_signed_message(
+        signed in crate::proptest::signed_message::<BIP340Challenge>()
+    ) {
+        ::proptest::prop_assert_eq!(
+            signed.signature().verify::<BIP340Challenge, CompressedPoint>(&signed.message, &signed.pubkey),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_proptest_boundary_scalars(
+        signed in crate::proptest::signed_message::<BIP340Challenge>(),
+        r in crate::proptest::near_p(),
+        s in crate::proptest::near_n(),
+    ) {
+        let verify = |r: [u8; 32], s: [u8; 32]| {
+            Secp256k1SchnorrSignature::from_parts(r, s)
+                .verify::<BIP340Challenge, CompressedPoint>(&signed.message, &signed.pubkey)
+        };
+        let signature = signed.signature();
+        if r >= Curve::P {
+            ::proptest::prop_assert_eq!(verify(r, signature.s()), Err(Secp256k1SchnorrError::InvalidSignatureR));
+        }
+        match s >= Curve::N {
+            true => ::proptest::prop_assert_eq!(verify(signature.r(), s), Err(Secp256k1SchnorrError::InvalidSignatureS)),
+            false => ::proptest::prop_assert!(s == signature.s() || verify(signature.r(), s).is_err()),
+        }
+    }
+}
+
+#[test]
+fn test_signature_bytes() {
+    let r: [u8; 32] = core::array::from_fn(|i| i as u8);
+    let s: [u8; 32] = core::array::from_fn(|i| 0xff - i as u8);
+    let signature = Secp256k1SchnorrSignature::from_parts(r, s);
+    assert_eq!(signature.r(), r);
+    assert_eq!(signature.s(), s);
+    assert_eq!(signature.as_bytes(), &signature.0);
+    assert_eq!(signature.to_bytes(), signature.0);
+
+    let decoded = Secp256k1SchnorrSignature::try_from(&signature.0[..]).unwrap();
+    assert_eq!(decoded.to_bytes(), signature.0);
+    assert_eq!(
+        Secp256k1SchnorrSignature::try_from(&signature.0[..63]).err(),
+        Some(Secp256k1SchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        Secp256k1SchnorrSignature::try_from(&[0u8; 65][..]).err(),
+        Some(Secp256k1SchnorrError::InvalidSignature)
+    );
+}
+
+#[test]
+fn test_signature_hex() {
+    let signature = Secp256k1SchnorrSignature(core::array::from_fn(|i| i as u8));
+    let hex = hex::encode(signature.0);
+    assert_eq!(signature.to_string(), hex);
+    assert_eq!(format!("{:x}", signature), hex);
+    assert_eq!(format!("{:#x}", signature), format!("0x{}", hex));
+
+    let parsed: Secp256k1SchnorrSignature = hex.parse().unwrap();
+    assert_eq!(parsed.0, signature.0);
+    let parsed: Secp256k1SchnorrSignature = hex.to_uppercase().parse().unwrap();
+    assert_eq!(parsed.0, signature.0);
+    assert_eq!(
+        hex[..126].parse::<Secp256k1SchnorrSignature>().err(),
+        Some(Secp256k1SchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        hex.replace('0', "g")
+            .parse::<Secp256k1SchnorrSignature>()
+            .err(),
+        Some(Secp256k1SchnorrError::InvalidSignature)
+    );
+}
+
+#[cfg(all(feature = "bip340", feature = "sign"))]
+#[test]
+fn test_xonly_public_key() {
+    use crate::keys::XOnlyPublicKey;
+
+    let message = *b"test";
+    let privkey = Curve::negate_n(&PRIVKEY_ONE);
+    let pubkey = Curve::mul_g(&privkey).unwrap();
+    let signature = Secp256k1SchnorrSignature::sign::<BIP340Challenge>(&message, &privkey).unwrap();
+
+    // An odd point converts to the 𝑋-only key of its negation
+    let (xonly, is_odd) = XOnlyPublicKey::from_point(&pubkey);
+    assert!(is_odd);
+    assert_eq!(XOnlyPublicKey::from(pubkey), xonly);
+    assert_eq!(XOnlyPublicKey::from(pubkey.compress()), xonly);
+    assert_eq!(xonly.lift_x(), Ok(Curve::G));
+    assert_eq!(CompressedPoint::from(xonly), Curve::G.compress());
+
+    signature
+        .verify_xonly::<BIP340Challenge>(&message, &xonly)
+        .expect("Invalid signature");
+    signature
+        .verify_xonly::<BIP340Challenge>(&message, &xonly.0)
+        .expect("Invalid signature");
+    signature
+        .verify::<BIP340Challenge, CompressedPoint>(&message, &xonly.to_compressed())
+        .expect("Invalid signature");
+
+    // X = 5 is not on the curve
+    let mut invalid = [0u8; 32];
+    invalid[31] = 5;
+    assert_eq!(
+        XOnlyPublicKey::from(invalid).lift_x(),
+        Err(Secp256k1SchnorrError::InvalidPublicKey)
+    );
+}
+
+#[cfg(all(feature = "sha256", feature = "sign"))]
+#[test]
+fn test_signature_verification_xonly_relaxed() {
+    let message = *b"test";
+    let privkey = Curve::negate_n(&PRIVKEY_ONE);
+    let signature = Secp256k1SchnorrSignature::sign::<Sha256Challenge>(&message, &privkey).unwrap();
+    let xonly = Curve::G.x();
+
+    // Sha256Challenge signatures only verify under the signer's actual, odd point
+    assert_eq!(
+        signature.verify_xonly::<Sha256Challenge>(&message, &xonly),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+    signature
+        .verify_xonly_relaxed::<Sha256Challenge>(&message, &xonly)
+        .expect("Invalid signature");
+    assert_eq!(
+        signature.verify_xonly_relaxed::<Sha256Challenge>(b"tset", &xonly),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+
+    // X = 5 is not on the curve
+    let mut invalid = [0u8; 32];
+    invalid[31] = 5;
+    assert_eq!(
+        signature.verify_xonly_relaxed::<Sha256Challenge>(&message, &invalid),
+        Err(Secp256k1SchnorrError::InvalidPublicKey)
+    );
+}
+
+#[cfg(all(feature = "bip340", feature = "sign"))]
+#[test]
+fn test_signature_prehashed() {
+    use sha2::{Digest, Sha256};
+
+    let payload = [0x42u8; 1024];
+    let digest: [u8; 32] = Sha256::digest(payload).into();
+    let signature = Secp256k1SchnorrSignature::sign_prehashed::<BIP340Challenge>(
+        &digest,
+        &PRIVKEY_ONE,
+        &[0u8; 32],
+    )
+    .unwrap();
+    signature
+        .verify_prehashed::<BIP340Challenge, UncompressedPoint>(&digest, &Curve::G)
+        .expect("Invalid signature");
+
+    // The digest, not the payload, is the signed message
+    assert_eq!(
+        signature.0,
+        Secp256k1SchnorrSignature::sign::<BIP340Challenge>(&digest, &PRIVKEY_ONE)
+            .unwrap()
+            .0
+    );
+    assert_eq!(
+        signature.verify::<BIP340Challenge, UncompressedPoint>(&payload, &Curve::G),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+}
+
+#[cfg(feature = "sign")]
+fn parts_roundtrip<C: Secp256k1SchnorrSign>() {
+    let parts: [&[u8]; 3] = [b"program", &7u64.to_le_bytes(), b"payload"];
+    let message = parts.concat();
+
+    // Signing and verifying the parts is signing and verifying their concatenation
+    let signature =
+        Secp256k1SchnorrSignature::sign_parts::<C>(&parts, &PRIVKEY_THREE, &[1u8; 32]).unwrap();
+    assert_eq!(
+        signature.0,
+        Secp256k1SchnorrSignature::sign_with_aux::<C>(&message, &PRIVKEY_THREE, &[1u8; 32])
+            .unwrap()
+            .0
+    );
+    let pubkey = Curve::mul_g(&PRIVKEY_THREE).unwrap();
+    signature
+        .verify_parts::<C, UncompressedPoint>(&parts, &pubkey)
+        .expect("Invalid signature");
+    signature
+        .verify::<C, UncompressedPoint>(&message, &pubkey)
+        .expect("Invalid signature");
+    assert_eq!(
+        signature.verify_parts::<C, UncompressedPoint>(&parts[..2], &pubkey),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        C::challenge_parts(&signature.r(), &pubkey, &parts),
+        C::challenge(&signature.r(), &pubkey, &message)
+    );
+}
+
+#[cfg(all(feature = "bip340", feature = "sign"))]
+#[test]
+fn test_signature_parts_bip340() {
+    parts_roundtrip::<BIP340Challenge>();
+}
+
+#[cfg(all(feature = "sha256", feature = "sign"))]
+#[test]
+fn test_signature_parts_sha256() {
+    parts_roundtrip::<Sha256Challenge>();
+}
+
+#[cfg(all(feature = "keccak256", feature = "sign"))]
+#[test]
+fn test_signature_parts_keccak256() {
+    parts_roundtrip::<Keccak256Challenge>();
+}
+
+#[cfg(feature = "sha256")]
+#[test]
+fn test_challenge_parts_spill() {
+    // More parts than fit on the stack are hashed identically
+    let part = [0x5au8; 3];
+    let parts = [&part[..]; 20];
+    let message = parts.concat();
+    let r = Curve::G.x();
+    for len in [1, 14, 15, 20] {
+        assert_eq!(
+            Sha256Challenge::challenge_parts(&r, &Curve::G, &parts[..len]),
+            Sha256Challenge::challenge(&r, &Curve::G, &message[..len * 3])
+        );
+    }
+}
+
+#[test]
+fn test_signature_component_refs() {
+    let signature = Secp256k1SchnorrSignature(core::array::from_fn(|i| i as u8));
+    assert_eq!(signature.r_ref(), &signature.r());
+    assert_eq!(signature.s_ref(), &signature.s());
+    assert_eq!(signature.r_ref()[..], signature.0[..32]);
+    assert_eq!(signature.s_ref()[..], signature.0[32..]);
+}
+
+#[cfg(all(feature = "signature", feature = "bip340", feature = "sign"))]
+#[test]
+fn test_signature_traits() {
+    use crate::signature::SigningKey;
+    use ::signature::{Keypair, Signer, Verifier};
+
+    let signing_key = SigningKey::<BIP340Challenge>::new(Curve::negate_n(&PRIVKEY_ONE)).unwrap();
+    let verifying_key = signing_key.verifying_key();
+    let mut pubkey = Curve::G;
+    pubkey.invert();
+    assert_eq!(verifying_key.pubkey().0, pubkey.compress().0);
+
+    let signature = signing_key.sign(b"test");
+    let expected =
+        Secp256k1SchnorrSignature::sign::<BIP340Challenge>(b"test", &Curve::negate_n(&PRIVKEY_ONE))
+            .unwrap();
+    assert_eq!(signature.0, expected.0);
+    assert!(verifying_key.verify(b"test", &signature).is_ok());
+    assert!(verifying_key.verify(b"tesT", &signature).is_err());
+
+    assert!(SigningKey::<BIP340Challenge>::new([0u8; 32]).is_err());
+    assert!(SigningKey::<BIP340Challenge>::new(Curve::N).is_err());
+}
+
+#[cfg(all(feature = "rand_core", feature = "bip340"))]
+#[test]
+fn test_sign_with_rng() {
+    let mut rng = rand_core::OsRng;
+    let a = Secp256k1SchnorrSignature::sign_with_rng::<BIP340Challenge, _>(
+        b"test",
+        &PRIVKEY_ONE,
+        &mut rng,
+    )
+    .unwrap();
+    let b = Secp256k1SchnorrSignature::sign_with_rng::<BIP340Challenge, _>(
+        b"test",
+        &PRIVKEY_ONE,
+        &mut rng,
+    )
+    .unwrap();
+    // Fresh aux randomness yields a fresh nonce on every call
+    assert_ne!(a.r(), b.r());
+    assert!(a
+        .verify::<BIP340Challenge, UncompressedPoint>(b"test", &Curve::G)
+        .is_ok());
+    assert!(b
+        .verify::<BIP340Challenge, UncompressedPoint>(b"test", &Curve::G)
+        .is_ok());
+}
+
+#[cfg(all(feature = "tagged", feature = "bip340", feature = "sign"))]
+crate::define_tagged_challenge!(
+    /// BIP340 redeclared through the macro, to check it against `BIP340Challenge`
+    Bip340Tagged,
+    "BIP0340"
+);
+
+#[cfg(all(feature = "tagged", feature = "bip340", feature = "sign"))]
+crate::define_tagged_challenge!(MyProtocolChallenge, "MyProtocol");
+
+#[cfg(all(feature = "tagged", feature = "bip340", feature = "sign"))]
+#[test]
+fn test_tagged_challenge() {
+    let privkey = Curve::negate_n(&PRIVKEY_THREE);
+    let aux = [0x42u8; 32];
+    let expected =
+        Secp256k1SchnorrSignature::sign_with_aux::<BIP340Challenge>(b"test", &privkey, &aux)
+            .unwrap();
+    let signature =
+        Secp256k1SchnorrSignature::sign_with_aux::<Bip340Tagged>(b"test", &privkey, &aux).unwrap();
+    assert_eq!(signature.0, expected.0);
+
+    // A different tag yields a different signature, which does not verify as BIP340
+    let pubkey = Curve::mul_g(&privkey).unwrap();
+    let signature =
+        Secp256k1SchnorrSignature::sign_with_aux::<MyProtocolChallenge>(b"test", &privkey, &aux)
+            .unwrap();
+    assert_ne!(signature.0, expected.0);
+    assert!(signature
+        .verify::<MyProtocolChallenge, UncompressedPoint>(b"test", &pubkey)
+        .is_ok());
+    assert!(signature
+        .verify::<BIP340Challenge, UncompressedPoint>(b"test", &pubkey)
+        .is_err());
+}
+
+#[cfg(feature = "eip712")]
+#[test]
+fn test_eip712_challenge() {
+    use crate::challenges::eip712::{eip712_digest, eip712_message, Eip712Challenge};
+    use sha3::{Digest, Keccak256};
+
+    // The "Mail" example of the EIP-712 specification
+    let domain_separator: [u8; 32] =
+        hex::decode("f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let struct_hash: [u8; 32] =
+        hex::decode("c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let digest = eip712_digest(&domain_separator, &struct_hash);
+    assert_eq!(
+        hex::encode(digest),
+        "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+    );
+
+    let message = eip712_message(&domain_separator, &struct_hash);
+    let r = Curve::G.x();
+    let expected: [u8; 32] = Keccak256::new()
+        .chain_update(r)
+        .chain_update(Curve::G.x())
+        .chain_update(digest)
+        .finalize()
+        .into();
+    assert_eq!(
+        Eip712Challenge::challenge(&r, &Curve::G, &message),
+        expected
+    );
+    assert_eq!(
+        Eip712Challenge::challenge_parts(&r, &Curve::G, &[&domain_separator, &struct_hash]),
+        expected
+    );
+
+    #[cfg(feature = "sign")]
+    {
+        let signature =
+            Secp256k1SchnorrSignature::sign::<Eip712Challenge>(&message, &PRIVKEY_ONE).unwrap();
+        assert!(signature
+            .verify::<Eip712Challenge, UncompressedPoint>(&message, &Curve::G)
+            .is_ok());
+        assert!(signature
+            .verify::<Keccak256Challenge, UncompressedPoint>(&message, &Curve::G)
+            .is_err());
+    }
+}
+
+#[cfg(feature = "eip191")]
+#[test]
+fn test_eip191_challenge() {
+    use crate::challenges::eip191::{eip191_digest, Eip191Challenge};
+    use sha3::{Digest, Keccak256};
+
+    // hashMessage("Hello World") as computed by ethers
+    assert_eq!(
+        hex::encode(eip191_digest(&[b"Hello World"])),
+        "a1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2"
+    );
+    let message = [0x5au8; 1234];
+    let expected: [u8; 32] = Keccak256::new()
+        .chain_update(b"\x19Ethereum Signed Message:\n1234")
+        .chain_update(message)
+        .finalize()
+        .into();
+    assert_eq!(
+        eip191_digest(&[&message[..1000], &message[1000..]]),
+        expected
+    );
+    assert_eq!(
+        hex::encode(eip191_digest(&[])),
+        hex::encode(Keccak256::digest(b"\x19Ethereum Signed Message:\n0"))
+    );
+
+    let r = Curve::G.x();
+    assert_eq!(
+        Eip191Challenge::challenge(&r, &Curve::G, &message),
+        <[u8; 32]>::from(
+            Keccak256::new()
+                .chain_update(r)
+                .chain_update(Curve::G.x())
+                .chain_update(expected)
+                .finalize()
+        )
+    );
+
+    #[cfg(feature = "sign")]
+    {
+        let signature =
+            Secp256k1SchnorrSignature::sign::<Eip191Challenge>(&message, &PRIVKEY_ONE).unwrap();
+        assert!(signature
+            .verify::<Eip191Challenge, UncompressedPoint>(&message, &Curve::G)
+            .is_ok());
+    }
+}
+
+#[cfg(feature = "evm")]
+#[test]
+fn test_evm_schnorr_signature() {
+    use crate::evm::EvmSchnorrSignature;
+
+    // Signed by 3 with the nonce 7, as an EVM contract computes the challenge
+    let signature = EvmSchnorrSignature(
+        hex::decode("6745cdab9ae82ef8729a465631363f91a9cf81ad1672d68fe3a2e8161f5996cb35d16902d0b88ce957ced30293a2beb642bfa820940fe373eb1659b58dd68327")
+            .unwrap()
+            .try_into()
+            .unwrap(),
+    );
+    let pubkey = Curve::mul_g(&{
+        let mut three = [0u8; 32];
+        three[31] = 3;
+        three
+    })
+    .unwrap();
+    assert!(signature.verify(b"test", &pubkey).is_ok());
+    assert!(signature.verify(b"tesT", &pubkey).is_err());
+    assert!(signature.verify(b"test", &Curve::G).is_err());
+
+    let mut high_s = signature.0;
+    high_s[32..].clone_from_slice(&Curve::N);
+    assert_eq!(
+        EvmSchnorrSignature(high_s).verify(b"test", &pubkey).err(),
+        Some(Secp256k1SchnorrError::InvalidSignatureS)
+    );
+
+    #[cfg(feature = "sign")]
+    for privkey in [PRIVKEY_ONE, Curve::negate_n(&PRIVKEY_ONE)] {
+        let pubkey = Curve::mul_g(&privkey).unwrap();
+        let signature = EvmSchnorrSignature::sign(b"test", &privkey, &[0x42; 32]).unwrap();
+        assert!(signature.verify(b"test", &pubkey).is_ok());
+    }
+}
+
+#[cfg(feature = "bsm")]
+#[test]
+fn test_bsm_challenge() {
+    use crate::challenges::bsm::{bsm_digest, BsmChallenge};
+    use sha2::{Digest, Sha256};
+
+    let bsm = |prefix: &[u8], message: &[u8]| -> [u8; 32] {
+        Sha256::digest(
+            Sha256::new()
+                .chain_update(b"\x18Bitcoin Signed Message:\n")
+                .chain_update(prefix)
+                .chain_update(message)
+                .finalize(),
+        )
+        .into()
+    };
+    let message = [0x5au8; 300];
+    assert_eq!(bsm_digest(&[b"test"]), bsm(&[4], b"test"));
+    assert_eq!(bsm_digest(&[&message[..252]]), bsm(&[252], &message[..252]));
+    assert_eq!(
+        bsm_digest(&[&message[..100], &message[100..]]),
+        bsm(&[0xfd, 0x2c, 0x01], &message)
+    );
+
+    #[cfg(feature = "sign")]
+    {
+        let privkey = Curve::negate_n(&PRIVKEY_ONE);
+        let digest = bsm_digest(&[b"test"]);
+        let signature = Secp256k1SchnorrSignature::sign::<BsmChallenge>(b"test", &privkey).unwrap();
+        let expected =
+            Secp256k1SchnorrSignature::sign::<BIP340Challenge>(&digest, &privkey).unwrap();
+        assert_eq!(signature.0, expected.0);
+        assert!(signature
+            .verify_xonly::<BsmChallenge>(b"test", &Curve::G.x())
+            .is_ok());
+    }
+}
+
+#[cfg(feature = "bch")]
+#[test]
+fn test_bch_schnorr_challenge() {
+    use crate::challenges::bch::BchSchnorrChallenge;
+
+    // The Y-coordinate of G is a square, so that of -G is not
+    assert!(crate::is_square_mod_p(&Curve::G.y()));
+    assert!(!crate::is_square_mod_p(&Curve::negate_p(&Curve::G.y())));
+
+    // Vectors of the quadratic residue Schnorr draft Bitcoin Cash adopted
+    let vectors = [
+        (
+            "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "787A848E71043D280C50470E8E1532B2DD5D20EE912A45DBDD2BD1DFBF187EF67031A98831859DC34DFFEEDDA86831842CCD0079E1F92AF177F7F22CC1DCED05",
+        ),
+        (
+            "02DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            "2A298DACAE57395A15D0795DDBFD1DCB564DA82B0F269BC70A74F8220429BA1D1E51A22CCEC35599B8F266912281F8365FFC2D035A230434A1A64DC59F7013FD",
+        ),
+    ];
+    for (pubkey, message, signature) in vectors {
+        let pubkey = CompressedPoint(hex::decode(pubkey).unwrap().try_into().unwrap());
+        let message = hex::decode(message).unwrap();
+        let signature =
+            Secp256k1SchnorrSignature(hex::decode(signature).unwrap().try_into().unwrap());
+        assert!(signature
+            .verify::<BchSchnorrChallenge, CompressedPoint>(&message, &pubkey)
+            .is_ok());
+        assert!(signature
+            .verify::<Sha256Challenge, CompressedPoint>(&message, &pubkey)
+            .is_err());
+    }
+
+    // RFC6979 nonces as libsecp256k1 derives them for Bitcoin Cash
+    #[cfg(feature = "sign")]
+    {
+        let signature =
+            Secp256k1SchnorrSignature::sign::<BchSchnorrChallenge>(&[0u8; 32], &PRIVKEY_ONE)
+                .unwrap();
+        assert_eq!(
+            hex::encode(signature.0),
+            "31ab79a6591ae4af37c4223bfa75b7396ac9c14950ea78d94afc14be3a6b61dc2a901f43c609dfb10dfe3f00015957f6461d48b61df9f452143ac6f6f7dd4edd"
+        );
+        let signature =
+            Secp256k1SchnorrSignature::sign::<BchSchnorrChallenge>(b"test", &PRIVKEY_THREE)
+                .unwrap();
+        assert_eq!(
+            hex::encode(signature.0),
+            "94e7d273f0f3d89a66ee2b039c9e43cb7ad9ce75383a40c1796b26300ad13c2871825acfd7f1dfd1b6b03837e4fe42e47f7abff4a78562c97916b1ec5d19f744"
+        );
+
+        let pubkey = Curve::mul_g(&PRIVKEY_THREE).unwrap();
+        let signature = Secp256k1SchnorrSignature::sign_with_aux::<BchSchnorrChallenge>(
+            b"test",
+            &PRIVKEY_THREE,
+            &[0x42; 32],
+        )
+        .unwrap();
+        assert!(signature
+            .verify::<BchSchnorrChallenge, UncompressedPoint>(b"test", &pubkey)
+            .is_ok());
+    }
+}
+
+#[cfg(feature = "zilliqa")]
+#[test]
+fn test_zilliqa_schnorr_signature() {
+    use crate::zilliqa::ZilliqaSchnorrSignature;
+
+    // Computed with the reference algorithm from fixed nonces
+    let vectors = [
+        (
+            "02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+            "74657374",
+            "9eaff371fc60d97f9f7220f9432a48f1c1251e104ab87134e407b6ccf749606623f025aa0add738121a99d143681252831ee5f9c7e67ecd8d38d98b2ba906157",
+        ),
+        (
+            "02dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+            "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+            "eee0e5464443fd4f17bc07989ee3237a53b300b47139f3c63f4693170fd93c581749a8e9f0de37740470904a8565915c9bb9ed9b11b8247058f0eddf03eec148",
+        ),
+    ];
+    for (pubkey, message, signature) in vectors {
+        let pubkey = CompressedPoint(hex::decode(pubkey).unwrap().try_into().unwrap());
+        let message = hex::decode(message).unwrap();
+        let signature =
+            ZilliqaSchnorrSignature(hex::decode(signature).unwrap().try_into().unwrap());
+        assert!(signature.verify(&message, &pubkey).is_ok());
+        assert!(signature.verify(b"tesT", &pubkey).is_err());
+    }
+
+    let mut zero_r = [0u8; 64];
+    zero_r[63] = 1;
+    assert_eq!(
+        ZilliqaSchnorrSignature(zero_r)
+            .verify(b"test", &Curve::G)
+            .err(),
+        Some(Secp256k1SchnorrError::InvalidSignatureR)
+    );
+
+    #[cfg(feature = "rng")]
+    {
+        let mut k = [0u8; 32];
+        k[31] = 7;
+        let signature =
+            ZilliqaSchnorrSignature::sign_with_nonce(b"test", &PRIVKEY_THREE, &k).unwrap();
+        assert_eq!(signature.0[..], hex::decode(vectors[0].2).unwrap()[..]);
+
+        let mut rng = crate::rng::SeededRng::new([0x42; 32]);
+        let pubkey = Curve::mul_g(&PRIVKEY_THREE).unwrap();
+        let signature = ZilliqaSchnorrSignature::sign(b"test", &PRIVKEY_THREE, &mut rng).unwrap();
+        assert!(signature.verify(b"test", &pubkey).is_ok());
+    }
+}
+
+#[cfg(all(feature = "halfagg", feature = "sign"))]
+#[test]
+fn test_half_aggregation() {
+    use crate::halfagg::{aggregate, verify_aggregate};
+
+    let privkeys = [PRIVKEY_ONE, PRIVKEY_THREE, Curve::negate_n(&PRIVKEY_THREE)];
+    let signed: Vec<_> = privkeys
+        .iter()
+        .enumerate()
+        .map(|(i, privkey)| {
+            let message = [i as u8; 32];
+            let pubkey = Curve::mul_g(privkey).unwrap().x();
+            let signature =
+                Secp256k1SchnorrSignature::sign::<BIP340Challenge>(&message, privkey).unwrap();
+            (pubkey, message, signature)
+        })
+        .collect();
+    let entries: Vec<_> = signed.iter().map(|(p, m, _)| (*p, *m)).collect();
+
+    let aggregated = aggregate(&signed).unwrap();
+    assert_eq!(aggregated.len(), 32 * 4);
+    assert!(verify_aggregate(&entries, &aggregated).is_ok());
+
+    // A single signature aggregates to itself
+    let single = aggregate(&signed[..1]).unwrap();
+    assert_eq!(single[..], signed[0].2 .0[..]);
+    assert!(verify_aggregate(&entries[..1], &single).is_ok());
+
+    // The empty aggregate is a zero s
+    assert!(verify_aggregate(&[], &aggregate(&[]).unwrap()).is_ok());
+
+    // Reordered, modified, truncated or tampered aggregates fail
+    let mut reordered = entries.clone();
+    reordered.swap(1, 2);
+    assert!(verify_aggregate(&reordered, &aggregated).is_err());
+    let mut modified = entries.clone();
+    modified[2].1[0] ^= 1;
+    assert!(verify_aggregate(&modified, &aggregated).is_err());
+    assert!(verify_aggregate(&entries[..2], &aggregated).is_err());
+    let mut tampered = aggregated.clone();
+    tampered[127] ^= 1;
+    assert!(verify_aggregate(&entries, &tampered).is_err());
+}
+
+#[cfg(all(feature = "blind", feature = "bip340", feature = "sha256"))]
+#[test]
+fn test_blind_signature() {
+    use crate::{blind, rng::SeededRng};
+
+    fn roundtrip<C: Secp256k1SchnorrVerify>(privkey: &[u8; 32], rng: &mut SeededRng) {
+        let pubkey = Curve::mul_g(privkey).unwrap();
+        let (nonce, commitment) = blind::commit(rng, privkey).unwrap();
+        let (factors, challenge) =
+            blind::blind::<C, _>(rng, &commitment, &pubkey, b"coin").unwrap();
+        let blind_signature = blind::blind_sign::<C>(nonce, privkey, &challenge).unwrap();
+        let signature = blind::unblind(&factors, &blind_signature);
+        assert!(signature
+            .verify::<C, UncompressedPoint>(b"coin", &pubkey)
+            .is_ok());
+        assert!(signature
+            .verify::<C, UncompressedPoint>(b"coiN", &pubkey)
+            .is_err());
+        // The signer's view shares neither the nonce nor the challenge of the signature
+        assert_ne!(signature.r(), commitment.x());
+        assert_ne!(
+            C::challenge_scalar(signature.r_ref(), &pubkey, b"coin"),
+            challenge
+        );
+    }
+
+    let mut rng = SeededRng::new([0x42; 32]);
+    for privkey in [PRIVKEY_THREE, Curve::negate_n(&PRIVKEY_THREE)] {
+        roundtrip::<BIP340Challenge>(&privkey, &mut rng);
+        roundtrip::<Sha256Challenge>(&privkey, &mut rng);
+    }
+
+    // Sessions of a signer run strictly one after another
+    let pubkey = Curve::mul_g(&PRIVKEY_THREE).unwrap();
+    let mut signer = blind::BlindSigner::new(PRIVKEY_THREE);
+    assert_eq!(
+        signer.sign::<BIP340Challenge>(&[0x01; 32]),
+        Err(Secp256k1SchnorrError::InvalidState)
+    );
+    let commitment = signer.commit(&mut rng).unwrap();
+    assert!(signer.is_open());
+    assert_eq!(
+        signer.commit(&mut rng).map(|_| ()),
+        Err(Secp256k1SchnorrError::InvalidState)
+    );
+    let (factors, challenge) =
+        blind::blind::<BIP340Challenge, _>(&mut rng, &commitment, &pubkey, b"coin").unwrap();
+    let blind_signature = signer.sign::<BIP340Challenge>(&challenge).unwrap();
+    assert!(!signer.is_open());
+    assert!(blind::unblind(&factors, &blind_signature)
+        .verify::<BIP340Challenge, UncompressedPoint>(b"coin", &pubkey)
+        .is_ok());
+    // A nonce cannot be used twice, and aborted sessions make way for new ones
+    assert_eq!(
+        signer.sign::<BIP340Challenge>(&challenge),
+        Err(Secp256k1SchnorrError::InvalidState)
+    );
+    signer.commit(&mut rng).unwrap();
+    signer.abort();
+    assert!(signer.commit(&mut rng).is_ok());
+}
+
+#[cfg(all(
+    feature = "dleq",
+    feature = "sign",
+    feature = "bip340",
+    feature = "keccak256"
+))]
+#[test]
+fn test_dleq_proof() {
+    use crate::dleq::DleqProof;
+
+    let h = Curve::mul_g(&PRIVKEY_THREE).unwrap();
+    let aux = [0x42; 32];
+
+    let (proof, a, b) =
+        DleqProof::prove::<BIP340Challenge>(&PRIVKEY_ONE, &h, b"ctx", &aux).unwrap();
+    assert_eq!(a.x(), Curve::G.x());
+    assert_eq!(b.x(), h.x());
+    assert!(proof.verify::<BIP340Challenge>(&a, &h, &b, b"ctx").is_ok());
+    // Bound to the context, the challenge scheme and every point of the statement
+    assert!(proof.verify::<BIP340Challenge>(&a, &h, &b, b"ctX").is_err());
+    assert!(proof
+        .verify::<Keccak256Challenge>(&a, &h, &b, b"ctx")
+        .is_err());
+    assert!(proof.verify::<BIP340Challenge>(&h, &h, &b, b"ctx").is_err());
+    assert!(proof.verify::<BIP340Challenge>(&a, &h, &a, b"ctx").is_err());
+
+    // A proof for a different secret does not carry over, even with consistent points
+    let (other, _, _) =
+        DleqProof::prove::<BIP340Challenge>(&PRIVKEY_THREE, &h, b"ctx", &aux).unwrap();
+    assert!(other.verify::<BIP340Challenge>(&a, &h, &b, b"ctx").is_err());
+
+    // Tampered responses and out-of-range scalars are rejected
+    let mut tampered = DleqProof(proof.0);
+    tampered.0[63] ^= 1;
+    assert!(tampered
+        .verify::<BIP340Challenge>(&a, &h, &b, b"ctx")
+        .is_err());
+    let mut invalid = DleqProof(proof.0);
+    invalid.0[32..].clone_from_slice(&Curve::N);
+    assert!(invalid
+        .verify::<BIP340Challenge>(&a, &h, &b, b"ctx")
+        .is_err());
+}
+
+#[cfg(all(
+    feature = "s2c",
+    feature = "sign",
+    feature = "bip340",
+    feature = "sha256"
+))]
+#[test]
+fn test_sign_to_contract() {
+    use crate::s2c;
+
+    fn roundtrip<C: Secp256k1SchnorrSign>(privkey: &[u8; 32]) {
+        let pubkey = Curve::mul_g(privkey).unwrap();
+        let host_data = [0x42; 32];
+
+        // The device commits to its nonce before learning the host's data
+        let host_commitment = s2c::host_commit(&host_data);
+        let original_nonce = s2c::signer_commit::<C>(b"test", privkey, &host_commitment).unwrap();
+        let signature = s2c::sign_with_commitment::<C>(b"test", privkey, &host_data).unwrap();
+
+        assert!(signature
+            .verify::<C, UncompressedPoint>(b"test", &pubkey)
+            .is_ok());
+        assert!(s2c::verify_commitment(&signature, &original_nonce, &host_data).is_ok());
+        assert_ne!(signature.r(), original_nonce.x());
+
+        // A nonce that ignores the host's data fails the commitment check
+        assert!(s2c::verify_commitment(&signature, &original_nonce, &[0x43; 32]).is_err());
+        let plain = Secp256k1SchnorrSignature::sign::<C>(b"test", privkey).unwrap();
+        assert!(s2c::verify_commitment(&plain, &original_nonce, &host_data).is_err());
+    }
+
+    for privkey in [PRIVKEY_THREE, Curve::negate_n(&PRIVKEY_THREE)] {
+        roundtrip::<BIP340Challenge>(&privkey);
+        roundtrip::<Sha256Challenge>(&privkey);
+        #[cfg(feature = "bch")]
+        roundtrip::<crate::challenges::bch::BchSchnorrChallenge>(&privkey);
+    }
+
+    assert_eq!(
+        s2c::verify_commitment(
+            &Secp256k1SchnorrSignature([0u8; 64]),
+            &CompressedPoint([0x05; 33]),
+            b"data"
+        ),
+        Err(Secp256k1SchnorrError::InvalidNonce)
+    );
+}
+
+#[cfg(all(
+    feature = "dlc",
+    feature = "sign",
+    feature = "bip340",
+    feature = "sha256"
+))]
+#[test]
+fn test_dlc_attestation() {
+    use crate::dlc;
+
+    fn roundtrip<C: Secp256k1SchnorrSign>(privkey: &[u8; 32]) {
+        let pubkey = Curve::mul_g(privkey).unwrap();
+        let (nonce, nonce_point) = dlc::announce::<C>(privkey, b"coin-flip", &[0x42; 32]).unwrap();
+
+        // Clients compute every anticipation point ahead of the event
+        let heads = dlc::anticipation_point::<C, _>(&pubkey, &nonce_point, b"heads").unwrap();
+        let tails = dlc::anticipation_point::<C, _>(&pubkey, &nonce_point, b"tails").unwrap();
+        assert_ne!(heads, tails);
+
+        let attestation = dlc::attest::<C>(nonce, privkey, b"heads").unwrap();
+        assert_eq!(Curve::mul_g(&attestation.s()).unwrap(), heads);
+        assert!(
+            dlc::verify_attestation::<C, _>(&attestation, &pubkey, &nonce_point, b"heads").is_ok()
+        );
+        assert!(
+            dlc::verify_attestation::<C, _>(&attestation, &pubkey, &nonce_point, b"tails").is_err()
+        );
+
+        // A valid signature under another nonce is not an attestation to the announcement
+        let other = Secp256k1SchnorrSignature::sign::<C>(b"heads", privkey).unwrap();
+        assert_eq!(
+            dlc::verify_attestation::<C, _>(&other, &pubkey, &nonce_point, b"heads"),
+            Err(Secp256k1SchnorrError::NonceMismatch)
+        );
+    }
+
+    for privkey in [PRIVKEY_THREE, Curve::negate_n(&PRIVKEY_THREE)] {
+        roundtrip::<BIP340Challenge>(&privkey);
+        roundtrip::<Sha256Challenge>(&privkey);
+    }
+}
+
+#[cfg(all(feature = "ecvrf", feature = "sign"))]
+#[test]
+fn test_ecvrf() {
+    use crate::ecvrf::EcvrfProof;
+
+    // ECVRF-SECP256K1-SHA256-TAI vectors from an independent implementation of RFC 9381
+    let vectors: [([u8; 32], &[u8], &str, &str); 3] = [
+        (
+            PRIVKEY_ONE,
+            b"",
+            "024192220588c4ef502f5d2ab75552edfbe0256cebb0424efb9c4c58f438c3dcb43740e701a78589f13a3577908db37b1ddb55edaf0706552da59a41b69be3740878407cf6d13675cd94802a33b5e629f7",
+            "6bf7eda22a89f87fb8c8e17fa111727ca02d0a23db29fdcbe7ac84280e8bde24",
+        ),
+        (
+            PRIVKEY_THREE,
+            b"sample",
+            "02fd028adb3bb2fa452a3fbc7728de8993c1d6c4632917bdc85df637fd0d7ea32883aa045df17acf55b8d502a3d4485cc8b8df928ff65717a36b59ee35910de2b852498cb7680c7e58c37bff3a720486cf",
+            "a7dd81caa8dd317d001c9bce16be6df7989ac915f16264eb0a22a5114ca6260a",
+        ),
+        (
+            hex::decode("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721")
+                .unwrap()
+                .try_into()
+                .unwrap(),
+            b"Hello, World!",
+            "026f8c62238a1b220490fcaa50078de9878f5279d0a1b84413fb3be3b5c87a96251f5179a1598d0f3004c70ec16cbd347bdbea7a97e47c31c82b0f4f5e4c8012a1699fb0eb23a886cfe1935ede7f92cfcb",
+            "2d7aa6c120da7af3ddf9f594c4130943298b3e85dc24ae1b2f8c738b75afa675",
+        ),
+    ];
+    for (privkey, alpha, pi, beta) in vectors {
+        let pubkey = Curve::mul_g(&privkey).unwrap();
+        let proof = EcvrfProof::prove(&privkey, alpha).unwrap();
+        assert_eq!(hex::encode(proof.0), pi);
+        assert_eq!(hex::encode(proof.verify(&pubkey, alpha).unwrap()), beta);
+        assert_eq!(hex::encode(proof.proof_to_hash().unwrap()), beta);
+        assert_eq!(
+            proof.verify(&pubkey, b"other input"),
+            Err(Secp256k1SchnorrError::InvalidProof)
+        );
+    }
+
+    // Proofs are bound to the prover's key, and tampered proofs are rejected
+    let proof = EcvrfProof::prove(&PRIVKEY_THREE, b"sample").unwrap();
+    let other = Curve::mul_g(&PRIVKEY_ONE).unwrap();
+    assert!(proof.verify(&other, b"sample").is_err());
+    let pubkey = Curve::mul_g(&PRIVKEY_THREE).unwrap();
+    for i in [1, 40, 80] {
+        let mut tampered = EcvrfProof(proof.0);
+        tampered.0[i] ^= 1;
+        assert!(tampered.verify(&pubkey, b"sample").is_err());
+    }
+}
+
+#[cfg(all(feature = "bip32", feature = "sign"))]
+#[test]
+fn test_bip32_derivation() {
+    use crate::bip32::{ExtendedPrivateKey, BIP32_HARDENED};
+
+    // BIP32 test vector 1
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let master = ExtendedPrivateKey::new_master(&seed).unwrap();
+    assert_eq!(
+        hex::encode(master.privkey()),
+        "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35"
+    );
+    assert_eq!(
+        hex::encode(master.chain_code),
+        "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508"
+    );
+    assert_eq!(
+        hex::encode(master.public_key().unwrap().fingerprint()),
+        "3442193e"
+    );
+
+    let vectors = [
+        (
+            &[BIP32_HARDENED][..],
+            "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+            "47fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141",
+            "035a784662a4a20a65bf6aab9ae98a6c068a81c52e4b032c0fb5400c706cfccc56",
+            "3442193e",
+        ),
+        (
+            &[BIP32_HARDENED, 1][..],
+            "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+            "2a7857631386ba23dacac34180dd1983734e444fdbf774041578e9b6adb37c19",
+            "03501e454bf00751f24b1b489aa925215d66af2234e3891c3b21a52bedb3cd711c",
+            "5c1bd648",
+        ),
+        (
+            &[BIP32_HARDENED, 1, BIP32_HARDENED + 2][..],
+            "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca",
+            "04466b9cc8e161e966409ca52986c584f07e9dc81f735db683c3ff6ec7b1503f",
+            "0357bfe1e341d01c69fe5654309956cbea516822fba8a601743a012a7896ee8dc2",
+            "bef5a2f9",
+        ),
+        (
+            &[BIP32_HARDENED, 1, BIP32_HARDENED + 2, 2][..],
+            "0f479245fb19a38a1954c5c7c0ebab2f9bdfd96a17563ef28a6a4b1a2a764ef4",
+            "cfb71883f01676f587d023cc53a35bc7f88f724b1f8c2892ac1275ac822a3edd",
+            "02e8445082a72f29b75ca48748a914df60622a609cacfce8ed0e35804560741d29",
+            "ee7ab90c",
+        ),
+    ];
+    for (path, privkey, chain_code, pubkey, parent_fingerprint) in vectors {
+        let child = master.derive_path(path).unwrap();
+        assert_eq!(hex::encode(child.privkey()), privkey);
+        assert_eq!(hex::encode(child.chain_code), chain_code);
+        assert_eq!(child.depth as usize, path.len());
+        assert_eq!(hex::encode(child.parent_fingerprint), parent_fingerprint);
+        assert_eq!(child.child_number, path[path.len() - 1]);
+
+        let xpub = child.public_key().unwrap();
+        assert_eq!(hex::encode(xpub.pubkey.0), pubkey);
+
+        // Non-hardened children derive identically from the parent's extended public key
+        let (last, parent) = path.split_last().unwrap();
+        if *last < BIP32_HARDENED {
+            let parent = master.derive_path(parent).unwrap().public_key().unwrap();
+            assert!(parent.derive_child(*last).unwrap() == xpub);
+        } else {
+            assert_eq!(
+                xpub.derive_child(*last).map(|_| ()),
+                Err(Secp256k1SchnorrError::InvalidSecretKey)
+            );
+        }
+    }
+
+    // Signatures of derived keys verify against publicly derived keys
+    let xpub = master
+        .derive_path(&[BIP32_HARDENED])
+        .unwrap()
+        .public_key()
+        .unwrap();
+    let user = master.derive_path(&[BIP32_HARDENED, 7, 42]).unwrap();
+    let signature =
+        Secp256k1SchnorrSignature::sign::<BIP340Challenge>(b"test", user.privkey()).unwrap();
+    let pubkey = xpub.derive_path(&[7, 42]).unwrap().pubkey;
+    assert!(signature
+        .verify::<BIP340Challenge, CompressedPoint>(b"test", &pubkey)
+        .is_ok());
+
+    assert!(ExtendedPrivateKey::new_master(&[0u8; 15]).is_err());
+}
+
+#[cfg(all(feature = "sign", feature = "sha256"))]
+#[test]
+fn test_key_tweaks() {
+    use crate::keys::{pubkey_tweak_add, pubkey_tweak_mul, seckey_tweak_add, seckey_tweak_mul};
+
+    let tweak = [0x42; 32];
+    for privkey in [PRIVKEY_THREE, Curve::negate_n(&PRIVKEY_THREE)] {
+        let pubkey = Curve::mul_g(&privkey).unwrap();
+
+        let added = seckey_tweak_add(&privkey, &tweak).unwrap();
+        assert_eq!(
+            pubkey_tweak_add(&pubkey, &tweak).unwrap(),
+            Curve::mul_g(&added).unwrap()
+        );
+        let multiplied = seckey_tweak_mul(&privkey, &tweak).unwrap();
+        assert_eq!(
+            pubkey_tweak_mul(&pubkey, &tweak).unwrap(),
+            Curve::mul_g(&multiplied).unwrap()
+        );
+
+        // Signatures of the tweaked secret key verify against the untweaked public key
+        let signature =
+            Secp256k1SchnorrSignature::sign::<Sha256Challenge>(b"test", &added).unwrap();
+        assert!(signature
+            .verify_tweaked::<Sha256Challenge, UncompressedPoint>(b"test", &pubkey, &tweak)
+            .is_ok());
+        assert!(signature
+            .verify_tweaked::<Sha256Challenge, UncompressedPoint>(b"test", &pubkey, &[0x43; 32])
+            .is_err());
+        assert!(signature
+            .verify::<Sha256Challenge, UncompressedPoint>(b"test", &pubkey)
+            .is_err());
+    }
+
+    // BIP340 signatures under a tweaked key verify once the key is lifted, as BIP341 does
+    let internal_key = crate::keys::lift_x(&Curve::mul_g(&PRIVKEY_THREE).unwrap().x()).unwrap();
+    let privkey = match Curve::mul_g(&PRIVKEY_THREE).unwrap().is_odd() {
+        true => Curve::negate_n(&PRIVKEY_THREE),
+        false => PRIVKEY_THREE,
+    };
+    let signature = Secp256k1SchnorrSignature::sign::<BIP340Challenge>(
+        b"test",
+        &seckey_tweak_add(&privkey, &tweak).unwrap(),
+    )
+    .unwrap();
+    assert!(signature
+        .verify_tweaked::<BIP340Challenge, UncompressedPoint>(b"test", &internal_key, &tweak)
+        .is_ok());
+
+    // Tweaks must be scalars, and may not cancel the key out
+    let pubkey = Curve::mul_g(&PRIVKEY_THREE).unwrap();
+    assert_eq!(
+        pubkey_tweak_add(&pubkey, &Curve::N),
+        Err(Secp256k1SchnorrError::ArithmeticOverflow)
+    );
+    assert_eq!(
+        seckey_tweak_add(&PRIVKEY_THREE, &Curve::N),
+        Err(Secp256k1SchnorrError::ArithmeticOverflow)
+    );
+    let cancelling = Curve::negate_n(&PRIVKEY_THREE);
+    assert_eq!(
+        seckey_tweak_add(&PRIVKEY_THREE, &cancelling),
+        Err(Secp256k1SchnorrError::InvalidSecretKey)
+    );
+    assert_eq!(
+        pubkey_tweak_add(&pubkey, &cancelling),
+        Err(Secp256k1SchnorrError::PointAtInfinity)
+    );
+    assert_eq!(
+        seckey_tweak_mul(&PRIVKEY_THREE, &[0u8; 32]),
+        Err(Secp256k1SchnorrError::ArithmeticOverflow)
+    );
+    assert!(pubkey_tweak_mul(&pubkey, &[0u8; 32]).is_err());
+}
+
+#[cfg(all(feature = "sign", feature = "sha256"))]
+#[test]
+fn test_verify_recover_r() {
+    for privkey in [PRIVKEY_THREE, Curve::negate_n(&PRIVKEY_THREE)] {
+        let pubkey = Curve::mul_g(&privkey).unwrap();
+
+        // BIP340 recovers the even R, while other schemes recover R with its own parity
+        let signature =
+            Secp256k1SchnorrSignature::sign::<BIP340Challenge>(b"test", &privkey).unwrap();
+        let r = signature
+            .verify_recover_r::<BIP340Challenge, UncompressedPoint>(b"test", &pubkey)
+            .unwrap();
+        assert_eq!(r, crate::keys::lift_x(&signature.r()).unwrap());
+
+        let signature =
+            Secp256k1SchnorrSignature::sign::<Sha256Challenge>(b"test", &privkey).unwrap();
+        let r = signature
+            .verify_parts_recover_r::<Sha256Challenge, UncompressedPoint>(&[b"te", b"st"], &pubkey)
+            .unwrap();
+        assert_eq!(r.x(), signature.r());
+        let e = Sha256Challenge::challenge_scalar(signature.r_ref(), &pubkey, b"test");
+        let e_p = Curve::ecmul(&pubkey, &e).unwrap();
+        assert_eq!(r + e_p, Curve::mul_g(&signature.s()).unwrap());
+
+        assert_eq!(
+            signature
+                .verify_recover_r::<Sha256Challenge, UncompressedPoint>(b"tesT", &pubkey)
+                .map(|_| ()),
+            Err(Secp256k1SchnorrError::InvalidSignature)
+        );
+    }
+}
+
+#[cfg(all(feature = "bip340", feature = "sha256", feature = "keccak256"))]
+#[test]
+fn test_estimated_compute_units() {
+    use crate::compute::{
+        hash_compute_units, SECP256K1_RECOVER_COMPUTE_UNITS, VERIFY_ARITHMETIC_COMPUTE_UNITS,
+    };
+
+    // Slices are charged one unit per two bytes, with a minimum of 10
+    assert_eq!(hash_compute_units(&[]), 85);
+    assert_eq!(hash_compute_units(&[4, 32, 100]), 85 + 10 + 16 + 50);
+
+    let base = SECP256K1_RECOVER_COMPUTE_UNITS + VERIFY_ARITHMETIC_COMPUTE_UNITS;
+    assert_eq!(
+        Secp256k1SchnorrSignature::estimated_compute_units::<Sha256Challenge>(100),
+        base + 85 + 16 + 16 + 50
+    );
+    assert_eq!(
+        Secp256k1SchnorrSignature::estimated_compute_units::<Keccak256Challenge>(100),
+        Secp256k1SchnorrSignature::estimated_compute_units::<Sha256Challenge>(100)
+    );
+    assert_eq!(
+        Secp256k1SchnorrSignature::estimated_compute_units::<BIP340Challenge>(100),
+        base + 85 + 4 * 16 + 50
+    );
+
+    // Estimates grow with the message, and stay within the default budget of an instruction
+    let small = Secp256k1SchnorrSignature::estimated_compute_units::<BIP340Challenge>(32);
+    let large = Secp256k1SchnorrSignature::estimated_compute_units::<BIP340Challenge>(1024);
+    assert!(small < large && large < 200_000);
+
+    #[cfg(feature = "bch")]
+    assert!(
+        Secp256k1SchnorrSignature::estimated_compute_units::<
+            crate::challenges::bch::BchSchnorrChallenge,
+        >(32)
+            > base + crate::compute::square_check_compute_units()
+    );
+}
+
+#[cfg(all(feature = "bench-sbf", feature = "sign"))]
+#[test]
+fn test_bench_instruction() {
+    use crate::program::bench::{BenchInstruction, BenchPoint, BenchScheme};
+
+    let pubkey = Curve::mul_g(&PRIVKEY_THREE).unwrap();
+    for scheme in BenchScheme::ALL {
+        let signature = scheme.sign(b"test", &PRIVKEY_THREE).unwrap();
+        for point in BenchPoint::ALL {
+            let data = BenchInstruction::encode(scheme, point, &signature, &pubkey, b"test");
+            let instruction = BenchInstruction::parse(&data).unwrap();
+            assert_eq!(instruction.scheme, scheme);
+            assert_eq!(instruction.point, point);
+            // Off-chain there are no compute units to measure
+            assert_eq!(instruction.verify(), Ok(0));
+
+            let mut tampered = data.clone();
+            *tampered.last_mut().unwrap() ^= 1;
+            assert_eq!(
+                BenchInstruction::parse(&tampered).unwrap().verify(),
+                Err(Secp256k1SchnorrError::InvalidSignature)
+            );
+        }
+    }
+    assert_eq!(
+        BenchInstruction::parse(&[0u8; 65]),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+}
+
+/// Measures every scheme and point type in the benchmark program built for SBF, failing if any
+/// verification costs more than its estimate. Run with `cargo test-sbf --features bench-sbf`,
+/// which builds the program before running the harness against it.
+#[cfg(all(feature = "bench-sbf", feature = "sign", not(target_os = "solana")))]
+#[test]
+fn test_bench_sbf_compute_units() {
+    use crate::program::bench::{BenchInstruction, BenchPoint, BenchScheme};
+    use solana_program_test::{tokio, ProgramTest};
+    use solana_sdk::{
+        instruction::Instruction, pubkey::Pubkey, signer::Signer, transaction::Transaction,
+    };
+
+    let program_id = Pubkey::new_unique();
+    let mut program_test = ProgramTest::new("solana_secp256k1_schnorr", program_id, None);
+    program_test.prefer_bpf(true);
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        let (banks_client, payer, blockhash) = program_test.start().await;
+        let pubkey = Curve::mul_g(&PRIVKEY_THREE).unwrap();
+        for len in [32, 256] {
+            let message = vec![0x42u8; len];
+            for scheme in BenchScheme::ALL {
+                let signature = scheme.sign(&message, &PRIVKEY_THREE).unwrap();
+                for point in BenchPoint::ALL {
+                    let data = BenchInstruction::encode(scheme, point, &signature, &pubkey, &message);
+                    let transaction = Transaction::new_signed_with_payer(
+                        &[Instruction::new_with_bytes(program_id, &data, vec![])],
+                        Some(&payer.pubkey()),
+                        &[&payer],
+                        blockhash,
+                    );
+                    let simulation = banks_client
+                        .simulate_transaction(transaction)
+                        .await
+                        .unwrap();
+                    simulation.result.unwrap().unwrap();
+                    let return_data = simulation
+                        .simulation_details
+                        .unwrap()
+                        .return_data
+                        .expect("Missing compute units");
+                    let units = u64::from_le_bytes(return_data.data.try_into().unwrap());
+                    let estimate = scheme.estimated_compute_units(len);
+                    println!("{scheme:?} {point:?} {len}: {units} / {estimate} CU");
+                    assert!(
+                        units <= estimate,
+                        "{scheme:?} over {point:?} with a {len}-byte message consumed {units} CU, over its estimate of {estimate}"
+                    );
+                }
+            }
+        }
+    });
+}
+
+#[cfg(all(feature = "debug-logs", feature = "sign"))]
+#[test]
+fn test_debug_logs() {
+    let pubkey = Curve::mul_g(&PRIVKEY_ONE).unwrap();
+    let signature =
+        Secp256k1SchnorrSignature::sign::<BIP340Challenge>(b"test", &PRIVKEY_ONE).unwrap();
+
+    // Logging each failed stage leaves the errors returned off-chain unchanged
+    let mut out_of_range = Secp256k1SchnorrSignature(signature.0);
+    out_of_range.0[..32].clone_from_slice(&Curve::P);
+    assert_eq!(
+        out_of_range.verify::<BIP340Challenge, UncompressedPoint>(b"test", &pubkey),
+        Err(Secp256k1SchnorrError::InvalidSignatureR)
+    );
+    let mut out_of_range = Secp256k1SchnorrSignature(signature.0);
+    out_of_range.0[32..].clone_from_slice(&Curve::N);
+    assert_eq!(
+        out_of_range.verify::<BIP340Challenge, UncompressedPoint>(b"test", &pubkey),
+        Err(Secp256k1SchnorrError::InvalidSignatureS)
+    );
+    assert_eq!(
+        signature.verify::<BIP340Challenge, UncompressedPoint>(b"tesT", &pubkey),
+        Err(Secp256k1SchnorrError::InvalidSignature)
+    );
+    signature
+        .verify::<BIP340Challenge, UncompressedPoint>(b"test", &pubkey)
+        .expect("Invalid signature");
+}
+
+#[cfg(all(feature = "anchor", feature = "sign"))]
+#[test]
+fn test_anchor_integration() {
+    use anchor_lang::{
+        error::Error,
+        prelude::{borsh, AnchorDeserialize},
+        Space,
+    };
+
+    let pubkey = Curve::mul_g(&PRIVKEY_ONE).unwrap();
+    let signature =
+        Secp256k1SchnorrSignature::sign::<BIP340Challenge>(b"test", &PRIVKEY_ONE).unwrap();
+
+    // Signatures serialize as their 64 bytes, without a length prefix
+    let bytes = borsh::to_vec(&signature).unwrap();
+    assert_eq!(bytes, signature.0);
+    assert_eq!(Secp256k1SchnorrSignature::INIT_SPACE, bytes.len());
+    let decoded = Secp256k1SchnorrSignature::try_from_slice(&bytes).unwrap();
+    assert_eq!(decoded.0, signature.0);
+    assert!(Secp256k1SchnorrSignature::try_from_slice(&bytes[..63]).is_err());
+
+    crate::anchor::verify::<BIP340Challenge, UncompressedPoint>(&signature, b"test", &pubkey)
+        .expect("Invalid signature");
+    match crate::anchor::verify::<BIP340Challenge, UncompressedPoint>(&signature, b"tesT", &pubkey)
+    {
+        Err(Error::AnchorError(error)) => {
+            assert_eq!(error.error_name, "InvalidSignature");
+            assert_eq!(
+                error.error_code_number,
+                Secp256k1SchnorrError::InvalidSignature as u32
+            );
+        }
+        _ => panic!("Expected an AnchorError"),
+    }
+}
+
+#[cfg(all(feature = "pinocchio-example", feature = "sign"))]
+#[test]
+fn test_pinocchio_example() {
+    use crate::program::example::{process_instruction, verify_example_instruction};
+    use ::pinocchio::{entrypoint::lazy::InstructionContext, error::ProgramError};
+
+    let pubkey = Curve::mul_g(&PRIVKEY_THREE).unwrap().compress();
+    let signature =
+        Secp256k1SchnorrSignature::sign::<BIP340Challenge>(b"test", &PRIVKEY_THREE).unwrap();
+    let mut data = signature.0.to_vec();
+    data.extend_from_slice(&pubkey.0);
+    data.extend_from_slice(b"test");
+    verify_example_instruction(&data).expect("Invalid signature");
+    assert_eq!(
+        verify_example_instruction(&data[..96]),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+
+    // Serializes the loader input of an instruction without accounts: the account count, the
+    // data length, the data and the program id, 8-byte aligned
+    let input = |data: &[u8]| {
+        let mut bytes = 0u64.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(data);
+        bytes.extend_from_slice(&[0u8; 32]);
+        let mut words = vec![0u64; bytes.len().div_ceil(8)];
+        for (word, chunk) in words.iter_mut().zip(bytes.chunks(8)) {
+            let mut le = [0u8; 8];
+            le[..chunk.len()].clone_from_slice(chunk);
+            *word = u64::from_le_bytes(le);
+        }
+        words
+    };
+    let mut valid = input(&data);
+    let context = unsafe { InstructionContext::new_unchecked(valid.as_mut_ptr() as *mut u8) };
+    assert_eq!(process_instruction(context), Ok(()));
+
+    let mut tampered = data.clone();
+    *tampered.last_mut().unwrap() ^= 1;
+    let mut invalid = input(&tampered);
+    let context = unsafe { InstructionContext::new_unchecked(invalid.as_mut_ptr() as *mut u8) };
+    assert_eq!(
+        process_instruction(context),
+        Err(ProgramError::Custom(
+            Secp256k1SchnorrError::InvalidSignature as u32
+        ))
+    );
+}
+
+/// Measures the total compute units of the example program built for SBF, entrypoint included,
+/// failing if a verification costs more than its estimate. Run with
+/// `cargo test-sbf --features pinocchio-example,program-test`.
+#[cfg(all(
+    feature = "pinocchio-example",
+    feature = "program-test",
+    feature = "sign",
+    not(target_os = "solana")
+))]
+#[test]
+fn test_pinocchio_example_sbf_compute_units() {
+    use solana_program_test::{tokio, ProgramTest};
+    use solana_sdk::{
+        instruction::Instruction, pubkey::Pubkey, signer::Signer, transaction::Transaction,
+    };
+
+    let program_id = Pubkey::new_unique();
+    let mut program_test = ProgramTest::new("solana_secp256k1_schnorr", program_id, None);
+    program_test.prefer_bpf(true);
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        let (banks_client, payer, blockhash) = program_test.start().await;
+        let pubkey = Curve::mul_g(&PRIVKEY_THREE).unwrap().compress();
+        for len in [32, 256] {
+            let message = vec![0x42u8; len];
+            let signature =
+                Secp256k1SchnorrSignature::sign::<BIP340Challenge>(&message, &PRIVKEY_THREE)
+                    .unwrap();
+            let mut data = signature.0.to_vec();
+            data.extend_from_slice(&pubkey.0);
+            data.extend_from_slice(&message);
+            let transaction = Transaction::new_signed_with_payer(
+                &[Instruction::new_with_bytes(program_id, &data, vec![])],
+                Some(&payer.pubkey()),
+                &[&payer],
+                blockhash,
+            );
+            let simulation = banks_client
+                .simulate_transaction(transaction)
+                .await
+                .unwrap();
+            simulation.result.unwrap().unwrap();
+            let units = simulation.simulation_details.unwrap().units_consumed;
+            let estimate =
+                Secp256k1SchnorrSignature::estimated_compute_units::<BIP340Challenge>(len);
+            println!("pinocchio example {len}: {units} / {estimate} CU");
+            assert!(
+                units <= estimate,
+                "The example program consumed {units} CU over a {len}-byte message, over its estimate of {estimate}"
+            );
+        }
+    });
+}
+
+#[cfg(all(feature = "program", feature = "sign"))]
+#[test]
+fn test_verify_from_instruction_data() {
+    use crate::program::offsets::{
+        verify_from_instruction_data, verify_from_instruction_data_with, SchnorrSignatureOffsets,
+        CURRENT_INSTRUCTION,
+    };
+
+    // Three entries share a message, which follows the count and their offsets
+    let message = b"test";
+    let header = 1 + 3 * 11;
+    let mut payload = message.to_vec();
+    let mut offsets = Vec::new();
+    for privkey in [PRIVKEY_ONE, PRIVKEY_THREE, PRIVKEY_ONE] {
+        let base = (header + payload.len()) as u16;
+        payload.extend_from_slice(&Curve::mul_g(&privkey).unwrap().compress().0);
+        payload.extend_from_slice(
+            &Secp256k1SchnorrSignature::sign::<BIP340Challenge>(message, &privkey)
+                .unwrap()
+                .0,
+        );
+        offsets.push(SchnorrSignatureOffsets {
+            signature_offset: base + 33,
+            signature_instruction_index: CURRENT_INSTRUCTION,
+            pubkey_offset: base,
+            pubkey_instruction_index: CURRENT_INSTRUCTION,
+            message_data_offset: header as u16,
+            message_data_size: message.len() as u16,
+            message_instruction_index: CURRENT_INSTRUCTION,
+        });
+    }
+    let encode = |offsets: &[SchnorrSignatureOffsets], payload: &[u8]| {
+        let mut data = vec![offsets.len() as u8];
+        for entry in offsets {
+            data.extend_from_slice(&entry.to_bytes());
+        }
+        data.extend_from_slice(payload);
+        data
+    };
+    let data = encode(&offsets, &payload);
+    assert_eq!(
+        verify_from_instruction_data::<BIP340Challenge>(&data),
+        Ok(vec![true, true, true])
+    );
+
+    // Invalid signatures and public keys are reported rather than failing the call
+    let mut tampered = payload.clone();
+    tampered[4 + 33 + 97] ^= 1;
+    tampered[4 + 2 * 97] = 0x05;
+    assert_eq!(
+        verify_from_instruction_data::<BIP340Challenge>(&encode(&offsets, &tampered)),
+        Ok(vec![true, false, false])
+    );
+    assert_eq!(
+        verify_from_instruction_data::<Sha256Challenge>(&data),
+        Ok(vec![false, false, false])
+    );
+
+    // Messages may live in other instructions, resolved by the caller
+    let other = [0u8, 0, b't', b'e', b's', b't'];
+    let mut moved = offsets.clone();
+    moved[2].message_data_offset = 2;
+    moved[2].message_instruction_index = 1;
+    let data = encode(&moved, &payload);
+    assert_eq!(
+        verify_from_instruction_data::<BIP340Challenge>(&data),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+    assert_eq!(
+        verify_from_instruction_data_with::<BIP340Challenge, _>(&data, |index| match index {
+            1 => Ok(&other[..]),
+            _ => Err(Secp256k1SchnorrError::InvalidMessage),
+        }),
+        Ok(vec![true, true, true])
+    );
+
+    // Malformed layouts fail the call
+    let mut out_of_bounds = offsets.clone();
+    out_of_bounds[0].message_data_size = u16::MAX;
+    assert_eq!(
+        verify_from_instruction_data::<BIP340Challenge>(&encode(&out_of_bounds, &payload)),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+    assert_eq!(
+        verify_from_instruction_data::<BIP340Challenge>(&[2, 0, 0]),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+    assert_eq!(
+        verify_from_instruction_data::<BIP340Challenge>(&[]),
+        Err(Secp256k1SchnorrError::InvalidMessage)
+    );
+    assert_eq!(
+        verify_from_instruction_data::<BIP340Challenge>(&[0]),
+        Ok(vec![])
+    );
+}
@@ -0,0 +1,150 @@
+//! JavaScript bindings via `wasm-bindgen`. Build the WebAssembly module and generate its bindings
+//! with:
+//!
+//! ```sh
+//! cargo rustc --release --target wasm32-unknown-unknown --crate-type cdylib --features wasm
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/solana_secp256k1_schnorr.wasm
+//! ```
+
+use solana_secp256k1::{CompressedPoint, Secp256k1Point};
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    address::Secp256k1SchnorrSignatureBundle, errors::Secp256k1SchnorrError, keys, program::Scheme,
+    rng, Secp256k1SchnorrSignature,
+};
+
+fn array<const N: usize>(
+    bytes: &[u8],
+    error: Secp256k1SchnorrError,
+) -> Result<[u8; N], Secp256k1SchnorrError> {
+    bytes.try_into().map_err(|_| error)
+}
+
+fn js_error(error: Secp256k1SchnorrError) -> JsError {
+    JsError::new(&format!("{:?}", error))
+}
+
+fn pubkey(bytes: &[u8]) -> Result<CompressedPoint, Secp256k1SchnorrError> {
+    keys::parse_sec1_compressed(bytes)
+}
+
+/// ### Keypair
+///
+/// A private key and its 33-byte compressed public key.
+#[wasm_bindgen]
+pub struct Keypair {
+    privkey: [u8; 32],
+    pubkey: CompressedPoint,
+}
+
+#[wasm_bindgen]
+impl Keypair {
+    /// Generates a keypair from 32 bytes of secret, uniformly random `seed`, such as the output
+    /// of `crypto.getRandomValues`. The same seed always yields the same keypair.
+    #[wasm_bindgen(constructor)]
+    pub fn new(seed: &[u8]) -> Result<Keypair, JsError> {
+        let seed = array(seed, Secp256k1SchnorrError::InvalidSecretKey).map_err(js_error)?;
+        let (privkey, pubkey) = rng::keygen(&mut rng::SeededRng::new(seed)).map_err(js_error)?;
+        Ok(Keypair {
+            privkey,
+            pubkey: pubkey.compress(),
+        })
+    }
+
+    /// Restores the keypair of an existing 32-byte private key.
+    #[wasm_bindgen(js_name = fromPrivkey)]
+    pub fn from_privkey(privkey: &[u8]) -> Result<Keypair, JsError> {
+        let privkey = array(privkey, Secp256k1SchnorrError::InvalidSecretKey).map_err(js_error)?;
+        let pubkey = solana_secp256k1::Curve::mul_g(&privkey)
+            .map_err(|_| js_error(Secp256k1SchnorrError::InvalidSecretKey))?;
+        Ok(Keypair {
+            privkey,
+            pubkey: pubkey.compress(),
+        })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn privkey(&self) -> Vec<u8> {
+        self.privkey.to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn pubkey(&self) -> Vec<u8> {
+        self.pubkey.0.to_vec()
+    }
+
+    /// The 20-byte Ethereum address of the public key
+    #[wasm_bindgen(getter)]
+    pub fn address(&self) -> Vec<u8> {
+        keys::eth_address(&self.pubkey).to_vec()
+    }
+
+    /// Signs a message under `scheme`, a `Scheme` discriminant, returning the 64-byte signature.
+    pub fn sign(&self, scheme: u8, message: &[u8]) -> Result<Vec<u8>, JsError> {
+        Scheme::try_from(scheme)
+            .and_then(|scheme| scheme.sign(message, &self.privkey))
+            .map(|signature| signature.0.to_vec())
+            .map_err(js_error)
+    }
+
+    /// Signs a message under `scheme`, returning the 97-byte signature bundle accepted by
+    /// `verifyByEthAddress`.
+    #[wasm_bindgen(js_name = signBundle)]
+    pub fn sign_bundle(&self, scheme: u8, message: &[u8]) -> Result<Vec<u8>, JsError> {
+        let signature = self.sign(scheme, message)?;
+        bundle_encode(&signature, &self.pubkey.0)
+    }
+}
+
+/// ### Verify
+///
+/// Verifies a 64-byte signature over a message against a 33-byte compressed public key under
+/// `scheme`, a `Scheme` discriminant, throwing the name of the `Secp256k1SchnorrError` if it is
+/// invalid.
+#[wasm_bindgen]
+pub fn verify(scheme: u8, message: &[u8], signature: &[u8], pubkey: &[u8]) -> Result<(), JsError> {
+    let signature = array(signature, Secp256k1SchnorrError::InvalidSignature).map_err(js_error)?;
+    let pubkey = self::pubkey(pubkey).map_err(js_error)?;
+    Scheme::try_from(scheme)
+        .and_then(|scheme| scheme.verify(&Secp256k1SchnorrSignature(signature), message, &pubkey))
+        .map_err(js_error)
+}
+
+/// ### Bundle Encode
+///
+/// Encodes a 64-byte signature and the signer's 33-byte compressed public key into a 97-byte
+/// signature bundle.
+#[wasm_bindgen(js_name = bundleEncode)]
+pub fn bundle_encode(signature: &[u8], pubkey: &[u8]) -> Result<Vec<u8>, JsError> {
+    let signature =
+        array::<64>(signature, Secp256k1SchnorrError::InvalidSignature).map_err(js_error)?;
+    let pubkey = self::pubkey(pubkey).map_err(js_error)?;
+    let mut bundle = signature.to_vec();
+    bundle.extend_from_slice(&pubkey.0);
+    Ok(bundle)
+}
+
+/// ### Verify By Eth Address
+///
+/// Verifies a 97-byte signature bundle over a message under `scheme`, a `Scheme` discriminant,
+/// against a signer known only by their 20-byte Ethereum address.
+#[wasm_bindgen(js_name = verifyByEthAddress)]
+pub fn verify_by_eth_address(
+    scheme: u8,
+    message: &[u8],
+    bundle: &[u8],
+    address: &[u8],
+) -> Result<(), JsError> {
+    let bundle = Secp256k1SchnorrSignatureBundle(
+        array(bundle, Secp256k1SchnorrError::InvalidMessage).map_err(js_error)?,
+    );
+    let address = array::<20>(address, Secp256k1SchnorrError::InvalidMessage).map_err(js_error)?;
+    let pubkey = self::pubkey(&bundle.pubkey().0).map_err(js_error)?;
+    if keys::eth_address(&pubkey).ne(&address) {
+        return Err(js_error(Secp256k1SchnorrError::Unauthorized));
+    }
+    Scheme::try_from(scheme)
+        .and_then(|scheme| scheme.verify(&bundle.signature(), message, &pubkey))
+        .map_err(js_error)
+}
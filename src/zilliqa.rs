@@ -0,0 +1,135 @@
+use solana_nostd_sha256::hashv;
+use solana_secp256k1::{Curve, Secp256k1Point, UncompressedPoint};
+
+use crate::{errors::Secp256k1SchnorrError, recover_nonce, scalar::Scalar};
+
+#[cfg(feature = "rng")]
+use crate::rng::{self, Secp256k1SchnorrRng};
+
+pub const ZILLIQA_SIGNATURE_LENGTH: usize = 64;
+
+/// ### Zilliqa Challenge
+///
+/// Computes the challenge `𝑟 = SHA256(𝑄∥𝑃∥𝑀) mod 𝑁` of the Zilliqa Schnorr scheme, over the
+/// compressed commitment 𝑄 and the compressed public key 𝑃.
+pub fn zilliqa_challenge<T: Secp256k1Point, U: Secp256k1Point>(
+    commitment: &T,
+    pubkey: &U,
+    message: &[u8],
+) -> [u8; 32] {
+    Scalar::reduce(&hashv(&[
+        &commitment.compress().0,
+        &pubkey.compress().0,
+        message,
+    ]))
+    .to_bytes()
+}
+
+/// # ZilliqaSchnorrSignature
+/// A signature `𝑟∥𝑠` of the Schnorr scheme used by Zilliqa, where 𝑟 is the challenge itself and
+/// 𝑠 = 𝑘 - 𝑟⋅𝑑, so that signatures produced by Zilliqa wallets and nodes can be checked by
+/// cross-chain verifiers on Solana.
+pub struct ZilliqaSchnorrSignature(pub [u8; ZILLIQA_SIGNATURE_LENGTH]);
+
+impl ZilliqaSchnorrSignature {
+    pub fn r(&self) -> [u8; 32] {
+        let mut r = [0u8; 32];
+        r.clone_from_slice(&self.0[..32]);
+        r
+    }
+
+    pub fn s(&self) -> [u8; 32] {
+        let mut s = [0u8; 32];
+        s.clone_from_slice(&self.0[32..]);
+        s
+    }
+
+    /// ### Verify
+    /// Verifies the signature as the Zilliqa reference implementation does, recovering the
+    /// commitment 𝑄 = 𝑠⋅𝐺 + 𝑟⋅𝑃 with a single `secp256k1_recover` call and checking that it hashes
+    /// back to 𝑟.
+    ///
+    /// Fails with `InvalidSignatureR` or `InvalidSignatureS` unless both 𝑟 and 𝑠 are nonzero
+    /// scalars, and with `InvalidSignature` if the challenge does not match.
+    ///
+    /// Example:
+    /// ```rs
+    /// use solana_secp256k1_schnorr::zilliqa::ZilliqaSchnorrSignature;
+    ///
+    /// ZilliqaSchnorrSignature(signature).verify(&message, &pubkey)
+    ///     .expect("Invalid signature");
+    /// ```
+    pub fn verify<T: Secp256k1Point>(
+        &self,
+        message: &[u8],
+        pubkey: &T,
+    ) -> Result<(), Secp256k1SchnorrError> {
+        let (r, s) = (self.r(), self.s());
+        Scalar::new_nonzero(r).map_err(|_| Secp256k1SchnorrError::InvalidSignatureR)?;
+        Scalar::new_nonzero(s).map_err(|_| Secp256k1SchnorrError::InvalidSignatureS)?;
+
+        // Q = s*G + r*P = s*G - (-r)*P
+        let q = UncompressedPoint(recover_nonce(&s, &Curve::negate_n(&r), pubkey)?);
+
+        if zilliqa_challenge(&q, pubkey, message).ne(&r) {
+            return Err(Secp256k1SchnorrError::InvalidSignature);
+        }
+        Ok(())
+    }
+
+    /// Signs with the nonce `k`, failing with `InvalidChallenge` or `InvalidNonce` on the zero
+    /// 𝑟 or 𝑠 that the reference implementation retries with a fresh nonce.
+    #[cfg(feature = "rng")]
+    pub(crate) fn sign_with_nonce(
+        message: &[u8],
+        privkey: &[u8; 32],
+        k: &[u8; 32],
+    ) -> Result<Self, Secp256k1SchnorrError> {
+        let pubkey = Curve::mul_g(privkey).map_err(|_| Secp256k1SchnorrError::InvalidSecretKey)?;
+        let q = Curve::mul_g(k).map_err(|_| Secp256k1SchnorrError::InvalidNonce)?;
+
+        let r = zilliqa_challenge(&q, &pubkey, message);
+        if r.eq(&[0u8; 32]) {
+            return Err(Secp256k1SchnorrError::InvalidChallenge);
+        }
+        // s = k - r*d
+        let s = Curve::add_mod_n(k, &Curve::negate_n(&Curve::mul_mod_n(&r, privkey)));
+        if s.eq(&[0u8; 32]) {
+            return Err(Secp256k1SchnorrError::InvalidNonce);
+        }
+
+        let mut signature = [0u8; ZILLIQA_SIGNATURE_LENGTH];
+        signature[..32].clone_from_slice(&r);
+        signature[32..].clone_from_slice(&s);
+        Ok(Self(signature))
+    }
+}
+
+#[cfg(feature = "rng")]
+impl ZilliqaSchnorrSignature {
+    /// ### Sign
+    /// Signs a message as the Zilliqa reference implementation does, drawing a fresh nonce from
+    /// `rng` until both 𝑟 and 𝑠 are nonzero.
+    ///
+    /// Fails with `InvalidSecretKey` unless `privkey` is a nonzero scalar.
+    pub fn sign<R: Secp256k1SchnorrRng>(
+        message: &[u8],
+        privkey: &[u8; 32],
+        rng: &mut R,
+    ) -> Result<Self, Secp256k1SchnorrError> {
+        Scalar::new_nonzero(*privkey)?;
+        loop {
+            #[allow(unused_mut)]
+            let mut k = rng::scalar(rng);
+            let signature = Self::sign_with_nonce(message, privkey, &k);
+            #[cfg(feature = "hardening")]
+            crate::hardening::zeroize(&mut k);
+            match signature {
+                Err(
+                    Secp256k1SchnorrError::InvalidChallenge | Secp256k1SchnorrError::InvalidNonce,
+                ) => continue,
+                signature => return signature,
+            }
+        }
+    }
+}